@@ -1,5 +1,9 @@
 use itertools::Itertools;
-use plonkish_backend::backend::{self, baloo, cq};
+use plonkish_backend::{
+    backend::{self, baloo, cq},
+    halo2_curves::bn256::{Bn256, Fr},
+    pcs::univariate::UnivariateKzg,
+};
 use regex::Regex;
 use std::collections::HashMap;
 use std::{
@@ -55,6 +59,7 @@ struct BenchmarkResult {
     setup_time: u64,  // in milliseconds
     prove_time: u64,  // in milliseconds
     verify_time: u64, // in milliseconds
+    proof_size: u64,  // in bytes
 }
 
 // Enum for different output formats
@@ -90,11 +95,12 @@ fn bench_baloo(k: usize, verbose: bool, debug: bool) -> BenchmarkResult {
     let setup_time = extract_setup_time(&all_timings, debug);
     let prove_time = extract_prove_time(&all_timings, debug);
     let verify_time = extract_verify_time(&all_timings, debug);
+    let proof_size = extract_proof_size(&all_timings, debug);
 
     if verbose || debug {
         println!(
-            "Baloo times extracted - Setup: {}ms, Prove: {}ms, Verify: {}ms",
-            setup_time, prove_time, verify_time
+            "Baloo times extracted - Setup: {}ms, Prove: {}ms, Verify: {}ms, Proof size: {}bytes",
+            setup_time, prove_time, verify_time, proof_size
         );
     }
 
@@ -105,6 +111,7 @@ fn bench_baloo(k: usize, verbose: bool, debug: bool) -> BenchmarkResult {
         setup_time,
         prove_time,
         verify_time,
+        proof_size,
     }
 }
 
@@ -132,11 +139,12 @@ fn bench_CQ(k: usize, verbose: bool, debug: bool) -> BenchmarkResult {
     let setup_time = extract_setup_time(&all_timings, debug);
     let prove_time = extract_prove_time(&all_timings, debug);
     let verify_time = extract_verify_time(&all_timings, debug);
+    let proof_size = extract_proof_size(&all_timings, debug);
 
     if verbose || debug {
         println!(
-            "CQ times extracted - Setup: {}ms, Prove: {}ms, Verify: {}ms",
-            setup_time, prove_time, verify_time
+            "CQ times extracted - Setup: {}ms, Prove: {}ms, Verify: {}ms, Proof size: {}bytes",
+            setup_time, prove_time, verify_time, proof_size
         );
     }
 
@@ -147,6 +155,53 @@ fn bench_CQ(k: usize, verbose: bool, debug: bool) -> BenchmarkResult {
         setup_time,
         prove_time,
         verify_time,
+        proof_size,
+    }
+}
+
+fn bench_plookup(k: usize, verbose: bool, debug: bool) -> BenchmarkResult {
+    type Plookup = backend::plookup::Plookup<Fr, UnivariateKzg<Bn256>>;
+
+    // Capture and redirect detailed output if not verbose
+    let timings = if !verbose && !debug {
+        with_suppressed_output(|| Plookup::test_plookup_by_k(k))
+    } else {
+        Plookup::test_plookup_by_k(k)
+    };
+
+    // Write results to file
+    for timing in &timings {
+        writeln!(&mut System::Plookup.output(), "{}", timing).unwrap();
+    }
+
+    let all_timings = timings.join("\n");
+
+    if debug {
+        println!("\nDEBUG: Plookup raw timing output:");
+        println!("{}", all_timings);
+    }
+
+    // Extract performance metrics from combined timings
+    let setup_time = extract_setup_time(&all_timings, debug);
+    let prove_time = extract_prove_time(&all_timings, debug);
+    let verify_time = extract_verify_time(&all_timings, debug);
+    let proof_size = extract_proof_size(&all_timings, debug);
+
+    if verbose || debug {
+        println!(
+            "Plookup times extracted - Setup: {}ms, Prove: {}ms, Verify: {}ms, Proof size: {}bytes",
+            setup_time, prove_time, verify_time, proof_size
+        );
+    }
+
+    // Return structured benchmark result
+    BenchmarkResult {
+        system: System::Plookup,
+        k_value: k,
+        setup_time,
+        prove_time,
+        verify_time,
+        proof_size,
     }
 }
 
@@ -281,6 +336,24 @@ fn extract_verify_time(timing: &str, debug: bool) -> u64 {
     0
 }
 
+fn extract_proof_size(timing: &str, debug: bool) -> u64 {
+    let re = Regex::new(r"[Pp]roof size: (\d+)\s*bytes").unwrap();
+    if let Some(cap) = re.captures(timing) {
+        if let Ok(value) = cap[1].parse::<u64>() {
+            if debug {
+                println!("DEBUG: Found proof size {}", value);
+            }
+            return value;
+        }
+    }
+
+    if debug {
+        println!("DEBUG: Could not find proof size");
+    }
+
+    0
+}
+
 // Helper function to suppress stdout output
 fn with_suppressed_output<F, T>(f: F) -> T
 where
@@ -295,31 +368,32 @@ where
 fn display_table_results(results: &[BenchmarkResult]) {
     println!("\n✓ Results:");
     println!(
-        "┌──────────┬─────────┬────────────────────┬──────────────┬───────────────┬────────────┐"
+        "┌──────────┬─────────┬────────────────────┬──────────────┬───────────────┬────────────┬────────────┐"
     );
     println!(
-        "│ System   │ K-value │ Setup+Preprocess   │ Prove        │ Verify        │ Total      │"
+        "│ System   │ K-value │ Setup+Preprocess   │ Prove        │ Verify        │ Total      │ Proof size │"
     );
     println!(
-        "├──────────┼─────────┼────────────────────┼──────────────┼───────────────┼────────────┤"
+        "├──────────┼─────────┼────────────────────┼──────────────┼───────────────┼────────────┼────────────┤"
     );
 
     // Add data rows
     for result in results {
         let total = result.setup_time + result.prove_time + result.verify_time;
         println!(
-            "│ {:<8} │ {:<7} │ {:<18} │ {:<12} │ {:<13} │ {:<10} │",
+            "│ {:<8} │ {:<7} │ {:<18} │ {:<12} │ {:<13} │ {:<10} │ {:<10} │",
             result.system.to_string(),
             result.k_value,
             format!("{}ms", result.setup_time),
             format!("{}ms", result.prove_time),
             format!("{}ms", result.verify_time),
-            format!("{}ms", total)
+            format!("{}ms", total),
+            format!("{}B", result.proof_size)
         );
     }
 
     println!(
-        "└──────────┴─────────┴────────────────────┴──────────────┴───────────────┴────────────┘"
+        "└──────────┴─────────┴────────────────────┴──────────────┴───────────────┴────────────┴────────────┘"
     );
 }
 
@@ -327,38 +401,40 @@ fn display_table_results(results: &[BenchmarkResult]) {
 fn display_compact_results(results: &[BenchmarkResult]) {
     println!("\n✓ Results:");
     println!(
-        "{:10} {:7} {:12} {:10} {:10} {:10}",
-        "System", "K", "Setup (ms)", "Prove (ms)", "Verify (ms)", "Total (ms)"
+        "{:10} {:7} {:12} {:10} {:10} {:10} {:10}",
+        "System", "K", "Setup (ms)", "Prove (ms)", "Verify (ms)", "Total (ms)", "Proof (B)"
     );
-    println!("{}", "-".repeat(60));
+    println!("{}", "-".repeat(71));
 
     for result in results {
         let total = result.setup_time + result.prove_time + result.verify_time;
         println!(
-            "{:10} {:7} {:12} {:10} {:10} {:10}",
+            "{:10} {:7} {:12} {:10} {:10} {:10} {:10}",
             result.system.to_string(),
             result.k_value,
             result.setup_time,
             result.prove_time,
             result.verify_time,
-            total
+            total,
+            result.proof_size
         );
     }
 }
 
 // Display results in CSV format
 fn display_csv_results(results: &[BenchmarkResult]) {
-    println!("System,K,SetupTime,ProveTime,VerifyTime,TotalTime");
+    println!("System,K,SetupTime,ProveTime,VerifyTime,TotalTime,ProofSize");
     for result in results {
         let total = result.setup_time + result.prove_time + result.verify_time;
         println!(
-            "{},{},{},{},{},{}",
+            "{},{},{},{},{},{},{}",
             result.system,
             result.k_value,
             result.setup_time,
             result.prove_time,
             result.verify_time,
-            total
+            total,
+            result.proof_size
         );
     }
 }
@@ -374,7 +450,8 @@ fn display_json_results(results: &[BenchmarkResult]) {
         println!("    \"setup_time\": {},", result.setup_time);
         println!("    \"prove_time\": {},", result.prove_time);
         println!("    \"verify_time\": {},", result.verify_time);
-        println!("    \"total_time\": {}", total);
+        println!("    \"total_time\": {},", total);
+        println!("    \"proof_size\": {}", result.proof_size);
         if i < results.len() - 1 {
             println!("  }},");
         } else {
@@ -435,11 +512,12 @@ fn display_summary(results: &[BenchmarkResult]) {
 enum System {
     CQ,
     Baloo,
+    Plookup,
 }
 
 impl System {
     fn all() -> Vec<System> {
-        vec![System::CQ, System::Baloo]
+        vec![System::CQ, System::Baloo, System::Plookup]
     }
 
     fn output_path(&self) -> String {
@@ -458,6 +536,7 @@ impl System {
         match self {
             System::Baloo => bench_baloo(k, verbose, debug),
             System::CQ => bench_CQ(k, verbose, debug),
+            System::Plookup => bench_plookup(k, verbose, debug),
         }
     }
 }
@@ -467,6 +546,7 @@ impl Display for System {
         match self {
             System::Baloo => write!(f, "Baloo"),
             System::CQ => write!(f, "CQ"),
+            System::Plookup => write!(f, "Plookup"),
         }
     }
 }
@@ -484,7 +564,9 @@ fn parse_args() -> (Vec<System>, Range<usize>, bool, OutputFormat, bool) {
                         "CQ" => systems.push(System::CQ),
                         "Baloo" => systems.push(System::Baloo),
                         "baloo" => systems.push(System::Baloo),
-                        _ => panic!("system should be one of {{all, cq, baloo}}"),
+                        "Plookup" => systems.push(System::Plookup),
+                        "plookup" => systems.push(System::Plookup),
+                        _ => panic!("system should be one of {{all, cq, baloo, plookup}}"),
                     },
 
                     "--k" => {
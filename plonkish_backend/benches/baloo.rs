@@ -0,0 +1,46 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use halo2_curves::bn256::Bn256;
+use plonkish_backend::backend::{
+    baloo::{
+        preprocessor::{preprocess, ProverTablePreprocessing},
+        prover::Prover,
+    },
+    cq::generate_table_and_lookup,
+};
+use std::ops::Range;
+
+// Table preprocessing and some of the quotients are quadratic, which keeps
+// tables of 2^16 and beyond out of reach. The speedup of the `parallel`
+// feature is the ratio to a run with `RAYON_NUM_THREADS=1`.
+const LOG_TABLE_SIZE_RANGE: Range<usize> = 8..13;
+
+fn bench_preprocess(c: &mut Criterion) {
+    let mut group = c.benchmark_group("baloo_preprocess");
+    group.sample_size(10);
+
+    for k in LOG_TABLE_SIZE_RANGE {
+        let (table, lookup) = generate_table_and_lookup(1 << k, 1 << (k - 1));
+        let (_, pp, _) = preprocess::<Bn256>(table.len(), lookup.len()).unwrap();
+        group.bench_with_input(BenchmarkId::from_parameter(k), &k, |b, _| {
+            b.iter(|| ProverTablePreprocessing::new(black_box(&pp), black_box(&table)).unwrap())
+        });
+    }
+}
+
+fn bench_prove(c: &mut Criterion) {
+    let mut group = c.benchmark_group("baloo_prove");
+    group.sample_size(10);
+
+    for k in LOG_TABLE_SIZE_RANGE {
+        let (table, lookup) = generate_table_and_lookup(1 << k, 1 << (k - 1));
+        let (param, pp, _) = preprocess::<Bn256>(table.len(), lookup.len()).unwrap();
+        let table_pp = ProverTablePreprocessing::new(&pp, &table).unwrap();
+        let prover = Prover::new_with_preprocessed(&table_pp, &param, &pp);
+        group.bench_with_input(BenchmarkId::from_parameter(k), &k, |b, _| {
+            b.iter(|| prover.prove(black_box(&lookup)).unwrap())
+        });
+    }
+}
+
+criterion_group!(benches, bench_preprocess, bench_prove);
+criterion_main!(benches);
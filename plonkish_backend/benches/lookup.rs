@@ -0,0 +1,141 @@
+use criterion::{
+    black_box, criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion,
+};
+use halo2_curves::bn256::{Bn256, Fr};
+use plonkish_backend::{
+    backend::{
+        baloo::Baloo,
+        cq::{generate_table_and_lookup, Cq},
+        logup::Logup,
+        lookup::LookupScheme,
+        plookup::Plookup,
+    },
+    pcs::univariate::UnivariateKzg,
+};
+use rand::rngs::OsRng;
+use std::{
+    env,
+    fs::{create_dir_all, File},
+    io::Write,
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+// Log2 of the table and lookup sizes of the full grid, which takes hours as
+// table preprocessing is quadratic, overridden by e.g.
+// `LOOKUP_BENCH_LOG_TABLE_SIZES=12..15 LOOKUP_BENCH_LOG_LOOKUP_SIZES=8..9`.
+const LOG_TABLE_SIZES: Range<usize> = 10..23;
+const LOG_LOOKUP_SIZES: Range<usize> = 6..17;
+// quick mode of `LOOKUP_BENCH_QUICK=1`, e.g. to check a change of a backend
+const QUICK_LOG_TABLE_SIZES: Range<usize> = 10..13;
+const QUICK_LOG_LOOKUP_SIZES: Range<usize> = 6..10;
+
+// mean time of every phase, in rows of `scheme,log_t,log_m,...`
+const OUTPUT_DIR: &str = "../target/bench";
+const OUTPUT_CSV: &str = "../target/bench/lookup.csv";
+const CSV_HEADER: &str =
+    "scheme,log_table_size,log_lookup_size,setup_ms,preprocess_ms,prove_ms,verify_ms,proof_size";
+
+fn log_sizes(var: &str, default: Range<usize>, quick: Range<usize>) -> Range<usize> {
+    let Ok(value) = env::var(var) else {
+        let is_quick = env::var("LOOKUP_BENCH_QUICK").is_ok_and(|value| value != "0");
+        return if is_quick { quick } else { default };
+    };
+    let (start, end) = value
+        .split_once("..")
+        .unwrap_or_else(|| panic!("{var} should be a range like 10..13"));
+    start.parse().expect("range start to be usize")..end.parse().expect("range end to be usize")
+}
+
+// Benchmark `f` as `id`, returning the mean time over all iterations run.
+fn bench_phase<O>(
+    group: &mut BenchmarkGroup<WallTime>,
+    id: &str,
+    mut f: impl FnMut() -> O,
+) -> Duration {
+    let (mut total, mut count) = (Duration::ZERO, 0);
+    group.bench_function(id, |b| {
+        b.iter_custom(|iters| {
+            let start = Instant::now();
+            for _ in 0..iters {
+                black_box(f());
+            }
+            let elapsed = start.elapsed();
+            total += elapsed;
+            count += iters;
+            elapsed
+        })
+    });
+    total.div_f64(count as f64)
+}
+
+// Benchmark `S` on every size of the grid with lookups of at most
+// `max_lookup_size` of the table size.
+fn bench_scheme<S: LookupScheme<Fr>>(
+    c: &mut Criterion,
+    name: &str,
+    max_lookup_size: fn(usize) -> usize,
+    csv: &mut File,
+) {
+    let log_table_sizes = log_sizes(
+        "LOOKUP_BENCH_LOG_TABLE_SIZES",
+        LOG_TABLE_SIZES,
+        QUICK_LOG_TABLE_SIZES,
+    );
+    let log_lookup_sizes = log_sizes(
+        "LOOKUP_BENCH_LOG_LOOKUP_SIZES",
+        LOG_LOOKUP_SIZES,
+        QUICK_LOG_LOOKUP_SIZES,
+    );
+    for log_t in log_table_sizes {
+        for log_m in log_lookup_sizes.clone() {
+            if 1 << log_m > max_lookup_size(1 << log_t) {
+                continue;
+            }
+            let (table, lookup) = generate_table_and_lookup(1 << log_t, 1 << log_m);
+            let (t, m) = (table.len(), lookup.len());
+            let param = S::setup(t, m, OsRng).unwrap();
+            let (pp, vp) = S::preprocess(&param, &table, m).unwrap();
+            let proof = S::prove(&pp, &lookup).unwrap();
+            let lookup_comm = S::commit_lookup(&pp, &lookup).unwrap();
+
+            let mut group = c.benchmark_group(format!("{name}/t=2^{log_t}/m=2^{log_m}"));
+            group.sample_size(10);
+            let times = [
+                bench_phase(&mut group, "setup", || S::setup(t, m, OsRng).unwrap()),
+                bench_phase(&mut group, "preprocess", || {
+                    S::preprocess(&param, &table, m).unwrap()
+                }),
+                bench_phase(&mut group, "prove", || S::prove(&pp, &lookup).unwrap()),
+                bench_phase(&mut group, "verify", || {
+                    S::verify(&vp, &lookup_comm, &proof).unwrap()
+                }),
+            ];
+            group.finish();
+
+            let times = times.map(|time| format!("{:.3}", time.as_secs_f64() * 1000.0));
+            let proof_size = S::proof_to_bytes(&proof).len();
+            writeln!(
+                csv,
+                "{name},{log_t},{log_m},{},{proof_size}",
+                times.join(",")
+            )
+            .unwrap();
+        }
+    }
+}
+
+fn bench_lookup_schemes(c: &mut Criterion) {
+    create_dir_all(OUTPUT_DIR).unwrap();
+    let mut csv = File::create(OUTPUT_CSV).unwrap();
+    writeln!(csv, "{CSV_HEADER}").unwrap();
+
+    bench_scheme::<Baloo>(c, "baloo", |_| usize::MAX, &mut csv);
+    bench_scheme::<Cq>(c, "cq", |_| usize::MAX, &mut csv);
+    // the padded lookup and the table are of the same domain
+    bench_scheme::<Plookup<Fr, UnivariateKzg<Bn256>>>(c, "plookup", |t| t - 1, &mut csv);
+    bench_scheme::<Logup<Fr, UnivariateKzg<Bn256>>>(c, "logup", |t| t, &mut csv);
+}
+
+criterion_group!(benches, bench_lookup_schemes);
+criterion_main!(benches);
@@ -0,0 +1,61 @@
+/*
+ * C API of Baloo over BN254, for the cdylib of plonkish_backend built with
+ * `cargo rustc --release --crate-type cdylib --features ffi`, see
+ * `src/backend/baloo/ffi.rs`.
+ *
+ * Field elements are concatenated 32-byte little-endian reprs, phi is the
+ * 32-byte compressed [phi(x)]1 and proofs are of
+ * BalooProof::to_versioned_bytes, prefixed by the protocol and format version.
+ */
+
+#ifndef BALOO_H
+#define BALOO_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define BALOO_OK 0
+#define BALOO_INVALID_PROOF 1
+#define BALOO_INVALID_ARGUMENT (-1)
+#define BALOO_ERROR (-2)
+
+#define BALOO_PHI_SIZE 32
+
+typedef struct BalooSrs BalooSrs;
+typedef struct BalooProver BalooProver;
+typedef struct BalooVerifier BalooVerifier;
+
+/* Freed by baloo_buffer_free. */
+typedef struct BalooBuffer {
+    uint8_t *ptr;
+    size_t len;
+} BalooBuffer;
+
+int baloo_setup(size_t t, size_t m, BalooSrs **srs);
+
+int baloo_preprocess(const BalooSrs *srs, const uint8_t *table, size_t table_len, size_t m,
+                     BalooProver **prover, BalooVerifier **verifier);
+
+int baloo_prove(const BalooProver *prover, const uint8_t *lookup, size_t lookup_len,
+                BalooBuffer *proof);
+
+int baloo_commit_lookup(const BalooProver *prover, const uint8_t *lookup, size_t lookup_len,
+                        uint8_t phi[BALOO_PHI_SIZE]);
+
+int baloo_verify(const BalooVerifier *verifier, const uint8_t *proof, size_t proof_len,
+                 const uint8_t phi[BALOO_PHI_SIZE]);
+
+void baloo_srs_free(BalooSrs *srs);
+void baloo_prover_free(BalooProver *prover);
+void baloo_verifier_free(BalooVerifier *verifier);
+void baloo_buffer_free(BalooBuffer buffer);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* BALOO_H */
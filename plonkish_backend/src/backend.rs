@@ -13,10 +13,21 @@ use rand::RngCore;
 use std::{collections::BTreeSet, fmt::Debug};
 
 pub mod baloo;
+pub mod caulk;
+pub mod caulk_plus;
 pub mod cq;
+pub mod flookup;
+pub mod gas;
 pub mod hyperplonk;
+pub mod lasso;
+pub mod logup;
+pub mod logup_gkr;
+pub mod lookup;
+pub mod multiset;
 pub mod plookup;
+pub mod range;
 pub mod unihyperplonk;
+pub mod univariate_sum_check;
 
 pub trait PlonkishBackend<F: Field>: Clone + Debug {
     type Pcs: PolynomialCommitmentScheme<F>;
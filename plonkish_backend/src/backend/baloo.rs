@@ -1,41 +1,274 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, hash::Hash, io::Cursor, sync::OnceLock};
 
-use halo2_curves::bn256::{Bn256, Fr, G1Affine, G2Affine};
+use halo2_curves::{
+    bn256::{Bn256, Fr, G1Affine},
+    group::GroupEncoding,
+};
+use rand::RngCore;
 
 use crate::{
-    backend::cq::generate_table_and_lookup,
+    backend::{baloo::plan::PreparedG2, cq::generate_table_and_lookup, lookup},
     pcs::{
         univariate::{
             UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam, UnivariateKzgProverParam,
             UnivariateKzgVerifierParam,
-        }, PolynomialCommitmentScheme,
+        }, PairingCommitmentScheme, PolynomialCommitmentScheme,
     },
     poly::univariate::UnivariatePolynomial,
     poly::Polynomial,
     util::{
-        arithmetic::Field,
-        transcript::InMemoryTranscript,
+        arithmetic::{
+            fe_mod_from_le_bytes, CurveAffine, Field, MultiMillerLoop, PrimeField,
+            WithSmallOrderMulGroup,
+        },
+        hash::{Hash as _, Keccak256},
+        metrics,
+        transcript::{
+            FieldTranscript, G2TranscriptRead, G2TranscriptWrite, InMemoryTranscript,
+            Keccak256Transcript, TranscriptRead, TranscriptWrite,
+        },
+        Deserialize, DeserializeOwned, Serialize,
     },
     Error,
 };
 
+pub mod circuit;
+pub mod degree;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod gated;
+pub mod indexed;
+pub mod interactive;
+pub mod job;
+pub mod link;
+pub mod multi;
+pub mod padding;
+pub mod plan;
 pub mod preprocessor;
+pub mod proof;
 pub mod prover;
+pub mod registry;
+pub mod segment;
+pub mod solidity;
+pub mod tables;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod tuple;
 pub mod util;
 pub mod verifier;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
+pub mod witness;
+
+pub use proof::{BalooProof, ProofStats};
+pub use verifier::VerifyError;
+pub use witness::{check_witness, WitnessError, WitnessReport};
+
+/// Pairing engine the Baloo prover and verifier are generic over, with the
+/// transcript their proofs are written to, as `G2` commitments can only be
+/// written to a transcript which supports the curve.
+///
+/// Only BN254 is implemented, as the pinned `halo2curves` has no BLS12-381.
+/// Once it does, supporting BLS12-381 takes an impl of this trait, with the
+/// `G2` transcript impls in `util::transcript` extended to its `G2Affine`, and
+/// an SRS loaded into [`UnivariateKzgParam`] in place of [`preprocess`]'s
+/// trusted setup.
+///
+/// [`UnivariateKzgParam`]: crate::pcs::univariate::UnivariateKzgParam
+/// [`preprocess`]: preprocessor::preprocess
+pub trait BalooEngine:
+    MultiMillerLoop<
+    Scalar: WithSmallOrderMulGroup<3> + Hash + Serialize + DeserializeOwned,
+    G1Affine: Serialize + DeserializeOwned,
+    G2Affine: Serialize + DeserializeOwned,
+>
+{
+    type Transcript: InMemoryTranscript<Param = ()>
+        + TranscriptRead<Self::G1Affine, Self::Scalar>
+        + TranscriptWrite<Self::G1Affine, Self::Scalar>
+        + G2TranscriptRead<Self::G2Affine, Self::Scalar>
+        + G2TranscriptWrite<Self::G2Affine, Self::Scalar>;
+}
+
+impl BalooEngine for Bn256 {
+    type Transcript = Keccak256Transcript<Cursor<Vec<u8>>>;
+}
+
+/// Univariate PCS the Baloo prover and verifier are generic over, which on top
+/// of [`PairingCommitmentScheme`] exposes the powers of `s` the degree checks
+/// are built from. Its commitments are `G1` and `G2` points of `M`, as the
+/// verifier pairs them.
+pub trait BalooPcs<M: BalooEngine>:
+    PairingCommitmentScheme<
+    M::Scalar,
+    Polynomial = UnivariatePolynomial<M::Scalar>,
+    Commitment = UnivariateKzgCommitment<M::G1Affine>,
+    CommitmentChunk = M::G1Affine,
+    G2Commitment = UnivariateKzgCommitment<M::G2Affine>,
+    G2CommitmentChunk = M::G2Affine,
+>
+{
+    /// Largest degree committable with `pp`.
+    fn max_degree(pp: &Self::ProverParam) -> usize;
+
+    /// Largest degree committable with the whole SRS of `param`, which the
+    /// prover may hold besides `pp`.
+    fn srs_degree(param: &Self::Param) -> usize;
+
+    /// `[s^i]1` for `i` up to [`BalooPcs::max_degree`].
+    fn monomial_g1(pp: &Self::ProverParam) -> &[M::G1Affine];
+
+    /// `[s^i]2`.
+    fn monomial_g2(param: &Self::Param) -> &[M::G2Affine];
+
+    /// `[1]2`.
+    fn g2(vp: &Self::VerifierParam) -> M::G2Affine;
+
+    /// `[s]2`.
+    fn s_g2(vp: &Self::VerifierParam) -> M::G2Affine;
+
+    fn commit_monomial(
+        pp: &Self::ProverParam,
+        coeffs: &[M::Scalar],
+    ) -> UnivariateKzgCommitment<M::G1Affine>;
+
+    /// Commitment to the polynomial of `evals` on the domain of their size.
+    fn commit_lagrange(
+        pp: &Self::ProverParam,
+        evals: &[M::Scalar],
+    ) -> UnivariateKzgCommitment<M::G1Affine>;
+
+    /// `[p(s)]2` with the whole SRS the prover holds, unlike
+    /// [`PairingCommitmentScheme::commit_g2`] with a trimmed one.
+    fn commit_monomial_g2(
+        param: &Self::Param,
+        coeffs: &[M::Scalar],
+    ) -> UnivariateKzgCommitment<M::G2Affine>;
+}
+
+impl<M: BalooEngine> BalooPcs<M> for UnivariateKzg<M> {
+    fn max_degree(pp: &UnivariateKzgProverParam<M>) -> usize {
+        pp.degree()
+    }
+
+    fn srs_degree(param: &UnivariateKzgParam<M>) -> usize {
+        param.srs_degree()
+    }
+
+    fn monomial_g1(pp: &UnivariateKzgProverParam<M>) -> &[M::G1Affine] {
+        pp.monomial_g1()
+    }
+
+    fn monomial_g2(param: &UnivariateKzgParam<M>) -> &[M::G2Affine] {
+        param.powers_of_s_g2()
+    }
+
+    fn g2(vp: &UnivariateKzgVerifierParam<M>) -> M::G2Affine {
+        vp.g2()
+    }
+
+    fn s_g2(vp: &UnivariateKzgVerifierParam<M>) -> M::G2Affine {
+        vp.s_g2()
+    }
+
+    fn commit_monomial(
+        pp: &UnivariateKzgProverParam<M>,
+        coeffs: &[M::Scalar],
+    ) -> UnivariateKzgCommitment<M::G1Affine> {
+        UnivariateKzg::<M>::commit_monomial(pp, coeffs)
+    }
+
+    fn commit_lagrange(
+        pp: &UnivariateKzgProverParam<M>,
+        evals: &[M::Scalar],
+    ) -> UnivariateKzgCommitment<M::G1Affine> {
+        UnivariateKzg::<M>::commit_lagrange(pp, evals)
+    }
+
+    fn commit_monomial_g2(
+        param: &UnivariateKzgParam<M>,
+        coeffs: &[M::Scalar],
+    ) -> UnivariateKzgCommitment<M::G2Affine> {
+        UnivariateKzg::<M>::commit_monomial_g2(param, coeffs)
+    }
+}
 
 // Specific implementation for Bn256 curves
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BalooProverParam {
     param: UnivariateKzgParam<Bn256>,
     pp: UnivariateKzgProverParam<Bn256>,
-    table: Vec<Fr>,
+    table: preprocessor::ProverTablePreprocessing,
     d: usize,
 }
 
-#[derive(Clone, Debug)]
-pub struct BalooVerifierParam {
-    vp: UnivariateKzgVerifierParam<Bn256>,
+/// Everything the verifier needs besides the proof, `[φ(x)]1` and `m`, see
+/// [`preprocessor::preprocess_verifier`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+// points and scalars of a `BalooEngine` and the `vp` of a PCS are
+// (de)serializable already
+#[serde(bound = "")]
+pub struct BalooVerifierParam<M: BalooEngine = Bn256, Pcs: BalooPcs<M> = UnivariateKzg<M>> {
+    vp: Pcs::VerifierParam,
+    // lookup size the shifts are for
+    m: usize,
+    // table size, of which `ζ` and `α` are squeezed outside the domain too
+    t: usize,
+    t_comm_1: UnivariateKzgCommitment<M::G1Affine>,
+    z_h_comm_1: UnivariateKzgCommitment<M::G1Affine>,
+    // [x^m]1
+    x_m_exponent_poly_comm_1: UnivariateKzgCommitment<M::G1Affine>,
+    // [x^(d-m+1)]2
+    x_exponent_poly_comm_2: UnivariateKzgCommitment<M::G2Affine>,
+    // [x^(d-m+2)]1 and [x^(d-m+2)]2
+    x_exponent_poly_2_comm_1: UnivariateKzgCommitment<M::G1Affine>,
+    x_exponent_poly_2_comm_2: UnivariateKzgCommitment<M::G2Affine>,
+    // prepared on first use, so also for a deserialized verifier param
+    #[serde(skip)]
+    prepared_g2: OnceLock<PreparedG2<M>>,
+}
+
+impl<M: BalooEngine, Pcs: BalooPcs<M>> BalooVerifierParam<M, Pcs> {
+    pub fn m(&self) -> usize {
+        self.m
+    }
+
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    pub fn t_comm_1(&self) -> &UnivariateKzgCommitment<M::G1Affine> {
+        &self.t_comm_1
+    }
+
+    pub fn z_h_comm_1(&self) -> &UnivariateKzgCommitment<M::G1Affine> {
+        &self.z_h_comm_1
+    }
+
+    /// [`srs_digest`] of the SRS the verifier param is read off.
+    pub fn srs_digest(&self) -> [u8; 32] {
+        srs_digest::<M>(&Pcs::g2(&self.vp), &Pcs::s_g2(&self.vp))
+    }
+
+    /// `G2Prepared` of `[1]2`, `[s]2` and the shifts, the only fixed `G2`
+    /// points of the pairing checks.
+    pub fn prepared_g2(&self) -> &PreparedG2<M> {
+        self.prepared_g2.get_or_init(|| {
+            PreparedG2::new([
+                Pcs::g2(&self.vp),
+                Pcs::s_g2(&self.vp),
+                self.x_exponent_poly_comm_2.0,
+                self.x_exponent_poly_2_comm_2.0,
+            ])
+        })
+    }
+}
+
+/// Digest of an SRS by `[1]2 || [s]2`, shared by the prover param and the
+/// verifier param of the same SRS whatever their sizes.
+pub fn srs_digest<M: BalooEngine>(g2: &M::G2Affine, s_g2: &M::G2Affine) -> [u8; 32] {
+    let bytes = [g2.to_bytes().as_ref(), s_g2.to_bytes().as_ref()].concat();
+    Keccak256::digest(bytes).into()
 }
 
 #[derive(Clone, Debug)]
@@ -44,6 +277,92 @@ pub struct BalooInfo {
     lookup: Vec<Fr>,
 }
 
+/// How the looked-up vector `φ` is given to the verifier.
+#[derive(Clone, Debug)]
+pub enum PhiInstance<C: CurveAffine = G1Affine> {
+    /// `φ` is private and the verifier is given `[φ(x)]1`.
+    Committed(UnivariateKzgCommitment<C>),
+    /// `φ` is public, and the verifier evaluates it from the values directly.
+    Public(Vec<C::Scalar>),
+}
+
+/// Public instance of Baloo, whose digest is the first thing absorbed by the
+/// transcript. The table size is bound through `[z_H(x)]1 = [x^t - 1]1`, and
+/// the SRS through its [`srs_digest`].
+#[derive(Clone, Debug)]
+pub struct Instance<C: CurveAffine = G1Affine> {
+    pub m: usize,
+    pub t_comm_1: UnivariateKzgCommitment<C>,
+    pub z_h_comm_1: UnivariateKzgCommitment<C>,
+    pub srs_digest: [u8; 32],
+    pub phi: PhiInstance<C>,
+}
+
+impl<C: CurveAffine> lookup::Instance for Instance<C> {
+    const PROTOCOL_ID: &'static [u8] = b"baloo";
+    const VERSION: u8 = 2;
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&(self.m as u64).to_le_bytes());
+        bytes.extend_from_slice(self.t_comm_1.0.to_bytes().as_ref());
+        bytes.extend_from_slice(self.z_h_comm_1.0.to_bytes().as_ref());
+        bytes.extend_from_slice(&self.srs_digest);
+        match &self.phi {
+            PhiInstance::Committed(phi_comm_1) => {
+                bytes.push(0);
+                bytes.extend_from_slice(phi_comm_1.0.to_bytes().as_ref());
+            }
+            PhiInstance::Public(values) => {
+                bytes.push(1);
+                bytes.extend_from_slice(&(values.len() as u64).to_le_bytes());
+                for value in values {
+                    bytes.extend_from_slice(value.to_repr().as_ref());
+                }
+            }
+        }
+    }
+}
+
+// label of the instance digest in the transcript
+pub(crate) const INSTANCE_LABEL: &[u8] = b"instance";
+
+impl<C: CurveAffine> Instance<C> {
+    /// Absorb the protocol identifier as a label, then the digest, which binds
+    /// `m`, `[t(x)]1`, `t`, the SRS and `φ`, before any message of the proof.
+    pub(crate) fn absorb_into(&self, transcript: &mut impl FieldTranscript<C::Scalar>) {
+        let digest = lookup::Instance::digest(self);
+        transcript
+            .common_label(<Self as lookup::Instance>::PROTOCOL_ID)
+            .unwrap();
+        transcript
+            .common_field_element_labeled(INSTANCE_LABEL, &fe_mod_from_le_bytes(digest))
+            .unwrap();
+    }
+}
+
+/// Costs of a proving run, counted only with the `metrics` feature.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProveMetrics {
+    /// Time spent interpolating the table before round 1.
+    pub preprocess_ms: u64,
+    pub round_ms: Vec<u64>,
+    pub msm_count: u64,
+    pub fft_count: u64,
+    /// Peak of allocated bytes, which requires `metrics::CountingAllocator`
+    /// to be installed as the global allocator.
+    pub peak_alloc_bytes: u64,
+}
+
+/// Costs of a verifying run, counted only with the `metrics` feature.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerifyMetrics {
+    pub verify_ms: u64,
+    pub pairing_count: u64,
+    pub msm_count: u64,
+    /// Total number of scalar multiplications over all MSMs.
+    pub scalar_mul_count: u64,
+}
+
 #[derive(Clone, Debug)]
 pub struct Baloo;
 
@@ -67,171 +386,413 @@ impl Baloo {
     pub fn preprocess_with_info(
         info: &BalooInfo,
     ) -> Result<(BalooProverParam, BalooVerifierParam), Error> {
-        let m = info.lookup.len();
-        let t = info.table.len();
+        let (param, pp, vp) = preprocessor::preprocess(info.table.len(), info.lookup.len())?;
+        Self::preprocess_table(param, pp, vp, &info.table, info.lookup.len())
+    }
 
-        let (param, pp, vp) = preprocessor::preprocess(t, m)?;
+    /// [`Baloo::preprocess_with_info`] of `table` and lookups of size `m`
+    /// with a given SRS, see [`preprocessor::preprocess_with_param`].
+    pub fn preprocess_with_srs(
+        param: UnivariateKzgParam<Bn256>,
+        table: &[Fr],
+        m: usize,
+    ) -> Result<(BalooProverParam, BalooVerifierParam), Error> {
+        let (param, pp, vp) = preprocessor::preprocess_with_param(param, table.len(), m)?;
+        Self::preprocess_table(param, pp, vp, table, m)
+    }
+
+    /// [`Baloo::preprocess_with_srs`] with the table preprocessing of
+    /// `registry`, which preprocesses it only if it has it neither in memory
+    /// nor on disk.
+    pub fn preprocess_with_registry(
+        registry: &mut registry::TableRegistry,
+        param: UnivariateKzgParam<Bn256>,
+        table: &[Fr],
+        m: usize,
+    ) -> Result<(BalooProverParam, BalooVerifierParam), Error> {
+        let (param, pp, vp) = preprocessor::preprocess_with_param(param, table.len(), m)?;
+        let table = registry.get_or_preprocess(&pp, table)?;
+        Self::with_preprocessed_table(param, pp, vp, (*table).clone(), m)
+    }
+
+    fn preprocess_table(
+        param: UnivariateKzgParam<Bn256>,
+        pp: UnivariateKzgProverParam<Bn256>,
+        vp: UnivariateKzgVerifierParam<Bn256>,
+        table: &[Fr],
+        m: usize,
+    ) -> Result<(BalooProverParam, BalooVerifierParam), Error> {
+        let table = preprocessor::ProverTablePreprocessing::new(&pp, table)?;
+        Self::with_preprocessed_table(param, pp, vp, table, m)
+    }
 
-        let poly_size = std::cmp::max(t, m).next_power_of_two() * 2;
-        let d = poly_size - 2;
+    fn with_preprocessed_table(
+        param: UnivariateKzgParam<Bn256>,
+        pp: UnivariateKzgProverParam<Bn256>,
+        vp: UnivariateKzgVerifierParam<Bn256>,
+        table: preprocessor::ProverTablePreprocessing,
+        m: usize,
+    ) -> Result<(BalooProverParam, BalooVerifierParam), Error> {
+        let vp =
+            preprocessor::preprocess_verifier(&param, &pp, &vp, table.table_preprocessing(), m)?;
+        let d = pp.degree() - 1;
 
         Ok((
             BalooProverParam {
-                param: param.clone(),
+                param,
                 pp,
-                table: info.table.clone(),
+                table,
                 d,
             },
-            BalooVerifierParam { vp },
+            vp,
         ))
     }
 
+    /// Commit to `table` and the degree shifts of lookups of size `m`, for
+    /// the SRS of [`Baloo::preprocess`].
+    pub fn verifier_param(
+        param: &UnivariateKzgParam<Bn256>,
+        pp: &UnivariateKzgProverParam<Bn256>,
+        vp: &UnivariateKzgVerifierParam<Bn256>,
+        table: &[Fr],
+        m: usize,
+    ) -> Result<BalooVerifierParam, Error> {
+        let table = preprocessor::TablePreprocessing::new(pp, table)?;
+        preprocessor::preprocess_verifier(param, pp, vp, &table, m)
+    }
+
+    /// [`Baloo::verifier_param`] of the index column of a table of size `t`,
+    /// for [`Baloo::verify_indexed`].
+    pub fn index_verifier_param(
+        param: &UnivariateKzgParam<Bn256>,
+        pp: &UnivariateKzgProverParam<Bn256>,
+        vp: &UnivariateKzgVerifierParam<Bn256>,
+        t: usize,
+        m: usize,
+    ) -> Result<BalooVerifierParam, Error> {
+        Self::verifier_param(param, pp, vp, &indexed::index_column(t), m)
+    }
+
     pub fn prove(
         table: &Vec<Fr>,
         param: &UnivariateKzgParam<Bn256>,
         pp: &UnivariateKzgProverParam<Bn256>,
         lookup: &Vec<Fr>,
-    ) -> Vec<u8> {
+    ) -> Result<BalooProof, Error> {
         let prover = prover::Prover::new(table, param, pp);
         prover.prove(lookup)
     }
 
-    pub fn prove_with_param(pp: &BalooProverParam, lookup: &Vec<Fr>) -> Vec<u8> {
-        let table_vec = pp.table.clone(); // Clone to avoid lifetime issues
-        let lookup_vec = lookup.clone();
-        let prover = prover::Prover::new(&table_vec, &pp.param, &pp.pp);
-        prover.prove(&lookup_vec)
+    pub fn prove_public(
+        table: &Vec<Fr>,
+        param: &UnivariateKzgParam<Bn256>,
+        pp: &UnivariateKzgProverParam<Bn256>,
+        lookup: &Vec<Fr>,
+    ) -> Result<BalooProof, Error> {
+        let prover = prover::Prover::new(table, param, pp);
+        prover.prove_public(lookup)
+    }
+
+    /// Prove that every lookup column is in `table` with a single proof, see
+    /// [`prover::Prover::prove_columns`].
+    pub fn prove_columns(
+        table: &Vec<Fr>,
+        param: &UnivariateKzgParam<Bn256>,
+        pp: &UnivariateKzgProverParam<Bn256>,
+        columns: &[Vec<Fr>],
+    ) -> Result<BalooProof, Error> {
+        let prover = prover::Prover::new(table, param, pp);
+        prover.prove_columns(columns)
+    }
+
+    /// Prove that every row of the columns of `lookup` is a row of the
+    /// columns of `table`, compressed by a challenge, see [`tuple`].
+    pub fn prove_tuples(
+        table: &[Vec<Fr>],
+        param: &UnivariateKzgParam<Bn256>,
+        pp: &UnivariateKzgProverParam<Bn256>,
+        lookup: &[Vec<Fr>],
+    ) -> Result<BalooProof, Error> {
+        let table = table
+            .iter()
+            .map(|column| preprocessor::ProverTablePreprocessing::new(pp, column))
+            .collect::<Result<Vec<_>, _>>()?;
+        let table = table.iter().collect::<Vec<_>>();
+        tuple::prove_tuples::<Bn256, UnivariateKzg<Bn256>>(&table, param, pp, lookup)
+    }
+
+    /// Prove that `lookup[j]` is the value at `indices[j]` of `table`, with
+    /// the indices committed, see [`indexed`]. [`indexed::first_indices`]
+    /// are the indices of the first occurrence of each value.
+    pub fn prove_indexed(
+        table: &Vec<Fr>,
+        param: &UnivariateKzgParam<Bn256>,
+        pp: &UnivariateKzgProverParam<Bn256>,
+        lookup: &Vec<Fr>,
+        indices: &[usize],
+    ) -> Result<BalooProof, Error> {
+        let index_table =
+            preprocessor::ProverTablePreprocessing::new(pp, &indexed::index_column(table.len()))?;
+        let table = preprocessor::ProverTablePreprocessing::new(pp, table)?;
+        indexed::prove_indexed::<Bn256, UnivariateKzg<Bn256>>(
+            &table,
+            &index_table,
+            param,
+            pp,
+            lookup,
+            indices,
+        )
+    }
+
+    /// Prove that every `lookups[i]` is in `tables[i]` in a single proof,
+    /// all with the same SRS, see [`multi`].
+    pub fn prove_multi(
+        tables: &[Vec<Fr>],
+        param: &UnivariateKzgParam<Bn256>,
+        pp: &UnivariateKzgProverParam<Bn256>,
+        lookups: &[Vec<Fr>],
+    ) -> Result<multi::MultiProof, Error> {
+        let tables = tables
+            .iter()
+            .map(|table| preprocessor::ProverTablePreprocessing::new(pp, table))
+            .collect::<Result<Vec<_>, _>>()?;
+        let tables = tables.iter().collect::<Vec<_>>();
+        multi::prove_multi::<Bn256, UnivariateKzg<Bn256>>(&tables, param, pp, lookups)
+    }
+
+    /// Prove that every value of `lookup` is in `table`, of any sizes, with
+    /// both padded to powers of two, the lookup with `table[padding_index]`,
+    /// see [`padding`].
+    pub fn prove_padded(
+        table: &[Fr],
+        param: &UnivariateKzgParam<Bn256>,
+        pp: &UnivariateKzgProverParam<Bn256>,
+        lookup: &[Fr],
+        padding_index: usize,
+    ) -> Result<padding::PaddedProof, Error> {
+        padding::prove_padded::<Bn256, UnivariateKzg<Bn256>>(
+            table,
+            param,
+            pp,
+            lookup,
+            padding_index,
+        )
+    }
+
+    pub fn prove_with_metrics(
+        table: &Vec<Fr>,
+        param: &UnivariateKzgParam<Bn256>,
+        pp: &UnivariateKzgProverParam<Bn256>,
+        lookup: &Vec<Fr>,
+    ) -> Result<(BalooProof, ProveMetrics), Error> {
+        metrics::reset();
+        let proof = Self::prove(table, param, pp, lookup)?;
+        let counters = metrics::snapshot();
+
+        let mut round_ms = counters.round_ms;
+        let preprocess_ms = if round_ms.is_empty() {
+            0
+        } else {
+            round_ms.remove(0)
+        };
+        let metrics = ProveMetrics {
+            preprocess_ms,
+            round_ms,
+            msm_count: counters.msm_count,
+            fft_count: counters.fft_count,
+            peak_alloc_bytes: metrics::peak_alloc_bytes(),
+        };
+        Ok((proof, metrics))
+    }
+
+    /// Prove against the table preprocessed by [`Baloo::preprocess_with_info`],
+    /// so that proving many lookups commits to the table only once.
+    pub fn prove_with_param(pp: &BalooProverParam, lookup: &Vec<Fr>) -> Result<BalooProof, Error> {
+        let prover = prover::Prover::new_with_preprocessed(&pp.table, &pp.param, &pp.pp);
+        prover.prove(lookup)
     }
 
     pub fn verify(
-        vp: &UnivariateKzgVerifierParam<Bn256>,
-        proof: &Vec<u8>,
-        t_comm_1: &UnivariateKzgCommitment<G1Affine>,
-        z_h_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        vp: &BalooVerifierParam,
+        proof: &BalooProof,
         phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
-        x_m_exponent_poly_comm_1: &UnivariateKzgCommitment<G1Affine>,
-        x_exponent_poly_comm_2: &UnivariateKzgCommitment<G2Affine>,
-        x_exponent_poly_2_comm_1: &UnivariateKzgCommitment<G1Affine>,
-        x_exponent_poly_2_comm_2: &UnivariateKzgCommitment<G2Affine>,
         m: usize,
-    ) -> bool {
+    ) -> Result<(), VerifyError> {
         let verifier = verifier::Verifier::new(vp);
-        verifier.verify(
-            proof,
-            t_comm_1,
-            z_h_comm_1,
-            phi_comm_1,
-            x_m_exponent_poly_comm_1,
-            x_exponent_poly_comm_2,
-            x_exponent_poly_2_comm_1,
-            x_exponent_poly_2_comm_2,
-            m,
-        )
+        verifier.verify(proof, phi_comm_1, m)
+    }
+
+    /// Verify `proofs` with a single pairing product, see
+    /// [`verifier::Verifier::batch_verify`].
+    pub fn batch_verify(
+        vp: &BalooVerifierParam,
+        proofs: &[BalooProof],
+        phi_comms: &[UnivariateKzgCommitment<G1Affine>],
+        m: usize,
+    ) -> Result<(), VerifyError> {
+        let verifier = verifier::Verifier::new(vp);
+        verifier.batch_verify(proofs, phi_comms, m)
+    }
+
+    /// Verify a proof of [`Baloo::prove_columns`] for columns of size `m`,
+    /// see [`verifier::Verifier::verify_columns`].
+    pub fn verify_columns(
+        vp: &BalooVerifierParam,
+        proof: &BalooProof,
+        column_comms: &[UnivariateKzgCommitment<G1Affine>],
+        m: usize,
+    ) -> Result<(), VerifyError> {
+        let verifier = verifier::Verifier::new(vp);
+        verifier.verify_columns(proof, column_comms, m)
+    }
+
+    /// Verify a proof of [`Baloo::prove_tuples`] given the verifier param of
+    /// each table column, see [`Baloo::verifier_param`], and the commitment
+    /// of each lookup column.
+    pub fn verify_tuples(
+        vps: &[BalooVerifierParam],
+        proof: &BalooProof,
+        lookup_comms: &[UnivariateKzgCommitment<G1Affine>],
+        m: usize,
+    ) -> Result<(), VerifyError> {
+        tuple::verify_tuples(&vps.iter().collect::<Vec<_>>(), proof, lookup_comms, m)
     }
 
-    pub fn verify_with_param(
+    /// Verify a proof of [`Baloo::prove_indexed`] given the verifier param
+    /// of the table and of its index column, see
+    /// [`Baloo::index_verifier_param`], and `[φ(x)]1` and `[i(x)]1` of
+    /// [`Baloo::prepare_indexed_verification_data`].
+    pub fn verify_indexed(
         vp: &BalooVerifierParam,
-        proof: &Vec<u8>,
-        t_comm_1: &UnivariateKzgCommitment<G1Affine>,
-        z_h_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        index_vp: &BalooVerifierParam,
+        proof: &BalooProof,
         phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
-        x_m_exponent_poly_comm_1: &UnivariateKzgCommitment<G1Affine>,
-        x_exponent_poly_comm_2: &UnivariateKzgCommitment<G2Affine>,
-        x_exponent_poly_2_comm_1: &UnivariateKzgCommitment<G1Affine>,
-        x_exponent_poly_2_comm_2: &UnivariateKzgCommitment<G2Affine>,
+        index_comm_1: &UnivariateKzgCommitment<G1Affine>,
         m: usize,
-    ) -> bool {
-        let verifier = verifier::Verifier::new(&vp.vp);
-        verifier.verify(
-            proof,
-            t_comm_1,
-            z_h_comm_1,
-            phi_comm_1,
-            x_m_exponent_poly_comm_1,
-            x_exponent_poly_comm_2,
-            x_exponent_poly_2_comm_1,
-            x_exponent_poly_2_comm_2,
-            m,
-        )
+    ) -> Result<(), VerifyError> {
+        indexed::verify_indexed(vp, index_vp, proof, phi_comm_1, index_comm_1, m)
+    }
+
+    /// Verify a proof of [`Baloo::prove_multi`] given the verifier param of
+    /// each table, see [`Baloo::verifier_param`], and `[φ_i(x)]1` and the
+    /// size of each lookup.
+    pub fn verify_multi(
+        vps: &[BalooVerifierParam],
+        proof: &multi::MultiProof,
+        lookup_comms: &[UnivariateKzgCommitment<G1Affine>],
+        ms: &[usize],
+    ) -> Result<(), VerifyError> {
+        multi::verify_multi(&vps.iter().collect::<Vec<_>>(), proof, lookup_comms, ms)
+    }
+
+    /// Verify a proof of [`Baloo::prove_padded`] given the verifier param of
+    /// the padded table, see [`padding::pad_table`], and `[φ(x)]1` of
+    /// [`Baloo::prepare_padded_verification_data`].
+    pub fn verify_padded(
+        vp: &BalooVerifierParam,
+        proof: &padding::PaddedProof,
+        phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
+    ) -> Result<(), VerifyError> {
+        padding::verify_padded(vp, proof, phi_comm_1)
+    }
+
+    pub fn verify_with_metrics(
+        vp: &BalooVerifierParam,
+        proof: &BalooProof,
+        phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        m: usize,
+    ) -> (Result<(), VerifyError>, VerifyMetrics) {
+        metrics::reset();
+        let start = std::time::Instant::now();
+        let result = Self::verify(vp, proof, phi_comm_1, m);
+        let verify_ms = start.elapsed().as_millis() as u64;
+        let counters = metrics::snapshot();
+
+        let metrics = VerifyMetrics {
+            verify_ms,
+            pairing_count: counters.pairing_count,
+            msm_count: counters.msm_count,
+            scalar_mul_count: counters.msm_scalars,
+        };
+        (result, metrics)
+    }
+
+    pub fn verify_with_phi(
+        vp: &BalooVerifierParam,
+        proof: &BalooProof,
+        phi: &PhiInstance,
+        m: usize,
+    ) -> Result<(), VerifyError> {
+        let verifier = verifier::Verifier::new(vp);
+        verifier.verify_with_phi(proof, phi, m)
     }
 
-    // Helper method to prepare verification data
+    // Helper method to prepare verification data, i.e. [φ(x)]1
     pub fn prepare_verification_data(
-        param: &UnivariateKzgParam<Bn256>,
+        pp: &UnivariateKzgProverParam<Bn256>,
+        lookup: &[Fr],
+    ) -> UnivariateKzgCommitment<G1Affine> {
+        UnivariateKzg::<Bn256>::commit_lagrange(pp, lookup)
+    }
+
+    /// `[φ(x)]1` of `lookup` padded as by [`Baloo::prove_padded`].
+    pub fn prepare_padded_verification_data(
         pp: &UnivariateKzgProverParam<Bn256>,
         table: &[Fr],
         lookup: &[Fr],
-        m: usize,
-        t: usize,
-        d: usize,
+        padding_index: usize,
+    ) -> Result<UnivariateKzgCommitment<G1Affine>, Error> {
+        let lookup = padding::pad_lookup(lookup, table, padding_index)?;
+        Ok(Self::prepare_verification_data(pp, &lookup))
+    }
+
+    /// `[φ_j(x)]1` of each lookup column of [`Baloo::prove_tuples`].
+    pub fn prepare_tuple_verification_data(
+        pp: &UnivariateKzgProverParam<Bn256>,
+        lookup: &[Vec<Fr>],
+    ) -> Vec<UnivariateKzgCommitment<G1Affine>> {
+        lookup
+            .iter()
+            .map(|column| Self::prepare_verification_data(pp, column))
+            .collect()
+    }
+
+    /// `[φ(x)]1` of `lookup` and `[i(x)]1` of `indices` for
+    /// [`Baloo::verify_indexed`].
+    pub fn prepare_indexed_verification_data(
+        pp: &UnivariateKzgProverParam<Bn256>,
+        lookup: &[Fr],
+        indices: &[usize],
     ) -> (
-        UnivariateKzgCommitment<G1Affine>, // t_comm_1
-        UnivariateKzgCommitment<G1Affine>, // z_h_comm_1
-        UnivariateKzgCommitment<G1Affine>, // phi_comm_1
-        UnivariateKzgCommitment<G1Affine>, // x_m_exponent_poly_comm_1
-        UnivariateKzgCommitment<G2Affine>, // x_exponent_poly_comm_2
-        UnivariateKzgCommitment<G1Affine>, // x_exponent_poly_2_comm_1
-        UnivariateKzgCommitment<G2Affine>, // x_exponent_poly_2_comm_2
+        UnivariateKzgCommitment<G1Affine>,
+        UnivariateKzgCommitment<G1Affine>,
     ) {
-        let scalar_0 = Fr::zero();
-        let scalar_1 = Fr::one();
-
-        let z_h_poly_coeffs = vec![scalar_1.neg()]
-            .into_iter()
-            .chain(vec![scalar_0; t - 1])
-            .chain(vec![scalar_1])
-            .collect();
-        let z_h_poly = UnivariatePolynomial::monomial(z_h_poly_coeffs);
-        let z_h_comm_1 = UnivariateKzg::<Bn256>::commit_monomial(pp, z_h_poly.coeffs());
-
-        let t_poly = UnivariatePolynomial::lagrange(table.to_vec()).ifft();
-        let t_comm_1 = UnivariateKzg::<Bn256>::commit_monomial(pp, t_poly.coeffs());
-
-        let phi_poly = UnivariatePolynomial::lagrange(lookup.to_vec()).ifft();
-        let phi_comm_1 = UnivariateKzg::<Bn256>::commit_monomial(pp, phi_poly.coeffs());
-
-        // X^m
-        let x_m_exponent_poly = UnivariatePolynomial::monomial(
-            vec![scalar_0; m]
-                .into_iter()
-                .chain(vec![scalar_1])
-                .collect(),
-        );
-        let x_m_exponent_poly_comm_1 =
-            UnivariateKzg::<Bn256>::commit_monomial(pp, x_m_exponent_poly.clone().coeffs());
-
-        // X^(d-m+1)
-        let coeffs_x_exponent_poly = vec![scalar_0; d - m + 1]
-            .into_iter()
-            .chain(vec![scalar_1])
-            .collect();
-        let x_exponent_poly = UnivariatePolynomial::monomial(coeffs_x_exponent_poly);
-        // [X^(d-m+1)]2
-        let x_exponent_poly_comm_2 =
-            UnivariateKzg::<Bn256>::commit_monomial_g2(param, x_exponent_poly.coeffs());
-
-        // X^(d-m+2)
-        let coeffs_x_exponent_poly_2 = vec![scalar_0; d - m + 2]
-            .into_iter()
-            .chain(vec![scalar_1])
-            .collect();
-        let x_exponent_poly_2 = UnivariatePolynomial::monomial(coeffs_x_exponent_poly_2);
-        let x_exponent_poly_2_comm_1 =
-            UnivariateKzg::<Bn256>::commit_monomial(pp, x_exponent_poly_2.coeffs());
-        let x_exponent_poly_2_comm_2 =
-            UnivariateKzg::<Bn256>::commit_monomial_g2(param, x_exponent_poly_2.coeffs());
-
         (
-            t_comm_1,
-            z_h_comm_1,
-            phi_comm_1,
-            x_m_exponent_poly_comm_1,
-            x_exponent_poly_comm_2,
-            x_exponent_poly_2_comm_1,
-            x_exponent_poly_2_comm_2,
+            Self::prepare_verification_data(pp, lookup),
+            Self::prepare_verification_data(pp, &indexed::index_values(indices)),
         )
     }
 
+    /// Commitment of each lookup column at its positions in the concatenation
+    /// proved by [`Baloo::prove_columns`], i.e. of the column padded with
+    /// zeros elsewhere, so that they sum up to `[φ(x)]1` of the concatenation.
+    pub fn prepare_column_verification_data(
+        pp: &UnivariateKzgProverParam<Bn256>,
+        columns: &[Vec<Fr>],
+    ) -> Result<Vec<UnivariateKzgCommitment<G1Affine>>, Error> {
+        let n = util::concat_columns(columns)?.len();
+        let m = columns[0].len();
+        Ok(columns
+            .iter()
+            .enumerate()
+            .map(|(j, column)| {
+                let mut values = vec![Fr::ZERO; n];
+                values[j * m..(j + 1) * m].copy_from_slice(column);
+                Self::prepare_verification_data(pp, &values)
+            })
+            .collect())
+    }
+
     // Run the full Baloo protocol with given table and lookup
     pub fn test_baloo_by_input(table: Vec<Fr>, lookup: Vec<Fr>) -> Vec<String> {
         let mut timings: Vec<String> = vec![];
@@ -240,48 +801,29 @@ impl Baloo {
 
         let m = lookup.len();
         let t = table.len();
-        let poly_size = std::cmp::max(t, m).next_power_of_two() * 2;
-        let d = poly_size - 2;
 
         // 1. Setup
         let start = std::time::Instant::now();
         let (param, pp, vp) = Baloo::preprocess(t, m).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
         let duration1 = start.elapsed();
         timings.push(format!("Setup and preprocess: {}ms", duration1.as_millis()));
 
         // 2. Generate proof
         let start = std::time::Instant::now();
-        let proof = Baloo::prove(&table, &param, &pp, &lookup);
+        let proof = Baloo::prove(&table, &param, &pp, &lookup).unwrap();
         let duration2 = start.elapsed();
         timings.push(format!("Prove: {}ms", duration2.as_millis()));
+        timings.push(format!("Proof size: {}bytes", proof.to_bytes().len()));
 
         // 3. Prepare verification data
         let start = std::time::Instant::now();
-        let (
-            t_comm_1,
-            z_h_comm_1,
-            phi_comm_1,
-            x_m_exponent_poly_comm_1,
-            x_exponent_poly_comm_2,
-            x_exponent_poly_2_comm_1,
-            x_exponent_poly_2_comm_2,
-        ) = Baloo::prepare_verification_data(&param, &pp, &table, &lookup, m, t, d);
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
 
         // 4. Verify
-        let result = Baloo::verify(
-            &vp,
-            &proof,
-            &t_comm_1,
-            &z_h_comm_1,
-            &phi_comm_1,
-            &x_m_exponent_poly_comm_1,
-            &x_exponent_poly_comm_2,
-            &x_exponent_poly_2_comm_1,
-            &x_exponent_poly_2_comm_2,
-            m,
-        );
+        let result = Baloo::verify(&vp, &proof, &phi_comm_1, m);
 
-        assert!(result);
+        result.unwrap();
         let duration3 = start.elapsed();
         timings.push(format!("Verify: {}ms", duration3.as_millis()));
 
@@ -299,11 +841,55 @@ impl Baloo {
     }
 }
 
+impl lookup::LookupScheme<Fr> for Baloo {
+    type Param = UnivariateKzgParam<Bn256>;
+    type ProverParam = BalooProverParam;
+    type VerifierParam = BalooVerifierParam;
+    type LookupCommitment = UnivariateKzgCommitment<G1Affine>;
+    type Proof = BalooProof;
+
+    fn setup(t: usize, m: usize, rng: impl RngCore) -> Result<Self::Param, Error> {
+        UnivariateKzg::<Bn256>::setup(preprocessor::poly_size(t, m), 1, rng)
+    }
+
+    fn preprocess(
+        param: &Self::Param,
+        table: &[Fr],
+        m: usize,
+    ) -> Result<(BalooProverParam, BalooVerifierParam), Error> {
+        Self::preprocess_with_srs(param.clone(), table, m)
+    }
+
+    fn commit_lookup(
+        pp: &BalooProverParam,
+        lookup: &[Fr],
+    ) -> Result<Self::LookupCommitment, Error> {
+        Ok(Self::prepare_verification_data(&pp.pp, lookup))
+    }
+
+    fn prove(pp: &BalooProverParam, lookup: &[Fr]) -> Result<BalooProof, Error> {
+        Self::prove_with_param(pp, &lookup.to_vec())
+    }
+
+    fn proof_to_bytes(proof: &BalooProof) -> Vec<u8> {
+        proof.to_bytes()
+    }
+
+    fn verify(
+        vp: &BalooVerifierParam,
+        phi_comm_1: &Self::LookupCommitment,
+        proof: &BalooProof,
+    ) -> Result<(), Error> {
+        Ok(Self::verify(vp, proof, phi_comm_1, vp.m())?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::transcript::{FieldTranscript, Keccak256Transcript, PoseidonTranscript};
+    use halo2_curves::bn256::G2Affine;
     type Pcs = UnivariateKzg<Bn256>;
-    use std::cmp::max;
     use std::time::Instant;
 
     #[test]
@@ -313,8 +899,6 @@ mod tests {
 
         let m = lookup.len();
         let t = table.len();
-        let poly_size = max(t, m).next_power_of_two() * 2;
-        let d = poly_size - 2;
 
         // 1. Setup
         let start = Instant::now();
@@ -327,7 +911,7 @@ mod tests {
 
         // 2. Generate proof
         let start = Instant::now();
-        let proof = Baloo::prove(&table, &param, &pp, &lookup);
+        let proof = Baloo::prove(&table, &param, &pp, &lookup).unwrap();
         let duration2 = start.elapsed();
         println!(
             "\n ------------prove: {}ms----------- \n",
@@ -336,29 +920,11 @@ mod tests {
 
         // 3. Prepare verification data
         let start = Instant::now();
-        let (
-            t_comm_1,
-            z_h_comm_1,
-            phi_comm_1,
-            x_m_exponent_poly_comm_1,
-            x_exponent_poly_comm_2,
-            x_exponent_poly_2_comm_1,
-            x_exponent_poly_2_comm_2,
-        ) = Baloo::prepare_verification_data(&param, &pp, &table, &lookup, m, t, d);
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
 
         // 4. Verify the proof
-        let result = Baloo::verify(
-            &vp,
-            &proof,
-            &t_comm_1,
-            &z_h_comm_1,
-            &phi_comm_1,
-            &x_m_exponent_poly_comm_1,
-            &x_exponent_poly_comm_2,
-            &x_exponent_poly_2_comm_1,
-            &x_exponent_poly_2_comm_2,
-            m,
-        );
+        let result = Baloo::verify(&vp, &proof, &phi_comm_1, m);
 
         let duration3 = start.elapsed();
         println!(
@@ -366,7 +932,7 @@ mod tests {
             duration3.as_millis()
         );
 
-        assert!(result);
+        assert_eq!(result, Ok(()));
         println!("Finished to verify: baloo");
     }
 
@@ -376,9 +942,6 @@ mod tests {
         let (table, lookup) = generate_table_and_lookup(8, 4);
 
         let m = lookup.len();
-        let t = table.len();
-        let poly_size = max(t, m).next_power_of_two() * 2;
-        let d = poly_size - 2;
 
         // 1. Setup using the info struct API
         let start = Instant::now();
@@ -397,7 +960,7 @@ mod tests {
 
         // 2. Generate proof using the new API
         let start = Instant::now();
-        let proof = Baloo::prove_with_param(&pp, &lookup);
+        let proof = Baloo::prove_with_param(&pp, &lookup).unwrap();
         let duration2 = start.elapsed();
         println!(
             "\n ------------prove with param: {}ms----------- \n",
@@ -407,29 +970,10 @@ mod tests {
         // 3. Prepare verification data and verify
         let start = Instant::now();
 
-        let (
-            t_comm_1,
-            z_h_comm_1,
-            phi_comm_1,
-            x_m_exponent_poly_comm_1,
-            x_exponent_poly_comm_2,
-            x_exponent_poly_2_comm_1,
-            x_exponent_poly_2_comm_2,
-        ) = Baloo::prepare_verification_data(&pp.param, &pp.pp, &table, &lookup, m, t, d);
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp.pp, &lookup);
 
         // Verify the proof
-        let result = Baloo::verify_with_param(
-            &vp,
-            &proof,
-            &t_comm_1,
-            &z_h_comm_1,
-            &phi_comm_1,
-            &x_m_exponent_poly_comm_1,
-            &x_exponent_poly_comm_2,
-            &x_exponent_poly_2_comm_1,
-            &x_exponent_poly_2_comm_2,
-            m,
-        );
+        let result = Baloo::verify(&vp, &proof, &phi_comm_1, m);
 
         let duration3 = start.elapsed();
         println!(
@@ -437,10 +981,666 @@ mod tests {
             duration3.as_millis()
         );
 
-        assert!(result);
+        assert_eq!(result, Ok(()));
         println!("Finished to verify: baloo with info");
     }
 
+    fn prove_and_verify_public(lookup: &Vec<Fr>, claimed: &Vec<Fr>) -> Result<(), VerifyError> {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let m = lookup.len();
+        let t = table.len();
+
+        let (param, pp, vp) = Baloo::preprocess(t, m).unwrap();
+        let proof = Baloo::prove_public(&table, &param, &pp, lookup).unwrap();
+        // no opening of φ in the proof
+        assert_eq!(proof.v2, None);
+        assert_eq!(
+            proof.to_bytes().len() + 32,
+            Baloo::prove(&table, &param, &pp, lookup)
+                .unwrap()
+                .to_bytes()
+                .len()
+        );
+
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        Baloo::verify_with_phi(&vp, &proof, &PhiInstance::Public(claimed.clone()), m)
+    }
+
+    #[test]
+    fn test_baloo_public_lookup() {
+        let lookup = [3, 7, 3, 4].map(Fr::from).to_vec();
+        assert_eq!(prove_and_verify_public(&lookup, &lookup), Ok(()));
+    }
+
+    #[test]
+    fn test_baloo_public_lookup_mismatch() {
+        let lookup = [3, 7, 3, 4].map(Fr::from).to_vec();
+        let claimed = [3, 7, 3, 5].map(Fr::from).to_vec();
+        // the claimed values are bound by the transcript, so the challenges
+        // differ from the prover's ones
+        assert_eq!(
+            prove_and_verify_public(&lookup, &claimed),
+            Err(VerifyError::Subtable)
+        );
+        assert!(matches!(
+            prove_and_verify_public(&lookup, &claimed[..2].to_vec()),
+            Err(VerifyError::Instance(_))
+        ));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_baloo_metrics() {
+        let (table, lookup) = generate_table_and_lookup(8, 4);
+        let m = lookup.len();
+        let t = table.len();
+
+        let (param, pp, vp) = Baloo::preprocess(t, m).unwrap();
+        let (proof, prove_metrics) =
+            Baloo::prove_with_metrics(&table, &param, &pp, &lookup).unwrap();
+        // [t]1, [z_H]1, [φ]1 of the instance, and [ξ]1, [z_I]2, [t_I]1, [D]1,
+        // [R]1, [Q_D]1, [E]1, [Q_E]1, [a]1, [w1..w4]1 of the proof
+        assert_eq!(prove_metrics.msm_count, 16);
+        assert!(prove_metrics.fft_count > 0);
+        assert_eq!(prove_metrics.round_ms.len(), 3);
+
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        let (result, verify_metrics) = Baloo::verify_with_metrics(&vp, &proof, &phi_comm_1, m);
+        assert_eq!(result, Ok(()));
+        // the 14 pairing terms of the checks grouped by [1]2, [s]2, [z_I]2,
+        // [x^(d-m+1)]2 and [x^(d-m+2)]2, with an MSM for each group
+        assert_eq!(verify_metrics.pairing_count, 5);
+        assert_eq!(verify_metrics.msm_count, 5);
+        assert!(verify_metrics.scalar_mul_count >= verify_metrics.msm_count);
+    }
+
+    #[test]
+    fn test_baloo_instance_digest() {
+        let (table, lookup) = generate_table_and_lookup(8, 4);
+        let m = lookup.len();
+        let t = table.len();
+        let (param, pp, vp) = Baloo::preprocess(t, m).unwrap();
+
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let instance = Instance {
+            m,
+            t_comm_1: vp.t_comm_1().clone(),
+            z_h_comm_1: vp.z_h_comm_1().clone(),
+            srs_digest: vp.srs_digest(),
+            phi: PhiInstance::Committed(Baloo::prepare_verification_data(&pp, &lookup)),
+        };
+        let prover = prover::Prover::new(&table, &param, &pp);
+        assert_eq!(
+            lookup::Instance::digest(&prover.instance(&lookup, false).unwrap()),
+            lookup::Instance::digest(&instance)
+        );
+
+        let other_m = Instance {
+            m: 2 * m,
+            ..instance.clone()
+        };
+        assert_ne!(
+            lookup::Instance::digest(&other_m),
+            lookup::Instance::digest(&instance)
+        );
+
+        let other_table = table.iter().map(|value| value.double()).collect::<Vec<_>>();
+        let other_table = Instance {
+            t_comm_1: preprocessor::TablePreprocessing::new(&pp, &other_table)
+                .unwrap()
+                .t_comm_1()
+                .clone(),
+            ..instance.clone()
+        };
+        assert_ne!(
+            lookup::Instance::digest(&other_table),
+            lookup::Instance::digest(&instance)
+        );
+
+        // another SRS of the same sizes
+        let (other_param, other_pp, other_vp) = Baloo::preprocess(t, m).unwrap();
+        let other_vp =
+            Baloo::verifier_param(&other_param, &other_pp, &other_vp, &table, m).unwrap();
+        let other_srs = Instance {
+            srs_digest: other_vp.srs_digest(),
+            ..instance.clone()
+        };
+        assert_ne!(
+            lookup::Instance::digest(&other_srs),
+            lookup::Instance::digest(&instance)
+        );
+
+        let public = Instance {
+            phi: PhiInstance::Public(lookup.clone()),
+            ..instance.clone()
+        };
+        assert_eq!(
+            lookup::Instance::digest(&prover.instance(&lookup, true).unwrap()),
+            lookup::Instance::digest(&public)
+        );
+    }
+
+    #[test]
+    fn test_baloo_domain_separation() {
+        let (table, lookup) = generate_table_and_lookup(8, 4);
+        let m = lookup.len();
+        let (param, pp, vp) = Baloo::preprocess(table.len(), m).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        let proof = Baloo::prove(&table, &param, &pp, &lookup)
+            .unwrap()
+            .to_bytes();
+
+        let verify = |label: Option<&'static [u8]>| {
+            let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+            if let Some(label) = label {
+                FieldTranscript::<Fr>::common_label(&mut transcript, label).unwrap();
+            }
+            verifier::Verifier::new(&vp).verify_in_transcript(&mut transcript, &phi_comm_1, m)
+        };
+        assert_eq!(verify(None), Ok(()));
+        // a statement of an enclosing protocol changes every challenge
+        assert_eq!(verify(Some(b"other")), Err(VerifyError::Subtable));
+    }
+
+    #[test]
+    fn test_baloo_instance_mismatch() {
+        let (table, lookup) = generate_table_and_lookup(8, 4);
+        let m = lookup.len();
+        let t = table.len();
+        let (param, pp, vp) = Baloo::preprocess(t, m).unwrap();
+        let proof = Baloo::prove(&table, &param, &pp, &lookup).unwrap();
+
+        // the table commitment doesn't match the one bound by the prover
+        let other_table = table.iter().map(|value| value.double()).collect::<Vec<_>>();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &other_table, m).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        assert_eq!(
+            Baloo::verify(&vp, &proof, &phi_comm_1, m),
+            Err(VerifyError::Subtable)
+        );
+    }
+
+    #[test]
+    fn test_baloo_verify_error() {
+        let (table, lookup) = generate_table_and_lookup(8, 4);
+        let m = lookup.len();
+        let t = table.len();
+        let (param, pp, vp) = Baloo::preprocess(t, m).unwrap();
+        let proof = Baloo::prove(&table, &param, &pp, &lookup).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        let verify = |proof: &BalooProof, m: usize| Baloo::verify(&vp, proof, &phi_comm_1, m);
+        assert_eq!(verify(&proof, m), Ok(()));
+
+        // swap [w3]1 and [w4]1
+        let mut invalid_proof = proof.clone();
+        std::mem::swap(&mut invalid_proof.w3_comm_1, &mut invalid_proof.w4_comm_1);
+        assert_eq!(
+            verify(&invalid_proof, m),
+            Err(VerifyError::Evaluation("w3"))
+        );
+
+        // rejected while parsing
+        let bytes = proof.to_bytes();
+        assert!(matches!(
+            BalooProof::<Bn256>::from_bytes(&bytes[..bytes.len() - 1]).map_err(VerifyError::from),
+            Err(VerifyError::Transcript(_))
+        ));
+        // a [z_I]2 on the twist but not in the subgroup of prime order
+        let mut x = halo2_curves::bn256::Fq2::ONE;
+        let off_subgroup = loop {
+            let y = (x.square() * x + G2Affine::b()).sqrt();
+            if let Some(y) = Option::from(y) {
+                break G2Affine::from_xy(x, y).unwrap();
+            }
+            x += halo2_curves::bn256::Fq2::ONE;
+        };
+        let mut invalid_proof = proof.clone();
+        invalid_proof.z_i_comm_2 = off_subgroup;
+        assert!(matches!(
+            BalooProof::<Bn256>::from_bytes(&invalid_proof.to_bytes()),
+            Err(Error::Transcript(..))
+        ));
+        assert!(matches!(
+            verify(&invalid_proof, m),
+            Err(VerifyError::Transcript(Error::Transcript(..)))
+        ));
+        assert!(matches!(verify(&proof, 3), Err(VerifyError::Instance(_))));
+        // the shifts of the verifier param are for lookups of size m only
+        assert_eq!(
+            verify(&proof, 2 * m),
+            Err(VerifyError::Instance(
+                "Verifier param is for lookups of size 4 but got 8".to_string()
+            ))
+        );
+        // or before the challenges are derived, if it doesn't open φ(α)
+        let public_proof = Baloo::prove_public(&table, &param, &pp, &lookup).unwrap();
+        assert!(matches!(
+            verify(&public_proof, m),
+            Err(VerifyError::Instance(_))
+        ));
+
+        // and usable with `?` in functions returning `Error`
+        let err: Error = VerifyError::Degree("w1").into();
+        assert_eq!(
+            err,
+            Error::InvalidSnark("w1 pairing check failed".to_string())
+        );
+    }
+
+    #[test]
+    fn test_baloo_batch_verify() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookups = [[3, 7, 3, 4], [5, 5, 2, 2], [8, 2, 6, 5]]
+            .map(|lookup| lookup.map(Fr::from).to_vec())
+            .to_vec();
+        let m = 4;
+        let (param, pp, vp) = Baloo::preprocess(table.len(), m).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let proofs = lookups
+            .iter()
+            .map(|lookup| Baloo::prove(&table, &param, &pp, lookup).unwrap())
+            .collect::<Vec<_>>();
+        let phi_comms = lookups
+            .iter()
+            .map(|lookup| Baloo::prepare_verification_data(&pp, lookup))
+            .collect::<Vec<_>>();
+        assert_eq!(Baloo::batch_verify(&vp, &proofs, &phi_comms, m), Ok(()));
+        assert_eq!(Baloo::batch_verify(&vp, &[], &[], m), Ok(()));
+
+        #[cfg(feature = "metrics")]
+        {
+            // [1]2, [s]2, [x^(d-m+1)]2, [x^(d-m+2)]2 and [z_I]2 of each proof
+            metrics::reset();
+            Baloo::batch_verify(&vp, &proofs, &phi_comms, m).unwrap();
+            assert_eq!(metrics::snapshot().pairing_count, 4 + proofs.len() as u64);
+        }
+
+        // a single invalid proof fails the batch, with the check it fails
+        let mut invalid_proofs = proofs.clone();
+        let invalid_proof = &mut invalid_proofs[1];
+        std::mem::swap(&mut invalid_proof.w3_comm_1, &mut invalid_proof.w4_comm_1);
+        assert_eq!(
+            Baloo::batch_verify(&vp, &invalid_proofs, &phi_comms, m),
+            Err(VerifyError::Evaluation("w3"))
+        );
+        // as does a proof against another lookup
+        let mut other_phi_comms = phi_comms.clone();
+        other_phi_comms.swap(0, 2);
+        assert_eq!(
+            Baloo::batch_verify(&vp, &proofs, &other_phi_comms, m),
+            Err(VerifyError::Subtable)
+        );
+        assert!(matches!(
+            Baloo::batch_verify(&vp, &proofs, &phi_comms[..2], m),
+            Err(VerifyError::Instance(_))
+        ));
+    }
+
+    #[test]
+    fn test_baloo_verify_stream() {
+        // a byte at a time, as of a slow socket
+        struct Trickle<'a>(&'a [u8]);
+
+        impl std::io::Read for Trickle<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let n = buf.len().min(self.0.len()).min(1);
+                buf[..n].copy_from_slice(&self.0[..n]);
+                self.0 = &self.0[n..];
+                Ok(n)
+            }
+        }
+
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookups = [[3, 7, 3, 4], [5, 5, 2, 2]]
+            .map(|lookup| lookup.map(Fr::from).to_vec())
+            .to_vec();
+        let m = 4;
+        let (param, pp, vp) = Baloo::preprocess(table.len(), m).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let verifier = verifier::Verifier::new(&vp);
+        let bytes = lookups
+            .iter()
+            .flat_map(|lookup| {
+                Baloo::prove(&table, &param, &pp, lookup)
+                    .unwrap()
+                    .to_bytes()
+            })
+            .collect::<Vec<_>>();
+        let phi_comms = lookups
+            .iter()
+            .map(|lookup| Baloo::prepare_verification_data(&pp, lookup))
+            .collect::<Vec<_>>();
+
+        // the bytes of the next proof are left in the reader
+        let mut reader = Trickle(&bytes);
+        assert_eq!(
+            verifier.verify_stream(&mut reader, &phi_comms[0], m),
+            Ok(())
+        );
+        assert_eq!(reader.0, &bytes[bytes.len() / 2..]);
+        assert_eq!(
+            verifier.batch_verify_stream(Trickle(&bytes), &phi_comms, m),
+            Ok(())
+        );
+
+        assert!(matches!(
+            verifier.verify_stream(&bytes[..bytes.len() / 2 - 1], &phi_comms[0], m),
+            Err(VerifyError::Transcript(_))
+        ));
+        assert!(verifier
+            .batch_verify_stream(
+                bytes.as_slice(),
+                &[phi_comms[1].clone(), phi_comms[0].clone()],
+                m
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_baloo_poseidon_transcript() {
+        let (table, lookup) = generate_table_and_lookup(8, 4);
+        let m = lookup.len();
+        let (param, pp, vp) = Baloo::preprocess(table.len(), m).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+
+        let proof = prover::Prover::new(&table, &param, &pp)
+            .prove_in::<PoseidonTranscript<Fr, Cursor<Vec<u8>>>>(&lookup)
+            .unwrap();
+        let verifier = verifier::Verifier::new(&vp);
+        assert_eq!(
+            verifier.verify_in::<PoseidonTranscript<Fr, Cursor<Vec<u8>>>>(&proof, &phi_comm_1, m),
+            Ok(())
+        );
+        // the encoding is the same but the challenges differ
+        assert!(Baloo::verify(&vp, &proof, &phi_comm_1, m).is_err());
+    }
+
+    #[cfg(feature = "merlin")]
+    #[test]
+    fn test_baloo_merlin_transcript() {
+        use crate::util::transcript::MerlinTranscript;
+
+        let (table, lookup) = generate_table_and_lookup(8, 4);
+        let m = lookup.len();
+        let (param, pp, vp) = Baloo::preprocess(table.len(), m).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        let app_transcript = || {
+            let mut transcript = merlin::Transcript::new(b"app");
+            transcript.append_message(b"statement", b"lookup");
+            transcript
+        };
+
+        let mut transcript = MerlinTranscript::from_transcript(app_transcript(), Vec::new());
+        prover::Prover::new(&table, &param, &pp)
+            .prove_in_transcript(&lookup, &mut transcript)
+            .unwrap();
+        let (_, proof) = transcript.into_transcript();
+
+        let verifier = verifier::Verifier::new(&vp);
+        let mut transcript = MerlinTranscript::from_transcript(app_transcript(), proof.as_slice());
+        assert_eq!(
+            verifier.verify_in_transcript(&mut transcript, &phi_comm_1, m),
+            Ok(())
+        );
+        let mut transcript =
+            MerlinTranscript::from_transcript(merlin::Transcript::new(b"app"), proof.as_slice());
+        assert!(verifier
+            .verify_in_transcript(&mut transcript, &phi_comm_1, m)
+            .is_err());
+    }
+
+    #[test]
+    fn test_baloo_circuit_lookup() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let rows = [3, 7, 5].map(Fr::from).to_vec();
+        let m = 4;
+        let (param, pp, vp) = Baloo::preprocess(table.len(), m).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+
+        // messages of the outer proof before and after the lookup
+        let lookup = circuit::CircuitLookup::new(prover::Prover::new(&table, &param, &pp));
+        assert_eq!(lookup.lookup_values(&rows).unwrap()[3], table[0]);
+        let mut transcript = Keccak256Transcript::<Cursor<Vec<u8>>>::new(());
+        transcript.write_field_element(&Fr::from(42)).unwrap();
+        let phi_comm_1 = lookup.prove(&rows, &mut transcript).unwrap();
+        transcript.write_field_element(&Fr::from(43)).unwrap();
+        let proof = transcript.into_proof();
+
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        assert_eq!(transcript.read_field_element().unwrap(), Fr::from(42));
+        assert_eq!(
+            circuit::verify_circuit_lookup(&vp, &mut transcript, m),
+            Ok(phi_comm_1)
+        );
+        assert_eq!(transcript.read_field_element().unwrap(), Fr::from(43));
+
+        // bound to the outer messages before it
+        let mut tampered = proof.clone();
+        tampered[0] ^= 1;
+        let mut transcript = Keccak256Transcript::from_proof((), tampered.as_slice());
+        transcript.read_field_element().unwrap();
+        assert!(circuit::verify_circuit_lookup(&vp, &mut transcript, m).is_err());
+
+        // and failing for rows out of the table
+        let rows = [9, 7, 5].map(Fr::from).to_vec();
+        let mut transcript = Keccak256Transcript::<Cursor<Vec<u8>>>::new(());
+        assert!(lookup.prove(&rows, &mut transcript).is_err());
+    }
+
+    #[test]
+    fn test_baloo_padded() {
+        let table = (1..=6).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = [6, 2, 6].map(Fr::from).to_vec();
+        let (param, pp, vp) = Baloo::preprocess(table.len(), lookup.len()).unwrap();
+        let padded_table = padding::pad_table(&table).unwrap();
+        assert_eq!(padded_table[6..], [Fr::from(6), Fr::from(6)]);
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &padded_table, 4).unwrap();
+        let phi_comm_1 = Baloo::prepare_padded_verification_data(&pp, &table, &lookup, 1).unwrap();
+
+        let proof = Baloo::prove_padded(&table, &param, &pp, &lookup, 1).unwrap();
+        assert_eq!((proof.lookup_size(), proof.table_size()), (3, 6));
+        assert_eq!(Baloo::verify_padded(&vp, &proof, &phi_comm_1), Ok(()));
+        let reloaded = padding::PaddedProof::from_bytes(&proof.to_bytes()).unwrap();
+        assert_eq!(reloaded, proof);
+
+        // bound to the sizes before padding
+        let mut bytes = proof.to_bytes();
+        bytes[0] = 4;
+        let tampered = padding::PaddedProof::from_bytes(&bytes).unwrap();
+        assert!(Baloo::verify_padded(&vp, &tampered, &phi_comm_1).is_err());
+        let mut bytes = proof.to_bytes();
+        bytes[8] = 7;
+        let tampered = padding::PaddedProof::from_bytes(&bytes).unwrap();
+        assert!(Baloo::verify_padded(&vp, &tampered, &phi_comm_1).is_err());
+
+        assert_eq!(
+            Baloo::prove_padded(&table, &param, &pp, &lookup, 6),
+            Err(Error::SizeOutOfRange {
+                name: "padding index",
+                size: 6,
+                max: 6
+            })
+        );
+    }
+
+    #[test]
+    fn test_baloo_columns() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let columns = [[3, 7, 3, 4], [5, 5, 2, 2]]
+            .map(|column| column.map(Fr::from).to_vec())
+            .to_vec();
+        let m = 4;
+        let (param, pp, vp) = Baloo::preprocess(table.len(), columns.len() * m).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, columns.len() * m).unwrap();
+
+        let proof = Baloo::prove_columns(&table, &param, &pp, &columns).unwrap();
+        let column_comms = Baloo::prepare_column_verification_data(&pp, &columns).unwrap();
+        assert_eq!(Baloo::verify_columns(&vp, &proof, &column_comms, m), Ok(()));
+        // the column commitments sum up to the one of the concatenation
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &columns.concat());
+        assert_eq!(Baloo::verify(&vp, &proof, &phi_comm_1, 8), Ok(()));
+
+        // columns in the other order, or committed as standalone lookups
+        let swapped_columns = vec![columns[1].clone(), columns[0].clone()];
+        let swapped_comms = Baloo::prepare_column_verification_data(&pp, &swapped_columns).unwrap();
+        assert!(Baloo::verify_columns(&vp, &proof, &swapped_comms, m).is_err());
+        let standalone_comms = columns
+            .iter()
+            .map(|column| Baloo::prepare_verification_data(&pp, column))
+            .collect::<Vec<_>>();
+        assert!(Baloo::verify_columns(&vp, &proof, &standalone_comms, m).is_err());
+        assert!(matches!(
+            Baloo::verify_columns(&vp, &proof, &[], m),
+            Err(VerifyError::Instance(_))
+        ));
+
+        let three_columns = [columns.clone(), vec![columns[0].clone()]].concat();
+        assert_eq!(
+            Baloo::prove_columns(&table, &param, &pp, &three_columns),
+            Err(Error::NotPowerOfTwo {
+                name: "lookup columns",
+                size: 3
+            })
+        );
+        let uneven_columns = vec![columns[0].clone(), columns[1][..2].to_vec()];
+        assert_eq!(
+            Baloo::prove_columns(&table, &param, &pp, &uneven_columns),
+            Err(Error::SizeMismatch {
+                name: "lookup column",
+                expected: 4,
+                got: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_baloo_tuples() {
+        // rows (i, i^2)
+        let table = [1, 2]
+            .map(|e| (1..=8u64).map(|i| Fr::from(i.pow(e))).collect::<Vec<_>>())
+            .to_vec();
+        let lookup = [[3, 7, 3, 4], [9, 49, 9, 16]]
+            .map(|column| column.map(Fr::from).to_vec())
+            .to_vec();
+        let m = 4;
+        let (param, pp, vp) = Baloo::preprocess(8, m).unwrap();
+        let vps = table
+            .iter()
+            .map(|column| Baloo::verifier_param(&param, &pp, &vp, column, m).unwrap())
+            .collect::<Vec<_>>();
+
+        let proof = Baloo::prove_tuples(&table, &param, &pp, &lookup).unwrap();
+        let lookup_comms = Baloo::prepare_tuple_verification_data(&pp, &lookup);
+        assert_eq!(Baloo::verify_tuples(&vps, &proof, &lookup_comms, m), Ok(()));
+
+        // columns in the other order
+        let swapped_comms = vec![lookup_comms[1].clone(), lookup_comms[0].clone()];
+        assert!(Baloo::verify_tuples(&vps, &proof, &swapped_comms, m).is_err());
+        assert!(matches!(
+            Baloo::verify_tuples(&vps, &proof, &lookup_comms[..1], m),
+            Err(VerifyError::Instance(_))
+        ));
+
+        // (3, 16) isn't a row even though 3 and 16 are in their columns
+        let mixed_lookup = vec![lookup[0].clone(), [9, 49, 16, 16].map(Fr::from).to_vec()];
+        assert_eq!(
+            Baloo::prove_tuples(&table, &param, &pp, &mixed_lookup),
+            Err(Error::NotInTable { positions: vec![2] })
+        );
+    }
+
+    #[test]
+    fn test_baloo_indexed() {
+        // 3 is at both 2 and 4
+        let table = [1, 2, 3, 4, 3, 6, 7, 8].map(Fr::from).to_vec();
+        let lookup = [3, 7, 3, 4].map(Fr::from).to_vec();
+        let indices = [2, 6, 4, 3];
+        let m = lookup.len();
+        let (param, pp, vp) = Baloo::preprocess(table.len(), m).unwrap();
+        let index_vp = Baloo::index_verifier_param(&param, &pp, &vp, table.len(), m).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+
+        let proof = Baloo::prove_indexed(&table, &param, &pp, &lookup, &indices).unwrap();
+        let (phi_comm_1, index_comm_1) =
+            Baloo::prepare_indexed_verification_data(&pp, &lookup, &indices);
+        assert_eq!(
+            Baloo::verify_indexed(&vp, &index_vp, &proof, &phi_comm_1, &index_comm_1, m),
+            Ok(())
+        );
+
+        // the same values at other indices
+        let first_indices = indexed::first_indices(&table, &lookup).unwrap();
+        assert_eq!(first_indices, vec![2, 6, 2, 3]);
+        let (_, other_index_comm_1) =
+            Baloo::prepare_indexed_verification_data(&pp, &lookup, &first_indices);
+        assert!(
+            Baloo::verify_indexed(&vp, &index_vp, &proof, &phi_comm_1, &other_index_comm_1, m)
+                .is_err()
+        );
+        assert_eq!(
+            Baloo::prove_indexed(&table, &param, &pp, &lookup, &[2, 6, 3, 3]),
+            Err(Error::InvalidSnark(
+                "Lookup value 2 isn't the table value at index 3".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_baloo_multi() {
+        // a range table and a smaller table of squares
+        let tables = vec![
+            (0..16).map(Fr::from).collect::<Vec<_>>(),
+            (1..=8u64).map(|i| Fr::from(i * i)).collect::<Vec<_>>(),
+        ];
+        let lookups = [[3, 15, 0, 3], [49, 4, 64, 4]]
+            .map(|lookup| lookup.map(Fr::from).to_vec())
+            .to_vec();
+        let ms = [4, 4];
+        let (param, pp, vp) = Baloo::preprocess(16, 4).unwrap();
+        let vps = tables
+            .iter()
+            .map(|table| Baloo::verifier_param(&param, &pp, &vp, table, 4).unwrap())
+            .collect::<Vec<_>>();
+
+        let proof = Baloo::prove_multi(&tables, &param, &pp, &lookups).unwrap();
+        let lookup_comms = lookups
+            .iter()
+            .map(|lookup| Baloo::prepare_verification_data(&pp, lookup))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            Baloo::verify_multi(&vps, &proof, &lookup_comms, &ms),
+            Ok(())
+        );
+        let reloaded = multi::MultiProof::from_bytes(&proof.to_bytes(), 2).unwrap();
+        assert_eq!(reloaded, proof);
+
+        // lookups swapped between the tables
+        let swapped_comms = vec![lookup_comms[1].clone(), lookup_comms[0].clone()];
+        assert!(Baloo::verify_multi(&vps, &proof, &swapped_comms, &ms).is_err());
+        assert!(matches!(
+            Baloo::verify_multi(&vps[..1], &proof, &lookup_comms[..1], &ms[..1]),
+            Err(VerifyError::Instance(_))
+        ));
+
+        // a table preprocessed with another SRS
+        let (other_param, other_pp, other_vp) = Baloo::preprocess(16, 4).unwrap();
+        let other_vps = vec![
+            vps[0].clone(),
+            Baloo::verifier_param(&other_param, &other_pp, &other_vp, &tables[1], 4).unwrap(),
+        ];
+        assert_eq!(
+            Baloo::verify_multi(&other_vps, &proof, &lookup_comms, &ms),
+            Err(VerifyError::Instance(
+                "Tables should be preprocessed with the same SRS".to_string()
+            ))
+        );
+    }
+
     #[test]
     fn test_baloo_by_input() {
         let table_size = 2_usize.pow(6);
@@ -0,0 +1,77 @@
+//! Baloo in place of the lookup argument of a plonkish constraint system,
+//! whose lookup of a column into a fixed table is proved in the transcript of
+//! the outer proof.
+//!
+//! The prover writes `[φ(x)]1` of the looked-up column, padded to a power of
+//! two, and then the Baloo proof, both after the messages of the outer proof
+//! so far, so the challenges of the lookup bind the whole outer statement.
+//! The verifier reads them back at the same point of the outer transcript
+//! and returns `[φ(x)]1`, for the outer verifier to check it against its own
+//! commitment of the column, e.g. by an opening at a common point.
+
+use crate::{
+    backend::{
+        baloo::{
+            prover::Prover, verifier::Verifier, BalooEngine, BalooPcs, BalooVerifierParam,
+            VerifyError,
+        },
+        lookup::witness::pad_to_power_of_two,
+    },
+    pcs::univariate::{UnivariateKzg, UnivariateKzgCommitment},
+    util::transcript::{G2TranscriptRead, G2TranscriptWrite, TranscriptRead, TranscriptWrite},
+    Error,
+};
+use halo2_curves::bn256::Bn256;
+
+/// Prover side of a lookup of a circuit column into the table of `prover`.
+pub struct CircuitLookup<'a, M: BalooEngine = Bn256, Pcs: BalooPcs<M> = UnivariateKzg<M>> {
+    prover: Prover<'a, M, Pcs>,
+}
+
+impl<'a, M: BalooEngine, Pcs: BalooPcs<M>> CircuitLookup<'a, M, Pcs> {
+    pub fn new(prover: Prover<'a, M, Pcs>) -> Self {
+        Self { prover }
+    }
+
+    /// Values of `rows` padded with the first table value to the next power
+    /// of two, which is the `m` of the lookup, as the padding is in the table
+    /// whatever the rows are.
+    pub fn lookup_values(&self, rows: &[M::Scalar]) -> Result<Vec<M::Scalar>, Error> {
+        let padding = *self
+            .prover
+            .table()
+            .first()
+            .ok_or_else(|| Error::InvalidSnark("Table should be non-empty".to_string()))?;
+        Ok(pad_to_power_of_two(rows, padding))
+    }
+
+    /// Write `[φ(x)]1` of the witness values of the looked-up column, one per
+    /// row, and the Baloo proof of their lookup into `transcript` of the
+    /// outer proof, and return `[φ(x)]1`.
+    pub fn prove(
+        &self,
+        rows: &[M::Scalar],
+        transcript: &mut (impl TranscriptWrite<M::G1Affine, M::Scalar>
+                  + G2TranscriptWrite<M::G2Affine, M::Scalar>),
+    ) -> Result<UnivariateKzgCommitment<M::G1Affine>, Error> {
+        let lookup = self.lookup_values(rows)?;
+        let phi_comm_1 = Pcs::commit_lagrange(self.prover.pp(), &lookup);
+        transcript.write_commitment(&phi_comm_1.0)?;
+        self.prover.prove_in_transcript(&lookup, transcript)?;
+        Ok(phi_comm_1)
+    }
+}
+
+/// Read `[φ(x)]1` and the Baloo proof of [`CircuitLookup::prove`] for a
+/// padded lookup of size `m` from `transcript` of the outer proof, and return
+/// `[φ(x)]1` once the proof is verified.
+pub fn verify_circuit_lookup<M: BalooEngine, Pcs: BalooPcs<M>>(
+    vp: &BalooVerifierParam<M, Pcs>,
+    transcript: &mut (impl TranscriptRead<M::G1Affine, M::Scalar>
+              + G2TranscriptRead<M::G2Affine, M::Scalar>),
+    m: usize,
+) -> Result<UnivariateKzgCommitment<M::G1Affine>, VerifyError> {
+    let phi_comm_1 = UnivariateKzgCommitment(transcript.read_commitment()?);
+    Verifier::new(vp).verify_in_transcript(transcript, &phi_comm_1, m)?;
+    Ok(phi_comm_1)
+}
@@ -0,0 +1,265 @@
+//! Degree bounds of the polynomials of the Baloo prover, and the shifts `X^(d-m+1)`
+//! and `X^(d-m+2)` of the verifier's degree checks, derived in one place so
+//! that the two sides can't diverge.
+//!
+//! With lookup size `m`, table size `t`, `k = |I| ≤ m` distinct lookup values
+//! and SRS degree bound `d`.
+//!
+//! Unlike [`DegreeBoundCommitmentScheme`], which commits to the shift of each
+//! bounded polynomial on its own, the shifts here are folded into the
+//! quotients `w1` and `w2` of the openings at `α`, saving commitments.
+//!
+//! [`DegreeBoundCommitmentScheme`]: crate::pcs::DegreeBoundCommitmentScheme
+
+use crate::{poly::univariate::UnivariatePolynomial, util::arithmetic::Field, Error};
+
+/// Exponent of the shift in `w1`, which proves `deg(E) < m` (and `deg(φ) < m`)
+/// by `X^(d-m+1) * (E(X) - E(α)) / (X - α)` having degree at most `d - 1`.
+pub fn w1_shift(d: usize, m: usize) -> usize {
+    d - m + 1
+}
+
+/// Exponent of the shift in the verifier's `w2` check, which proves
+/// `deg(z_I) ≤ m` and `deg(R) < m - 1` by `X^(d-m+1) * (z_I(X) - X^m)`
+/// having degree at most `d + 1`, the largest the SRS supports.
+pub fn w2_shift(d: usize, m: usize) -> usize {
+    d - m + 2
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DegreeBounds {
+    d: usize,
+    t: usize,
+    m: usize,
+}
+
+impl DegreeBounds {
+    /// Returns an error if the shifts `X^(d-m+1)` and `X^(d-m+2)`, or the
+    /// table polynomial, don't fit in the SRS.
+    pub fn new(d: usize, t: usize, m: usize) -> Result<Self, Error> {
+        if m == 0 || m > d {
+            return Err(Error::SizeOutOfRange {
+                name: "lookup",
+                size: m,
+                max: d,
+            });
+        }
+        if t == 0 || t > d + 1 {
+            return Err(Error::SizeOutOfRange {
+                name: "table",
+                size: t,
+                max: d + 1,
+            });
+        }
+        Ok(Self { d, t, m })
+    }
+
+    pub fn w1_shift(&self) -> usize {
+        w1_shift(self.d, self.m)
+    }
+
+    pub fn w2_shift(&self) -> usize {
+        w2_shift(self.d, self.m)
+    }
+
+    // φ(X), interpolated over V
+    pub fn phi(&self) -> usize {
+        self.m - 1
+    }
+
+    // t(X), interpolated over H
+    pub fn t(&self) -> usize {
+        self.t - 1
+    }
+
+    // ξ(X), interpolated over V
+    pub fn v(&self) -> usize {
+        self.m - 1
+    }
+
+    // z_I(X) = Π_{i ∈ I}(X - ω^i)
+    pub fn z_i(&self, k: usize) -> usize {
+        k
+    }
+
+    // t_I(X), interpolated over H_I
+    pub fn t_i(&self, k: usize) -> usize {
+        k.saturating_sub(1)
+    }
+
+    // D(X) = Σ_i μ_i(α) * τ_col(i)(X) / τ_col(i)(0)
+    pub fn d(&self, k: usize) -> usize {
+        k.saturating_sub(1)
+    }
+
+    // R(X), the remainder of D(X) * t_I(X) - φ(α) by z_I(X), with R(0) = 0
+    pub fn r(&self, k: usize) -> usize {
+        k.saturating_sub(1)
+    }
+
+    // Q_D(X), the quotient of D(X) * t_I(X) - φ(α) by z_I(X)
+    pub fn q_d(&self, k: usize) -> usize {
+        k.saturating_sub(2)
+    }
+
+    // E(X) = Σ_i μ_i(X) * τ_col(i)(β) / τ_col(i)(0)
+    pub fn e(&self) -> usize {
+        self.m - 1
+    }
+
+    // Q_E(X) = (E(X) * (β - ξ(X)) + ξ(X) * z_I(β) / z_I(0)) / z_V(X)
+    pub fn q_e(&self) -> usize {
+        self.m.saturating_sub(2)
+    }
+
+    pub fn w1(&self) -> usize {
+        self.w1_shift() + self.e().saturating_sub(1)
+    }
+
+    // X^(d-m+1) * (z_I(X) - X^m) has degree d + 1 unless z_I is monic of
+    // degree m
+    pub fn w2(&self) -> usize {
+        (self.w2_shift() - 1) + self.m
+    }
+
+    // (z_I(X) - z_I(β)) / (X - β) has degree m - 1 if |I| = m
+    pub fn w3(&self) -> usize {
+        self.m - 1
+    }
+
+    pub fn w4(&self) -> usize {
+        self.m.saturating_sub(2)
+    }
+
+    // a(X) = (t(X) - t_I(X)) / z_I(X) + γ * z_H(X) / z_I(X)
+    pub fn a(&self, k: usize) -> usize {
+        self.t - k
+    }
+
+    /// Returns [`Error::InvalidSnark`] with `name`, the degree and the bound
+    /// if `poly` has degree greater than `bound`.
+    pub fn check<F: Field>(
+        &self,
+        name: &str,
+        poly: &UnivariatePolynomial<F>,
+        bound: usize,
+    ) -> Result<(), Error> {
+        // leading zeros left by arithmetic don't count
+        let degree = poly
+            .coeffs()
+            .iter()
+            .rposition(|coeff| !bool::from(coeff.is_zero()))
+            .unwrap_or_default();
+        if degree > bound {
+            return Err(Error::InvalidSnark(format!(
+                "Degree of {name} should be at most {bound} but got {degree}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backend::baloo::{degree::DegreeBounds, preprocessor::preprocess, prover::Prover},
+        poly::univariate::UnivariatePolynomial,
+        Error,
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+
+    #[test]
+    fn test_degree_bounds() {
+        let (d, t, m, k) = (14, 8, 4, 3);
+        let bounds = DegreeBounds::new(d, t, m).unwrap();
+        assert_eq!((bounds.w1_shift(), bounds.w2_shift()), (11, 12));
+        // w1 and w2 are the only ones up against the SRS
+        assert_eq!((bounds.w1(), bounds.w2()), (d - 1, d + 1));
+
+        for (name, bound) in [
+            ("phi", bounds.phi()),
+            ("t", bounds.t()),
+            ("v", bounds.v()),
+            ("z_I", bounds.z_i(k)),
+            ("t_I", bounds.t_i(k)),
+            ("D", bounds.d(k)),
+            ("R", bounds.r(k)),
+            ("Q_D", bounds.q_d(k)),
+            ("E", bounds.e()),
+            ("Q_E", bounds.q_e()),
+            ("w1", bounds.w1()),
+            ("w2", bounds.w2()),
+            ("w3", bounds.w3()),
+            ("w4", bounds.w4()),
+            ("a", bounds.a(k)),
+        ] {
+            let fitting = UnivariatePolynomial::monomial(vec![Fr::one(); bound + 1]);
+            assert_eq!(bounds.check(name, &fitting, bound), Ok(()));
+            // leading zeros are ignored
+            let padded = UnivariatePolynomial::monomial(
+                [vec![Fr::one(); bound + 1], vec![Fr::zero(); 2]].concat(),
+            );
+            assert_eq!(bounds.check(name, &padded, bound), Ok(()));
+
+            let oversized = UnivariatePolynomial::monomial(vec![Fr::one(); bound + 2]);
+            assert_eq!(
+                bounds.check(name, &oversized, bound),
+                Err(Error::InvalidSnark(format!(
+                    "Degree of {name} should be at most {bound} but got {}",
+                    bound + 1
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn test_degree_one_over_bound() {
+        let (d, t, m, k) = (14, 8, 4, 3);
+        let bounds = DegreeBounds::new(d, t, m).unwrap();
+        // R, w2 and w3 of the largest polynomials the prover may send
+        assert_eq!(
+            (bounds.r(k), bounds.w2(), bounds.w3()),
+            (k - 1, d + 1, m - 1)
+        );
+
+        for (name, bound) in [("R", bounds.r(k)), ("w2", bounds.w2()), ("w3", bounds.w3())] {
+            let x_pow = |degree: usize| {
+                let mut coeffs = vec![Fr::zero(); degree + 1];
+                coeffs[degree] = Fr::one();
+                UnivariatePolynomial::monomial(coeffs)
+            };
+            assert_eq!(bounds.check(name, &x_pow(bound), bound), Ok(()));
+            assert_eq!(
+                bounds.check(name, &x_pow(bound + 1), bound),
+                Err(Error::InvalidSnark(format!(
+                    "Degree of {name} should be at most {bound} but got {}",
+                    bound + 1
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn test_prove_oversized_lookup() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let (param, pp, _) = preprocess::<Bn256>(table.len(), 4).unwrap();
+        // d = 14, so X^(d-m+1) would underflow
+        let lookup = [table.clone(), table.clone()].concat();
+        assert_eq!(
+            Prover::new(&table, &param, &pp).prove(&lookup),
+            Err(Error::SizeOutOfRange {
+                name: "lookup",
+                size: 16,
+                max: 14
+            })
+        );
+        assert_eq!(
+            DegreeBounds::new(14, 16, 4),
+            Err(Error::SizeOutOfRange {
+                name: "table",
+                size: 16,
+                max: 15
+            })
+        );
+    }
+}
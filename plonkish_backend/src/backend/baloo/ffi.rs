@@ -0,0 +1,373 @@
+//! C API of Baloo over BN254, for other languages to link the `cdylib` of the
+//! crate, declared in `include/baloo.h`. The crate is only an `rlib` by
+//! default, so the `cdylib` is built with
+//! `cargo rustc --release --crate-type cdylib --features ffi`.
+//!
+//! Field elements are passed as concatenated 32-byte little-endian reprs,
+//! `[φ(x)]1` as a 32-byte compressed point and proofs as
+//! [`BalooProof::to_versioned_bytes`]. Parameters are kept behind opaque handles, which
+//! are freed by their `_free` function, as are the buffers returned.
+//!
+//! Every function returns [`BALOO_OK`] on success and a negative status on
+//! failure, with [`baloo_verify`] returning [`BALOO_INVALID_PROOF`] for a
+//! well-formed proof which doesn't verify. Panics are caught at the boundary
+//! and reported as [`BALOO_ERROR`].
+
+use crate::{
+    backend::baloo::{
+        preprocessor::poly_size, util::field_elements_from_le_bytes, Baloo, BalooProof,
+        BalooProverParam, BalooVerifierParam,
+    },
+    pcs::{
+        univariate::{UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam},
+        PolynomialCommitmentScheme,
+    },
+    Error,
+};
+use halo2_curves::{
+    bn256::{Bn256, Fr, G1Affine},
+    group::GroupEncoding,
+};
+use rand::rngs::OsRng;
+use std::{
+    os::raw::c_int,
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr, slice,
+};
+
+pub const BALOO_OK: c_int = 0;
+/// A well-formed proof which doesn't verify.
+pub const BALOO_INVALID_PROOF: c_int = 1;
+/// A null pointer or a malformed field element, point or proof.
+pub const BALOO_INVALID_ARGUMENT: c_int = -1;
+/// Any other failure, e.g. an SRS too small for the table.
+pub const BALOO_ERROR: c_int = -2;
+
+/// Size of a compressed `[φ(x)]1`.
+pub const BALOO_PHI_SIZE: usize = 32;
+
+/// SRS of [`baloo_setup`].
+pub struct BalooSrs(UnivariateKzgParam<Bn256>);
+
+/// Preprocessed table of [`baloo_preprocess`] to prove lookups into.
+pub struct BalooProver(BalooProverParam);
+
+/// Verifier param of [`baloo_preprocess`].
+pub struct BalooVerifier(BalooVerifierParam);
+
+/// Bytes owned by the library, freed by [`baloo_buffer_free`].
+#[repr(C)]
+pub struct BalooBuffer {
+    pub ptr: *mut u8,
+    pub len: usize,
+}
+
+impl BalooBuffer {
+    fn new(bytes: Vec<u8>) -> Self {
+        let len = bytes.len();
+        let ptr = Box::into_raw(bytes.into_boxed_slice()) as *mut u8;
+        Self { ptr, len }
+    }
+}
+
+/// Sample an SRS for tables of size `t` and lookups of size `m` into `*srs`.
+///
+/// # Safety
+///
+/// `srs` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn baloo_setup(t: usize, m: usize, srs: *mut *mut BalooSrs) -> c_int {
+    status(|| {
+        let param = UnivariateKzg::<Bn256>::setup(poly_size(t, m), 1, OsRng)?;
+        write_handle(srs, BalooSrs(param))
+    })
+}
+
+/// Preprocess the `table_len` bytes of `table` for lookups of size `m` with
+/// `srs`, into `*prover` and `*verifier`.
+///
+/// # Safety
+///
+/// `srs` must be a live handle of [`baloo_setup`], `table` valid for reads of
+/// `table_len` bytes, and `prover` and `verifier` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn baloo_preprocess(
+    srs: *const BalooSrs,
+    table: *const u8,
+    table_len: usize,
+    m: usize,
+    prover: *mut *mut BalooProver,
+    verifier: *mut *mut BalooVerifier,
+) -> c_int {
+    status(|| {
+        if prover.is_null() || verifier.is_null() {
+            return Err(null_pointer());
+        }
+        let srs = handle(srs)?;
+        let table: Vec<Fr> = field_elements_from_le_bytes(bytes(table, table_len)?)?;
+        let (pp, vp) = Baloo::preprocess_with_srs(srs.0.clone(), &table, m)?;
+        write_handle(prover, BalooProver(pp))?;
+        write_handle(verifier, BalooVerifier(vp))
+    })
+}
+
+/// Prove that the `lookup_len` bytes of `lookup` are in the table of
+/// `prover`, into `*proof`.
+///
+/// # Safety
+///
+/// `prover` must be a live handle of [`baloo_preprocess`], `lookup` valid for
+/// reads of `lookup_len` bytes and `proof` valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn baloo_prove(
+    prover: *const BalooProver,
+    lookup: *const u8,
+    lookup_len: usize,
+    proof: *mut BalooBuffer,
+) -> c_int {
+    status(|| {
+        if proof.is_null() {
+            return Err(null_pointer());
+        }
+        let prover = handle(prover)?;
+        let lookup = field_elements_from_le_bytes(bytes(lookup, lookup_len)?)?;
+        let bytes = Baloo::prove_with_param(&prover.0, &lookup)?.to_versioned_bytes();
+        proof.write(BalooBuffer::new(bytes));
+        Ok(())
+    })
+}
+
+/// Commit to the `lookup_len` bytes of `lookup`, writing the
+/// [`BALOO_PHI_SIZE`] bytes of `[φ(x)]1` to `phi`.
+///
+/// # Safety
+///
+/// `prover` must be a live handle of [`baloo_preprocess`], `lookup` valid for
+/// reads of `lookup_len` bytes and `phi` valid for writes of
+/// [`BALOO_PHI_SIZE`] bytes.
+#[no_mangle]
+pub unsafe extern "C" fn baloo_commit_lookup(
+    prover: *const BalooProver,
+    lookup: *const u8,
+    lookup_len: usize,
+    phi: *mut u8,
+) -> c_int {
+    status(|| {
+        if phi.is_null() {
+            return Err(null_pointer());
+        }
+        let prover = handle(prover)?;
+        let lookup: Vec<Fr> = field_elements_from_le_bytes(bytes(lookup, lookup_len)?)?;
+        let phi_comm_1 = Baloo::prepare_verification_data(&prover.0.pp, &lookup);
+        let repr = phi_comm_1.0.to_bytes();
+        ptr::copy_nonoverlapping(repr.as_ref().as_ptr(), phi, BALOO_PHI_SIZE);
+        Ok(())
+    })
+}
+
+/// Verify the `proof_len` bytes of `proof` against the [`BALOO_PHI_SIZE`]
+/// bytes of `phi` of [`baloo_commit_lookup`].
+///
+/// # Safety
+///
+/// `verifier` must be a live handle of [`baloo_preprocess`], `proof` valid
+/// for reads of `proof_len` bytes and `phi` of [`BALOO_PHI_SIZE`] bytes.
+#[no_mangle]
+pub unsafe extern "C" fn baloo_verify(
+    verifier: *const BalooVerifier,
+    proof: *const u8,
+    proof_len: usize,
+    phi: *const u8,
+) -> c_int {
+    status(|| {
+        let verifier = handle(verifier)?;
+        let proof = BalooProof::from_versioned_bytes(bytes(proof, proof_len)?)?;
+        let mut repr = <G1Affine as GroupEncoding>::Repr::default();
+        repr.as_mut().copy_from_slice(bytes(phi, BALOO_PHI_SIZE)?);
+        let phi_comm_1 = Option::from(G1Affine::from_bytes(&repr))
+            .map(UnivariateKzgCommitment)
+            .ok_or(Status(BALOO_INVALID_ARGUMENT))?;
+        match Baloo::verify(&verifier.0, &proof, &phi_comm_1, verifier.0.m()) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(Status(BALOO_INVALID_PROOF)),
+        }
+    })
+}
+
+/// # Safety
+///
+/// `srs` must be null or a handle of [`baloo_setup`] not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn baloo_srs_free(srs: *mut BalooSrs) {
+    free_handle(srs)
+}
+
+/// # Safety
+///
+/// `prover` must be null or a handle of [`baloo_preprocess`] not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn baloo_prover_free(prover: *mut BalooProver) {
+    free_handle(prover)
+}
+
+/// # Safety
+///
+/// `verifier` must be null or a handle of [`baloo_preprocess`] not freed
+/// yet.
+#[no_mangle]
+pub unsafe extern "C" fn baloo_verifier_free(verifier: *mut BalooVerifier) {
+    free_handle(verifier)
+}
+
+/// # Safety
+///
+/// `buffer` must be one returned by the library and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn baloo_buffer_free(buffer: BalooBuffer) {
+    if !buffer.ptr.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+            buffer.ptr, buffer.len,
+        )));
+    }
+}
+
+// a failing status, converted from any `Error` along the way
+struct Status(c_int);
+
+impl From<Error> for Status {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Serialization(_) | Error::Transcript(..) => Self(BALOO_INVALID_ARGUMENT),
+            _ => Self(BALOO_ERROR),
+        }
+    }
+}
+
+fn status(f: impl FnOnce() -> Result<(), Status>) -> c_int {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => BALOO_OK,
+        Ok(Err(Status(status))) => status,
+        Err(_) => BALOO_ERROR,
+    }
+}
+
+fn null_pointer() -> Status {
+    Status(BALOO_INVALID_ARGUMENT)
+}
+
+unsafe fn bytes<'a>(ptr: *const u8, len: usize) -> Result<&'a [u8], Status> {
+    match (ptr.is_null(), len) {
+        (_, 0) => Ok(&[]),
+        (true, _) => Err(null_pointer()),
+        (false, _) => Ok(slice::from_raw_parts(ptr, len)),
+    }
+}
+
+unsafe fn handle<'a, T>(handle: *const T) -> Result<&'a T, Status> {
+    handle.as_ref().ok_or_else(null_pointer)
+}
+
+unsafe fn write_handle<T>(handle: *mut *mut T, value: T) -> Result<(), Status> {
+    if handle.is_null() {
+        return Err(null_pointer());
+    }
+    handle.write(Box::into_raw(Box::new(value)));
+    Ok(())
+}
+
+unsafe fn free_handle<T>(handle: *mut T) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        backend::{
+            baloo::ffi::{
+                baloo_buffer_free, baloo_commit_lookup, baloo_preprocess, baloo_prove,
+                baloo_prover_free, baloo_setup, baloo_srs_free, baloo_verifier_free, baloo_verify,
+                BalooBuffer, BALOO_INVALID_ARGUMENT, BALOO_INVALID_PROOF, BALOO_OK, BALOO_PHI_SIZE,
+            },
+            cq::generate_table_and_lookup,
+        },
+        util::arithmetic::fe_to_bytes,
+    };
+    use halo2_curves::bn256::Fr;
+    use std::ptr;
+
+    fn to_bytes(values: &[Fr]) -> Vec<u8> {
+        values.iter().flat_map(fe_to_bytes).collect()
+    }
+
+    #[test]
+    fn prove_and_verify() {
+        let (table, lookup) = generate_table_and_lookup(8, 4);
+        let (table, lookup) = (to_bytes(&table), to_bytes(&lookup));
+        unsafe {
+            let mut srs = ptr::null_mut();
+            assert_eq!(baloo_setup(8, 4, &mut srs), BALOO_OK);
+            let (mut prover, mut verifier) = (ptr::null_mut(), ptr::null_mut());
+            assert_eq!(
+                baloo_preprocess(
+                    srs,
+                    table.as_ptr(),
+                    table.len(),
+                    4,
+                    &mut prover,
+                    &mut verifier
+                ),
+                BALOO_OK
+            );
+
+            let mut proof = BalooBuffer {
+                ptr: ptr::null_mut(),
+                len: 0,
+            };
+            assert_eq!(
+                baloo_prove(prover, lookup.as_ptr(), lookup.len(), &mut proof),
+                BALOO_OK
+            );
+            let mut phi = [0; BALOO_PHI_SIZE];
+            assert_eq!(
+                baloo_commit_lookup(prover, lookup.as_ptr(), lookup.len(), phi.as_mut_ptr()),
+                BALOO_OK
+            );
+            assert_eq!(
+                baloo_verify(verifier, proof.ptr, proof.len, phi.as_ptr()),
+                BALOO_OK
+            );
+
+            let other_lookup = to_bytes(&[1, 1, 1, 1].map(Fr::from));
+            let mut other_phi = [0; BALOO_PHI_SIZE];
+            baloo_commit_lookup(
+                prover,
+                other_lookup.as_ptr(),
+                other_lookup.len(),
+                other_phi.as_mut_ptr(),
+            );
+            assert_eq!(
+                baloo_verify(verifier, proof.ptr, proof.len, other_phi.as_ptr()),
+                BALOO_INVALID_PROOF
+            );
+            assert_eq!(
+                baloo_verify(verifier, proof.ptr, proof.len - 1, phi.as_ptr()),
+                BALOO_INVALID_ARGUMENT
+            );
+            assert_eq!(
+                baloo_verify(ptr::null(), proof.ptr, proof.len, phi.as_ptr()),
+                BALOO_INVALID_ARGUMENT
+            );
+            assert_eq!(
+                baloo_prove(prover, lookup.as_ptr(), lookup.len() - 1, &mut proof),
+                BALOO_INVALID_ARGUMENT
+            );
+
+            baloo_buffer_free(proof);
+            baloo_verifier_free(verifier);
+            baloo_prover_free(prover);
+            baloo_srs_free(srs);
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Lookups gated by a selector `s_i ∈ {0, 1}`, where only the rows with
+//! `s_i = 1` must be in the table, e.g. of the rows of a circuit the gate of
+//! the lookup is enabled on.
+//!
+//! The prover maps the disabled rows of the lookup `f` to a designated table
+//! value `d`, and proves by Baloo that `φ' = s f + (1 - s) d` is in the
+//! table. With the selector `s` public, as fixed columns of circuits are,
+//! the verifier commits to it in `G2`, and the prover shows that `[φ'(x)]1`
+//! is of `f` and `s` by the quotient `q = (φ' - d - s (f - d)) / z_V`, which
+//! the verifier checks with
+//!     e([φ']1 - d [1]1, [1]2) = e([f]1 - d [1]1, [s]2) e([q]1, [z_V]2).
+//! That `s` is boolean is then up to the verifier, who knows it.
+
+use crate::{
+    backend::baloo::{
+        link::z_v_comm_2, prover::Prover, util::multi_pairing, verifier::Verifier, BalooEngine,
+        BalooPcs, BalooProof, BalooVerifierParam, VerifyError,
+    },
+    pcs::univariate::UnivariateKzgCommitment,
+    poly::univariate::UnivariatePolynomial,
+    util::arithmetic::{Field, Group, PrimeCurveAffine},
+    Error,
+};
+use halo2_curves::bn256::Bn256;
+
+/// `[φ'(x)]1` of the gated lookup, `[q(x)]1` and the Baloo proof of `φ'`.
+#[derive(Clone, Debug)]
+pub struct GatedProof<M: BalooEngine = Bn256> {
+    pub phi_comm_1: UnivariateKzgCommitment<M::G1Affine>,
+    pub q_comm_1: UnivariateKzgCommitment<M::G1Affine>,
+    pub proof: BalooProof<M>,
+}
+
+/// Lookup with the rows off `selector` set to `default`.
+pub fn gate_lookup<F: Copy>(lookup: &[F], selector: &[bool], default: F) -> Result<Vec<F>, Error> {
+    if selector.len() != lookup.len() {
+        return Err(Error::SizeMismatch {
+            name: "selector",
+            expected: lookup.len(),
+            got: selector.len(),
+        });
+    }
+    Ok(lookup
+        .iter()
+        .zip(selector)
+        .map(|(value, enabled)| if *enabled { *value } else { default })
+        .collect())
+}
+
+/// Prove that every value of `lookup` on `selector` is in the table of
+/// `prover`, with the other rows set to the table value at `default_index`.
+pub fn prove_gated<M: BalooEngine, Pcs: BalooPcs<M>>(
+    prover: &Prover<M, Pcs>,
+    lookup: &[M::Scalar],
+    selector: &[bool],
+    default_index: usize,
+) -> Result<GatedProof<M>, Error> {
+    let table = prover.table();
+    let default = *table.get(default_index).ok_or(Error::SizeOutOfRange {
+        name: "default index",
+        size: default_index,
+        max: table.len(),
+    })?;
+    let gated = gate_lookup(lookup, selector, default)?;
+    let m = lookup.len();
+    if !m.is_power_of_two() {
+        return Err(Error::NotPowerOfTwo {
+            name: "lookup",
+            size: m,
+        });
+    }
+
+    // φ' - d - s (f - d)
+    let f_poly = UnivariatePolynomial::lagrange(lookup.to_vec()).into_ifft() + -default;
+    let s_poly = UnivariatePolynomial::lagrange(selector_evals(selector)).into_ifft();
+    let mut poly = UnivariatePolynomial::lagrange(gated.clone()).into_ifft() + -default;
+    poly -= &s_poly * &f_poly;
+    let q_poly = poly.divide_by_vanishing_poly(m)?;
+    Ok(GatedProof {
+        phi_comm_1: Pcs::commit_lagrange(prover.pp(), &gated),
+        q_comm_1: Pcs::commit_monomial(prover.pp(), q_poly.coeffs()),
+        proof: prover.prove(&gated)?,
+    })
+}
+
+/// `[s(x)]2` of `selector` the verifier of [`verify_gated`] commits to.
+pub fn selector_comm_2<M: BalooEngine, Pcs: BalooPcs<M>>(
+    param: &Pcs::Param,
+    selector: &[bool],
+) -> Result<UnivariateKzgCommitment<M::G2Affine>, Error> {
+    if !selector.len().is_power_of_two() {
+        return Err(Error::NotPowerOfTwo {
+            name: "selector",
+            size: selector.len(),
+        });
+    }
+    let s_poly = UnivariatePolynomial::lagrange(selector_evals(selector)).into_ifft();
+    Ok(Pcs::commit_monomial_g2(param, s_poly.coeffs()))
+}
+
+/// Verify a proof of [`prove_gated`] of the lookup of `[f(x)]1`, given
+/// `[s(x)]2` of [`selector_comm_2`], `[z_V(x)]2` of [`z_v_comm_2`] and the
+/// table value `default` of the disabled rows.
+pub fn verify_gated<M: BalooEngine, Pcs: BalooPcs<M>>(
+    vp: &BalooVerifierParam<M, Pcs>,
+    selector_comm_2: &UnivariateKzgCommitment<M::G2Affine>,
+    z_v_comm_2: &UnivariateKzgCommitment<M::G2Affine>,
+    f_comm_1: &UnivariateKzgCommitment<M::G1Affine>,
+    default: M::Scalar,
+    proof: &GatedProof<M>,
+) -> Result<(), VerifyError> {
+    let d_comm_1 = M::G1Affine::generator() * default;
+    let g1s = [
+        (proof.phi_comm_1.0.to_curve() - d_comm_1).into(),
+        (d_comm_1 - f_comm_1.0).into(),
+        -proof.q_comm_1.0,
+    ];
+    let g2s = [Pcs::g2(&vp.vp), selector_comm_2.0, z_v_comm_2.0];
+    if multi_pairing::<M>(&g1s, &g2s) != M::Gt::identity() {
+        return Err(VerifyError::Selector);
+    }
+    Verifier::new(vp).verify(&proof.proof, &proof.phi_comm_1, vp.m)
+}
+
+fn selector_evals<F: Field>(selector: &[bool]) -> Vec<F> {
+    selector
+        .iter()
+        .map(|enabled| if *enabled { F::ONE } else { F::ZERO })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        backend::baloo::{preprocessor::preprocess, Baloo},
+        pcs::univariate::UnivariateKzg,
+    };
+    use halo2_curves::bn256::Fr;
+
+    type Pcs = UnivariateKzg<Bn256>;
+
+    #[test]
+    fn test_gated() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        // 100 and 0 are on disabled rows
+        let lookup = [7, 100, 3, 0].map(|i| Fr::from(i as u64)).to_vec();
+        let selector = [true, false, true, false];
+        let m = lookup.len();
+        let (param, pp, vp) = preprocess::<Bn256>(table.len(), m).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let prover = Prover::new(&table, &param, &pp);
+
+        let proof = prove_gated(&prover, &lookup, &selector, 0).unwrap();
+        let f_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        let z_v_comm_2 = z_v_comm_2::<Bn256, Pcs>(&param, m);
+        let verify = |selector: &[bool], default: Fr, proof: &GatedProof| {
+            let selector_comm_2 = selector_comm_2::<Bn256, Pcs>(&param, selector).unwrap();
+            verify_gated(
+                &vp,
+                &selector_comm_2,
+                &z_v_comm_2,
+                &f_comm_1,
+                default,
+                proof,
+            )
+        };
+        assert_eq!(verify(&selector, table[0], &proof), Ok(()));
+        assert_eq!(
+            gate_lookup(&lookup, &selector, table[0]).unwrap(),
+            [7, 1, 3, 1].map(Fr::from)
+        );
+
+        // an enabled row out of the table can't be proved
+        assert_eq!(
+            prove_gated(&prover, &lookup, &[true, true, true, false], 0).map(|_| ()),
+            Err(Error::NotInTable { positions: vec![1] })
+        );
+        // nor verified against another selector or default
+        assert_eq!(
+            verify(&[true, true, true, false], table[0], &proof),
+            Err(VerifyError::Selector)
+        );
+        assert_eq!(
+            verify(&selector, table[1], &proof),
+            Err(VerifyError::Selector)
+        );
+        assert!(matches!(
+            prove_gated(&prover, &lookup, &selector[..3], 0),
+            Err(Error::SizeMismatch { .. })
+        ));
+    }
+}
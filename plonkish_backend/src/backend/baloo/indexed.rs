@@ -0,0 +1,97 @@
+//! Indexed lookups, which prove that each lookup value `φ_j` is the table
+//! value at a committed index `i_j`, e.g. a read of a memory-checking
+//! argument, instead of just that it's somewhere in the table.
+//!
+//! It's the lookup of the tuples `(i_j, φ_j)` into the table of the rows
+//! `(i, t_i)`, see [`tuple`], so the verifier is given `[i(x)]1` of the
+//! indices next to `[φ(x)]1`, and the index column `0, 1, ..., t - 1` is
+//! preprocessed once like any other table.
+//!
+//! [`tuple`]: crate::backend::baloo::tuple
+
+use crate::{
+    backend::baloo::{
+        preprocessor::ProverTablePreprocessing, proof::BalooProof, tuple, BalooEngine, BalooPcs,
+        BalooVerifierParam, VerifyError,
+    },
+    pcs::univariate::UnivariateKzgCommitment,
+    util::arithmetic::PrimeField,
+    Error,
+};
+use std::{collections::HashMap, hash::Hash};
+
+/// The index column `0, 1, ..., t - 1` of a table of size `t`.
+pub fn index_column<F: PrimeField>(t: usize) -> Vec<F> {
+    (0..t).map(|i| F::from(i as u64)).collect()
+}
+
+/// Index of the first occurrence in `table` of each lookup value, or `None`
+/// if any of them isn't in the table.
+pub fn first_indices<F: Eq + Hash>(table: &[F], lookup: &[F]) -> Option<Vec<usize>> {
+    let mut index = HashMap::new();
+    for (i, value) in table.iter().enumerate().rev() {
+        index.insert(value, i);
+    }
+    lookup
+        .iter()
+        .map(|value| index.get(value).copied())
+        .collect()
+}
+
+/// Index column of `indices` in the field, committed to by the verifier
+/// data of [`verify_indexed`].
+pub fn index_values<F: PrimeField>(indices: &[usize]) -> Vec<F> {
+    indices.iter().map(|i| F::from(*i as u64)).collect()
+}
+
+/// Prove that `lookup[j]` is the value at `indices[j]` of the table
+/// preprocessed in `table`, with `index_table` the preprocessed
+/// [`index_column`] of its size.
+pub fn prove_indexed<M: BalooEngine, Pcs: BalooPcs<M>>(
+    table: &ProverTablePreprocessing<M>,
+    index_table: &ProverTablePreprocessing<M>,
+    param: &Pcs::Param,
+    pp: &Pcs::ProverParam,
+    lookup: &[M::Scalar],
+    indices: &[usize],
+) -> Result<BalooProof<M>, Error> {
+    if indices.len() != lookup.len() {
+        return Err(Error::SizeMismatch {
+            name: "indices",
+            expected: lookup.len(),
+            got: indices.len(),
+        });
+    }
+    if let Some(j) = (0..lookup.len()).find(|&j| table.table().get(indices[j]) != Some(&lookup[j]))
+    {
+        return Err(Error::InvalidSnark(format!(
+            "Lookup value {j} isn't the table value at index {}",
+            indices[j]
+        )));
+    }
+    tuple::prove_tuples::<M, Pcs>(
+        &[index_table, table],
+        param,
+        pp,
+        &[index_values(indices), lookup.to_vec()],
+    )
+}
+
+/// Verify a proof of [`prove_indexed`] for lookups of size `m`, given the
+/// verifier param of the table and of its [`index_column`], and the
+/// commitments of the lookup values and of their [`index_values`].
+pub fn verify_indexed<M: BalooEngine, Pcs: BalooPcs<M>>(
+    vp: &BalooVerifierParam<M, Pcs>,
+    index_vp: &BalooVerifierParam<M, Pcs>,
+    proof: &BalooProof<M>,
+    phi_comm_1: &UnivariateKzgCommitment<M::G1Affine>,
+    index_comm_1: &UnivariateKzgCommitment<M::G1Affine>,
+    m: usize,
+) -> Result<(), VerifyError> {
+    tuple::verify_tuples(
+        &[index_vp, vp],
+        proof,
+        &[index_comm_1.clone(), phi_comm_1.clone()],
+        m,
+    )
+}
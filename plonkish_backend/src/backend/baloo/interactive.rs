@@ -0,0 +1,276 @@
+//! The Baloo protocol as the interactive one it's compiled from, for
+//! debugging and research: the prover's messages are kept as they're sent
+//! and every challenge is asked from a verifier instead of derived by
+//! Fiat-Shamir.
+//!
+//! An [`InteractiveTranscript`] is a transcript of [`Prover::prove_in_transcript`]
+//! and [`Verifier::verify_in_transcript`], so any challenge can be injected,
+//! e.g. by [`chosen_challenges`], and any message altered before the
+//! verifier reads it, to test the soundness boundary cases. A challenge in a
+//! domain the protocol excludes it from, e.g. `α` in `V`, is rejected and
+//! asked again, as a derived one would be.
+//!
+//! [`Prover::prove_in_transcript`]: crate::backend::baloo::prover::Prover::prove_in_transcript
+//! [`Verifier::verify_in_transcript`]: crate::backend::baloo::verifier::Verifier::verify_in_transcript
+
+use crate::{
+    backend::baloo::BalooEngine,
+    util::transcript::{
+        FieldTranscript, FieldTranscriptRead, FieldTranscriptWrite, G2Transcript, G2TranscriptRead,
+        G2TranscriptWrite, Transcript, TranscriptRead, TranscriptWrite,
+    },
+    Error,
+};
+use halo2_curves::pairing::Engine;
+use std::io;
+
+/// A message of the prover, in `G1`, in `G2` or in the field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProverMessage<C1, C2, F> {
+    G1(C1),
+    G2(C2),
+    Scalar(F),
+}
+
+/// [`ProverMessage`] of the groups and field of `M`.
+pub type Message<M> =
+    ProverMessage<<M as Engine>::G1Affine, <M as Engine>::G2Affine, <M as Engine>::Scalar>;
+
+/// Transcript of the messages of the prover and the challenges of the
+/// `verifier`, which is given the messages sent so far.
+pub struct InteractiveTranscript<M: BalooEngine, V> {
+    messages: Vec<Message<M>>,
+    // messages sent so far, i.e. all of the prover's or the ones read
+    sent: usize,
+    challenges: Vec<M::Scalar>,
+    verifier: V,
+}
+
+impl<M: BalooEngine, V: FnMut(&[Message<M>]) -> M::Scalar> InteractiveTranscript<M, V> {
+    /// Transcript of the prover.
+    pub fn new(verifier: V) -> Self {
+        Self::with_messages(Vec::new(), verifier)
+    }
+
+    /// Transcript of the verifier reading `messages`, e.g. of the prover's
+    /// transcript, altered or not.
+    pub fn with_messages(messages: Vec<Message<M>>, verifier: V) -> Self {
+        Self {
+            messages,
+            sent: 0,
+            challenges: Vec::new(),
+            verifier,
+        }
+    }
+
+    pub fn messages(&self) -> &[Message<M>] {
+        &self.messages
+    }
+
+    pub fn into_messages(self) -> Vec<Message<M>> {
+        self.messages
+    }
+
+    /// Challenges asked so far, including the rejected ones.
+    pub fn challenges(&self) -> &[M::Scalar] {
+        &self.challenges
+    }
+
+    fn send(&mut self, message: Message<M>) -> Result<(), Error> {
+        self.messages.truncate(self.sent);
+        self.messages.push(message);
+        self.sent += 1;
+        Ok(())
+    }
+
+    fn receive<T>(
+        &mut self,
+        kind: &str,
+        extract: impl FnOnce(&Message<M>) -> Option<T>,
+    ) -> Result<T, Error> {
+        let message = self.messages.get(self.sent).ok_or_else(|| {
+            Error::Transcript(
+                io::ErrorKind::UnexpectedEof,
+                format!("Expected a {kind} message but the prover sent no more"),
+            )
+        })?;
+        let value = extract(message).ok_or_else(|| {
+            Error::Transcript(
+                io::ErrorKind::InvalidData,
+                format!("Expected a {kind} message but got {message:?}"),
+            )
+        })?;
+        self.sent += 1;
+        Ok(value)
+    }
+}
+
+/// Verifier answering with `challenges` in order, e.g. the ones of the
+/// prover's transcript to verify its messages. It panics when they run out.
+pub fn chosen_challenges<M: BalooEngine>(
+    challenges: Vec<M::Scalar>,
+) -> impl FnMut(&[Message<M>]) -> M::Scalar {
+    let mut challenges = challenges.into_iter();
+    move |_| challenges.next().expect("Ran out of chosen challenges")
+}
+
+impl<M: BalooEngine, V: FnMut(&[Message<M>]) -> M::Scalar> FieldTranscript<M::Scalar>
+    for InteractiveTranscript<M, V>
+{
+    fn squeeze_challenge(&mut self) -> M::Scalar {
+        let challenge = (self.verifier)(&self.messages[..self.sent]);
+        self.challenges.push(challenge);
+        challenge
+    }
+
+    // the instance is known to both, so only the messages are kept
+    fn common_field_element(&mut self, _: &M::Scalar) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn common_label(&mut self, _: &'static [u8]) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<M: BalooEngine, V: FnMut(&[Message<M>]) -> M::Scalar> FieldTranscriptRead<M::Scalar>
+    for InteractiveTranscript<M, V>
+{
+    fn read_field_element(&mut self) -> Result<M::Scalar, Error> {
+        self.receive("field", |message| match message {
+            ProverMessage::Scalar(fe) => Some(*fe),
+            _ => None,
+        })
+    }
+}
+
+impl<M: BalooEngine, V: FnMut(&[Message<M>]) -> M::Scalar> FieldTranscriptWrite<M::Scalar>
+    for InteractiveTranscript<M, V>
+{
+    fn write_field_element(&mut self, fe: &M::Scalar) -> Result<(), Error> {
+        self.send(ProverMessage::Scalar(*fe))
+    }
+}
+
+impl<M: BalooEngine, V: FnMut(&[Message<M>]) -> M::Scalar> Transcript<M::G1Affine, M::Scalar>
+    for InteractiveTranscript<M, V>
+{
+    fn common_commitment(&mut self, _: &M::G1Affine) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<M: BalooEngine, V: FnMut(&[Message<M>]) -> M::Scalar> TranscriptRead<M::G1Affine, M::Scalar>
+    for InteractiveTranscript<M, V>
+{
+    fn read_commitment(&mut self) -> Result<M::G1Affine, Error> {
+        self.receive("G1", |message| match message {
+            ProverMessage::G1(comm) => Some(*comm),
+            _ => None,
+        })
+    }
+}
+
+impl<M: BalooEngine, V: FnMut(&[Message<M>]) -> M::Scalar> TranscriptWrite<M::G1Affine, M::Scalar>
+    for InteractiveTranscript<M, V>
+{
+    fn write_commitment(&mut self, comm: &M::G1Affine) -> Result<(), Error> {
+        self.send(ProverMessage::G1(*comm))
+    }
+}
+
+impl<M: BalooEngine, V: FnMut(&[Message<M>]) -> M::Scalar> G2Transcript<M::G2Affine, M::Scalar>
+    for InteractiveTranscript<M, V>
+{
+    fn common_commitment_g2(&mut self, _: &M::G2Affine) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<M: BalooEngine, V: FnMut(&[Message<M>]) -> M::Scalar> G2TranscriptRead<M::G2Affine, M::Scalar>
+    for InteractiveTranscript<M, V>
+{
+    fn read_commitment_g2(&mut self) -> Result<M::G2Affine, Error> {
+        self.receive("G2", |message| match message {
+            ProverMessage::G2(comm) => Some(*comm),
+            _ => None,
+        })
+    }
+}
+
+impl<M: BalooEngine, V: FnMut(&[Message<M>]) -> M::Scalar> G2TranscriptWrite<M::G2Affine, M::Scalar>
+    for InteractiveTranscript<M, V>
+{
+    fn write_commitment_g2(&mut self, comm: &M::G2Affine) -> Result<(), Error> {
+        self.send(ProverMessage::G2(*comm))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        backend::baloo::{preprocessor::preprocess, prover::Prover, verifier::Verifier, Baloo},
+        util::arithmetic::Field,
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_interactive() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = [7, 3, 3, 4].map(|i| Fr::from(i as u64)).to_vec();
+        let m = lookup.len();
+        let (param, pp, vp) = preprocess::<Bn256>(table.len(), m).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        let prover = Prover::new(&table, &param, &pp);
+        let verifier = Verifier::new(&vp);
+
+        // α = 1 is in V, so it's rejected and asked again
+        let mut injected = false;
+        let mut transcript = InteractiveTranscript::<Bn256, _>::new(|messages: &[_]| {
+            if messages.len() == 3 && !injected {
+                injected = true;
+                Fr::ONE
+            } else {
+                Fr::random(OsRng)
+            }
+        });
+        prover
+            .prove_in_transcript(&lookup, &mut transcript)
+            .unwrap();
+        let challenges = transcript.challenges().to_vec();
+        assert_eq!(challenges[0], Fr::ONE);
+        assert_ne!(challenges[1], Fr::ONE);
+        let messages = transcript.into_messages();
+        assert!(matches!(
+            messages[..3],
+            [
+                ProverMessage::G1(_),
+                ProverMessage::G2(_),
+                ProverMessage::G1(_)
+            ]
+        ));
+
+        let verify = |messages: Vec<Message<Bn256>>, challenges: Vec<Fr>| {
+            let mut transcript =
+                InteractiveTranscript::with_messages(messages, chosen_challenges(challenges));
+            verifier.verify_in_transcript(&mut transcript, &phi_comm_1, m)
+        };
+        assert_eq!(verify(messages.clone(), challenges.clone()), Ok(()));
+
+        // another challenge than the prover's, or an altered message
+        let mut other_challenges = challenges.clone();
+        other_challenges[1] += Fr::ONE;
+        assert!(verify(messages.clone(), other_challenges).is_err());
+        let mut other_messages = messages.clone();
+        let v1 = other_messages
+            .iter_mut()
+            .find(|message| matches!(message, ProverMessage::Scalar(_)))
+            .unwrap();
+        *v1 = ProverMessage::Scalar(Fr::ONE);
+        assert!(verify(other_messages, challenges.clone()).is_err());
+        assert!(verify(messages[..messages.len() - 1].to_vec(), challenges).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+//! Proofs run in the background, e.g. by a service which has to report on
+//! and cancel the long ones.
+//!
+//! A [`ProvingJob`] proves on the rayon pool of the `parallel` feature, or on
+//! a thread of its own without it, and exposes the rounds done so far. A
+//! cancelled job stops at the end of the round it's in and returns
+//! [`Error::Cancelled`].
+
+use crate::{
+    backend::baloo::{prover::Prover, BalooEngine, BalooPcs, BalooProof},
+    util::{parallel::spawn, transcript::InMemoryTranscript},
+    Error,
+};
+use halo2_curves::bn256::Bn256;
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    mpsc::{self, Receiver, TryRecvError},
+    Arc,
+};
+
+/// Rounds done and cancellation of a proof, shared by the prover and its
+/// [`ProvingJob`].
+#[derive(Debug, Default)]
+pub(crate) struct Progress {
+    rounds_done: AtomicUsize,
+    cancelled: AtomicBool,
+}
+
+impl Progress {
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return Err(Error::Cancelled);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn finish_round(&self) -> Result<(), Error> {
+        self.check()?;
+        self.rounds_done.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// [`Prover::prove`] of a lookup run in the background.
+pub struct ProvingJob<M: BalooEngine = Bn256> {
+    progress: Arc<Progress>,
+    result: Receiver<Result<BalooProof<M>, Error>>,
+}
+
+impl<M: BalooEngine> ProvingJob<M> {
+    /// Rounds of the protocol, after which the proof is done.
+    pub const ROUNDS: usize = 3;
+
+    pub fn spawn<Pcs: BalooPcs<M> + 'static>(
+        prover: Arc<Prover<'static, M, Pcs>>,
+        lookup: Vec<M::Scalar>,
+    ) -> Self
+    where
+        Prover<'static, M, Pcs>: Send + Sync,
+        BalooProof<M>: Send,
+    {
+        let progress = Arc::new(Progress::default());
+        let (sender, result) = mpsc::channel();
+        spawn({
+            let progress = progress.clone();
+            move || {
+                let mut transcript = M::Transcript::new(());
+                let proof = prover.prove_with_progress(&lookup, false, &mut transcript, &progress);
+                // the job may have been dropped already
+                let _ = sender.send(proof);
+            }
+        });
+        Self { progress, result }
+    }
+
+    /// Rounds done so far, up to [`ProvingJob::ROUNDS`].
+    pub fn rounds_done(&self) -> usize {
+        self.progress.rounds_done.load(Ordering::Relaxed)
+    }
+
+    /// Stop the proof at the end of its current round.
+    pub fn cancel(&self) {
+        self.progress.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// The result if the job is done, without blocking.
+    pub fn try_wait(&self) -> Option<Result<BalooProof<M>, Error>> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(err_no_result())),
+        }
+    }
+
+    /// Block until the job is done.
+    pub fn wait(self) -> Result<BalooProof<M>, Error> {
+        self.result.recv().unwrap_or_else(|_| Err(err_no_result()))
+    }
+}
+
+// the prover panicked, or the result was taken by `try_wait` already
+fn err_no_result() -> Error {
+    Error::InvalidSnark("Proving job has no result".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        backend::baloo::{
+            preprocessor::{preprocess, ProverTablePreprocessing},
+            Baloo,
+        },
+        util::transcript::Keccak256Transcript,
+    };
+    use halo2_curves::bn256::Fr;
+
+    #[test]
+    fn test_proving_job() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = [7, 3, 3, 4].map(|i| Fr::from(i as u64)).to_vec();
+        let (param, pp, vp) = preprocess::<Bn256>(table.len(), lookup.len()).unwrap();
+        let expected = Prover::new(&table, &param, &pp).prove(&lookup);
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, lookup.len()).unwrap();
+        let table_pp = ProverTablePreprocessing::new(&pp, &table).unwrap();
+        let prover: Arc<Prover> = Arc::new(Prover::from_arcs(
+            Arc::new(table_pp),
+            Arc::new(param),
+            Arc::new(pp),
+        ));
+
+        let job = ProvingJob::spawn(prover.clone(), lookup.clone());
+        let proof = loop {
+            if let Some(proof) = job.try_wait() {
+                break proof;
+            }
+            std::thread::yield_now();
+        };
+        assert_eq!(job.rounds_done(), ProvingJob::<Bn256>::ROUNDS);
+        assert_eq!(proof, expected);
+        let phi_comm_1 = Baloo::prepare_verification_data(prover.pp(), &lookup);
+        assert_eq!(
+            Baloo::verify(&vp, &proof.unwrap(), &phi_comm_1, lookup.len()),
+            Ok(())
+        );
+
+        // every round is reported, and a cancelled proof stops after the
+        // round it's cancelled in
+        let mut transcript = Keccak256Transcript::new(());
+        let progress = Progress::default();
+        prover
+            .prove_with_progress(&lookup, false, &mut transcript, &progress)
+            .unwrap();
+        assert_eq!(
+            progress.rounds_done.load(Ordering::Relaxed),
+            ProvingJob::<Bn256>::ROUNDS
+        );
+        let progress = Progress::default();
+        progress.cancelled.store(true, Ordering::Relaxed);
+        let mut transcript = Keccak256Transcript::new(());
+        assert_eq!(
+            prover.prove_with_progress(&lookup, false, &mut transcript, &progress),
+            Err(Error::Cancelled)
+        );
+        assert_eq!(progress.rounds_done.load(Ordering::Relaxed), 0);
+
+        let job = ProvingJob::spawn(prover, lookup);
+        job.cancel();
+        assert!(matches!(job.wait(), Ok(_) | Err(Error::Cancelled)));
+    }
+}
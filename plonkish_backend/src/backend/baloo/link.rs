@@ -0,0 +1,205 @@
+//! Linking `[φ(x)]1` of a lookup to the commitments `[c_j(x)]1` of witness
+//! columns of an outer proof with the same SRS, e.g. PLONK or halo2, so that
+//! the lookup can't be about another vector than the columns of the circuit.
+//!
+//! Both are interpolated over the domain `V` of size `m`, and the [`Link`]
+//! is the relation `φ = Σ_j a_j c_j + b` on `V`. The outer columns may be
+//! blinded by multiples of `z_V`, so equal values on `V` don't make equal
+//! commitments. The prover instead commits to
+//! `q = (φ - Σ_j a_j c_j - b) / z_V` and the verifier checks
+//!     e([φ]1 - Σ_j a_j [c_j]1 - b [1]1, [1]2) = e([q]1, [z_V]2).
+
+use crate::{
+    backend::baloo::{util::multi_pairing, BalooEngine, BalooPcs, BalooVerifierParam, VerifyError},
+    pcs::univariate::UnivariateKzgCommitment,
+    poly::univariate::UnivariatePolynomial,
+    util::arithmetic::{variable_base_msm, CurveAffine, Field, Group, PrimeCurveAffine},
+    Error,
+};
+use std::iter;
+
+/// `φ = Σ_j coeffs[j] c_j + constant` on `V`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Link<F> {
+    pub coeffs: Vec<F>,
+    pub constant: F,
+}
+
+impl<F: Field> Link<F> {
+    /// `φ = c` on `V`.
+    pub fn equal() -> Self {
+        Self {
+            coeffs: vec![F::ONE],
+            constant: F::ZERO,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LinkProof<C: CurveAffine> {
+    /// `[q(x)]1`.
+    pub q_comm_1: UnivariateKzgCommitment<C>,
+}
+
+/// Prove that `lookup` is the `link` of the outer `columns` on `V`, with the
+/// columns' polynomials in monomial form as the outer prover committed them.
+pub fn prove_link<M: BalooEngine, Pcs: BalooPcs<M>>(
+    pp: &Pcs::ProverParam,
+    lookup: &[M::Scalar],
+    columns: &[&UnivariatePolynomial<M::Scalar>],
+    link: &Link<M::Scalar>,
+) -> Result<LinkProof<M::G1Affine>, Error> {
+    let m = lookup.len();
+    if !m.is_power_of_two() {
+        return Err(Error::NotPowerOfTwo {
+            name: "lookup",
+            size: m,
+        });
+    }
+    if link.coeffs.len() != columns.len() {
+        return Err(Error::SizeMismatch {
+            name: "link coefficients",
+            expected: columns.len(),
+            got: link.coeffs.len(),
+        });
+    }
+
+    let mut poly = UnivariatePolynomial::lagrange(lookup.to_vec()).into_ifft() + -link.constant;
+    for (coeff, column) in link.coeffs.iter().zip(columns) {
+        poly -= (coeff, *column);
+    }
+    let q_poly = poly.divide_by_vanishing_poly(m).map_err(|_| {
+        Error::InvalidSnark("Lookup isn't the link of the columns on V".to_string())
+    })?;
+    Ok(LinkProof {
+        q_comm_1: Pcs::commit_monomial(pp, q_poly.coeffs()),
+    })
+}
+
+/// `[z_V(x)]2 = [x^m - 1]2` of the verifier of [`verify_link`].
+pub fn z_v_comm_2<M: BalooEngine, Pcs: BalooPcs<M>>(
+    param: &Pcs::Param,
+    m: usize,
+) -> UnivariateKzgCommitment<M::G2Affine> {
+    let coeffs = iter::once(-M::Scalar::ONE)
+        .chain(iter::repeat(M::Scalar::ZERO).take(m - 1))
+        .chain(iter::once(M::Scalar::ONE))
+        .collect::<Vec<_>>();
+    Pcs::commit_monomial_g2(param, &coeffs)
+}
+
+/// Verify [`prove_link`] of `[φ(x)]1` and the outer `column_comms`.
+pub fn verify_link<M: BalooEngine, Pcs: BalooPcs<M>>(
+    vp: &BalooVerifierParam<M, Pcs>,
+    z_v_comm_2: &UnivariateKzgCommitment<M::G2Affine>,
+    phi_comm_1: &UnivariateKzgCommitment<M::G1Affine>,
+    column_comms: &[UnivariateKzgCommitment<M::G1Affine>],
+    link: &Link<M::Scalar>,
+    proof: &LinkProof<M::G1Affine>,
+) -> Result<(), VerifyError> {
+    if link.coeffs.len() != column_comms.len() {
+        return Err(VerifyError::Instance(format!(
+            "Expected a link coefficient for each of the {} columns but got {}",
+            column_comms.len(),
+            link.coeffs.len()
+        )));
+    }
+
+    // [φ]1 - Σ_j a_j [c_j]1 - b [1]1
+    let scalars = iter::once(M::Scalar::ONE)
+        .chain(link.coeffs.iter().map(|coeff| -*coeff))
+        .chain(iter::once(-link.constant))
+        .collect::<Vec<_>>();
+    let bases = iter::once(phi_comm_1.0)
+        .chain(column_comms.iter().map(|comm| comm.0))
+        .chain(iter::once(M::G1Affine::generator()))
+        .collect::<Vec<_>>();
+    let lhs = variable_base_msm(&scalars, &bases).into();
+    let g1s = [lhs, -proof.q_comm_1.0];
+    let g2s = [Pcs::g2(&vp.vp), z_v_comm_2.0];
+    if multi_pairing::<M>(&g1s, &g2s) != M::Gt::identity() {
+        return Err(VerifyError::Link);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        backend::baloo::{preprocessor::preprocess, Baloo},
+        pcs::univariate::UnivariateKzg,
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+
+    type Pcs = UnivariateKzg<Bn256>;
+
+    #[test]
+    fn test_link() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = [7, 3, 3, 4].map(|i| Fr::from(i as u64)).to_vec();
+        let m = lookup.len();
+        let (param, pp, vp) = preprocess::<Bn256>(table.len(), m).unwrap();
+        let proof = Baloo::prove(&table, &param, &pp, &lookup).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        assert_eq!(Baloo::verify(&vp, &proof, &phi_comm_1, m), Ok(()));
+        let z_v_comm_2 = z_v_comm_2::<Bn256, Pcs>(&param, m);
+
+        // outer columns blinded by z_V (r0 + r1 X), with lookup = a + 2 b + 1
+        let column = |values: &[u64], blinders: [u64; 2]| {
+            let poly =
+                UnivariatePolynomial::lagrange(values.iter().map(|v| Fr::from(*v)).collect())
+                    .into_ifft();
+            let z_v = UnivariatePolynomial::monomial(vec![
+                -Fr::ONE,
+                Fr::ZERO,
+                Fr::ZERO,
+                Fr::ZERO,
+                Fr::ONE,
+            ]);
+            &poly + &z_v * &UnivariatePolynomial::monomial(blinders.map(Fr::from).to_vec())
+        };
+        let a = column(&[2, 0, 2, 1], [5, 6]);
+        let b = column(&[2, 1, 0, 1], [7, 8]);
+        let column_comms = [&a, &b].map(|poly| Pcs::commit_monomial(&pp, poly.coeffs()));
+        let link = Link {
+            coeffs: vec![Fr::ONE, Fr::from(2)],
+            constant: Fr::ONE,
+        };
+        let link_proof = prove_link::<Bn256, Pcs>(&pp, &lookup, &[&a, &b], &link).unwrap();
+        let verify = |link: &Link<Fr>, proof: &LinkProof<_>| {
+            verify_link(&vp, &z_v_comm_2, &phi_comm_1, &column_comms, link, proof)
+        };
+        assert_eq!(verify(&link, &link_proof), Ok(()));
+
+        // φ equal to a single column
+        let c = column(&[7, 3, 3, 4], [9, 10]);
+        let c_comm = Pcs::commit_monomial(&pp, c.coeffs());
+        let equal = Link::equal();
+        let equal_proof = prove_link::<Bn256, Pcs>(&pp, &lookup, &[&c], &equal).unwrap();
+        assert_eq!(
+            verify_link(
+                &vp,
+                &z_v_comm_2,
+                &phi_comm_1,
+                &[c_comm],
+                &equal,
+                &equal_proof
+            ),
+            Ok(())
+        );
+
+        // another link can't be proved or verified
+        let other = Link {
+            coeffs: vec![Fr::ONE, Fr::from(2)],
+            constant: Fr::from(2),
+        };
+        assert!(prove_link::<Bn256, Pcs>(&pp, &lookup, &[&a, &b], &other).is_err());
+        assert_eq!(verify(&other, &link_proof), Err(VerifyError::Link));
+        assert!(matches!(
+            verify(&equal, &link_proof),
+            Err(VerifyError::Instance(_))
+        ));
+    }
+}
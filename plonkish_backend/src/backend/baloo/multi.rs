@@ -0,0 +1,178 @@
+//! Lookups of several lookups `φ_i` into their own tables `t_i`, e.g. a range
+//! table and a bitwise one of the same circuit, in a single proof with one
+//! transcript and one verification.
+//!
+//! Every table is preprocessed with the same SRS. The transcript absorbs
+//! `[t_i(x)]1` and `[φ_i(x)]1` of every pair first, then the Baloo proof of
+//! each pair is written to it in order, so the challenges of every proof
+//! depend on the whole instance. The verifier reads the pairing checks of
+//! every proof and executes them all at once with [`batch_execute`], so it
+//! pays a single Miller loop and final exponentiation.
+//!
+//! [`batch_execute`]: crate::backend::baloo::plan::batch_execute
+
+use crate::{
+    backend::baloo::{
+        preprocessor::ProverTablePreprocessing,
+        proof::BalooProof,
+        prover::Prover,
+        verifier::{execute_plans, Verifier},
+        BalooEngine, BalooPcs, BalooVerifierParam, PhiInstance, VerifyError,
+    },
+    pcs::univariate::UnivariateKzgCommitment,
+    util::{
+        arithmetic::CurveAffine,
+        transcript::{InMemoryTranscript, Transcript},
+    },
+    Error,
+};
+use halo2_curves::bn256::Bn256;
+
+// label absorbed before the pairs' commitments
+const MULTI_LABEL: &[u8] = b"multi";
+
+/// Baloo proof of each pair, in the order of the pairs, whose concatenation
+/// is the transcript of the prover.
+#[derive(Clone, Debug)]
+pub struct MultiProof<M: BalooEngine = Bn256> {
+    proofs: Vec<BalooProof<M>>,
+}
+
+// derived `PartialEq` would require `M: PartialEq`, as of `BalooProof`
+impl<M: BalooEngine> PartialEq for MultiProof<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.proofs == other.proofs
+    }
+}
+
+impl<M: BalooEngine> MultiProof<M> {
+    pub fn proofs(&self) -> &[BalooProof<M>] {
+        &self.proofs
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.proofs.iter().flat_map(BalooProof::to_bytes).collect()
+    }
+
+    /// Parse a proof of `n` pairs, failing if it's not of `n` times
+    /// `BalooProof::size(false)` bytes or a point isn't on the curve.
+    pub fn from_bytes(bytes: &[u8], n: usize) -> Result<Self, Error> {
+        let size = BalooProof::<M>::size(false);
+        if bytes.len() != n * size {
+            return Err(Error::Serialization(format!(
+                "Proof of {n} pairs should be of {} bytes but got {}",
+                n * size,
+                bytes.len()
+            )));
+        }
+        let proofs = bytes
+            .chunks(size)
+            .map(BalooProof::from_bytes)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { proofs })
+    }
+}
+
+/// Prove that every `lookups[i]` is in the table preprocessed in `tables[i]`,
+/// all with the same `param` and `pp`.
+pub fn prove_multi<M: BalooEngine, Pcs: BalooPcs<M>>(
+    tables: &[&ProverTablePreprocessing<M>],
+    param: &Pcs::Param,
+    pp: &Pcs::ProverParam,
+    lookups: &[Vec<M::Scalar>],
+) -> Result<MultiProof<M>, Error> {
+    if tables.is_empty() || lookups.len() != tables.len() {
+        return Err(Error::InvalidSnark(format!(
+            "Expected a lookup for each of the {} tables but got {}",
+            tables.len(),
+            lookups.len()
+        )));
+    }
+
+    let mut transcript = M::Transcript::new(());
+    let t_comms = tables
+        .iter()
+        .map(|table| table.t_comm_1().clone())
+        .collect::<Vec<_>>();
+    let lookup_comms = lookups
+        .iter()
+        .map(|lookup| Pcs::commit_lagrange(pp, lookup))
+        .collect::<Vec<_>>();
+    absorb_pairs(&mut transcript, &t_comms, &lookup_comms)?;
+
+    let proofs = tables
+        .iter()
+        .zip(lookups)
+        .map(|(table, lookup)| {
+            Prover::<M, Pcs>::with_pcs_preprocessed(table, param, pp).prove_with_transcript(
+                lookup,
+                false,
+                &mut transcript,
+            )
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(MultiProof { proofs })
+}
+
+/// Verify a proof of [`prove_multi`], given the verifier param of each table,
+/// the commitment of each lookup, e.g. of [`Baloo::prepare_verification_data`],
+/// and the size of each lookup.
+///
+/// [`Baloo::prepare_verification_data`]: crate::backend::baloo::Baloo::prepare_verification_data
+pub fn verify_multi<M: BalooEngine, Pcs: BalooPcs<M>>(
+    vps: &[&BalooVerifierParam<M, Pcs>],
+    proof: &MultiProof<M>,
+    lookup_comms: &[UnivariateKzgCommitment<M::G1Affine>],
+    ms: &[usize],
+) -> Result<(), VerifyError> {
+    let n = vps.len();
+    if n == 0 || lookup_comms.len() != n || ms.len() != n || proof.proofs.len() != n {
+        return Err(VerifyError::Instance(format!(
+            "Expected a lookup commitment, a lookup size and a proof for each of the {n} tables but got {}, {} and {}",
+            lookup_comms.len(),
+            ms.len(),
+            proof.proofs.len()
+        )));
+    }
+    let srs = |vp: &BalooVerifierParam<M, Pcs>| (Pcs::g2(&vp.vp), Pcs::s_g2(&vp.vp));
+    if vps[1..].iter().any(|vp| srs(vp) != srs(vps[0])) {
+        return Err(VerifyError::Instance(
+            "Tables should be preprocessed with the same SRS".to_string(),
+        ));
+    }
+    if proof.proofs.iter().any(BalooProof::is_public) {
+        return Err(VerifyError::Instance(
+            "Proof should open φ(α) for committed φ".to_string(),
+        ));
+    }
+
+    let bytes = proof.to_bytes();
+    let mut transcript = M::Transcript::from_proof((), bytes.as_slice());
+    let t_comms = vps.iter().map(|vp| vp.t_comm_1.clone()).collect::<Vec<_>>();
+    absorb_pairs(&mut transcript, &t_comms, lookup_comms)?;
+
+    let plans = vps
+        .iter()
+        .zip(lookup_comms)
+        .zip(ms)
+        .map(|((vp, phi_comm_1), m)| {
+            let phi = PhiInstance::Committed(phi_comm_1.clone());
+            Verifier::new(vp).plan_with_transcript(&mut transcript, &phi, *m)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // same SRS, so `[1]2` and `[s]2` are prepared for every pair
+    execute_plans(&plans, vps[0].prepared_g2())
+}
+
+fn absorb_pairs<C: CurveAffine>(
+    transcript: &mut impl Transcript<C, C::Scalar>,
+    t_comms: &[UnivariateKzgCommitment<C>],
+    lookup_comms: &[UnivariateKzgCommitment<C>],
+) -> Result<(), Error> {
+    transcript.common_label(MULTI_LABEL)?;
+    for (t_comm, lookup_comm) in t_comms.iter().zip(lookup_comms) {
+        transcript.common_commitment(&t_comm.0)?;
+        transcript.common_commitment(&lookup_comm.0)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,181 @@
+//! Lookups and tables of any size, padded to the powers of two Baloo
+//! interpolates over.
+//!
+//! The table is padded by repeating its last value and the lookup with a
+//! designated table value, so neither changes the statement that every
+//! lookup value is in the table. The sizes before padding are absorbed by the
+//! transcript before the Baloo proof of the padded lookup and carried in the
+//! [`PaddedProof`], so the proof binds the real statement and not only the
+//! padded one.
+
+use crate::{
+    backend::{
+        baloo::{
+            proof::BalooProof, prover::Prover, verifier::Verifier, BalooEngine, BalooPcs,
+            BalooVerifierParam, PhiInstance, VerifyError,
+        },
+        lookup::witness::pad_to_power_of_two,
+    },
+    pcs::univariate::UnivariateKzgCommitment,
+    util::transcript::{FieldTranscript, InMemoryTranscript},
+    Error,
+};
+use halo2_curves::bn256::Bn256;
+
+// label absorbed before the sizes before padding
+const PADDING_LABEL: &[u8] = b"padding";
+
+/// `table` padded to the next power of two by repeating its last value.
+pub fn pad_table<F: Copy>(table: &[F]) -> Result<Vec<F>, Error> {
+    let last = *table
+        .last()
+        .ok_or_else(|| Error::InvalidSnark("Table is empty".to_string()))?;
+    Ok(pad_to_power_of_two(table, last))
+}
+
+/// `lookup` padded to the next power of two with `table[padding_index]`.
+pub fn pad_lookup<F: Copy>(
+    lookup: &[F],
+    table: &[F],
+    padding_index: usize,
+) -> Result<Vec<F>, Error> {
+    let padding = *table.get(padding_index).ok_or(Error::SizeOutOfRange {
+        name: "padding index",
+        size: padding_index,
+        max: table.len(),
+    })?;
+    Ok(pad_to_power_of_two(lookup, padding))
+}
+
+/// Baloo proof of the padded lookup with the sizes before padding.
+#[derive(Clone, Debug)]
+pub struct PaddedProof<M: BalooEngine = Bn256> {
+    lookup_size: usize,
+    table_size: usize,
+    proof: BalooProof<M>,
+}
+
+// derived `PartialEq` would require `M: PartialEq`, as of `BalooProof`
+impl<M: BalooEngine> PartialEq for PaddedProof<M> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.lookup_size, self.table_size) == (other.lookup_size, other.table_size)
+            && self.proof == other.proof
+    }
+}
+
+impl<M: BalooEngine> PaddedProof<M> {
+    pub fn lookup_size(&self) -> usize {
+        self.lookup_size
+    }
+
+    pub fn table_size(&self) -> usize {
+        self.table_size
+    }
+
+    pub fn proof(&self) -> &BalooProof<M> {
+        &self.proof
+    }
+
+    /// `lookup_size || table_size`, each as 8 little-endian bytes, followed by
+    /// the Baloo proof.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(16 + BalooProof::<M>::size(false));
+        bytes.extend_from_slice(&(self.lookup_size as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.table_size as u64).to_le_bytes());
+        bytes.extend(self.proof.to_bytes());
+        bytes
+    }
+
+    /// Parse a proof of [`PaddedProof::to_bytes`], failing if it's not of
+    /// `16 + BalooProof::size(false)` bytes or a point isn't on the curve.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let size = 16 + BalooProof::<M>::size(false);
+        if bytes.len() != size {
+            return Err(Error::Serialization(format!(
+                "Padded proof should be of {size} bytes but got {}",
+                bytes.len()
+            )));
+        }
+        let size = |bytes: &[u8]| u64::from_le_bytes(bytes.try_into().unwrap()) as usize;
+        Ok(Self {
+            lookup_size: size(&bytes[..8]),
+            table_size: size(&bytes[8..16]),
+            proof: BalooProof::from_bytes(&bytes[16..])?,
+        })
+    }
+}
+
+/// Prove that every value of `lookup` is in `table`, of any sizes, padding
+/// the lookup with `table[padding_index]`. The SRS should be for the padded
+/// sizes, or any of [`preprocess`] with the sizes before padding.
+///
+/// [`preprocess`]: crate::backend::baloo::preprocessor::preprocess
+pub fn prove_padded<M: BalooEngine, Pcs: BalooPcs<M>>(
+    table: &[M::Scalar],
+    param: &Pcs::Param,
+    pp: &Pcs::ProverParam,
+    lookup: &[M::Scalar],
+    padding_index: usize,
+) -> Result<PaddedProof<M>, Error> {
+    if lookup.is_empty() {
+        return Err(Error::InvalidSnark("Lookup is empty".to_string()));
+    }
+    let padded_table = pad_table(table)?;
+    let padded_lookup = pad_lookup(lookup, table, padding_index)?;
+
+    let mut transcript = M::Transcript::new(());
+    absorb_sizes(&mut transcript, lookup.len(), table.len())?;
+    let proof = Prover::<M, Pcs>::with_pcs(&padded_table, param, pp).prove_with_transcript(
+        &padded_lookup,
+        false,
+        &mut transcript,
+    )?;
+    Ok(PaddedProof {
+        lookup_size: lookup.len(),
+        table_size: table.len(),
+        proof,
+    })
+}
+
+/// Verify a proof of [`prove_padded`] given the verifier param of the padded
+/// table, see [`pad_table`], and `[φ(x)]1` of the padded lookup, see
+/// [`pad_lookup`]. The sizes the proof is bound to are
+/// [`PaddedProof::lookup_size`] and [`PaddedProof::table_size`], which the
+/// caller compares with the ones of its statement.
+pub fn verify_padded<M: BalooEngine, Pcs: BalooPcs<M>>(
+    vp: &BalooVerifierParam<M, Pcs>,
+    proof: &PaddedProof<M>,
+    phi_comm_1: &UnivariateKzgCommitment<M::G1Affine>,
+) -> Result<(), VerifyError> {
+    let PaddedProof {
+        lookup_size,
+        table_size,
+        proof,
+    } = proof;
+    if *lookup_size == 0 || *table_size == 0 {
+        return Err(VerifyError::Instance(
+            "Sizes before padding should be non-zero".to_string(),
+        ));
+    }
+    if proof.is_public() {
+        return Err(VerifyError::Instance(
+            "Proof should open φ(α) for committed φ".to_string(),
+        ));
+    }
+
+    let bytes = proof.to_bytes();
+    let mut transcript = M::Transcript::from_proof((), bytes.as_slice());
+    absorb_sizes(&mut transcript, *lookup_size, *table_size)?;
+    let phi = PhiInstance::Committed(phi_comm_1.clone());
+    Verifier::new(vp).verify_with_transcript(&mut transcript, &phi, lookup_size.next_power_of_two())
+}
+
+fn absorb_sizes<F: From<u64>>(
+    transcript: &mut impl FieldTranscript<F>,
+    lookup_size: usize,
+    table_size: usize,
+) -> Result<(), Error> {
+    transcript.common_label(PADDING_LABEL)?;
+    transcript.common_field_element(&F::from(lookup_size as u64))?;
+    transcript.common_field_element(&F::from(table_size as u64))
+}
@@ -0,0 +1,404 @@
+//! Pairing checks of the Baloo verifier in data form, for external verifiers
+//! (Solidity, FFI, recursion) to consume, and for the Rust verifier to execute
+//! so that the two can't drift apart.
+
+use crate::{
+    backend::baloo::util::multi_pairing,
+    util::{
+        arithmetic::{variable_base_msm, Field, Group, MultiMillerLoop},
+        metrics,
+    },
+};
+use halo2_curves::{
+    bn256::{Bn256, Fr},
+    pairing::MillerLoopResult,
+};
+use rand::RngCore;
+use std::{collections::BTreeMap, fmt::Debug};
+
+/// `G2Prepared` of the `G2` points fixed by the verifier param, i.e. `[1]2`,
+/// `[s]2` and the shifts, so that a Miller loop only prepares the ones of the
+/// proof.
+#[derive(Clone)]
+pub struct PreparedG2<M: MultiMillerLoop = Bn256> {
+    points: Vec<(M::G2Affine, M::G2Prepared)>,
+}
+
+impl<M: MultiMillerLoop> PreparedG2<M> {
+    pub fn new(points: impl IntoIterator<Item = M::G2Affine>) -> Self {
+        Self {
+            points: points
+                .into_iter()
+                .map(|point| (point, point.into()))
+                .collect(),
+        }
+    }
+
+    fn get(&self, point: &M::G2Affine) -> Option<&M::G2Prepared> {
+        self.points
+            .iter()
+            .find_map(|(affine, prepared)| (affine == point).then_some(prepared))
+    }
+}
+
+impl<M: MultiMillerLoop> Default for PreparedG2<M> {
+    fn default() -> Self {
+        Self { points: Vec::new() }
+    }
+}
+
+// `G2Prepared` isn't `Debug`
+impl<M: MultiMillerLoop> Debug for PreparedG2<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.points.iter().map(|(affine, _)| affine))
+            .finish()
+    }
+}
+
+/// A named `G1` point known to the verifier.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum G1Element {
+    /// Commitment read from the proof, e.g. `"w1"`.
+    Proof(&'static str),
+    /// Commitment given as part of the instance, e.g. `"t"` or `"z_H"`.
+    Instance(&'static str),
+    /// `[1]1` from the verifier param.
+    Generator,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum G1Source<F = Fr> {
+    Element(G1Element),
+    /// `Σ_i scalar_i * element_i`, with scalars derived from the transcript.
+    Lincomb(Vec<(F, G1Element)>),
+}
+
+/// A named `G2` point known to the verifier.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum G2Source {
+    /// Commitment read from the proof, e.g. `"z_I"`.
+    Proof(&'static str),
+    /// Commitment given as part of the instance, e.g. `"x^(d-m+1)"`.
+    Instance(&'static str),
+    /// `[1]2` from the verifier param.
+    Generator,
+    /// `[s]2` from the verifier param.
+    Tau,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GtSource<F = Fr> {
+    Identity,
+    Product(Vec<(G1Source<F>, G2Source)>),
+}
+
+/// `Π_i e(lhs_i) == rhs`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PairingCheck<F = Fr> {
+    pub name: &'static str,
+    pub lhs: Vec<(G1Source<F>, G2Source)>,
+    pub rhs: GtSource<F>,
+}
+
+/// Ordered pairing checks which all hold for a valid proof.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerificationPlan<F = Fr> {
+    pub checks: Vec<PairingCheck<F>>,
+}
+
+/// Concrete points referenced by a [`VerificationPlan`].
+#[derive(Clone, Debug)]
+pub struct PlanElements<M: MultiMillerLoop = Bn256> {
+    pub proof_g1: BTreeMap<&'static str, M::G1Affine>,
+    pub proof_g2: BTreeMap<&'static str, M::G2Affine>,
+    pub instance_g1: BTreeMap<&'static str, M::G1Affine>,
+    pub instance_g2: BTreeMap<&'static str, M::G2Affine>,
+    pub g1: M::G1Affine,
+    pub g2: M::G2Affine,
+    pub s_g2: M::G2Affine,
+}
+
+impl<M: MultiMillerLoop> PlanElements<M> {
+    fn g1_element(&self, element: &G1Element) -> Option<M::G1Affine> {
+        match element {
+            G1Element::Proof(name) => self.proof_g1.get(name).copied(),
+            G1Element::Instance(name) => self.instance_g1.get(name).copied(),
+            G1Element::Generator => Some(self.g1),
+        }
+    }
+
+    fn g1(&self, source: &G1Source<M::Scalar>) -> Option<M::G1Affine> {
+        match source {
+            G1Source::Element(element) => self.g1_element(element),
+            G1Source::Lincomb(terms) => {
+                let (scalars, bases): (Vec<_>, Vec<_>) = terms
+                    .iter()
+                    .map(|(scalar, element)| Some((*scalar, self.g1_element(element)?)))
+                    .collect::<Option<Vec<_>>>()?
+                    .into_iter()
+                    .unzip();
+                Some(variable_base_msm(&scalars, &bases).into())
+            }
+        }
+    }
+
+    // `source` as `Σ_i scalar_i * point_i`, to be merged into a larger MSM
+    fn g1_terms(&self, source: &G1Source<M::Scalar>) -> Option<Vec<(M::Scalar, M::G1Affine)>> {
+        match source {
+            G1Source::Element(element) => Some(vec![(M::Scalar::ONE, self.g1_element(element)?)]),
+            G1Source::Lincomb(terms) => terms
+                .iter()
+                .map(|(scalar, element)| Some((*scalar, self.g1_element(element)?)))
+                .collect(),
+        }
+    }
+
+    fn g2(&self, source: &G2Source) -> Option<M::G2Affine> {
+        match source {
+            G2Source::Proof(name) => self.proof_g2.get(name).copied(),
+            G2Source::Instance(name) => self.instance_g2.get(name).copied(),
+            G2Source::Generator => Some(self.g2),
+            G2Source::Tau => Some(self.s_g2),
+        }
+    }
+
+    fn product(&self, terms: &[(G1Source<M::Scalar>, G2Source)]) -> Option<M::Gt> {
+        let (g1s, g2s): (Vec<_>, Vec<_>) = terms
+            .iter()
+            .map(|(g1, g2)| Some((self.g1(g1)?, self.g2(g2)?)))
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        Some(multi_pairing::<M>(&g1s, &g2s))
+    }
+}
+
+impl<F: Field> PairingCheck<F> {
+    /// Returns `false` if the check fails or references a missing element.
+    pub fn execute<M: MultiMillerLoop<Scalar = F>>(&self, elements: &PlanElements<M>) -> bool {
+        let lhs = elements.product(&self.lhs);
+        let rhs = match &self.rhs {
+            GtSource::Identity => Some(M::Gt::identity()),
+            GtSource::Product(terms) => elements.product(terms),
+        };
+        matches!((lhs, rhs), (Some(lhs), Some(rhs)) if lhs == rhs)
+    }
+}
+
+impl<F: Field> VerificationPlan<F> {
+    /// Pairings of executing every check on its own.
+    pub fn num_pairings(&self) -> usize {
+        self.checks
+            .iter()
+            .map(|check| match &check.rhs {
+                GtSource::Identity => check.lhs.len(),
+                GtSource::Product(terms) => check.lhs.len() + terms.len(),
+            })
+            .sum()
+    }
+
+    /// MSMs of the [`G1Source::Lincomb`] of every check.
+    pub fn num_msms(&self) -> usize {
+        self.checks
+            .iter()
+            .flat_map(|check| {
+                let rhs = match &check.rhs {
+                    GtSource::Identity => &[][..],
+                    GtSource::Product(terms) => &terms[..],
+                };
+                check.lhs.iter().chain(rhs)
+            })
+            .filter(|(g1, _)| matches!(g1, G1Source::Lincomb(_)))
+            .count()
+    }
+
+    /// Returns the name of the first failing check.
+    pub fn execute<M: MultiMillerLoop<Scalar = F>>(
+        &self,
+        elements: &PlanElements<M>,
+    ) -> Result<(), &'static str> {
+        match self.checks.iter().find(|check| !check.execute(elements)) {
+            Some(check) => Err(check.name),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Execute the checks of all `plans` at once, as `Π_j e(lhs_j / rhs_j)^r_j
+/// = 1` for random `r_j`, which fails with overwhelming probability if any
+/// check does. Terms are grouped by their `G2` point, so there is an MSM and
+/// a pairing per distinct `G2` point, e.g. `[1]2`, `[s]2`, and the shifts
+/// shared by the plans, plus `[z_I]2` of each proof, all in a single Miller
+/// loop and final exponentiation.
+pub fn batch_execute<M: MultiMillerLoop>(
+    plans: &[(&VerificationPlan<M::Scalar>, &PlanElements<M>)],
+    rng: impl RngCore,
+) -> bool {
+    batch_execute_with(plans, &PreparedG2::default(), rng)
+}
+
+/// [`batch_execute`] with the `G2Prepared` of the fixed `G2` points taken
+/// from `prepared`. A group of terms can't be paired with a precomputed
+/// `Gt` even if its `G2` point is fixed, as its `G1` point is an MSM with
+/// scalars from the transcript, but it costs the Miller loop only.
+pub fn batch_execute_with<M: MultiMillerLoop>(
+    plans: &[(&VerificationPlan<M::Scalar>, &PlanElements<M>)],
+    prepared: &PreparedG2<M>,
+    mut rng: impl RngCore,
+) -> bool {
+    let mut groups: Vec<(M::G2Affine, Vec<M::Scalar>, Vec<M::G1Affine>)> = Vec::new();
+    for (plan, elements) in plans {
+        for check in plan.checks.iter() {
+            let r = M::Scalar::random(&mut rng);
+            let rhs = match &check.rhs {
+                GtSource::Identity => &[][..],
+                GtSource::Product(terms) => &terms[..],
+            };
+            let terms = check
+                .lhs
+                .iter()
+                .map(|term| (r, term))
+                .chain(rhs.iter().map(|term| (-r, term)));
+            for (r, (g1, g2)) in terms {
+                let (g1_terms, g2) = match (elements.g1_terms(g1), elements.g2(g2)) {
+                    (Some(g1_terms), Some(g2)) => (g1_terms, g2),
+                    _ => return false,
+                };
+                let idx = match groups.iter().position(|(point, ..)| *point == g2) {
+                    Some(idx) => idx,
+                    None => {
+                        groups.push((g2, Vec::new(), Vec::new()));
+                        groups.len() - 1
+                    }
+                };
+                let (_, scalars, bases) = &mut groups[idx];
+                for (scalar, base) in g1_terms {
+                    scalars.push(r * scalar);
+                    bases.push(base);
+                }
+            }
+        }
+    }
+
+    let (g1s, g2s): (Vec<M::G1Affine>, Vec<M::G2Affine>) = groups
+        .into_iter()
+        .map(|(g2, scalars, bases)| (variable_base_msm(&scalars, &bases).into(), g2))
+        .unzip();
+    multi_pairing_with(&g1s, &g2s, prepared) == M::Gt::identity()
+}
+
+// `Π_i e(g1_i, g2_i)` in a single Miller loop and final exponentiation,
+// preparing only the `G2` points `prepared` doesn't have
+fn multi_pairing_with<M: MultiMillerLoop>(
+    g1s: &[M::G1Affine],
+    g2s: &[M::G2Affine],
+    prepared: &PreparedG2<M>,
+) -> M::Gt {
+    metrics::count_pairings(g1s.len());
+    let unprepared = g2s
+        .iter()
+        .map(|g2| prepared.get(g2).is_none().then(|| M::G2Prepared::from(*g2)))
+        .collect::<Vec<_>>();
+    let terms = g1s
+        .iter()
+        .zip(g2s)
+        .zip(&unprepared)
+        .map(|((g1, g2), unprepared)| (g1, unprepared.as_ref().or(prepared.get(g2)).unwrap()))
+        .collect::<Vec<_>>();
+    M::multi_miller_loop(&terms).final_exponentiation()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::{
+        baloo::{
+            plan::{
+                batch_execute_with, G1Element, G1Source, G2Source, GtSource, PairingCheck,
+                PreparedG2,
+            },
+            verifier::Verifier,
+            Baloo, BalooProof, PhiInstance,
+        },
+        cq::generate_table_and_lookup,
+    };
+    use halo2_curves::bn256::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_execute_plan() {
+        let (table, lookup) = generate_table_and_lookup(8, 4);
+        let m = lookup.len();
+        let t = table.len();
+        let (param, pp, vp) = Baloo::preprocess(t, m).unwrap();
+        let proof = Baloo::prove(&table, &param, &pp, &lookup).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let phi = PhiInstance::Committed(Baloo::prepare_verification_data(&pp, &lookup));
+        let plan = |proof: &BalooProof| Verifier::new(&vp).plan(proof, &phi, m).unwrap();
+
+        let (valid_plan, elements) = plan(&proof);
+        assert_eq!(
+            valid_plan
+                .checks
+                .iter()
+                .map(|check| check.name)
+                .collect::<Vec<_>>(),
+            vec!["subtable", "w1", "w2", "w3", "w4"]
+        );
+        assert_eq!(valid_plan.execute(&elements), Ok(()));
+
+        // swap [w3]1 and [w4]1
+        let mut invalid_proof = proof.clone();
+        std::mem::swap(&mut invalid_proof.w3_comm_1, &mut invalid_proof.w4_comm_1);
+        let (invalid_plan, elements) = plan(&invalid_proof);
+        assert_eq!(invalid_plan, valid_plan);
+        assert_eq!(invalid_plan.execute(&elements), Err("w3"));
+
+        // missing element fails instead of panicking
+        let check = PairingCheck {
+            name: "missing",
+            lhs: vec![(
+                G1Source::Element(G1Element::Proof("missing")),
+                G2Source::Generator,
+            )],
+            rhs: GtSource::Identity,
+        };
+        assert!(!check.execute(&elements));
+
+        // e([1]1, [1]2) = e(1 * [1]1, [1]2)
+        let check = PairingCheck {
+            name: "generator",
+            lhs: vec![(G1Source::Element(G1Element::Generator), G2Source::Generator)],
+            rhs: GtSource::Product(vec![(
+                G1Source::Lincomb(vec![(Fr::one(), G1Element::Generator)]),
+                G2Source::Generator,
+            )]),
+        };
+        assert!(check.execute(&elements));
+    }
+
+    #[test]
+    fn test_batch_execute_with_prepared() {
+        let (table, lookup) = generate_table_and_lookup(8, 4);
+        let m = lookup.len();
+        let (param, pp, vp) = Baloo::preprocess(table.len(), m).unwrap();
+        let proof = Baloo::prove(&table, &param, &pp, &lookup).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let phi = PhiInstance::Committed(Baloo::prepare_verification_data(&pp, &lookup));
+        let prepared = vp.prepared_g2();
+        // [1]2 and [s]2 of the SRS, [z_I]2 of the proof isn't fixed
+        let (plan, elements) = Verifier::new(&vp).plan(&proof, &phi, m).unwrap();
+        assert!(prepared.get(&elements.g2).is_some());
+        assert!(prepared.get(&elements.s_g2).is_some());
+        assert!(prepared.get(&proof.z_i_comm_2).is_none());
+
+        // same result whether the fixed points are prepared or not
+        for prepared in [prepared, &PreparedG2::default()] {
+            assert!(batch_execute_with(&[(&plan, &elements)], prepared, OsRng));
+        }
+        let mut invalid_proof = proof.clone();
+        std::mem::swap(&mut invalid_proof.w3_comm_1, &mut invalid_proof.w4_comm_1);
+        let (plan, elements) = Verifier::new(&vp).plan(&invalid_proof, &phi, m).unwrap();
+        assert!(!batch_execute_with(&[(&plan, &elements)], prepared, OsRng));
+    }
+}
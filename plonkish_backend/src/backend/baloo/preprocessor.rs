@@ -1,41 +1,690 @@
 use crate::pcs::univariate::{
-    UnivariateKzg, UnivariateKzgParam, UnivariateKzgProverParam, UnivariateKzgVerifierParam,
+    UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam, UnivariateKzgProverParam,
+    UnivariateKzgVerifierParam,
+};
+use crate::{
+    backend::baloo::{
+        degree::{self, DegreeBounds},
+        util::{log_2, subtable_indices_by},
+        BalooEngine, BalooPcs, BalooVerifierParam,
+    },
+    pcs::PolynomialCommitmentScheme,
+    poly::univariate::{Radix2Domain, UnivariatePolynomial},
+    util::{
+        arithmetic::{powers, variable_base_msm, Field},
+        parallel::par_map_collect,
+        trace::trace_span,
+        Deserialize, Serialize,
+    },
+    Error,
 };
-use crate::{pcs::PolynomialCommitmentScheme, Error};
 use halo2_curves::bn256::Bn256;
-use rand::rngs::OsRng;
-use std::cmp::max;
+use rand::{rngs::OsRng, RngCore};
+use std::{cmp::max, collections::HashMap, iter, sync::OnceLock};
 
-type Pcs = UnivariateKzg<Bn256>;
+pub fn preprocess<M: BalooEngine>(
+    t: usize,
+    m: usize,
+) -> Result<
+    (
+        UnivariateKzgParam<M>,
+        UnivariateKzgProverParam<M>,
+        UnivariateKzgVerifierParam<M>,
+    ),
+    Error,
+> {
+    preprocess_with_rng(t, m, OsRng)
+}
+
+/// [`preprocess`] with the toxic waste of the SRS sampled from `rng`, e.g. a
+/// seeded one for setups reproducible in tests. Anyone who knows the seed can
+/// forge proofs, so it's never for production.
+pub fn preprocess_with_rng<M: BalooEngine>(
+    t: usize,
+    m: usize,
+    mut rng: impl RngCore,
+) -> Result<
+    (
+        UnivariateKzgParam<M>,
+        UnivariateKzgProverParam<M>,
+        UnivariateKzgVerifierParam<M>,
+    ),
+    Error,
+> {
+    let _span = trace_span!(INFO, "setup", t, m);
+    let param = UnivariateKzg::<M>::setup(poly_size(t, m), 1, &mut rng).unwrap();
+    preprocess_with_param(param, t, m)
+}
 
-pub fn preprocess(
+/// [`preprocess`] with a given SRS, e.g. of a ceremony, so without any
+/// randomness. It fails if `param` is too small for `t` and `m`, and is
+/// trimmed to all of it if larger, as the degree checks shift up to the
+/// largest power of the SRS, see [`degree::w2_shift`].
+pub fn preprocess_with_param<M: BalooEngine>(
+    param: UnivariateKzgParam<M>,
     t: usize,
     m: usize,
 ) -> Result<
     (
-        UnivariateKzgParam<Bn256>,
-        UnivariateKzgProverParam<Bn256>,
-        UnivariateKzgVerifierParam<Bn256>,
+        UnivariateKzgParam<M>,
+        UnivariateKzgProverParam<M>,
+        UnivariateKzgVerifierParam<M>,
     ),
     Error,
 > {
-    let mut rng = OsRng;
-    let poly_size = max(t.next_power_of_two() * 2, m.next_power_of_two() * 2);
-    let param = Pcs::setup(poly_size, 1, &mut rng).unwrap();
-    let (pp, vp) = Pcs::trim(&param, poly_size, 1).unwrap();
+    let size = max(poly_size(t, m), param.degree() + 1);
+    let (pp, vp) = UnivariateKzg::<M>::trim(&param, size, 1)?;
+    // for the table and lookups to be committed without an iFFT
+    let pp = pp.with_lagrange_g1([t.next_power_of_two(), m.next_power_of_two()]);
 
     Ok((param, pp, vp))
 }
 
+pub(crate) fn poly_size(t: usize, m: usize) -> usize {
+    max(t.next_power_of_two() * 2, m.next_power_of_two() * 2)
+}
+
+/// Verifier parameter of lookups of size `m` into `table`, with the table
+/// commitments and the shifts `X^m`, `X^(d-m+1)` and `X^(d-m+2)` of the
+/// degree checks read off the SRS, so the verifier is left with `[φ(x)]1`.
+pub fn preprocess_verifier<M: BalooEngine>(
+    param: &UnivariateKzgParam<M>,
+    pp: &UnivariateKzgProverParam<M>,
+    vp: &UnivariateKzgVerifierParam<M>,
+    table: &TablePreprocessing<M>,
+    m: usize,
+) -> Result<BalooVerifierParam<M>, Error> {
+    preprocess_verifier_with_pcs::<M, UnivariateKzg<M>>(param, pp, vp, table, m)
+}
+
+/// [`preprocess_verifier`] for any [`BalooPcs`].
+pub fn preprocess_verifier_with_pcs<M: BalooEngine, Pcs: BalooPcs<M>>(
+    param: &Pcs::Param,
+    pp: &Pcs::ProverParam,
+    vp: &Pcs::VerifierParam,
+    table: &TablePreprocessing<M>,
+    m: usize,
+) -> Result<BalooVerifierParam<M, Pcs>, Error> {
+    let d = Pcs::max_degree(pp) - 1;
+    // a prover committing with powers above `d + 1` would pass the checks
+    // of `deg(E) < m` and `deg(z_I) ≤ m` with polynomials of higher degree
+    if Pcs::srs_degree(param) > d + 1 {
+        return Err(Error::InvalidPcsParam(format!(
+            "Degree checks need all powers of the SRS of degree {} but got {}",
+            Pcs::srs_degree(param),
+            d + 1
+        )));
+    }
+    DegreeBounds::new(d, table.table().len(), m)?;
+    let comm_1 = |n: usize| UnivariateKzgCommitment(Pcs::monomial_g1(pp)[n]);
+    let comm_2 = |n: usize| UnivariateKzgCommitment(Pcs::monomial_g2(param)[n]);
+
+    Ok(BalooVerifierParam {
+        vp: vp.clone(),
+        m,
+        t: table.table().len(),
+        t_comm_1: table.t_comm_1().clone(),
+        z_h_comm_1: table.z_h_comm_1().clone(),
+        x_m_exponent_poly_comm_1: comm_1(m),
+        x_exponent_poly_comm_2: comm_2(degree::w1_shift(d, m)),
+        x_exponent_poly_2_comm_1: comm_1(degree::w2_shift(d, m)),
+        x_exponent_poly_2_comm_2: comm_2(degree::w2_shift(d, m)),
+        prepared_g2: OnceLock::new(),
+    })
+}
+
+/// Table-dependent artifacts, computed once per table and shared by every
+/// proof against it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+// points and scalars of a `BalooEngine` are (de)serializable already
+#[serde(bound = "")]
+pub struct TablePreprocessing<M: BalooEngine = Bn256> {
+    table: Vec<M::Scalar>,
+    // [t(x)]1
+    t_comm_1: UnivariateKzgCommitment<M::G1Affine>,
+    // [z_H(x)]1 = [x^t - 1]1
+    z_h_comm_1: UnivariateKzgCommitment<M::G1Affine>,
+    // [Σ_i L_i(x)]1 = [1]1, the direction in which a tag moves [t(x)]1
+    lagrange_sum_comm_1: UnivariateKzgCommitment<M::G1Affine>,
+}
+
+impl<M: BalooEngine> TablePreprocessing<M> {
+    pub fn new(pp: &UnivariateKzgProverParam<M>, table: &[M::Scalar]) -> Result<Self, Error> {
+        Self::with_pcs::<UnivariateKzg<M>>(pp, table)
+    }
+
+    /// [`TablePreprocessing::new`] for any [`BalooPcs`].
+    pub fn with_pcs<Pcs: BalooPcs<M>>(
+        pp: &Pcs::ProverParam,
+        table: &[M::Scalar],
+    ) -> Result<Self, Error> {
+        let t = table.len();
+        let _span = trace_span!(INFO, "preprocess_table", t);
+        validate_table_size::<M, Pcs>(pp, t)?;
+
+        let t_comm_1 = Pcs::commit_lagrange(pp, table);
+
+        Ok(Self {
+            table: table.to_vec(),
+            t_comm_1,
+            z_h_comm_1: vanishing_comm_1::<M, Pcs>(pp, t),
+            lagrange_sum_comm_1: Pcs::monomial_g1(pp)[0].into(),
+        })
+    }
+
+    pub fn table(&self) -> &[M::Scalar] {
+        &self.table
+    }
+
+    pub fn t_comm_1(&self) -> &UnivariateKzgCommitment<M::G1Affine> {
+        &self.t_comm_1
+    }
+
+    pub fn z_h_comm_1(&self) -> &UnivariateKzgCommitment<M::G1Affine> {
+        &self.z_h_comm_1
+    }
+
+    /// Derive the preprocessing of `table + tag` (every entry shifted by the
+    /// same tag, e.g. a constant in a high column) without any FFT or MSM.
+    ///
+    /// Since `Σ_i L_i(X) = 1` on `H`, `t'(X) = t(X) + tag` and so
+    /// `[t'(x)]1 = [t(x)]1 + tag·[1]1`, while `z_H` is unchanged.
+    pub fn derive_tagged(&self, tag: M::Scalar) -> Self {
+        let t_comm_1 = variable_base_msm(
+            &[M::Scalar::ONE, tag],
+            &[self.t_comm_1.0, self.lagrange_sum_comm_1.0],
+        )
+        .into();
+
+        Self {
+            table: self.table.iter().map(|value| *value + tag).collect(),
+            t_comm_1: UnivariateKzgCommitment(t_comm_1),
+            z_h_comm_1: self.z_h_comm_1.clone(),
+            lagrange_sum_comm_1: self.lagrange_sum_comm_1.clone(),
+        }
+    }
+
+    /// Derive the preprocessing of the concatenation of `parts`.
+    ///
+    /// The concatenated table lives on a larger domain `H'` (of size the sum
+    /// of the parts), so only the domain bookkeeping is homomorphic:
+    /// `[z_H'(x)]1 = [x^|H'|]1 - [1]1` is read off the SRS directly. The
+    /// table commitment can't be lifted from the parts' commitments (over
+    /// `H'` the interpolant is `S(X^2) + X·D(X^2)`, which needs the even
+    /// powers of the SRS), so it is recommitted from the parts' values.
+    pub fn derive_concatenation(
+        pp: &UnivariateKzgProverParam<M>,
+        parts: &[&TablePreprocessing<M>],
+    ) -> Result<Self, Error> {
+        Self::derive_concatenation_with_pcs::<UnivariateKzg<M>>(pp, parts)
+    }
+
+    /// [`TablePreprocessing::derive_concatenation`] for any [`BalooPcs`].
+    pub fn derive_concatenation_with_pcs<Pcs: BalooPcs<M>>(
+        pp: &Pcs::ProverParam,
+        parts: &[&TablePreprocessing<M>],
+    ) -> Result<Self, Error> {
+        if parts.is_empty() {
+            return Err(Error::InvalidPcsParam(
+                "Expected at least one table to concatenate".to_string(),
+            ));
+        }
+        if parts[1..]
+            .iter()
+            .any(|part| part.lagrange_sum_comm_1 != parts[0].lagrange_sum_comm_1)
+        {
+            return Err(Error::InvalidPcsParam(
+                "Tables to concatenate are preprocessed with different SRS".to_string(),
+            ));
+        }
+
+        let table = parts
+            .iter()
+            .flat_map(|part| part.table.iter().copied())
+            .collect::<Vec<_>>();
+        validate_table_size::<M, Pcs>(pp, table.len())?;
+
+        let t_comm_1 = Pcs::commit_lagrange(pp, &table);
+
+        Ok(Self {
+            z_h_comm_1: vanishing_comm_1::<M, Pcs>(pp, table.len()),
+            table,
+            t_comm_1,
+            lagrange_sum_comm_1: parts[0].lagrange_sum_comm_1.clone(),
+        })
+    }
+
+    /// Derive the preprocessing of `Σ_j γ^j parts[j]`, the tables of the
+    /// columns of a tuple table compressed by `gamma`, see
+    /// [`tuple`](crate::backend::baloo::tuple).
+    ///
+    /// The parts share `H`, so `[t(x)]1 = Σ_j γ^j [t_j(x)]1` and `z_H` is
+    /// unchanged.
+    pub fn derive_combination(
+        parts: &[&TablePreprocessing<M>],
+        gamma: M::Scalar,
+    ) -> Result<Self, Error> {
+        validate_columns(parts)?;
+
+        let gammas = powers(gamma).take(parts.len()).collect::<Vec<_>>();
+        let table = (0..parts[0].table.len())
+            .map(|i| {
+                parts
+                    .iter()
+                    .zip(&gammas)
+                    .map(|(part, gamma)| part.table[i] * gamma)
+                    .sum()
+            })
+            .collect();
+        let t_comms = parts.iter().map(|part| part.t_comm_1.0).collect::<Vec<_>>();
+        let t_comm_1 = variable_base_msm(&gammas, &t_comms).into();
+
+        Ok(Self {
+            table,
+            t_comm_1: UnivariateKzgCommitment(t_comm_1),
+            z_h_comm_1: parts[0].z_h_comm_1.clone(),
+            lagrange_sum_comm_1: parts[0].lagrange_sum_comm_1.clone(),
+        })
+    }
+}
+
+/// Table-dependent artifacts of the prover on top of [`TablePreprocessing`],
+/// computed once and reused by every [`Prover::prove`] against the table, see
+/// [`Prover::new_with_preprocessed`].
+///
+/// Besides `t(X)`, the index of each table value and `H`, this caches the
+/// Caulk+ quotients `(t(X) - t_i) / (X - ω^i)` and `z_H(X) / (X - ω^i)` for
+/// every `i`, which `w5` and `w6` of any lookup are combinations of.
+///
+/// [`Prover::prove`]: crate::backend::baloo::prover::Prover::prove
+/// [`Prover::new_with_preprocessed`]: crate::backend::baloo::prover::Prover::new_with_preprocessed
+#[derive(Clone, Debug, Serialize, Deserialize)]
+// points and scalars of a `BalooEngine` are (de)serializable already
+#[serde(bound = "")]
+pub struct ProverTablePreprocessing<M: BalooEngine = Bn256> {
+    table: TablePreprocessing<M>,
+    // t(X)
+    t_poly: UnivariatePolynomial<M::Scalar>,
+    // index of each table value, the first one if repeated
+    index: HashMap<M::Scalar, usize>,
+    // H of order t
+    domain: Radix2Domain<M::Scalar>,
+    // H = [1, ω, ..., ω^(t-1)]
+    roots_of_unity: Vec<M::Scalar>,
+    // (t(X) - t_i) / (X - ω^i)
+    q_t_polys: Vec<UnivariatePolynomial<M::Scalar>>,
+    // z_H(X) = X^t - 1
+    z_h_poly: UnivariatePolynomial<M::Scalar>,
+    // z_H(X) / (X - ω^i), the vanishing polynomial of H without ω^i
+    z_h_quotients: Vec<UnivariatePolynomial<M::Scalar>>,
+}
+
+impl<M: BalooEngine> ProverTablePreprocessing<M> {
+    pub fn new(pp: &UnivariateKzgProverParam<M>, table: &[M::Scalar]) -> Result<Self, Error> {
+        Self::with_pcs::<UnivariateKzg<M>>(pp, table)
+    }
+
+    /// [`ProverTablePreprocessing::new`] for any [`BalooPcs`].
+    pub fn with_pcs<Pcs: BalooPcs<M>>(
+        pp: &Pcs::ProverParam,
+        table: &[M::Scalar],
+    ) -> Result<Self, Error> {
+        let _span = trace_span!(INFO, "preprocess_prover_table", t = table.len());
+        let table = TablePreprocessing::with_pcs::<Pcs>(pp, table)?;
+        let t = table.table.len();
+
+        let t_poly = UnivariatePolynomial::lagrange(table.table.clone()).into_ifft();
+        let mut index = HashMap::new();
+        for (i, value) in table.table.iter().enumerate().rev() {
+            index.insert(*value, i);
+        }
+        let domain = Radix2Domain::new(log_2(t));
+        let roots_of_unity = domain.elements();
+        // one division per table value, the bulk of the preprocessing
+        let q_t_polys = par_map_collect(0..t, |i| {
+            &(t_poly.clone() + -table.table[i])
+                / &UnivariatePolynomial::monomial(vec![-roots_of_unity[i], M::Scalar::ONE])
+        });
+        let (z_h_poly, z_h_quotients) = vanishing_polys(&roots_of_unity);
+
+        Ok(Self {
+            table,
+            t_poly,
+            index,
+            domain,
+            roots_of_unity,
+            q_t_polys,
+            z_h_poly,
+            z_h_quotients,
+        })
+    }
+
+    pub fn table(&self) -> &[M::Scalar] {
+        self.table.table()
+    }
+
+    pub fn t_comm_1(&self) -> &UnivariateKzgCommitment<M::G1Affine> {
+        self.table.t_comm_1()
+    }
+
+    pub fn z_h_comm_1(&self) -> &UnivariateKzgCommitment<M::G1Affine> {
+        self.table.z_h_comm_1()
+    }
+
+    /// Derive the preprocessing of `Σ_j γ^j parts[j]`, see
+    /// [`TablePreprocessing::derive_combination`].
+    ///
+    /// `t(X)` and the quotients are linear in the table, so they're the same
+    /// combination of the parts' ones, which saves the divisions of
+    /// [`ProverTablePreprocessing::new`] but not its quadratic cost.
+    pub fn derive_combination(
+        parts: &[&ProverTablePreprocessing<M>],
+        gamma: M::Scalar,
+    ) -> Result<Self, Error> {
+        let table = TablePreprocessing::derive_combination(
+            &parts.iter().map(|part| &part.table).collect::<Vec<_>>(),
+            gamma,
+        )?;
+
+        let gammas = powers(gamma).take(parts.len()).collect::<Vec<_>>();
+        let t_poly = gammas
+            .iter()
+            .zip(parts.iter().map(|part| &part.t_poly))
+            .sum::<UnivariatePolynomial<_>>();
+        let mut index = HashMap::new();
+        for (i, value) in table.table.iter().enumerate().rev() {
+            index.insert(*value, i);
+        }
+        let q_t_polys = par_map_collect(0..table.table.len(), |i| {
+            gammas
+                .iter()
+                .zip(parts.iter().map(|part| &part.q_t_polys[i]))
+                .sum::<UnivariatePolynomial<_>>()
+        });
+
+        Ok(Self {
+            table,
+            t_poly,
+            index,
+            domain: parts[0].domain,
+            roots_of_unity: parts[0].roots_of_unity.clone(),
+            q_t_polys,
+            z_h_poly: parts[0].z_h_poly.clone(),
+            z_h_quotients: parts[0].z_h_quotients.clone(),
+        })
+    }
+
+    /// The part shared with the verifier, see [`preprocess_verifier`].
+    pub fn table_preprocessing(&self) -> &TablePreprocessing<M> {
+        &self.table
+    }
+
+    pub(crate) fn t_poly(&self) -> &UnivariatePolynomial<M::Scalar> {
+        &self.t_poly
+    }
+
+    pub(crate) fn domain(&self) -> &Radix2Domain<M::Scalar> {
+        &self.domain
+    }
+
+    pub(crate) fn roots_of_unity(&self) -> &[M::Scalar] {
+        &self.roots_of_unity
+    }
+
+    pub(crate) fn q_t_polys(&self) -> &[UnivariatePolynomial<M::Scalar>] {
+        &self.q_t_polys
+    }
+
+    pub(crate) fn z_h_poly(&self) -> &UnivariatePolynomial<M::Scalar> {
+        &self.z_h_poly
+    }
+
+    pub(crate) fn z_h_quotients(&self) -> &[UnivariatePolynomial<M::Scalar>] {
+        &self.z_h_quotients
+    }
+
+    /// [`subtable_indices`](crate::backend::baloo::util::subtable_indices) of `lookup` by the
+    /// cached index, or the positions of the values missing in the table.
+    pub(crate) fn subtable_indices(
+        &self,
+        lookup: &[M::Scalar],
+    ) -> Result<(Vec<usize>, Vec<usize>), Error> {
+        subtable_indices_by(lookup, |value| self.index.get(value).copied()).ok_or_else(|| {
+            Error::NotInTable {
+                positions: (0..lookup.len())
+                    .filter(|&j| !self.index.contains_key(&lookup[j]))
+                    .collect(),
+            }
+        })
+    }
+}
+
+/// `z_H(X) = X^t - 1` of `H = [1, ω, ..., ω^(t-1)]` and its quotient by each
+/// `X - ω^i`, which is `Σ_j ω^(-i(j+1)) X^j` as `ω^(it) = 1`, so without any
+/// division.
+fn vanishing_polys<F: Field>(
+    roots_of_unity: &[F],
+) -> (UnivariatePolynomial<F>, Vec<UnivariatePolynomial<F>>) {
+    let t = roots_of_unity.len();
+    let z_h_poly = UnivariatePolynomial::monomial(
+        iter::once(-F::ONE)
+            .chain(iter::repeat(F::ZERO).take(t - 1))
+            .chain(iter::once(F::ONE))
+            .collect(),
+    );
+    let z_h_quotients = par_map_collect(0..t, |i| {
+        UnivariatePolynomial::monomial(
+            (0..t)
+                .map(|j| roots_of_unity[(t - i * (j + 1) % t) % t])
+                .collect(),
+        )
+    });
+    (z_h_poly, z_h_quotients)
+}
+
+fn validate_table_size<M: BalooEngine, Pcs: BalooPcs<M>>(
+    pp: &Pcs::ProverParam,
+    t: usize,
+) -> Result<(), Error> {
+    if !t.is_power_of_two() {
+        return Err(Error::NotPowerOfTwo {
+            name: "table",
+            size: t,
+        });
+    }
+    if t > Pcs::max_degree(pp) {
+        return Err(Error::SizeOutOfRange {
+            name: "table",
+            size: t,
+            max: Pcs::max_degree(pp),
+        });
+    }
+    Ok(())
+}
+
+// the columns of a tuple table should be at least one and of the same size,
+// preprocessed with the same SRS
+fn validate_columns<M: BalooEngine>(parts: &[&TablePreprocessing<M>]) -> Result<(), Error> {
+    let Some(first) = parts.first() else {
+        return Err(Error::InvalidPcsParam(
+            "Expected at least one table column".to_string(),
+        ));
+    };
+    for part in &parts[1..] {
+        if part.table.len() != first.table.len() {
+            return Err(Error::SizeMismatch {
+                name: "table column",
+                expected: first.table.len(),
+                got: part.table.len(),
+            });
+        }
+        if part.lagrange_sum_comm_1 != first.lagrange_sum_comm_1 {
+            return Err(Error::InvalidPcsParam(
+                "Table columns are preprocessed with different SRS".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// [x^n - 1]1 from the SRS, without the MSM over the dense vanishing polynomial
+pub(crate) fn vanishing_comm_1<M: BalooEngine, Pcs: BalooPcs<M>>(
+    pp: &Pcs::ProverParam,
+    n: usize,
+) -> UnivariateKzgCommitment<M::G1Affine> {
+    let monomial_g1 = Pcs::monomial_g1(pp);
+    let comm = variable_base_msm(
+        &[M::Scalar::ONE, -M::Scalar::ONE],
+        &[monomial_g1[n], monomial_g1[0]],
+    );
+    UnivariateKzgCommitment(comm.into())
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::backend::baloo::preprocessor::preprocess;
+    use crate::{
+        backend::baloo::{
+            preprocessor::{
+                poly_size, preprocess, preprocess_verifier, preprocess_with_param,
+                ProverTablePreprocessing, TablePreprocessing,
+            },
+            prover::Prover,
+            Baloo, BalooVerifierParam,
+        },
+        pcs::{
+            univariate::{UnivariateKzg, UnivariateKzgParam, UnivariateKzgProverParam},
+            PolynomialCommitmentScheme,
+        },
+        poly::{univariate::UnivariatePolynomial, Polynomial},
+        util::{arithmetic::Field, test::std_rng, DeserializeOwned, Serialize},
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+
+    fn reload<T: Serialize + DeserializeOwned>(value: &T) -> T {
+        bincode::deserialize(&bincode::serialize(value).unwrap()).unwrap()
+    }
 
     #[test]
     fn test_preprocess() {
-        let (param, pp, vp) = preprocess(10, 10).unwrap();
+        let (param, pp, vp) = preprocess::<Bn256>(10, 10).unwrap();
         println!("param: {:?}", param);
         println!("pp: {:?}", pp);
         println!("vp: {:?}", vp);
     }
+
+    #[test]
+    fn test_preprocess_verifier() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let (param, pp, vp) = preprocess::<Bn256>(table.len(), 4).unwrap();
+        let table = TablePreprocessing::new(&pp, &table).unwrap();
+        let vp = preprocess_verifier(&param, &pp, &vp, &table, 4).unwrap();
+        assert_eq!(vp.m(), 4);
+        assert_eq!(vp.t_comm_1(), table.t_comm_1());
+        // lookup sizes outside of [1, d], with d = 14
+        assert!(preprocess_verifier(&param, &pp, &vp.vp, &table, 0).is_err());
+        assert!(preprocess_verifier(&param, &pp, &vp.vp, &table, 16).is_err());
+    }
+
+    #[test]
+    fn test_preprocess_with_larger_param() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let size = 4 * poly_size(table.len(), 4);
+        let param = UnivariateKzg::<Bn256>::setup(size, 1, std_rng()).unwrap();
+
+        // trimmed to the whole SRS, so d + 1 is its largest power
+        let (param, pp, vp) = preprocess_with_param(param, table.len(), 4).unwrap();
+        assert_eq!(pp.degree(), size - 1);
+        let table = TablePreprocessing::new(&pp, &table).unwrap();
+        assert!(preprocess_verifier(&param, &pp, &vp, &table, 4).is_ok());
+
+        // a prover param trimmed below the SRS is refused
+        let (pp, vp) = UnivariateKzg::<Bn256>::trim(&param, poly_size(8, 4), 1).unwrap();
+        assert!(preprocess_verifier(&param, &pp, &vp, &table, 4).is_err());
+    }
+
+    #[test]
+    fn test_derive_tagged() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let tag = Fr::from(1 << 16);
+        let (param, pp, vp) = preprocess::<Bn256>(table.len(), 4).unwrap();
+
+        let base = TablePreprocessing::new(&pp, &table).unwrap();
+        let derived = base.derive_tagged(tag);
+
+        let tagged_table = table.iter().map(|value| *value + tag).collect::<Vec<_>>();
+        let expected = TablePreprocessing::new(&pp, &tagged_table).unwrap();
+        assert_eq!(derived.table(), expected.table());
+        assert_eq!(derived.t_comm_1(), expected.t_comm_1());
+        assert_eq!(derived.z_h_comm_1(), expected.z_h_comm_1());
+
+        // proof against the independently preprocessed table verifies with
+        // the derived commitments
+        let lookup = [3, 7, 3, 4]
+            .map(|i| Fr::from(i as u64) + tag)
+            .to_vec();
+        let m = lookup.len();
+        let proof = Prover::new(&tagged_table, &param, &pp)
+            .prove(&lookup)
+            .unwrap();
+        let vp = preprocess_verifier(&param, &pp, &vp, &derived, m).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        assert_eq!(Baloo::verify(&vp, &proof, &phi_comm_1, m), Ok(()));
+    }
+
+    #[test]
+    fn test_derive_concatenation() {
+        let lhs = (1..=4).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let rhs = (11..=14).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let (_, pp, _) = preprocess::<Bn256>(lhs.len() + rhs.len(), 4).unwrap();
+
+        let lhs = TablePreprocessing::new(&pp, &lhs).unwrap();
+        let rhs = TablePreprocessing::new(&pp, &rhs).unwrap();
+        let derived = TablePreprocessing::derive_concatenation(&pp, &[&lhs, &rhs]).unwrap();
+
+        let table = [lhs.table(), rhs.table()].concat();
+        let expected = TablePreprocessing::new(&pp, &table).unwrap();
+        assert_eq!(derived.table(), expected.table());
+        assert_eq!(derived.t_comm_1(), expected.t_comm_1());
+        assert_eq!(derived.z_h_comm_1(), expected.z_h_comm_1());
+
+        // concatenation of tables which doesn't land on a power-of-two domain
+        let odd = TablePreprocessing::new(&pp, &[Fr::ONE, Fr::from(2)]).unwrap();
+        assert!(TablePreprocessing::derive_concatenation(&pp, &[&lhs, &odd]).is_err());
+    }
+
+    #[test]
+    fn test_serialization() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = vec![Fr::from(7), Fr::from(3), Fr::from(3), Fr::from(4)];
+        let m = lookup.len();
+        let (param, pp, vp) = preprocess::<Bn256>(table.len(), m).unwrap();
+        let table_pp = ProverTablePreprocessing::new(&pp, &table).unwrap();
+        let vp = preprocess_verifier(&param, &pp, &vp, table_pp.table_preprocessing(), m).unwrap();
+
+        // as if written by a setup binary and read by the prover and verifier
+        let (param, pp): (UnivariateKzgParam<Bn256>, UnivariateKzgProverParam<Bn256>) =
+            (reload(&param), reload(&pp));
+        let table_pp = reload(&table_pp);
+        let vp: BalooVerifierParam = reload(&vp);
+
+        let proof = Prover::new_with_preprocessed(&table_pp, &param, &pp)
+            .prove(&lookup)
+            .unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        assert_eq!(Baloo::verify(&vp, &proof, &phi_comm_1, m), Ok(()));
+    }
+    #[test]
+    fn test_vanishing_polys() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let (_, pp, _) = preprocess::<Bn256>(table.len(), 4).unwrap();
+        let table_pp = ProverTablePreprocessing::new(&pp, &table).unwrap();
+        let (roots, z_h_poly) = (table_pp.roots_of_unity(), table_pp.z_h_poly());
+        assert_eq!(roots, table_pp.domain().elements());
+        assert_eq!(*z_h_poly, table_pp.domain().vanishing_poly());
+        for (root, quotient) in roots.iter().zip(table_pp.z_h_quotients()) {
+            assert_eq!(z_h_poly.evaluate(root), Fr::ZERO);
+            let divisor = UnivariatePolynomial::monomial(vec![-*root, Fr::ONE]);
+            assert_eq!(*quotient, z_h_poly / &divisor);
+        }
+    }
 }
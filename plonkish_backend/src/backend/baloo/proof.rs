@@ -0,0 +1,554 @@
+use crate::{
+    backend::{
+        baloo::{BalooEngine, Instance, PhiInstance},
+        lookup,
+    },
+    util::{
+        arithmetic::{Coordinates, CurveAffine, PrimeField},
+        transcript::{
+            FieldTranscriptRead, FieldTranscriptWrite, G2TranscriptRead, G2TranscriptWrite,
+            InMemoryTranscript, TranscriptRead, TranscriptWrite,
+        },
+        Deserialize, Itertools, Serialize,
+    },
+    Error,
+};
+use halo2_curves::bn256::{Bn256, G1Affine};
+use std::iter;
+
+/// Baloo proof, whose fields are in the order they are written to the
+/// transcript, so [`BalooProof::to_bytes`] is the transcript of the prover,
+/// e.g. the Keccak256 one for BN254.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+// points and scalars of a `BalooEngine` are (de)serializable already
+#[serde(bound = "")]
+pub struct BalooProof<M: BalooEngine = Bn256> {
+    // π1
+    pub v_comm_1: M::G1Affine,
+    pub z_i_comm_2: M::G2Affine,
+    pub t_i_comm_1: M::G1Affine,
+    // π2
+    pub d_comm_1: M::G1Affine,
+    pub r_comm_1: M::G1Affine,
+    pub q_d_comm_1: M::G1Affine,
+    // π3
+    pub e_comm_1: M::G1Affine,
+    pub q_e_comm_1: M::G1Affine,
+    // π4
+    pub v1: M::Scalar,
+    /// `φ(α)`, left out if `φ` is public.
+    pub v2: Option<M::Scalar>,
+    pub v3: M::Scalar,
+    pub v4: M::Scalar,
+    pub v5: M::Scalar,
+    pub a_comm_1: M::G1Affine,
+    pub w1_comm_1: M::G1Affine,
+    pub w2_comm_1: M::G1Affine,
+    pub w3_comm_1: M::G1Affine,
+    pub w4_comm_1: M::G1Affine,
+}
+
+// derived `PartialEq` would require `M: PartialEq`, which engines aren't
+impl<M: BalooEngine> PartialEq for BalooProof<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.g1_comms() == other.g1_comms()
+            && self.z_i_comm_2 == other.z_i_comm_2
+            && (self.v1, self.v2, self.v3, self.v4, self.v5)
+                == (other.v1, other.v2, other.v3, other.v4, other.v5)
+    }
+}
+
+// size of the repr of a field element, which is written as is
+fn repr_size<F: PrimeField>() -> usize {
+    F::Repr::default().as_ref().len()
+}
+
+/// Version of the format of [`BalooProof::to_versioned_bytes`], bumped on any
+/// change of [`BalooProof::to_bytes`] or of the flags.
+pub const PROOF_VERSION: u8 = 1;
+// flag of a proof for public `φ`, the only one so far
+const PUBLIC_PHI_FLAG: u8 = 1;
+
+const NUM_G1: usize = 12;
+const NUM_G2: usize = 1;
+// pairings and MSMs of the checks `subtable`, `w1`, `w2`, `w3` and `w4` of
+// the verifier, i.e. 2 + 3 + 4 + 3 + 2 and 2 + 2 + 3 + 2 + 1
+const NUM_PAIRINGS: usize = 14;
+const NUM_MSMS: usize = 10;
+
+/// What a Baloo proof is made of and what its verifier does, which are the
+/// same for any table and lookup size, to compare it with other lookup
+/// arguments without producing one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofStats {
+    pub size_in_bytes: usize,
+    pub num_g1: usize,
+    pub num_g2: usize,
+    pub num_field_elements: usize,
+    /// Pairings of the checks executed one by one, e.g. by [`Baloo::verify`],
+    /// which [`Baloo::batch_verify`] merges into one per distinct `G2` point.
+    ///
+    /// [`Baloo::verify`]: crate::backend::baloo::Baloo::verify
+    /// [`Baloo::batch_verify`]: crate::backend::baloo::Baloo::batch_verify
+    pub num_pairings: usize,
+    /// MSMs of the `G1` linear combinations of the checks.
+    pub num_msms: usize,
+}
+
+impl<M: BalooEngine> BalooProof<M> {
+    /// Size in bytes of a proof for committed `φ`, or public one if
+    /// `is_public`, with points uncompressed.
+    pub fn size(is_public: bool) -> usize {
+        let g1_size = 2 * repr_size::<<M::G1Affine as CurveAffine>::Base>();
+        let g2_size = 2 * repr_size::<<M::G2Affine as CurveAffine>::Base>();
+        let fe_size = repr_size::<M::Scalar>();
+        NUM_G1 * g1_size + NUM_G2 * g2_size + Self::num_field_elements(is_public) * fe_size
+    }
+
+    /// [`BalooProof::size`] of this proof, i.e. the length of
+    /// [`BalooProof::to_bytes`].
+    pub fn size_in_bytes(&self) -> usize {
+        Self::size(self.is_public())
+    }
+
+    /// [`ProofStats`] of a proof for committed `φ`, or public one if
+    /// `is_public`.
+    pub fn stats_of(is_public: bool) -> ProofStats {
+        ProofStats {
+            size_in_bytes: Self::size(is_public),
+            num_g1: NUM_G1,
+            num_g2: NUM_G2,
+            num_field_elements: Self::num_field_elements(is_public),
+            num_pairings: NUM_PAIRINGS,
+            num_msms: NUM_MSMS,
+        }
+    }
+
+    pub fn stats(&self) -> ProofStats {
+        Self::stats_of(self.is_public())
+    }
+
+    // `φ(α)` isn't sent if `φ` is public
+    fn num_field_elements(is_public: bool) -> usize {
+        if is_public {
+            4
+        } else {
+            5
+        }
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.v2.is_none()
+    }
+
+    fn g1_comms(&self) -> [M::G1Affine; NUM_G1] {
+        [
+            self.v_comm_1,
+            self.t_i_comm_1,
+            self.d_comm_1,
+            self.r_comm_1,
+            self.q_d_comm_1,
+            self.e_comm_1,
+            self.q_e_comm_1,
+            self.a_comm_1,
+            self.w1_comm_1,
+            self.w2_comm_1,
+            self.w3_comm_1,
+            self.w4_comm_1,
+        ]
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut transcript = M::Transcript::new(());
+        self.write(&mut transcript).unwrap();
+        transcript.into_proof()
+    }
+
+    /// Parse a proof, which is for public `φ` if it's of
+    /// `BalooProof::size(true)` bytes, failing if it's of neither size or a
+    /// point isn't on the curve or in its subgroup of prime order.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let is_public = if bytes.len() == Self::size(false) {
+            false
+        } else if bytes.len() == Self::size(true) {
+            true
+        } else {
+            return Err(Error::Serialization(format!(
+                "Proof should be of {} or {} bytes but got {}",
+                Self::size(false),
+                Self::size(true),
+                bytes.len()
+            )));
+        };
+        let mut transcript = M::Transcript::from_proof((), bytes);
+        Self::read(&mut transcript, is_public)
+    }
+
+    /// `len(PROTOCOL_ID) || PROTOCOL_ID || PROOF_VERSION || flags || proof`
+    /// of [`BalooProof::to_bytes`], with the flags telling whether `φ` is
+    /// public, for proofs stored or sent to another build of the crate.
+    pub fn to_versioned_bytes(&self) -> Vec<u8> {
+        let protocol_id = <Instance as lookup::Instance>::PROTOCOL_ID;
+        let flags = if self.is_public() { PUBLIC_PHI_FLAG } else { 0 };
+        let mut bytes = Vec::with_capacity(protocol_id.len() + 3 + self.size_in_bytes());
+        bytes.push(protocol_id.len() as u8);
+        bytes.extend_from_slice(protocol_id);
+        bytes.extend([PROOF_VERSION, flags]);
+        bytes.extend(self.to_bytes());
+        bytes
+    }
+
+    /// Parse a proof of [`BalooProof::to_versioned_bytes`], failing if it's
+    /// of another protocol or format version, has unknown flags or isn't of
+    /// the size its flags tell, or as [`BalooProof::from_bytes`] does.
+    pub fn from_versioned_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let protocol_id = <Instance as lookup::Instance>::PROTOCOL_ID;
+        let header = [&[protocol_id.len() as u8][..], protocol_id].concat();
+        let Some([version, flags, bytes @ ..]) = bytes.strip_prefix(header.as_slice()) else {
+            return Err(Error::Serialization("Not a Baloo proof".to_string()));
+        };
+        if *version != PROOF_VERSION {
+            return Err(Error::Serialization(format!(
+                "Proof of format version {version} but expected {PROOF_VERSION}"
+            )));
+        }
+        if flags & !PUBLIC_PHI_FLAG != 0 {
+            return Err(Error::Serialization(format!(
+                "Proof with unknown flags {flags:#04x}"
+            )));
+        }
+        let is_public = flags & PUBLIC_PHI_FLAG != 0;
+        if bytes.len() != Self::size(is_public) {
+            return Err(Error::Serialization(format!(
+                "Proof for {} φ should be of {} bytes but got {}",
+                if is_public { "public" } else { "committed" },
+                Self::size(is_public),
+                bytes.len()
+            )));
+        }
+        let mut transcript = M::Transcript::from_proof((), bytes);
+        Self::read(&mut transcript, is_public)
+    }
+
+    fn write(
+        &self,
+        transcript: &mut (impl TranscriptWrite<M::G1Affine, M::Scalar>
+                  + G2TranscriptWrite<M::G2Affine, M::Scalar>),
+    ) -> Result<(), Error> {
+        transcript.write_commitment(&self.v_comm_1)?;
+        transcript.write_commitment_g2(&self.z_i_comm_2)?;
+        transcript.write_commitments(&[
+            self.t_i_comm_1,
+            self.d_comm_1,
+            self.r_comm_1,
+            self.q_d_comm_1,
+            self.e_comm_1,
+            self.q_e_comm_1,
+        ])?;
+        transcript.write_field_element(&self.v1)?;
+        if let Some(v2) = &self.v2 {
+            transcript.write_field_element(v2)?;
+        }
+        transcript.write_field_elements(&[self.v3, self.v4, self.v5])?;
+        transcript.write_commitments(&[
+            self.a_comm_1,
+            self.w1_comm_1,
+            self.w2_comm_1,
+            self.w3_comm_1,
+            self.w4_comm_1,
+        ])
+    }
+
+    fn read(
+        transcript: &mut (impl TranscriptRead<M::G1Affine, M::Scalar>
+                  + G2TranscriptRead<M::G2Affine, M::Scalar>),
+        is_public: bool,
+    ) -> Result<Self, Error> {
+        let v_comm_1 = transcript.read_commitment()?;
+        let z_i_comm_2 = transcript.read_commitment_g2()?;
+        let [t_i_comm_1, d_comm_1, r_comm_1, q_d_comm_1, e_comm_1, q_e_comm_1] =
+            <[_; 6]>::try_from(transcript.read_commitments(6)?).unwrap();
+        let v1 = transcript.read_field_element()?;
+        let v2 = if is_public {
+            None
+        } else {
+            Some(transcript.read_field_element()?)
+        };
+        let [v3, v4, v5] = <[_; 3]>::try_from(transcript.read_field_elements(3)?).unwrap();
+        let [a_comm_1, w1_comm_1, w2_comm_1, w3_comm_1, w4_comm_1] =
+            <[_; 5]>::try_from(transcript.read_commitments(5)?).unwrap();
+        Ok(BalooProof {
+            v_comm_1,
+            z_i_comm_2,
+            t_i_comm_1,
+            d_comm_1,
+            r_comm_1,
+            q_d_comm_1,
+            e_comm_1,
+            q_e_comm_1,
+            v1,
+            v2,
+            v3,
+            v4,
+            v5,
+            a_comm_1,
+            w1_comm_1,
+            w2_comm_1,
+            w3_comm_1,
+            w4_comm_1,
+        })
+    }
+}
+
+impl BalooProof<Bn256> {
+    /// ABI encoding of `(uint256[] instance, bytes proof)` for on-chain
+    /// verifiers, where `instance` is `[φ(x)]1` as `[x, y]` for committed `φ`
+    /// or the lookup values for public one, and `proof` is
+    /// [`BalooProof::to_bytes`], which is in big-endian words already, see
+    /// [`evm_words`]. It fails if `phi` isn't of the kind the proof is for.
+    pub fn to_evm_calldata(&self, phi: &PhiInstance<G1Affine>) -> Result<Vec<u8>, Error> {
+        let instance = match (phi, self.is_public()) {
+            (PhiInstance::Committed(phi_comm_1), false) => evm_words(&phi_comm_1.0),
+            (PhiInstance::Public(values), true) => values.iter().map(evm_word).collect(),
+            _ => {
+                return Err(Error::InvalidSnark(
+                    "Proof and φ should be both committed or both public".to_string(),
+                ))
+            }
+        };
+        let proof = self.to_bytes();
+        let proof_offset = 2 * 32 + (1 + instance.len()) * 32;
+        Ok([
+            usize_word(2 * 32),
+            usize_word(proof_offset),
+            usize_word(instance.len()),
+        ]
+        .into_iter()
+        .chain(instance)
+        .flatten()
+        .chain(usize_word(proof.len()))
+        .chain(proof.iter().copied())
+        .chain(iter::repeat(0).take((32 - proof.len() % 32) % 32))
+        .collect())
+    }
+}
+
+/// Big-endian word of a field element, as taken by the EVM.
+pub fn evm_word<F: PrimeField>(fe: &F) -> [u8; 32] {
+    let mut repr = fe.to_repr();
+    repr.as_mut().reverse();
+    repr.as_ref().try_into().unwrap()
+}
+
+/// Big-endian words of the coordinates of a point, as taken by the BN254
+/// precompiles, so `c1` before `c0` for a coordinate in `Fq2`, and zeros for
+/// the identity.
+pub fn evm_words<C: CurveAffine>(point: &C) -> Vec<[u8; 32]> {
+    let num_words = 2 * repr_size::<C::Base>() / 32;
+    let Some(coordinates) = Option::<Coordinates<C>>::from(point.coordinates()) else {
+        return vec![[0; 32]; num_words];
+    };
+    [coordinates.x(), coordinates.y()]
+        .into_iter()
+        .flat_map(|coordinate| {
+            let mut repr = coordinate.to_repr();
+            repr.as_mut().reverse();
+            repr.as_ref()
+                .chunks(32)
+                .map(|word| word.try_into().unwrap())
+                .collect_vec()
+        })
+        .collect()
+}
+
+pub(crate) fn usize_word(value: usize) -> [u8; 32] {
+    let mut word = [0; 32];
+    word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backend::baloo::{
+            preprocessor::preprocess,
+            proof::{evm_word, evm_words, usize_word, BalooProof, ProofStats, PROOF_VERSION},
+            prover::Prover,
+            verifier::Verifier,
+            Baloo, PhiInstance,
+        },
+        Error,
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+
+    #[test]
+    fn test_proof_bytes() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = vec![Fr::from(7), Fr::from(3), Fr::from(3), Fr::from(4)];
+        let (param, pp, _) = preprocess::<Bn256>(table.len(), lookup.len()).unwrap();
+        let prover = Prover::new(&table, &param, &pp);
+
+        for proof in [
+            prover.prove(&lookup).unwrap(),
+            prover.prove_public(&lookup).unwrap(),
+        ] {
+            let bytes = proof.to_bytes();
+            assert_eq!(bytes.len(), BalooProof::<Bn256>::size(proof.is_public()));
+            assert_eq!(bytes.len(), proof.size_in_bytes());
+            assert_eq!(BalooProof::from_bytes(&bytes), Ok(proof.clone()));
+
+            let encoded = bincode::serialize(&proof).unwrap();
+            assert_eq!(bincode::deserialize::<BalooProof>(&encoded).unwrap(), proof);
+        }
+
+        // ξ, t_I, D, R, Q_D, E, Q_E, a and w1..w4 in G1, z_I in G2 and v1..v5
+        assert_eq!(BalooProof::<Bn256>::size(false), 12 * 64 + 128 + 5 * 32);
+        assert_eq!(BalooProof::<Bn256>::size(true), 12 * 64 + 128 + 4 * 32);
+        let proof = prover.prove(&lookup).unwrap();
+        assert_eq!(BalooProof::from_bytes(&proof.to_bytes()), Ok(proof));
+
+        let bytes = prover.prove(&lookup).unwrap().to_bytes();
+        assert_eq!(
+            BalooProof::<Bn256>::from_bytes(&bytes[1..]),
+            Err(Error::Serialization(
+                "Proof should be of 1056 or 1024 bytes but got 1055".to_string()
+            ))
+        );
+        // [ξ]1 off the curve
+        let mut invalid_bytes = bytes.clone();
+        invalid_bytes[63] ^= 1;
+        assert!(matches!(
+            BalooProof::<Bn256>::from_bytes(&invalid_bytes),
+            Err(Error::Transcript(..))
+        ));
+    }
+
+    #[test]
+    fn test_versioned_proof_bytes() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = vec![Fr::from(7), Fr::from(3), Fr::from(3), Fr::from(4)];
+        let (param, pp, _) = preprocess::<Bn256>(table.len(), lookup.len()).unwrap();
+        let prover = Prover::new(&table, &param, &pp);
+
+        for (proof, flags) in [
+            (prover.prove(&lookup).unwrap(), 0),
+            (prover.prove_public(&lookup).unwrap(), 1),
+        ] {
+            let bytes = proof.to_versioned_bytes();
+            assert_eq!(
+                bytes[..8],
+                [b"\x05baloo".as_slice(), &[PROOF_VERSION, flags]].concat()
+            );
+            assert_eq!(bytes[8..], proof.to_bytes());
+            assert_eq!(BalooProof::from_versioned_bytes(&bytes), Ok(proof));
+        }
+
+        let bytes = prover.prove(&lookup).unwrap().to_versioned_bytes();
+        let with_header_byte = |i: usize, byte: u8| {
+            let mut bytes = bytes.clone();
+            bytes[i] = byte;
+            BalooProof::<Bn256>::from_versioned_bytes(&bytes)
+        };
+        assert_eq!(
+            BalooProof::<Bn256>::from_versioned_bytes(&bytes[8..]),
+            Err(Error::Serialization("Not a Baloo proof".to_string()))
+        );
+        assert_eq!(
+            with_header_byte(6, PROOF_VERSION + 1),
+            Err(Error::Serialization(format!(
+                "Proof of format version {} but expected {PROOF_VERSION}",
+                PROOF_VERSION + 1
+            )))
+        );
+        assert_eq!(
+            with_header_byte(7, 2),
+            Err(Error::Serialization(
+                "Proof with unknown flags 0x02".to_string()
+            ))
+        );
+        assert_eq!(
+            with_header_byte(7, 1),
+            Err(Error::Serialization(
+                "Proof for public φ should be of 1024 bytes but got 1056".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_proof_stats() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = vec![Fr::from(7), Fr::from(3), Fr::from(3), Fr::from(4)];
+        let m = lookup.len();
+        let (param, pp, vp) = Baloo::preprocess(table.len(), m).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let prover = Prover::new(&table, &param, &pp);
+
+        let proof = prover.prove(&lookup).unwrap();
+        assert_eq!(
+            proof.stats(),
+            ProofStats {
+                size_in_bytes: 1056,
+                num_g1: 12,
+                num_g2: 1,
+                num_field_elements: 5,
+                num_pairings: 14,
+                num_msms: 10,
+            }
+        );
+        let public_proof = prover.prove_public(&lookup).unwrap();
+        assert_eq!(public_proof.stats().size_in_bytes, 1024);
+        assert_eq!(public_proof.stats().num_field_elements, 4);
+
+        // the counts of the verifier are those of its plan
+        let phi = PhiInstance::Committed(Baloo::prepare_verification_data(&pp, &lookup));
+        for (proof, phi) in [(proof, phi), (public_proof, PhiInstance::Public(lookup))] {
+            let (plan, _) = Verifier::new(&vp).plan(&proof, &phi, m).unwrap();
+            assert_eq!(proof.stats().num_pairings, plan.num_pairings());
+            assert_eq!(proof.stats().num_msms, plan.num_msms());
+        }
+    }
+
+    #[test]
+    fn test_evm_calldata() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = vec![Fr::from(7), Fr::from(3), Fr::from(3), Fr::from(4)];
+        let (param, pp, _) = preprocess::<Bn256>(table.len(), lookup.len()).unwrap();
+        let prover = Prover::new(&table, &param, &pp);
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+
+        let proof = prover.prove(&lookup).unwrap();
+        let calldata = proof
+            .to_evm_calldata(&PhiInstance::Committed(phi_comm_1.clone()))
+            .unwrap();
+        let expected = [
+            vec![usize_word(64), usize_word(160), usize_word(2)],
+            evm_words(&phi_comm_1.0),
+            vec![usize_word(1056)],
+        ]
+        .concat()
+        .concat();
+        assert_eq!(calldata[..expected.len()], expected);
+        assert_eq!(calldata[expected.len()..], proof.to_bytes());
+
+        // 1024 bytes of proof are padded to words already
+        let proof = prover.prove_public(&lookup).unwrap();
+        let calldata = proof
+            .to_evm_calldata(&PhiInstance::Public(lookup.clone()))
+            .unwrap();
+        let expected = [
+            vec![usize_word(64), usize_word(224), usize_word(4)],
+            lookup.iter().map(evm_word).collect(),
+            vec![usize_word(1024)],
+        ]
+        .concat()
+        .concat();
+        assert_eq!(calldata[..expected.len()], expected);
+        assert_eq!(calldata[expected.len()..], proof.to_bytes());
+        assert_eq!(
+            proof.to_evm_calldata(&PhiInstance::Committed(phi_comm_1)),
+            Err(Error::InvalidSnark(
+                "Proof and φ should be both committed or both public".to_string()
+            ))
+        );
+    }
+}
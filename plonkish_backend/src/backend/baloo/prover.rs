@@ -1,50 +1,154 @@
 use crate::{
-    backend::baloo::util::{log_2, pow_2},
-    pcs::{
+    backend::baloo::{
+        degree::DegreeBounds,
+        job::Progress,
+        preprocessor::{ProverTablePreprocessing, TablePreprocessing},
+        proof::BalooProof,
+        srs_digest,
+        util::{concat_columns, log_2, squeeze_challenge_outside_domains, Shared},
+        witness::check_witness,
+        BalooEngine, BalooPcs, Instance, PhiInstance,
+    },
+    pcs::univariate::{
+        UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam, UnivariateKzgProverParam,
+    },
+    poly::{
         univariate::{
-            UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam, UnivariateKzgProverParam,
-        }, PolynomialCommitmentScheme,
+            Radix2Domain, SparseUnivariatePolynomial, SubproductTree, UnivariatePolynomial,
+        },
+        Polynomial,
     },
-    poly::{univariate::UnivariatePolynomial, Polynomial},
     util::{
-        arithmetic::{barycentric_weights, root_of_unity, Field},
+        arithmetic::Field,
+        metrics,
+        trace::trace_span,
         transcript::{
-            FieldTranscript, FieldTranscriptWrite,
-            G2TranscriptWrite, InMemoryTranscript, Keccak256Transcript,
+            FieldTranscript, FieldTranscriptWrite, G2TranscriptWrite, InMemoryTranscript,
+            TranscriptWrite,
         },
     },
+    Error,
+};
+use halo2_curves::bn256::Bn256;
+use std::{
+    borrow::Cow,
+    ops::{Mul, Neg},
+    sync::Arc,
 };
-use halo2_curves::bn256::{Bn256, Fr, G1Affine, G2Affine};
-use std::{collections::HashSet, ops::Mul};
 
-type Pcs = UnivariateKzg<Bn256>;
-type Scalar = Fr;
+// The table of a `Prover`, preprocessed by every proof unless it's given
+// preprocessed.
+enum ProverTable<'b, M: BalooEngine> {
+    Values(&'b Vec<M::Scalar>),
+    Preprocessed(Shared<'b, ProverTablePreprocessing<M>>),
+}
 
-pub struct Prover<'b> {
-    table: &'b Vec<Fr>,
-    param: &'b UnivariateKzgParam<Bn256>,
-    pp: &'b UnivariateKzgProverParam<Bn256>,
+pub struct Prover<'b, M: BalooEngine = Bn256, Pcs: BalooPcs<M> = UnivariateKzg<M>> {
+    table: ProverTable<'b, M>,
+    param: Shared<'b, Pcs::Param>,
+    pp: Shared<'b, Pcs::ProverParam>,
     d: usize,
+    check_witness: bool,
 }
 
-impl Prover<'_> {
+impl<M: BalooEngine> Prover<'_, M> {
     pub fn new<'a>(
-        table: &'a Vec<Fr>,
-        param: &'a UnivariateKzgParam<Bn256>,
-        pp: &'a UnivariateKzgProverParam<Bn256>,
-    ) -> Prover<'a> {
-        let d = (1 << pp.k()) - 2;
+        table: &'a Vec<M::Scalar>,
+        param: &'a UnivariateKzgParam<M>,
+        pp: &'a UnivariateKzgProverParam<M>,
+    ) -> Prover<'a, M> {
+        Prover::with_pcs(table, param, pp)
+    }
+
+    /// Prover against a table preprocessed once with `pp`, so that any
+    /// number of proofs only do the work depending on the lookup.
+    pub fn new_with_preprocessed<'a>(
+        table_pp: &'a ProverTablePreprocessing<M>,
+        param: &'a UnivariateKzgParam<M>,
+        pp: &'a UnivariateKzgProverParam<M>,
+    ) -> Prover<'a, M> {
+        Prover::with_pcs_preprocessed(table_pp, param, pp)
+    }
+}
+
+impl<M: BalooEngine, Pcs: BalooPcs<M>> Prover<'_, M, Pcs> {
+    /// [`Prover::new`] for any [`BalooPcs`].
+    pub fn with_pcs<'a>(
+        table: &'a Vec<M::Scalar>,
+        param: &'a Pcs::Param,
+        pp: &'a Pcs::ProverParam,
+    ) -> Prover<'a, M, Pcs> {
+        Prover::with_table(
+            ProverTable::Values(table),
+            Shared::Borrowed(param),
+            Shared::Borrowed(pp),
+        )
+    }
+
+    /// [`Prover::new_with_preprocessed`] for any [`BalooPcs`].
+    pub fn with_pcs_preprocessed<'a>(
+        table_pp: &'a ProverTablePreprocessing<M>,
+        param: &'a Pcs::Param,
+        pp: &'a Pcs::ProverParam,
+    ) -> Prover<'a, M, Pcs> {
+        Prover::with_table(
+            ProverTable::Preprocessed(Shared::Borrowed(table_pp)),
+            Shared::Borrowed(param),
+            Shared::Borrowed(pp),
+        )
+    }
+
+    /// [`Prover::with_pcs_preprocessed`] owning its inputs, which is
+    /// `'static` and so can be kept across requests and shared by threads,
+    /// e.g. in an [`Arc`] itself.
+    pub fn from_arcs(
+        table_pp: Arc<ProverTablePreprocessing<M>>,
+        param: Arc<Pcs::Param>,
+        pp: Arc<Pcs::ProverParam>,
+    ) -> Prover<'static, M, Pcs> {
+        Prover::with_table(
+            ProverTable::Preprocessed(Shared::Owned(table_pp)),
+            Shared::Owned(param),
+            Shared::Owned(pp),
+        )
+    }
+
+    fn with_table<'a>(
+        table: ProverTable<'a, M>,
+        param: Shared<'a, Pcs::Param>,
+        pp: Shared<'a, Pcs::ProverParam>,
+    ) -> Prover<'a, M, Pcs> {
+        let d = Pcs::max_degree(&pp) - 1;
         Prover {
             table,
             param,
             pp,
             d,
+            check_witness: false,
+        }
+    }
+
+    pub(crate) fn table(&self) -> &[M::Scalar] {
+        match &self.table {
+            ProverTable::Values(table) => table,
+            ProverTable::Preprocessed(table_pp) => table_pp.table(),
         }
     }
 
+    pub(crate) fn pp(&self) -> &Pcs::ProverParam {
+        &self.pp
+    }
+
+    /// Run [`check_witness`] before proving, to fail early with the missing
+    /// lookup values instead of deep in the protocol.
+    pub fn with_witness_check(mut self, enabled: bool) -> Self {
+        self.check_witness = enabled;
+        self
+    }
+
     /// Compute polynomial multilication naively in O(n^2)
-    fn naive_multiplication(coeffs0: &[Scalar], coeffs1: &[Scalar]) -> Vec<Scalar> {
-        let mut c = vec![Scalar::zero(); coeffs0.len() + coeffs1.len() - 1];
+    fn naive_multiplication(coeffs0: &[M::Scalar], coeffs1: &[M::Scalar]) -> Vec<M::Scalar> {
+        let mut c = vec![M::Scalar::ZERO; coeffs0.len() + coeffs1.len() - 1];
 
         for i in 0..coeffs0.len() {
             for j in 0..coeffs1.len() {
@@ -55,251 +159,188 @@ impl Prover<'_> {
     }
 
     /// Compute polynomial multilication wrapper
-    pub fn multiplication(coeffs0: &[Scalar], coeffs1: &[Scalar]) -> Vec<Scalar> {
+    pub fn multiplication(coeffs0: &[M::Scalar], coeffs1: &[M::Scalar]) -> Vec<M::Scalar> {
         // TODO: to select different multiplication algs based
         // on the degree of polynomials.
         Self::naive_multiplication(coeffs0, coeffs1)
     }
 
-    fn eval_rec(
-        tree: &Vec<Vec<Vec<Scalar>>>,
-        k: usize,
-        base: usize,
-        f: &[Scalar],
-        u: &[Scalar],
-    ) -> Vec<Scalar> {
-        let n = u.len();
-        // println!("eval_rec> k={}, base={}, n={}", k, base, n);
-        // println!("f={}", scalar_vector_to_string(&f.to_vec()));
-        // println!("u={}", scalar_vector_to_string(&u.to_vec()));
-
-        if k == 0 {
-            return f.to_vec();
-        }
-
-        // println!("division> k-1={}, left={}, right={}, n={}", k-1, base+0, base+1, n);
-        let (q0, r0) = Self::division(f, &tree[k - 1][2 * base]);
-        let (q1, r1) = Self::division(f, &tree[k - 1][2 * base + 1]);
-
-        let (u0, u1) = u.split_at(n / 2);
-        // println!("u0={}", scalar_vector_to_string(&u0.to_vec()));
-        // println!("u1={}", scalar_vector_to_string(&u1.to_vec()));
-        let mut rs0: Vec<Scalar> = Self::eval_rec(tree, k - 1, base * 2, &r0, u0);
-        let mut rs1: Vec<Scalar> = Self::eval_rec(tree, k - 1, base * 2 + 1, &r1, u1);
-        rs0.append(&mut rs1);
-        rs0
-    }
-
-    // TODO: https://en.wikipedia.org/wiki/Synthetic_division
-    fn division(dividend: &[Scalar], divisor: &[Scalar]) -> (Vec<Scalar>, Vec<Scalar>) {
-        // Important: if dividend.len() < divisor.len(), then the quotient is zero and
-        // the remainder is the dividend.
-        if dividend.len() < divisor.len() {
-            return (vec![Scalar::zero()], dividend.to_vec());
-        }
-
-        let mut quotient = vec![Scalar::zero(); dividend.len() - divisor.len() + 1];
-        let mut remainder = dividend.to_vec();
-
-        for i in (0..quotient.len()).rev() {
-            quotient[i] =
-                remainder[i + divisor.len() - 1] * divisor[divisor.len() - 1].invert().unwrap();
-            for j in 0..divisor.len() {
-                remainder[i + j] -= quotient[i] * divisor[j];
-            }
-        }
-
-        // Remove leading zeros
-        while remainder.len() > 1 && remainder[remainder.len() - 1] == Scalar::zero() {
-            remainder.pop();
-        }
-
-        (quotient, remainder)
-    }
-
-    /// Compute subproduct tree in O(M(n) * log(n)) time, where O(M(n)) is the
-    /// asymptotic complexity of multiplication, and equal to O(nlog(n)) if using
-    /// FFT-based fast multiplication.
-    ///
-    /// Return a vector of levels, each level is a vector of polynomials,
-    /// and each polynomial is a vector of coefficients.
-    fn contruct_subproduct_tree(domain: &[Scalar]) -> Vec<Vec<Vec<Scalar>>> {
-        let n = domain.len();
-        assert!(n.is_power_of_two());
-
-        let mut tree = Vec::new();
-        let mut level = Vec::new();
-        for u in domain.iter() {
-            level.push(vec![-*u, Scalar::one()]);
-        }
-        tree.push(level.clone());
-
-        for k in 0..log_2(n) {
-            let mut new_level = Vec::new();
-            for i in 0..(n >> (k + 1)) {
-                let left = &level[2 * i];
-                let right = &level[2 * i + 1];
-                let poly = Self::multiplication(left, right);
-                new_level.push(poly);
-            }
-            tree.push(new_level.clone());
-            level = new_level;
-        }
-        assert_eq!(tree.len(), log_2(n) + 1);
-
-        // for i in 0..tree.len() {
-        //     println!("tree[{}]=", i);
-        //     let level = &tree[i];
-        //     for j in 0..level.len() {
-        //         println!("tree[{}][{}]={}", i, j, scalar_vector_to_string(&level[j]));
-        //     }
-        // }
-
-        tree
-    }
-
-    /// Compute f(X) = ∑i c_i * z(X) / (X - u_i) in O(M(n) * log(n)) time
-    ///
-    /// The algorithm is the core of fast interpolation,
-    /// from Modern Computer Algebra, Algorithm 10.9, "Linear Combination for linear moduli".
+    /// Polynomial interpolation in O(nlog^2(n)) time, by the
+    /// [`SubproductTree`] of the domain.
     ///
     /// # Arguments
     ///
-    /// - k: `log(n)`
-    /// - base: the base index of the subproduct tree
-    /// - c: a vector of coefficients of size n
-    /// - u: a vector of points of size n
-    ///
-    /// return: a polynomial in coefficients
+    /// - evals: a vector of evaluations of size n
+    /// - domain: a domain of n distinct points
     ///
-    /// # Example
+    /// # Return
     ///
-    /// TODO: I use this alg. to compute `z'(X)`. Is there
-    /// any faster algorithm? It is mentioned in [TAB20] that `z'(X)`
-    /// can be computed in O(n) time.
+    /// - a polynomial (coefficients) of degree (n-1)
     ///
-    fn linear_combination_linear_moduli_fix(
-        tree: &Vec<Vec<Vec<Scalar>>>,
-        k: usize,
-        base: usize,
-        c: &[Scalar],
-        u: &[Scalar],
-    ) -> Vec<Scalar> {
-        let n = u.len();
-
-        // println!("lc_fix> k={}, base={}, n={}", k, base, n);
-        // println!("c={}", scalar_vector_to_string(&c.to_vec()));
-        // println!("u={}", scalar_vector_to_string(&u.to_vec()));
-
-        if k == 0 {
-            assert_eq!(c.len(), 1);
-            assert_eq!(u.len(), 1);
-            return vec![c[0]];
-        }
-
-        assert!(n.is_power_of_two());
-        assert_eq!(n, pow_2(k));
+    pub fn compute_coeffs_from_evals_fast_2(
+        evals: &[M::Scalar],
+        domain: &[M::Scalar],
+    ) -> Vec<M::Scalar> {
+        assert_eq!(evals.len(), domain.len());
 
-        let node0 = &tree[k - 1][2 * base];
-        let node1 = &tree[k - 1][2 * base + 1];
-
-        let (c0, c1) = c.split_at(n / 2);
-        let (u0, u1) = u.split_at(n / 2);
+        SubproductTree::new(domain).interpolate(evals).into_coeffs()
+    }
 
-        let r0 = Self::linear_combination_linear_moduli_fix(tree, k - 1, 2 * base, c0, u0);
-        let r1 = Self::linear_combination_linear_moduli_fix(tree, k - 1, 2 * base + 1, c1, u1);
+    pub fn prove(&self, lookup: &Vec<M::Scalar>) -> Result<BalooProof<M>, Error> {
+        self.prove_inner(lookup, false)
+    }
 
-        let poly0 = UnivariatePolynomial::monomial(Self::multiplication(node1, &r0));
-        let poly1 = UnivariatePolynomial::monomial(Self::multiplication(node0, &r1));
-        (&poly0 + &poly1).coeffs().to_vec()
+    /// Prove for public lookup values, which the verifier evaluates `φ` from
+    /// directly instead of being given `[φ(x)]1`, so `φ(α)` is left out of
+    /// the proof and the `φ` term is dropped from `w1`.
+    pub fn prove_public(&self, lookup: &Vec<M::Scalar>) -> Result<BalooProof<M>, Error> {
+        self.prove_inner(lookup, true)
     }
 
-    /// Polynomial interpolation in O(M(n) * log(n)) time.
-    /// If O(M(n)) is O(nlog(n)) by using fast polynomial multiplication,
-    /// then the asymtotics is O(nlog^2(n)).
-    ///
-    /// # Arguments
+    /// Prove as [`Prover::prove`] but deriving the challenges with transcript
+    /// `T` instead of [`BalooEngine::Transcript`], e.g. a
+    /// [`PoseidonTranscript`] for proofs to be verified in a circuit. It's
+    /// verified by [`Verifier::verify_in`] with the same `T`.
     ///
-    /// - evals: a vector of evaluations of size n
-    /// - domain: a domain of size n, n must be a power of 2
-    ///
-    /// # Return
+    /// [`PoseidonTranscript`]: crate::util::transcript::PoseidonTranscript
+    /// [`Verifier::verify_in`]: crate::backend::baloo::verifier::Verifier::verify_in
+    pub fn prove_in<T>(&self, lookup: &Vec<M::Scalar>) -> Result<BalooProof<M>, Error>
+    where
+        T: InMemoryTranscript<Param = ()>
+            + TranscriptWrite<M::G1Affine, M::Scalar>
+            + G2TranscriptWrite<M::G2Affine, M::Scalar>,
+    {
+        self.prove_with_transcript(lookup, false, &mut T::new(()))
+    }
+
+    /// Prove as [`Prover::prove`] into `transcript`, which may already have
+    /// absorbed messages of an enclosing protocol, e.g. a [`MerlinTranscript`]
+    /// of an application.
     ///
-    /// - a polynomial (coefficients) of degree (n-1)
+    /// [`MerlinTranscript`]: crate::util::transcript::MerlinTranscript
+    pub fn prove_in_transcript(
+        &self,
+        lookup: &Vec<M::Scalar>,
+        transcript: &mut (impl TranscriptWrite<M::G1Affine, M::Scalar>
+                  + G2TranscriptWrite<M::G2Affine, M::Scalar>),
+    ) -> Result<BalooProof<M>, Error> {
+        self.prove_with_transcript(lookup, false, transcript)
+    }
+
+    /// Prove in a single proof that every lookup column is in the table, as
+    /// the lookup of their concatenation, see [`concat_columns`]. It's
+    /// verified by [`Verifier::verify_columns`] against the commitments of
+    /// the columns.
     ///
-    pub fn compute_coeffs_from_evals_fast_2(evals: &[Scalar], domain: &[Scalar]) -> Vec<Scalar> {
-        let n = domain.len();
-        assert!(n.is_power_of_two());
-        assert_eq!(evals.len(), n);
-
-        // 1. building up subproduct tree
-
-        let tree = Self::contruct_subproduct_tree(domain);
-        // 2. construct a polynomial with linear moduli
-
-        let f_derivative = Self::linear_combination_linear_moduli_fix(
-            &tree,
-            log_2(n),
-            0,
-            &vec![Scalar::one(); n],
-            domain,
-        );
+    /// [`Verifier::verify_columns`]: crate::backend::baloo::verifier::Verifier::verify_columns
+    pub fn prove_columns(&self, columns: &[Vec<M::Scalar>]) -> Result<BalooProof<M>, Error> {
+        self.prove(&concat_columns(columns)?)
+    }
 
-        let f_derivative_at_u = Self::eval_rec(&tree, log_2(n), 0, &f_derivative, domain);
+    /// Instance proved by [`Prover::prove`], or [`Prover::prove_public`] if
+    /// `is_public`, which the verifier has to reconstruct from its inputs.
+    pub fn instance(
+        &self,
+        lookup: &Vec<M::Scalar>,
+        is_public: bool,
+    ) -> Result<Instance<M::G1Affine>, Error> {
+        let table = match &self.table {
+            ProverTable::Values(table) => {
+                Cow::Owned(TablePreprocessing::with_pcs::<Pcs>(&self.pp, table)?)
+            }
+            ProverTable::Preprocessed(table_pp) => Cow::Borrowed(table_pp.table_preprocessing()),
+        };
+        let phi_poly = UnivariatePolynomial::lagrange(lookup.clone()).into_ifft();
+        Ok(self.instance_with_table(lookup, is_public, &table, &phi_poly))
+    }
 
-        let mut bary_centric_weights: Vec<Scalar> = f_derivative_at_u
-            .iter()
-            .map(|e| e.invert().unwrap())
-            .collect();
+    fn instance_with_table(
+        &self,
+        lookup: &Vec<M::Scalar>,
+        is_public: bool,
+        table: &TablePreprocessing<M>,
+        phi_poly: &UnivariatePolynomial<M::Scalar>,
+    ) -> Instance<M::G1Affine> {
+        let phi = if is_public {
+            PhiInstance::Public(lookup.clone())
+        } else {
+            PhiInstance::Committed(Pcs::commit_monomial(&self.pp, phi_poly.coeffs()))
+        };
+        let powers_of_s_g2 = Pcs::monomial_g2(&self.param);
+        Instance {
+            m: lookup.len(),
+            t_comm_1: table.t_comm_1().clone(),
+            z_h_comm_1: table.z_h_comm_1().clone(),
+            srs_digest: srs_digest::<M>(&powers_of_s_g2[0], &powers_of_s_g2[1]),
+            phi,
+        }
+    }
 
-        let bary_centric_weights2: Vec<Scalar> = f_derivative_at_u
-            .iter()
-            .map(|e| e.invert().unwrap())
-            .collect();
-        assert_eq!(bary_centric_weights, bary_centric_weights2);
-        // println!("bary_centric_weights={}", scalar_vector_to_string(&bary_centric_weights));
-        // println!("bary_centric_weights2={}", scalar_vector_to_string(&UniPolynomial::barycentric_weights(domain)));
-
-        bary_centric_weights
-            .iter_mut()
-            .enumerate()
-            .for_each(|(i, w)| *w *= evals[i]);
-
-        // println!("before linear_combination_linear_moduli_fix");
-        
-
-        // {
-        //     let z_poly = UniPolynomial::from_coeffs(&tree[log_2(n)][0]);
-        //     println!("z_poly={}", scalar_vector_to_string(&z_poly.coeffs));
-        //     let mut g = Self::zero();
-        //     for i in 0..n {
-        //         let (gi, _) = z_poly.div_by_linear_divisor(&domain[i]);
-        //         g = Self::add(&g, &gi, false);
-        //     }
-        //     println!("g={}", scalar_vector_to_string(&g.coeffs));
-        // }
-
-        Self::linear_combination_linear_moduli_fix(
-            &tree,
-            log_2(n),
-            0,
-            &bary_centric_weights,
-            domain,
-        )
+    fn prove_inner(
+        &self,
+        lookup: &Vec<M::Scalar>,
+        is_public: bool,
+    ) -> Result<BalooProof<M>, Error> {
+        let mut transcript = M::Transcript::new(());
+        self.prove_with_transcript(lookup, is_public, &mut transcript)
+    }
+
+    pub(crate) fn prove_with_transcript(
+        &self,
+        lookup: &Vec<M::Scalar>,
+        is_public: bool,
+        transcript: &mut (impl TranscriptWrite<M::G1Affine, M::Scalar>
+                  + G2TranscriptWrite<M::G2Affine, M::Scalar>),
+    ) -> Result<BalooProof<M>, Error> {
+        let progress = Progress::default();
+        self.prove_with_progress(lookup, is_public, transcript, &progress)
     }
 
-    pub fn prove(&self, lookup: &Vec<Fr>) -> Vec<u8> {
-        let table = self.table.clone();
-        let param = self.param.clone();
-        let pp = self.pp.clone();
+    /// [`Prover::prove_with_transcript`] reporting each round done to
+    /// `progress`, and stopping at the end of the round it's cancelled in.
+    pub(crate) fn prove_with_progress(
+        &self,
+        lookup: &Vec<M::Scalar>,
+        is_public: bool,
+        transcript: &mut (impl TranscriptWrite<M::G1Affine, M::Scalar>
+                  + G2TranscriptWrite<M::G2Affine, M::Scalar>),
+        progress: &Progress,
+    ) -> Result<BalooProof<M>, Error> {
+        progress.check()?;
+        if self.check_witness {
+            check_witness(self.table(), lookup)?;
+        }
+
+        let param: &Pcs::Param = &self.param;
+        let pp: &Pcs::ProverParam = &self.pp;
         let d = self.d;
 
         let m = lookup.len();
-        let t = table.len();
+        let t = self.table().len();
+        let _span = trace_span!(INFO, "prove", t, m);
+        let bounds = DegreeBounds::new(d, t, m)?;
+        if !m.is_power_of_two() {
+            return Err(Error::NotPowerOfTwo {
+                name: "lookup",
+                size: m,
+            });
+        }
+
+        // the table-only work, unless done once in advance
+        let preprocessed;
+        let table_pp: &ProverTablePreprocessing<M> = match &self.table {
+            ProverTable::Values(table) => {
+                preprocessed = ProverTablePreprocessing::with_pcs::<Pcs>(pp, table)?;
+                &preprocessed
+            }
+            ProverTable::Preprocessed(table_pp) => table_pp,
+        };
+        let table = table_pp.table();
 
         /************
           Round 1
         ************/
+        let round = trace_span!(INFO, "round 1");
         /*
         How to calculate ξ(x)(or v(x) in code)
 
@@ -330,57 +371,45 @@ impl Prover<'_> {
         Interpolation with ξ and get polynomial: ξ(x)
         */
 
-        // initialize transcript
-        let mut transcript = Keccak256Transcript::new(());
         // φ(x)
-        let phi_poly = UnivariatePolynomial::lagrange(lookup.clone()).ifft();
+        let phi_poly = UnivariatePolynomial::lagrange(lookup.clone()).into_ifft();
         // t(x)
-        let t_poly = UnivariatePolynomial::lagrange(table.clone()).ifft();
-
-        // remove duplicated elements
-        let t_values_from_lookup: HashSet<_> = lookup.clone().into_iter().collect();
-        // I: the index of t_values_from_lookup elements in sub table t_I
-        let i_values: Vec<_> = t_values_from_lookup
-            .iter()
-            .map(|elem| table.iter().position(|&x| x == *elem).unwrap())
-            .collect();
-        let log_m = log_2(m);
-        let v_root_of_unity = root_of_unity::<Fr>(log_m);
-        // cache all roots of unity
-        let log_t = log_2(t);
-        let t_root_of_unity = root_of_unity::<Fr>(log_t);
-        let t_roots_of_unity = (0..t)
-            .map(|i| t_root_of_unity.pow([i as u64]))
-            .collect::<Vec<Fr>>();
-        let v_roots_of_unity = (0..m)
-            .map(|i| v_root_of_unity.pow([i as u64]))
-            .collect::<Vec<Fr>>();
+        let t_poly = table_pp.t_poly();
+        bounds.check("phi", &phi_poly, bounds.phi())?;
+        bounds.check("t", t_poly, bounds.t())?;
+        metrics::mark_round();
+
+        // bind the instance, which includes the public lookup values if any
+        self.instance_with_table(lookup, is_public, table_pp.table_preprocessing(), &phi_poly)
+            .absorb_into(transcript);
+
+        // I: the indexes of the distinct lookup values in table, in ascending
+        // order, and col: the position in I of each lookup value
+        let (i_values, col_values) = table_pp.subtable_indices(lookup)?;
+        // V of order m
+        let v_domain = Radix2Domain::<M::Scalar>::new(log_2(m));
+        let t_roots_of_unity = table_pp.roots_of_unity();
+        let v_roots_of_unity = v_domain.elements();
         // H_I = {ξ_i} , i = [1...k], ξ(Xi)
         let h_i: Vec<_> = i_values.iter().map(|&i| t_roots_of_unity[i]).collect();
-        // TODO: optimize interpolation polynomial with https://github.com/gy001/hypercube/blob/main/univarization/src/unipoly.rs#L391
-        // refer to barycentric_weights in arithmetic.rs
-        let t_values_from_lookup_set: Vec<Fr> = t_values_from_lookup.clone().into_iter().collect();
+        // t_I: the distinct lookup values, ordered as I
+        let t_values_from_lookup_set: Vec<M::Scalar> = i_values.iter().map(|&i| table[i]).collect();
         // let t_i_poly = lagrange_interp(&h_i, &t_values_from_lookup_set);
-        let t_i_poly_coeffs =
-            Self::compute_coeffs_from_evals_fast_2(&t_values_from_lookup_set, &h_i);
-        let t_i_poly = UnivariatePolynomial::monomial(t_i_poly_coeffs);
-        let z_i_poly = UnivariatePolynomial::vanishing(&h_i, Fr::one());
-        // sanity check
-        for (i, &root) in h_i.iter().enumerate() {
-            assert_eq!(z_i_poly.evaluate(&root), Fr::zero());
-            assert_eq!(t_i_poly.evaluate(&root), t_values_from_lookup_set[i]);
-            assert_eq!(t_i_poly.evaluate(&root), t_poly.evaluate(&root));
+        // t_I(X) and Z_{H_I}(X) by the same subproduct tree of H_I
+        let tree = SubproductTree::new(&h_i);
+        let t_i_poly = tree.interpolate(&t_values_from_lookup_set);
+        let z_i_poly = tree.vanishing_poly().clone();
+        let k = h_i.len();
+        if cfg!(feature = "sanity-check") {
+            for (i, &root) in h_i.iter().enumerate() {
+                assert_eq!(z_i_poly.evaluate(&root), M::Scalar::ZERO);
+                assert_eq!(t_i_poly.evaluate(&root), t_values_from_lookup_set[i]);
+                assert_eq!(t_i_poly.evaluate(&root), t_poly.evaluate(&root));
+            }
         }
 
-        let mut col_values = Vec::new();
         let mut v_values = Vec::new();
-        for i in 0..m {
-            // find the index of 1 in jth row of M
-            let col_i = t_values_from_lookup
-                .iter()
-                .position(|&x| x == lookup[i])
-                .unwrap();
-            col_values.push(col_i);
+        for &col_i in col_values.iter() {
             let col_i_root = h_i[col_i];
             // Note: v = 1 / col_i_root in paper
             // Here we use different construction that does not affect the verification
@@ -388,29 +417,33 @@ impl Prover<'_> {
             v_values.push(v);
         }
         // ξ(x) polynomial
-        let v_poly = UnivariatePolynomial::lagrange(v_values.clone()).ifft();
+        let v_poly = UnivariatePolynomial::lagrange(v_values).into_ifft();
+
+        bounds.check("t_I", &t_i_poly, bounds.t_i(k))?;
+        bounds.check("z_I", &z_i_poly, bounds.z_i(k))?;
+        bounds.check("v", &v_poly, bounds.v())?;
 
         // [ξ(x)]1
-        let v_comm_1: UnivariateKzgCommitment<G1Affine> =
-            Pcs::commit_and_write(&pp, &v_poly, &mut transcript).unwrap();
+        let v_comm_1: UnivariateKzgCommitment<M::G1Affine> =
+            Pcs::commit_and_write(pp, &v_poly, transcript)?;
         // [z_I(x)]2
-        let z_i_comm_2: UnivariateKzgCommitment<G2Affine> =
-            Pcs::commit_monomial_g2(&param, z_i_poly.coeffs());
-        transcript
-            .write_commitment_g2(&z_i_comm_2.clone().to_affine())
-            .unwrap();
+        let z_i_comm_2: UnivariateKzgCommitment<M::G2Affine> =
+            Pcs::commit_monomial_g2(param, z_i_poly.coeffs());
+        transcript.write_commitment_g2(&z_i_comm_2.0)?;
         // [t(x)]1
-        let t_i_comm_1: UnivariateKzgCommitment<G1Affine> =
-            Pcs::commit_and_write(&pp, &t_i_poly, &mut transcript).unwrap();
+        let t_i_comm_1: UnivariateKzgCommitment<M::G1Affine> =
+            Pcs::commit_and_write(pp, &t_i_poly, transcript)?;
 
-        let alpha = transcript.squeeze_challenge();
-
-        // π1 = ([ξ(x)]1, [z_I(x)]2, [t(x)]1)
-        let pi_1 = (v_comm_1.clone(), z_i_comm_2.clone(), t_i_comm_1.clone());
+        // α and ζ must not be in V or H, see `squeeze_challenge_outside_domains`
+        let alpha = squeeze_challenge_outside_domains(transcript, &[m, t]);
+        metrics::mark_round();
+        round.exit();
+        progress.finish_round()?;
 
         /************
           Round 2
         ************/
+        let round = trace_span!(INFO, "round 2");
         /*
         Calculate μ_i(X), i = [0, m - 1], by interpolating it in V(multiplicative subgroup, order is m)
         col_i = col[i]
@@ -445,73 +478,69 @@ impl Prover<'_> {
         Q_E(X) = (E(X) * (β - v(X)) + v(X) * z_I(β) / z_I(0)) / z_V(X)
         */
 
-        let scalar_0 = Fr::from(0);
-        let scalar_1 = Fr::from(1);
+        let scalar_0 = M::Scalar::from(0);
+        let scalar_1 = M::Scalar::from(1);
 
         let zero_poly = UnivariatePolynomial::monomial(vec![scalar_0]);
 
-        // [-1, 0, 0, ..., 1], m - 1 0s in between
-        let z_v_values: Vec<Fr> = vec![scalar_1.neg()]
-            .into_iter()
-            .chain((0..m - 1).map(|_| scalar_0))
-            .chain(vec![scalar_1])
-            .collect();
-        assert_eq!(z_v_values.len(), m + 1);
         // X^m - 1
-        let z_v_poly = UnivariatePolynomial::monomial(z_v_values);
-        assert_eq!(z_v_poly.evaluate(&v_root_of_unity), scalar_0);
+        let z_v_poly = v_domain.vanishing_poly();
 
         // z_I(0)
         let z_i_at_0 = z_i_poly.evaluate(&scalar_0);
+        // 1 / m and 1 / z_I(0), shared by every μ_i and τ^_{col(i)}
+        let m_inv = M::Scalar::from(m as u64).invert().unwrap();
+        let z_i_at_0_inv = z_i_at_0.invert().unwrap();
+        // z_V(α), which isn't zero as α isn't in V
+        let z_v_at_alpha = z_v_poly.evaluate(&alpha);
 
         // calculate D(X) = Σ_{0, m-1} μ_i(α) * τ^_{col(i)}(X)
-        let mut d_poly: UnivariatePolynomial<Fr> = zero_poly.clone();
+        let mut d_poly: UnivariatePolynomial<M::Scalar> = zero_poly.clone();
         for i in 0..m {
             // col(i)
             let col_i = col_values[i];
             // ω^i
             let v_root = v_roots_of_unity[i];
-            // X - ω^i
-            let v_root_poly = UnivariatePolynomial::monomial(vec![-v_root, scalar_1]);
             // ξ_i
             let col_i_root = h_i[col_i];
             // X - ξ_i
             let x_root_poly = UnivariatePolynomial::monomial(vec![-col_i_root, scalar_1]);
-            // Lagrange polynomial on V: μ_i(X)
-            // z_v_poly / v_root_poly * v_root / Fr::from(m as u64);
-            let mu_poly =
-                &(&z_v_poly / &v_root_poly) * (v_root * (Fr::from(m as u64).invert().unwrap()));
+            // μ_i(α) = ω^i / m * z_V(α) / (α - ω^i), without interpolating μ_i(X)
+            let mu_poly_at_alpha =
+                v_root * m_inv * z_v_at_alpha * (alpha - v_root).invert().unwrap();
             // Normalized Lagrange Polynomial: τ_col(i)(X) / τ_col(i)(0)
-            // z_i_poly / x_root_poly * (-col_i_root) / z_i_at_0;
-            let normalized_lag_poly =
-                &(&z_i_poly / &x_root_poly) * (col_i_root.neg() * (z_i_at_0.invert().unwrap()));
-            // μ_i(α)
-            let mu_poly_at_alpha = mu_poly.evaluate(&alpha);
-            // D(X) = Σ_i(μ_i(α) * normalized_lag_poly)
-            d_poly += &normalized_lag_poly * mu_poly_at_alpha;
+            // z_i_poly / x_root_poly * (-col_i_root) / z_i_at_0, with the
+            // scalar folded into μ_i(α)
+            let lag_poly = &z_i_poly / &x_root_poly;
+            let scalar = mu_poly_at_alpha * col_i_root.neg() * z_i_at_0_inv;
+            // D(X) = Σ_i(μ_i(α) * normalized_lag_poly), accumulated in place
+            d_poly += (scalar, &lag_poly);
         }
 
         // D(X) * t_I(X)
-        let d_t_poly = d_poly.poly_mul(t_i_poly.clone());
+        let d_t_poly = d_poly.poly_mul(&t_i_poly);
         // φ(α)
         let phi_poly_at_alpha = phi_poly.evaluate(&alpha);
 
         // Q_D(X), R(X) = (D(X) * t_I(X) - φ(α)) / z_I(X)
         let (q_d_poly, r_poly) = (d_t_poly + phi_poly_at_alpha.neg()).div_rem(&z_i_poly);
         assert_eq!(r_poly.evaluate(&scalar_0), scalar_0);
+        bounds.check("D", &d_poly, bounds.d(k))?;
+        bounds.check("R", &r_poly, bounds.r(k))?;
+        bounds.check("Q_D", &q_d_poly, bounds.q_d(k))?;
 
         // π2 = ([D]1 = [D(x)]1, [R]1 = [R(x)]1, [Q2]1 = [Q_D(x)]1)
-        let d_comm_1: UnivariateKzgCommitment<G1Affine> =
-            Pcs::commit_and_write(&pp, &d_poly, &mut transcript).unwrap();
-        let r_comm_1: UnivariateKzgCommitment<G1Affine> =
-            Pcs::commit_and_write(&pp, &r_poly, &mut transcript).unwrap();
-        let q_d_comm_1: UnivariateKzgCommitment<G1Affine> =
-            Pcs::commit_and_write(&pp, &q_d_poly, &mut transcript).unwrap();
+        let d_comm_1: UnivariateKzgCommitment<M::G1Affine> =
+            Pcs::commit_and_write(pp, &d_poly, transcript)?;
+        let r_comm_1: UnivariateKzgCommitment<M::G1Affine> =
+            Pcs::commit_and_write(pp, &r_poly, transcript)?;
+        let q_d_comm_1: UnivariateKzgCommitment<M::G1Affine> =
+            Pcs::commit_and_write(pp, &q_d_poly, transcript)?;
 
         let beta = transcript.squeeze_challenge();
 
         // calculate E(X) = Σ_i(μ_i(X) * normalized_lag_poly(β))
-        let mut e_poly: UnivariatePolynomial<Fr> = zero_poly.clone();
+        let mut e_poly: UnivariatePolynomial<M::Scalar> = zero_poly;
         for i in 0..m {
             // col(i)
             let col_i = col_values[i];
@@ -524,17 +553,17 @@ impl Prover<'_> {
             // X - ξ_i
             let x_root_poly = UnivariatePolynomial::monomial(vec![-col_i_root, scalar_1]);
             // Lagrange polynomial on V: μ_i(X)
-            // z_v_poly / v_root_poly * v_root / Fr::from(m as u64);
-            let mu_poly =
-                &(&z_v_poly / &v_root_poly) * (v_root * (Fr::from(m as u64).invert().unwrap()));
+            // z_v_poly / v_root_poly * v_root / M::Scalar::from(m as u64),
+            // with the scalar folded into the one of E(X)
+            let mu_poly = &z_v_poly / &v_root_poly;
             // Normalized Lagrange Polynomial: τ_col(i)(X) / τ_col(i)(0)
             // z_i_poly / x_root_poly * (-col_i_root) / z_i_at_0;
-            let normalized_lag_poly =
-                &(&z_i_poly / &x_root_poly) * (col_i_root.neg() * (z_i_at_0.invert().unwrap()));
+            let lag_poly = &z_i_poly / &x_root_poly;
             // Normalized Lagrange Polynomial at β: τ_col(i)(β) / τ_col(i)(0)
-            let normalized_lag_poly_at_beta = normalized_lag_poly.evaluate(&beta);
-            // E(X) = Σ_i(μ_i(X) * normalized_lag_poly(β))
-            e_poly += &mu_poly * normalized_lag_poly_at_beta;
+            let normalized_lag_poly_at_beta =
+                lag_poly.evaluate(&beta) * col_i_root.neg() * z_i_at_0_inv;
+            // E(X) = Σ_i(μ_i(X) * normalized_lag_poly(β)), accumulated in place
+            e_poly += (v_root * m_inv * normalized_lag_poly_at_beta, &mu_poly);
         }
 
         let z_i_at_beta = z_i_poly.evaluate(&beta);
@@ -543,7 +572,7 @@ impl Prover<'_> {
         let q_e_poly = {
             // let beta_poly = UnivariatePolynomial::lagrange(vec![beta; m]);
             // beta - v_poly
-            let aaa: UnivariatePolynomial<Fr> = &v_poly * scalar_1.neg() + beta;
+            let aaa: UnivariatePolynomial<M::Scalar> = &v_poly * scalar_1.neg() + beta;
             // e_poly * (beta - v_poly)
             let bbb = &e_poly.poly_mul(aaa);
             // z_i_at_beta / z_i_at_0
@@ -551,28 +580,31 @@ impl Prover<'_> {
             // v_poly * z_i_at_beta / z_i_at_0
             let ddd = &v_poly * ccc;
             // e_poly * (beta - v_poly) + v_poly * z_i_at_beta / z_i_at_0
-            &(bbb + ddd) / &z_v_poly
+            (bbb + ddd).coset_div(&z_v_poly).ok_or_else(|| {
+                Error::InvalidSnark("z_V(X) should divide the relation of E(X)".to_string())
+            })?
         };
+        bounds.check("E", &e_poly, bounds.e())?;
+        bounds.check("Q_E", &q_e_poly, bounds.q_e())?;
 
         // π3 = ([E]1 = [E(x)]1, [Q1]1 = [Q_E(x)]1)
-        let e_comm_1: UnivariateKzgCommitment<G1Affine> =
-            Pcs::commit_and_write(&pp, &e_poly, &mut transcript).unwrap();
-        let q_e_comm_1: UnivariateKzgCommitment<G1Affine> =
-            Pcs::commit_and_write(&pp, &q_e_poly, &mut transcript).unwrap();
-
-        let gamma: Fr = transcript.squeeze_challenge();
-        let zeta: Fr = transcript.squeeze_challenge();
+        let e_comm_1: UnivariateKzgCommitment<M::G1Affine> =
+            Pcs::commit_and_write(pp, &e_poly, transcript)?;
+        let q_e_comm_1: UnivariateKzgCommitment<M::G1Affine> =
+            Pcs::commit_and_write(pp, &q_e_poly, transcript)?;
+
+        let gamma: M::Scalar = transcript.squeeze_challenge();
+        let zeta = squeeze_challenge_outside_domains(transcript, &[m, t]);
+        metrics::mark_round();
+        round.exit();
+        progress.finish_round()?;
         let gamma_2 = gamma.mul(gamma);
         let gamma_3 = gamma_2.mul(gamma);
 
-        // π2 = ([D]1, [R]1, [Q2]1)
-        let pi_2 = (d_comm_1.clone(), r_comm_1.clone(), q_d_comm_1.clone());
-        // π3 = ([E]1, [Q1]1)
-        let pi_3 = (e_comm_1.clone(), q_e_comm_1.clone());
-
         /************
           Round 3: optimize with linear combination of polynomials
         ************/
+        let round = trace_span!(INFO, "round 3");
         /*
         Calculate w1, w2, w3, w4
         */
@@ -602,32 +634,27 @@ impl Prover<'_> {
         let p_e_poly = &(&(&beta_sub_v_poly * v5) + &v_poly * (v4.mul(v3.invert().unwrap())))
             - &q_e_poly * z_v_zeta;
         // X^(d-m+1)
-        let coeffs = vec![scalar_0; d - m + 1]
-            .into_iter()
-            .chain(vec![scalar_1])
-            .collect();
-        let x_exponent_poly = UnivariatePolynomial::monomial(coeffs);
+        let x_exponent_poly = SparseUnivariatePolynomial::term(bounds.w1_shift(), scalar_1);
         // calculate [w1]1, [w2]1, [w2]1, [w4]1
         // X - α
         let x_alpha_poly = UnivariatePolynomial::monomial(vec![-alpha, scalar_1]);
-        // calculate w1 = X^(d-m+1) * (E(X) - E(α) + (φ(X) - φ(α))γ) / X - α
-        let mut w1 = &(&(e_poly.clone() + v1.neg()) + &(phi_poly.clone() + v2.neg()) * gamma)
-            / &x_alpha_poly;
-        w1 = w1.poly_mul(x_exponent_poly.clone());
+        // calculate w1 = X^(d-m+1) * (E(X) - E(α) + (φ(X) - φ(α))γ) / X - α,
+        // or w1 = X^(d-m+1) * (E(X) - E(α)) / X - α if φ is public
+        let mut w1 = if is_public {
+            &(e_poly.clone() + v1.neg()) / &x_alpha_poly
+        } else {
+            &(&(e_poly.clone() + v1.neg()) + &(phi_poly.clone() + v2.neg()) * gamma)
+                / &x_alpha_poly
+        };
+        w1 = &x_exponent_poly * &w1;
         // calculate polynomial X
         let x_poly = UnivariatePolynomial::monomial(vec![scalar_0, scalar_1]);
         // X^m
-        let x_m_exponent_poly = UnivariatePolynomial::monomial(
-            vec![scalar_0; m]
-                .into_iter()
-                .chain(vec![scalar_1])
-                .collect(),
-        );
+        let x_m_exponent_poly = SparseUnivariatePolynomial::term(m, scalar_1);
         // calculate w2 = (z_I(X) - v3 / X + γ * R(X) / X +  γ^2 * X^(d-m+1) * (z_I(X) - X^m)) + γ^3 * X^(d-m+1) * R(X)
         let w2 = &(&(&(z_i_poly.clone() + v3.neg()) / &x_poly) + &(&(&r_poly * gamma) / &x_poly))
-            + &x_exponent_poly.poly_mul(
-                &(&(&z_i_poly - x_m_exponent_poly.clone()) * gamma_2) + &r_poly * gamma_3,
-            );
+            + &x_exponent_poly
+                * &(&(&(&x_m_exponent_poly - &z_i_poly) * gamma_2.neg()) + &r_poly * gamma_3);
         // calculate X - β
         let x_beta_poly = UnivariatePolynomial::monomial(vec![-beta, scalar_1]);
         // calculate w3 = (D(X) - E(α) + (z_I(X) - z_I(β))γ + P_D(X)γ^2) / X - β
@@ -640,105 +667,133 @@ impl Prover<'_> {
         // calculate w4 = (E(X) - E(ζ) + P_E(X)γ) / X - ζ
         // v5 = E(ζ)
         let w4 = &(&(e_poly + v5.neg()) + &p_e_poly * gamma) / &x_zeta_poly;
+        bounds.check("w1", &w1, bounds.w1())?;
+        bounds.check("w2", &w2, bounds.w2())?;
+        bounds.check("w3", &w3, bounds.w3())?;
+        bounds.check("w4", &w4, bounds.w4())?;
 
         // caulk+ calculate w5, w6
-        // z_h_poly = X^t - 1, [-1, 0, ..., 0, 1], t-1 0s in between
-        let z_h_poly_coeffs = vec![scalar_1.neg()]
-            .into_iter()
-            .chain(vec![scalar_0; t - 1])
-            .chain(vec![scalar_1])
-            .collect();
-        let z_h_poly = UnivariatePolynomial::monomial(z_h_poly_coeffs);
+        // z_h_poly = X^t - 1, cached by the table preprocessing
+        let z_h_poly = table_pp.z_h_poly();
 
-        // calculate barycentric_weights
-        let bc_weights = barycentric_weights(&h_i);
+        // barycentric weights of H_I
+        let bc_weights = tree.barycentric_weights();
 
         // w5_poly = (t_poly - t_I_poly) / z_I_poly
-        let w5_poly_direct = &(&t_poly - &t_i_poly) / &z_i_poly;
-        // q_t_poly_i = (t_poly - table[i])/X-root_of_unity^i
-        let q_t_polys: Vec<_> = table
-            .clone()
-            .into_iter()
-            .enumerate()
-            .map(|(i, point)| {
-                &(t_poly.clone() + point.neg())
-                    / &(x_poly.clone() + t_root_of_unity.pow([i as u64]).neg())
-            })
-            .collect();
+        // q_t_poly_i = (t_poly - table[i])/X-root_of_unity^i, cached by the
+        // table preprocessing
+        let q_t_polys = table_pp.q_t_polys();
         // optimize w5_poly = bc_weights[0] * q_t_polys[i_values[0]] + bc_weights[1] * q_t_polys[i_values[1]] + ... + bc_weights[h_i.len()-1] * q_t_polys[i_values[h_i.len()-1]]
         let w5_poly = bc_weights
-            .clone()
-            .into_iter()
+            .iter()
             .enumerate()
-            .map(|(i, weight)| &q_t_polys[i_values[i]] * weight)
-            .reduce(|acc, poly| &acc + poly)
+            .map(|(i, weight)| &q_t_polys[i_values[i]] * *weight)
+            .reduce(|mut acc, poly| {
+                acc += poly;
+                acc
+            })
             .unwrap();
-        assert_eq!(w5_poly, w5_poly_direct);
-        // w6_poly = z_H_poly * (bc_weights[0] * 1/X-h_i[0] + bc_weights[1] * 1/X-h_i[1] + ... + bc_weights[h_i.len()-1] * 1/X-h_i[h_i.len()-1])
-        let denom_polys: Vec<_> = h_i
-            .into_iter()
-            .map(|root| UnivariatePolynomial::monomial(vec![root.neg(), scalar_1]))
-            .collect();
+        // w6_poly = bc_weights[0] * z_H_poly/X-h_i[0] + bc_weights[1] * z_H_poly/X-h_i[1] + ... + bc_weights[h_i.len()-1] * z_H_poly/X-h_i[h_i.len()-1]
+        // z_H_poly/X-root_of_unity^i, cached by the table preprocessing
+        let z_h_quotients = table_pp.z_h_quotients();
         let w6_poly = bc_weights
-            .clone()
-            .into_iter()
+            .iter()
             .enumerate()
-            .map(|(i, weight)| &(&z_h_poly / &denom_polys[i]) * weight)
-            .reduce(|acc, poly| &acc + poly)
+            .map(|(i, weight)| &z_h_quotients[i_values[i]] * *weight)
+            .reduce(|mut acc, poly| {
+                acc += poly;
+                acc
+            })
             .unwrap();
-        // w6_poly = z_H_poly / z_I_poly
-        let w6_poly_direct = &z_h_poly / &z_i_poly;
-        assert_eq!(w6_poly, w6_poly_direct);
+        if cfg!(feature = "sanity-check") {
+            // w5_poly = (t_poly - t_I_poly) / z_I_poly and w6_poly = z_H_poly / z_I_poly
+            assert_eq!(w5_poly, (t_poly - &t_i_poly).coset_div(&z_i_poly).unwrap());
+            assert_eq!(w6_poly, z_h_poly.coset_div(&z_i_poly).unwrap());
+        }
 
         // Compress Caulk+ proof.
-        let a_poly = &w5_poly.clone() + &w6_poly.clone() * gamma;
+        let a_poly = &w5_poly + &w6_poly * gamma;
+        bounds.check("a", &a_poly, bounds.a(k))?;
 
         // write v1, v2, v3, v4, v5 to transcript
-        transcript.write_field_element(&v1).unwrap();
-        transcript.write_field_element(&v2).unwrap();
-        transcript.write_field_element(&v3).unwrap();
-        transcript.write_field_element(&v4).unwrap();
-        transcript.write_field_element(&v5).unwrap();
+        transcript.write_field_element(&v1)?;
+        if !is_public {
+            transcript.write_field_element(&v2)?;
+        }
+        transcript.write_field_element(&v3)?;
+        transcript.write_field_element(&v4)?;
+        transcript.write_field_element(&v5)?;
 
         // [a]1
-        let a_comm_1: UnivariateKzgCommitment<G1Affine> =
-            Pcs::commit_and_write(&pp, &a_poly, &mut transcript).unwrap();
+        let a_comm_1: UnivariateKzgCommitment<M::G1Affine> =
+            Pcs::commit_and_write(pp, &a_poly, transcript)?;
         // calculate [w1]1, [w2]1, [w2]1, [w4]1 and write to transcript
-        let w1_comm_1: UnivariateKzgCommitment<G1Affine> =
-            Pcs::commit_and_write(&pp, &w1, &mut transcript).unwrap();
-        let w2_comm_1: UnivariateKzgCommitment<G1Affine> =
-            Pcs::commit_and_write(&pp, &w2, &mut transcript).unwrap();
-        let w3_comm_1: UnivariateKzgCommitment<G1Affine> =
-            Pcs::commit_and_write(&pp, &w3, &mut transcript).unwrap();
-        let w4_comm_1: UnivariateKzgCommitment<G1Affine> =
-            Pcs::commit_and_write(&pp, &w4, &mut transcript).unwrap();
-
-        // π4 = (v1, v2, v3, v4, v5, [a]1, [w1]1, [w2]1, [w3]1, [w4]1)
-        let pi_4 = (
+        let w1_comm_1: UnivariateKzgCommitment<M::G1Affine> =
+            Pcs::commit_and_write(pp, &w1, transcript)?;
+        let w2_comm_1: UnivariateKzgCommitment<M::G1Affine> =
+            Pcs::commit_and_write(pp, &w2, transcript)?;
+        let w3_comm_1: UnivariateKzgCommitment<M::G1Affine> =
+            Pcs::commit_and_write(pp, &w3, transcript)?;
+        let w4_comm_1: UnivariateKzgCommitment<M::G1Affine> =
+            Pcs::commit_and_write(pp, &w4, transcript)?;
+
+        let proof = BalooProof {
+            // π1 = ([ξ(x)]1, [z_I(x)]2, [t(x)]1)
+            v_comm_1: v_comm_1.to_affine(),
+            z_i_comm_2: z_i_comm_2.to_affine(),
+            t_i_comm_1: t_i_comm_1.to_affine(),
+            // π2 = ([D]1, [R]1, [Q2]1)
+            d_comm_1: d_comm_1.to_affine(),
+            r_comm_1: r_comm_1.to_affine(),
+            q_d_comm_1: q_d_comm_1.to_affine(),
+            // π3 = ([E]1, [Q1]1)
+            e_comm_1: e_comm_1.to_affine(),
+            q_e_comm_1: q_e_comm_1.to_affine(),
+            // π4 = (v1, v2, v3, v4, v5, [a]1, [w1]1, [w2]1, [w3]1, [w4]1)
             v1,
-            v2,
+            v2: (!is_public).then_some(v2),
             v3,
             v4,
             v5,
-            a_comm_1.clone(),
-            w1_comm_1.clone(),
-            w2_comm_1.clone(),
-            w3_comm_1.clone(),
-            w4_comm_1.clone(),
-        );
+            a_comm_1: a_comm_1.to_affine(),
+            w1_comm_1: w1_comm_1.to_affine(),
+            w2_comm_1: w2_comm_1.to_affine(),
+            w3_comm_1: w3_comm_1.to_affine(),
+            w4_comm_1: w4_comm_1.to_affine(),
+        };
+
+        metrics::mark_round();
+        round.exit();
+        progress.finish_round()?;
 
-        // generate proof from transcript
-        transcript.into_proof()
+        Ok(proof)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::util::transcript::{
-        FieldTranscriptRead, FieldTranscriptWrite, G2TranscriptRead, G2TranscriptWrite,
+    use crate::{
+        backend::{
+            baloo::{
+                preprocessor::{preprocess, preprocess_with_rng},
+                verifier::Verifier,
+                witness::WitnessError,
+                Baloo,
+            },
+            lookup,
+        },
+        pcs::PolynomialCommitmentScheme,
+        util::{
+            test::std_rng,
+            transcript::{
+                FieldTranscriptRead, FieldTranscriptWrite, G2TranscriptRead, G2TranscriptWrite,
+                Keccak256Transcript,
+            },
+        },
     };
-    use halo2_curves::bn256::Fr;
+    use halo2_curves::bn256::{Fr, G2Affine};
+    use rand::rngs::OsRng;
     type Pcs = UnivariateKzg<Bn256>;
 
     #[test]
@@ -757,10 +812,138 @@ mod tests {
         let m = lookup.len();
         let t = table.len();
         // 1. setup
-        let (param, pp, _) = preprocess(t, m).unwrap();
+        let (param, pp, _) = preprocess::<Bn256>(t, m).unwrap();
         // 2. generate proof
         let prover = Prover::new(&table, &param, &pp);
-        let proof = prover.prove(&lookup);
+        prover.prove(&lookup).unwrap();
+    }
+
+    #[test]
+    fn test_deterministic_proof() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = vec![Fr::from(7), Fr::from(3), Fr::from(3), Fr::from(4)];
+        let (param, pp, _) = preprocess::<Bn256>(table.len(), lookup.len()).unwrap();
+
+        let proof = Prover::new(&table, &param, &pp).prove(&lookup).unwrap();
+        for _ in 0..4 {
+            assert_eq!(
+                Prover::new(&table, &param, &pp).prove(&lookup).unwrap(),
+                proof
+            );
+        }
+    }
+
+    #[test]
+    fn test_deterministic_proof_from_setup() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = vec![Fr::from(7), Fr::from(3), Fr::from(3), Fr::from(4)];
+        let setup = || preprocess_with_rng::<Bn256>(table.len(), lookup.len(), std_rng()).unwrap();
+        let (param, pp, vp) = setup();
+        let proof = Prover::new(&table, &param, &pp).prove(&lookup).unwrap();
+
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, lookup.len()).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        assert_eq!(
+            Baloo::verify(&vp, &proof, &phi_comm_1, lookup.len()),
+            Ok(())
+        );
+
+        // reproducible from the setup on
+        let (param, pp, _) = setup();
+        assert_eq!(
+            Prover::new(&table, &param, &pp).prove(&lookup).unwrap(),
+            proof
+        );
+    }
+
+    #[test]
+    fn test_prove_with_preprocessed() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let (param, pp, _) = preprocess::<Bn256>(table.len(), 4).unwrap();
+        let table_pp = ProverTablePreprocessing::new(&pp, &table).unwrap();
+        let prover = Prover::new_with_preprocessed(&table_pp, &param, &pp);
+
+        // the same proofs as preprocessing the table for each of them
+        for lookup in [[7, 3, 3, 4], [1, 1, 1, 1], [8, 6, 2, 5]] {
+            let lookup = lookup.map(|i| Fr::from(i as u64)).to_vec();
+            let fresh = Prover::new(&table, &param, &pp);
+            assert_eq!(prover.prove(&lookup), fresh.prove(&lookup));
+            assert_eq!(prover.prove_public(&lookup), fresh.prove_public(&lookup));
+            assert_eq!(
+                lookup::Instance::digest(&prover.instance(&lookup, false).unwrap()),
+                lookup::Instance::digest(&fresh.instance(&lookup, false).unwrap())
+            );
+        }
+
+        assert!(ProverTablePreprocessing::new(&pp, &table[..6]).is_err());
+    }
+
+    #[test]
+    fn test_shared_prover_and_verifier() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let (param, pp, vp) = preprocess::<Bn256>(table.len(), 4).unwrap();
+        let table_pp = ProverTablePreprocessing::new(&pp, &table).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, 4).unwrap();
+        let lookups = [[7, 3, 3, 4], [1, 1, 1, 1], [8, 6, 2, 5]]
+            .map(|lookup| lookup.map(|i| Fr::from(i as u64)).to_vec());
+        let proofs = lookups
+            .iter()
+            .map(|lookup| Prover::new(&table, &param, &pp).prove_public(lookup))
+            .collect::<Vec<_>>();
+
+        // owned by the threads, the same proofs as a borrowing prover
+        let prover = Arc::new(Prover::from_arcs(
+            Arc::new(table_pp),
+            Arc::new(param),
+            Arc::new(pp),
+        ));
+        let verifier = Arc::new(Verifier::from_arc(Arc::new(vp)));
+        assert_send_sync(&prover);
+        assert_send_sync(&verifier);
+        let handles = lookups
+            .into_iter()
+            .zip(proofs)
+            .map(|(lookup, expected)| {
+                let prover = prover.clone();
+                let verifier = verifier.clone();
+                std::thread::spawn(move || {
+                    let proof = prover.prove_public(&lookup);
+                    assert_eq!(proof, expected);
+                    let phi = PhiInstance::Public(lookup.clone());
+                    verifier.verify_with_phi(&proof.unwrap(), &phi, lookup.len())
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_prove_with_witness_check() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = vec![Fr::from(7), Fr::from(9), Fr::from(3), Fr::from(4)];
+        let (param, pp, _) = preprocess::<Bn256>(table.len(), lookup.len()).unwrap();
+
+        let err = Prover::new(&table, &param, &pp)
+            .with_witness_check(true)
+            .prove(&lookup)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Error::from(WitnessError::MissingValues(vec![(1, Fr::from(9))]))
+        );
+        // the same error as of the prover without the check
+        assert_eq!(Prover::new(&table, &param, &pp).prove(&lookup), Err(err));
+        assert_eq!(
+            Prover::new(&table, &param, &pp).prove(&lookup[..3].to_vec()),
+            Err(Error::NotPowerOfTwo {
+                name: "lookup",
+                size: 3
+            })
+        );
     }
 
     #[test]
@@ -785,7 +968,7 @@ mod tests {
             let poly =
                 <Pcs as PolynomialCommitmentScheme<Fr>>::Polynomial::monomial(lookup.clone());
             print!("coeffs: {:?}\n", poly.coeffs());
-            let comm = Pcs::commit_and_write(&pp, &poly, &mut transcript).unwrap();
+            let comm = Pcs::commit_and_write(pp, &poly, &mut transcript).unwrap();
             let point = <Pcs as PolynomialCommitmentScheme<Fr>>::Polynomial::squeeze_point(
                 m,
                 &mut transcript,
@@ -856,7 +1039,7 @@ mod tests {
         let comm_2_affine = comm_2.clone().to_affine();
         transcript.write_commitment_g2(&comm_2_affine).unwrap();
 
-        let comm_table = Pcs::commit_and_write(&pp, &poly_table, &mut transcript).unwrap();
+        let comm_table = Pcs::commit_and_write(pp, &poly_table, &mut transcript).unwrap();
         println!("comm_table: {:?}", comm_table);
 
         let zeta: Fr = transcript.squeeze_challenge();
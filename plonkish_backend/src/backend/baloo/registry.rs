@@ -0,0 +1,335 @@
+//! Registry of preprocessed tables keyed by their [`TableHash`], so that a
+//! service preprocesses each of its tables once instead of on every process
+//! start.
+//!
+//! What it caches is the [`ProverTablePreprocessing`] of a table, i.e.
+//! `[t(x)]1`, `[z_H(x)]1` and the quotients `(t(X) - t_i) / (X - ω^i)`, whose
+//! `t` divisions are the bulk of the preprocessing. With a directory, every
+//! table preprocessed is also written to `<dir>/<hash>.bin`, see
+//! [`ProverTablePreprocessing::write_to`], and read back from there by any
+//! later registry of the same directory.
+
+use crate::{
+    backend::baloo::{preprocessor::ProverTablePreprocessing, BalooEngine, BalooPcs},
+    pcs::univariate::{UnivariateKzg, UnivariateKzgProverParam},
+    util::{
+        arithmetic::CurveAffine,
+        hash::{FixedOutputReset, Hash, Keccak256, Update},
+    },
+    Error,
+};
+use halo2_curves::{bn256::Bn256, group::GroupEncoding};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Keccak256 of `[s]1` of the SRS and of the table values, as the
+/// commitments of the preprocessing depend on both.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TableHash([u8; 32]);
+
+impl TableHash {
+    pub fn new<C: CurveAffine>(s_g1: &C, table: &[C::Scalar]) -> Self {
+        let mut hasher = Keccak256::new();
+        Update::update(&mut hasher, s_g1.to_bytes().as_ref());
+        Update::update(&mut hasher, &(table.len() as u64).to_le_bytes());
+        for value in table {
+            hasher.update_field_element(value);
+        }
+        let mut hash = [0; 32];
+        hash.copy_from_slice(&hasher.finalize_fixed_reset());
+        Self(hash)
+    }
+
+    /// [`TableHash::new`] of `table` preprocessed with `pp`.
+    pub fn with_pcs<M: BalooEngine, Pcs: BalooPcs<M>>(
+        pp: &Pcs::ProverParam,
+        table: &[M::Scalar],
+    ) -> Self {
+        Self::new(&Pcs::monomial_g1(pp)[1], table)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Display for TableHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TableRegistry<M: BalooEngine = Bn256> {
+    // where preprocessed tables are persisted, if anywhere
+    dir: Option<PathBuf>,
+    tables: HashMap<TableHash, Arc<ProverTablePreprocessing<M>>>,
+}
+
+impl<M: BalooEngine> TableRegistry<M> {
+    /// Registry kept in memory only.
+    pub fn new() -> Self {
+        Self {
+            dir: None,
+            tables: HashMap::new(),
+        }
+    }
+
+    /// Registry persisted in `dir`, which is created if missing.
+    pub fn with_dir(dir: impl AsRef<Path>) -> Result<Self, Error> {
+        fs::create_dir_all(dir.as_ref()).map_err(err_io)?;
+        Ok(Self {
+            dir: Some(dir.as_ref().to_path_buf()),
+            tables: HashMap::new(),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Preprocessed table of `hash` in memory.
+    pub fn get(&self, hash: &TableHash) -> Option<Arc<ProverTablePreprocessing<M>>> {
+        self.tables.get(hash).cloned()
+    }
+
+    /// Preprocessing of `table` with `pp`, from memory, else from the
+    /// directory, else computed and then persisted.
+    pub fn get_or_preprocess(
+        &mut self,
+        pp: &UnivariateKzgProverParam<M>,
+        table: &[M::Scalar],
+    ) -> Result<Arc<ProverTablePreprocessing<M>>, Error> {
+        self.get_or_preprocess_with_pcs::<UnivariateKzg<M>>(pp, table)
+    }
+
+    /// [`TableRegistry::get_or_preprocess`] for any [`BalooPcs`].
+    pub fn get_or_preprocess_with_pcs<Pcs: BalooPcs<M>>(
+        &mut self,
+        pp: &Pcs::ProverParam,
+        table: &[M::Scalar],
+    ) -> Result<Arc<ProverTablePreprocessing<M>>, Error> {
+        let hash = TableHash::with_pcs::<M, Pcs>(pp, table);
+        if let Some(preprocessed) = self.get(&hash) {
+            return Ok(preprocessed);
+        }
+
+        let preprocessed = match self.load(&hash, table)? {
+            Some(preprocessed) => preprocessed,
+            None => {
+                let preprocessed = ProverTablePreprocessing::with_pcs::<Pcs>(pp, table)?;
+                self.store(&hash, &preprocessed)?;
+                preprocessed
+            }
+        };
+        let preprocessed = Arc::new(preprocessed);
+        self.tables.insert(hash, preprocessed.clone());
+        Ok(preprocessed)
+    }
+
+    fn path(&self, hash: &TableHash) -> Option<PathBuf> {
+        self.dir.as_ref().map(|dir| dir.join(format!("{hash}.bin")))
+    }
+
+    fn load(
+        &self,
+        hash: &TableHash,
+        table: &[M::Scalar],
+    ) -> Result<Option<ProverTablePreprocessing<M>>, Error> {
+        let Some(path) = self.path(hash) else {
+            return Ok(None);
+        };
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err_io(err)),
+        };
+        let preprocessed = ProverTablePreprocessing::from_file_bytes(&bytes, hash)?;
+        if preprocessed.table() != table {
+            return Err(Error::Serialization(format!(
+                "Preprocessed table in {} isn't of the table of hash {hash}",
+                path.display()
+            )));
+        }
+        Ok(Some(preprocessed))
+    }
+
+    fn store(
+        &self,
+        hash: &TableHash,
+        preprocessed: &ProverTablePreprocessing<M>,
+    ) -> Result<(), Error> {
+        let Some(path) = self.path(hash) else {
+            return Ok(());
+        };
+        preprocessed.write_to(path, hash)
+    }
+}
+
+// magic and version of the file format of `ProverTablePreprocessing::write_to`
+const MAGIC: &[u8; 8] = b"baloo-tp";
+const FORMAT_VERSION: u8 = 2;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 32 + 32;
+
+impl<M: BalooEngine> ProverTablePreprocessing<M> {
+    /// Write to `path` as `MAGIC || version || hash || Keccak256(payload) ||
+    /// payload`, with `hash` the [`TableHash`] of the table and SRS it's of
+    /// and the payload in bincode.
+    ///
+    /// It's written to a temporary file first, so that a concurrent
+    /// [`ProverTablePreprocessing::read_from`] never reads a partial one.
+    pub fn write_to(&self, path: impl AsRef<Path>, hash: &TableHash) -> Result<(), Error> {
+        let path = path.as_ref();
+        let payload =
+            bincode::serialize(self).map_err(|err| Error::Serialization(err.to_string()))?;
+        let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(FORMAT_VERSION);
+        bytes.extend_from_slice(hash.as_bytes());
+        bytes.extend_from_slice(&Keccak256::digest(&payload));
+        bytes.extend_from_slice(&payload);
+        let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        fs::write(&tmp_path, bytes).map_err(err_io)?;
+        fs::rename(&tmp_path, path).map_err(err_io)
+    }
+
+    /// Read back what [`ProverTablePreprocessing::write_to`] wrote to `path`
+    /// for the table and SRS of `hash`, failing if it's of another format
+    /// version, of another table or SRS, or corrupted.
+    pub fn read_from(path: impl AsRef<Path>, hash: &TableHash) -> Result<Self, Error> {
+        let bytes = fs::read(path).map_err(err_io)?;
+        Self::from_file_bytes(&bytes, hash)
+    }
+
+    fn from_file_bytes(bytes: &[u8], hash: &TableHash) -> Result<Self, Error> {
+        if bytes.len() < HEADER_LEN || !bytes.starts_with(MAGIC) {
+            return Err(Error::Serialization(
+                "Not a file of a preprocessed table".to_string(),
+            ));
+        }
+        let (version, bytes) = (bytes[MAGIC.len()], &bytes[MAGIC.len() + 1..]);
+        if version != FORMAT_VERSION {
+            return Err(Error::Serialization(format!(
+                "Preprocessed table of format version {version} but expected {FORMAT_VERSION}"
+            )));
+        }
+        let (file_hash, bytes) = bytes.split_at(32);
+        if file_hash != hash.as_bytes() {
+            return Err(Error::Serialization(format!(
+                "Preprocessed table isn't of the table of hash {hash}"
+            )));
+        }
+        let (checksum, payload) = bytes.split_at(32);
+        if checksum != Keccak256::digest(payload).as_slice() {
+            return Err(Error::Serialization(
+                "Preprocessed table is corrupted".to_string(),
+            ));
+        }
+        bincode::deserialize(payload).map_err(|err| Error::Serialization(err.to_string()))
+    }
+}
+
+impl<M: BalooEngine> Default for TableRegistry<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn err_io(err: io::Error) -> Error {
+    Error::Serialization(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backend::baloo::{
+            preprocessor::{
+                poly_size, preprocess, preprocess_with_param, ProverTablePreprocessing,
+            },
+            registry::{TableHash, TableRegistry},
+            Baloo,
+        },
+        pcs::{univariate::UnivariateKzg, PolynomialCommitmentScheme},
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+    use rand::rngs::OsRng;
+    use std::{env, fs, sync::Arc};
+
+    #[test]
+    fn test_table_registry() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = vec![Fr::from(3), Fr::from(8), Fr::from(3), Fr::from(1)];
+        let (t, m) = (table.len(), lookup.len());
+        let param = UnivariateKzg::<Bn256>::setup(poly_size(t, m), 1, OsRng).unwrap();
+        let (_, pp, _) = preprocess_with_param(param.clone(), t, m).unwrap();
+        let dir = env::temp_dir().join(format!("baloo-registry-{}", std::process::id()));
+
+        let mut registry = TableRegistry::with_dir(&dir).unwrap();
+        let preprocessed = registry.get_or_preprocess(&pp, &table).unwrap();
+        let again = registry.get_or_preprocess(&pp, &table).unwrap();
+        assert!(Arc::ptr_eq(&preprocessed, &again));
+        assert_eq!(registry.len(), 1);
+
+        // a registry of another process loads it from the directory
+        let mut reloaded = TableRegistry::with_dir(&dir).unwrap();
+        let hash = TableHash::new(&pp.monomial_g1()[1], &table);
+        assert!(reloaded.get(&hash).is_none());
+        let loaded = reloaded.get_or_preprocess(&pp, &table).unwrap();
+        assert_eq!(loaded.table(), preprocessed.table());
+        assert_eq!(loaded.t_comm_1(), preprocessed.t_comm_1());
+        assert_eq!(loaded.z_h_comm_1(), preprocessed.z_h_comm_1());
+
+        let (baloo_pp, baloo_vp) =
+            Baloo::preprocess_with_registry(&mut reloaded, param, &table, m).unwrap();
+        assert_eq!(reloaded.len(), 1);
+        let proof = Baloo::prove_with_param(&baloo_pp, &lookup).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        assert!(Baloo::verify(&baloo_vp, &proof, &phi_comm_1, m).is_ok());
+
+        // the hash binds the SRS as well as the table
+        let (_, other_pp, _) = preprocess::<Bn256>(t, m).unwrap();
+        assert_ne!(TableHash::new(&other_pp.monomial_g1()[1], &table), hash);
+        assert_ne!(TableHash::new(&pp.monomial_g1()[1], &table[1..]), hash);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_to_read_from() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let (_, pp, _) = preprocess::<Bn256>(table.len(), 4).unwrap();
+        let preprocessed = ProverTablePreprocessing::new(&pp, &table).unwrap();
+        let hash = TableHash::new(&pp.monomial_g1()[1], &table);
+        let path = env::temp_dir().join(format!("baloo-table-{}.bin", std::process::id()));
+
+        preprocessed.write_to(&path, &hash).unwrap();
+        let read = ProverTablePreprocessing::<Bn256>::read_from(&path, &hash).unwrap();
+        assert_eq!(read.table(), preprocessed.table());
+        assert_eq!(read.t_comm_1(), preprocessed.t_comm_1());
+        assert_eq!(read.q_t_polys(), preprocessed.q_t_polys());
+
+        // another table, another format version, or a corrupted payload
+        let other_hash = TableHash::new(&pp.monomial_g1()[1], &table[..4]);
+        assert!(ProverTablePreprocessing::<Bn256>::read_from(&path, &other_hash).is_err());
+        let bytes = fs::read(&path).unwrap();
+        for idx in [8, bytes.len() - 1] {
+            let mut bytes = bytes.clone();
+            bytes[idx] ^= 1;
+            fs::write(&path, bytes).unwrap();
+            assert!(ProverTablePreprocessing::<Bn256>::read_from(&path, &hash).is_err());
+        }
+        fs::write(&path, &bytes[..40]).unwrap();
+        assert!(ProverTablePreprocessing::<Bn256>::read_from(&path, &hash).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,144 @@
+//! Segment lookups, where each lookup row carries the id of the table its
+//! value must be in, out of a small fixed set of tables, e.g. of the opcode
+//! dependent tables of a zkVM.
+//!
+//! The tables are concatenated into the tuple table of the rows `(k, t_k)`
+//! of every value `t_k` of the `k`-th table, and the rows `(id_i, f_i)` of
+//! the lookup are looked up into it by [`tuple`]. The verifier is given
+//! `[id(x)]1` of the table ids as well as `[f(x)]1` of the values, so that a
+//! value in another table than its own isn't accepted.
+//!
+//! [`tuple`]: crate::backend::baloo::tuple
+
+use crate::{
+    backend::{
+        baloo::{
+            preprocessor::ProverTablePreprocessing, tuple, BalooEngine, BalooPcs, BalooProof,
+            BalooVerifierParam, VerifyError,
+        },
+        lookup::witness::pad_to_power_of_two,
+    },
+    pcs::univariate::UnivariateKzgCommitment,
+    util::arithmetic::PrimeField,
+    Error,
+};
+
+/// Columns of the ids and of the values of the concatenated `tables`,
+/// padded to the next power of two by repeating the last row.
+pub fn segment_table<F: PrimeField>(tables: &[Vec<F>]) -> Result<[Vec<F>; 2], Error> {
+    if let Some(k) = tables.iter().position(Vec::is_empty) {
+        return Err(Error::InvalidSnark(format!("Table {k} is empty")));
+    }
+    let (ids, values): (Vec<_>, Vec<_>) = tables
+        .iter()
+        .enumerate()
+        .flat_map(|(k, table)| table.iter().map(move |value| (segment_id(k), *value)))
+        .unzip();
+    let (Some(last_id), Some(last_value)) = (ids.last(), values.last()) else {
+        return Err(Error::InvalidSnark("No table to segment".to_string()));
+    };
+    Ok([
+        pad_to_power_of_two(&ids, *last_id),
+        pad_to_power_of_two(&values, *last_value),
+    ])
+}
+
+/// Column of the ids of `table_ids`, which the verifier commits to or is
+/// given the commitment of.
+pub fn segment_ids<F: PrimeField>(table_ids: &[usize]) -> Vec<F> {
+    table_ids.iter().copied().map(segment_id).collect()
+}
+
+/// Prove that every `lookup[i]` is in the table `table_ids[i]`, given the
+/// preprocessing of both columns of [`segment_table`].
+pub fn prove_segments<M: BalooEngine, Pcs: BalooPcs<M>>(
+    table: [&ProverTablePreprocessing<M>; 2],
+    param: &Pcs::Param,
+    pp: &Pcs::ProverParam,
+    table_ids: &[usize],
+    lookup: &[M::Scalar],
+) -> Result<BalooProof<M>, Error> {
+    if table_ids.len() != lookup.len() {
+        return Err(Error::SizeMismatch {
+            name: "table ids",
+            expected: lookup.len(),
+            got: table_ids.len(),
+        });
+    }
+    let lookup = [segment_ids(table_ids), lookup.to_vec()];
+    tuple::prove_tuples::<M, Pcs>(&table, param, pp, &lookup)
+}
+
+/// Verify a proof of [`prove_segments`] given the verifier param of both
+/// columns of [`segment_table`], `[id(x)]1` of [`segment_ids`] and `[f(x)]1`
+/// of the lookup.
+pub fn verify_segments<M: BalooEngine, Pcs: BalooPcs<M>>(
+    vps: [&BalooVerifierParam<M, Pcs>; 2],
+    proof: &BalooProof<M>,
+    ids_comm_1: &UnivariateKzgCommitment<M::G1Affine>,
+    lookup_comm_1: &UnivariateKzgCommitment<M::G1Affine>,
+    m: usize,
+) -> Result<(), VerifyError> {
+    let lookup_comms = [ids_comm_1.clone(), lookup_comm_1.clone()];
+    tuple::verify_tuples(&vps, proof, &lookup_comms, m)
+}
+
+fn segment_id<F: PrimeField>(k: usize) -> F {
+    F::from(k as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        backend::baloo::{preprocessor::preprocess, Baloo},
+        pcs::univariate::UnivariateKzg,
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+
+    type Pcs = UnivariateKzg<Bn256>;
+
+    #[test]
+    fn test_segments() {
+        // e.g. the results of two opcodes
+        let tables = [vec![1, 2, 3], vec![10, 20, 30, 40, 50]]
+            .map(|table| table.into_iter().map(Fr::from).collect::<Vec<_>>());
+        let table = segment_table(&tables).unwrap();
+        assert_eq!(table[0], [0, 0, 0, 1, 1, 1, 1, 1].map(Fr::from));
+        assert_eq!(table[1][7], Fr::from(50));
+
+        let (table_ids, lookup) = (vec![1, 0, 1, 0], [40, 3, 10, 1].map(Fr::from).to_vec());
+        let m = lookup.len();
+        let (param, pp, vp) = preprocess::<Bn256>(table[0].len(), m).unwrap();
+        let table_pps = table
+            .iter()
+            .map(|column| ProverTablePreprocessing::new(&pp, column).unwrap())
+            .collect::<Vec<_>>();
+        let table_pps = [&table_pps[0], &table_pps[1]];
+        let vps = table
+            .iter()
+            .map(|column| Baloo::verifier_param(&param, &pp, &vp, column, m).unwrap())
+            .collect::<Vec<_>>();
+        let vps = [&vps[0], &vps[1]];
+
+        let proof =
+            prove_segments::<Bn256, Pcs>(table_pps, &param, &pp, &table_ids, &lookup).unwrap();
+        let ids_comm_1 = Baloo::prepare_verification_data(&pp, &segment_ids::<Fr>(&table_ids));
+        let lookup_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        assert_eq!(
+            verify_segments(vps, &proof, &ids_comm_1, &lookup_comm_1, m),
+            Ok(())
+        );
+
+        // 40 is in the table 1 but not in the table 0
+        let other_ids = vec![0, 0, 1, 0];
+        assert_eq!(
+            prove_segments::<Bn256, Pcs>(table_pps, &param, &pp, &other_ids, &lookup),
+            Err(Error::NotInTable { positions: vec![0] })
+        );
+        let other_ids_comm_1 =
+            Baloo::prepare_verification_data(&pp, &segment_ids::<Fr>(&other_ids));
+        assert!(verify_segments(vps, &proof, &other_ids_comm_1, &lookup_comm_1, m).is_err());
+        assert!(segment_table::<Fr>(&[vec![Fr::from(1)], vec![]]).is_err());
+    }
+}
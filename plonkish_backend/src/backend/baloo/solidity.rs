@@ -0,0 +1,610 @@
+//! Solidity verifier of Baloo proofs over BN254, which re-derives the
+//! challenges of the Keccak256 transcript and executes the pairing checks of
+//! [`Verifier`] with the `ecAdd`, `ecMul`, `modexp` and `ecPairing`
+//! precompiles. Only proofs for committed `φ` are supported.
+//!
+//! [`Verifier`]: crate::backend::baloo::verifier::Verifier
+
+use crate::{
+    backend::{
+        baloo::{
+            proof::{evm_words, usize_word, BalooProof},
+            util::log_2,
+            BalooPcs, BalooVerifierParam, Instance, PhiInstance, INSTANCE_LABEL,
+        },
+        lookup,
+    },
+    pcs::univariate::UnivariateKzgCommitment,
+    util::{
+        hash::{Hash, Keccak256},
+        Itertools,
+    },
+    Error,
+};
+use halo2_curves::bn256::{Bn256, G1Affine};
+
+/// Signature of the function of the contract which verifies a proof.
+pub const VERIFY_SIGNATURE: &str = "verify(uint256[2],bytes)";
+
+/// Generate the source of a contract `BalooVerifier`, for the table and lookup
+/// size `vp` is for, whose `verify(phi, proof)` returns whether `proof` of
+/// [`Prover::prove`] is valid for `phi = [φ(x)]1`. It reverts if `phi` or
+/// `proof` isn't well-formed, e.g. with a point off the curve.
+///
+/// [`Prover::prove`]: crate::backend::baloo::prover::Prover::prove
+pub fn generate_verifier<Pcs: BalooPcs<Bn256>>(vp: &BalooVerifierParam<Bn256, Pcs>) -> String {
+    let constants = [
+        ("T", evm_words(&vp.t_comm_1.0)),
+        ("Z_H", evm_words(&vp.z_h_comm_1.0)),
+        ("X_M", evm_words(&vp.x_m_exponent_poly_comm_1.0)),
+        ("X_D2", evm_words(&vp.x_exponent_poly_2_comm_1.0)),
+        ("G2", evm_words(&Pcs::g2(&vp.vp))),
+        ("S_G2", evm_words(&Pcs::s_g2(&vp.vp))),
+        ("X_D1_G2", evm_words(&vp.x_exponent_poly_comm_2.0)),
+        ("X_D2_G2", evm_words(&vp.x_exponent_poly_2_comm_2.0)),
+    ]
+    .into_iter()
+    .flat_map(|(name, words)| {
+        let suffixes: &[&str] = if words.len() == 2 {
+            &["X", "Y"]
+        } else {
+            &["X1", "X0", "Y1", "Y0"]
+        };
+        words.into_iter().zip(suffixes).map(move |(word, suffix)| {
+            format!(
+                "    uint256 internal constant {name}_{suffix} = 0x{};",
+                hex(&word)
+            )
+        })
+    })
+    .join("\n");
+
+    VERIFIER_TEMPLATE
+        .replace("{{M}}", &vp.m.to_string())
+        .replace("{{LOG_M}}", &log_2(vp.m).to_string())
+        .replace("{{LOG_T}}", &log_2(vp.t).to_string())
+        .replace(
+            "{{PROOF_SIZE}}",
+            &BalooProof::<Bn256>::size(false).to_string(),
+        )
+        .replace("{{TRANSCRIPT_PREFIX}}", &hex(&transcript_prefix()))
+        .replace("{{INSTANCE_PREFIX}}", &hex(&instance_prefix(vp)))
+        .replace("{{CONSTANTS}}", &constants)
+}
+
+/// Calldata of `verify(phi, proof)` of the contract of [`generate_verifier`],
+/// failing if `proof` is for public `φ`.
+pub fn encode_calldata(
+    proof: &BalooProof<Bn256>,
+    phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
+) -> Result<Vec<u8>, Error> {
+    if proof.is_public() {
+        return Err(Error::InvalidSnark(
+            "Solidity verifier only supports proofs for committed φ".to_string(),
+        ));
+    }
+    let proof = proof.to_bytes();
+    let selector = &Keccak256::digest(VERIFY_SIGNATURE)[..4];
+    // `phi` is inline and `proof` is at the offset after it, padded to words,
+    // which it is already
+    let offset = usize_word(3 * 32);
+    let len = usize_word(proof.len());
+    Ok(selector
+        .iter()
+        .copied()
+        .chain(evm_words(&phi_comm_1.0).concat())
+        .chain(offset)
+        .chain(len)
+        .chain(proof)
+        .collect())
+}
+
+// `len(PROTOCOL_ID) || PROTOCOL_ID || len(INSTANCE_LABEL) || INSTANCE_LABEL`
+// with lengths in 8 bytes, as absorbed by `Instance::absorb_into` before the
+// instance digest
+fn transcript_prefix() -> Vec<u8> {
+    [<Instance as lookup::Instance>::PROTOCOL_ID, INSTANCE_LABEL]
+        .into_iter()
+        .flat_map(|label| {
+            (label.len() as u64)
+                .to_le_bytes()
+                .into_iter()
+                .chain(label.iter().copied())
+        })
+        .collect()
+}
+
+// encoding of the instance up to `[φ(x)]1`, which it ends with compressed
+fn instance_prefix<Pcs: BalooPcs<Bn256>>(vp: &BalooVerifierParam<Bn256, Pcs>) -> Vec<u8> {
+    let mut bytes = lookup::Instance::to_bytes(&Instance {
+        m: vp.m,
+        t_comm_1: vp.t_comm_1.clone(),
+        z_h_comm_1: vp.z_h_comm_1.clone(),
+        srs_digest: vp.srs_digest(),
+        phi: PhiInstance::Committed(UnivariateKzgCommitment::default()),
+    });
+    bytes.truncate(bytes.len() - 32);
+    bytes
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+const VERIFIER_TEMPLATE: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.19;
+
+/// Verifier of Baloo proofs for lookups of size {{M}}, generated by
+/// plonkish_backend. The transcript is replayed byte by byte as by its
+/// Keccak256 transcript, which absorbs field elements in little-endian.
+contract BalooVerifier {
+    uint256 internal constant R =
+        0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001;
+    uint256 internal constant Q =
+        0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47;
+
+    uint256 internal constant LOG_M = {{LOG_M}};
+    uint256 internal constant LOG_T = {{LOG_T}};
+    uint256 internal constant PROOF_SIZE = {{PROOF_SIZE}};
+    bytes internal constant TRANSCRIPT_PREFIX = hex"{{TRANSCRIPT_PREFIX}}";
+    bytes internal constant INSTANCE_PREFIX = hex"{{INSTANCE_PREFIX}}";
+
+{{CONSTANTS}}
+
+    // offsets of the proof, in the order of the transcript
+    uint256 internal constant XI = 0;
+    uint256 internal constant Z_I = 64;
+    uint256 internal constant T_I = 192;
+    uint256 internal constant D = 256;
+    uint256 internal constant R_ = 320;
+    uint256 internal constant Q_D = 384;
+    uint256 internal constant E = 448;
+    uint256 internal constant Q_E = 512;
+    uint256 internal constant V1 = 576;
+    uint256 internal constant V2 = 608;
+    uint256 internal constant V3 = 640;
+    uint256 internal constant V4 = 672;
+    uint256 internal constant V5 = 704;
+    uint256 internal constant A = 736;
+    uint256 internal constant W1 = 800;
+    uint256 internal constant W2 = 864;
+    uint256 internal constant W3 = 928;
+    uint256 internal constant W4 = 992;
+
+    struct Transcript {
+        bytes state;
+    }
+
+    struct Challenges {
+        uint256 alpha;
+        uint256 beta;
+        uint256 gamma;
+        uint256 zeta;
+        uint256 v1;
+        uint256 v2;
+        uint256 v3;
+        uint256 v4;
+        uint256 v5;
+    }
+
+    function verify(uint256[2] calldata phi, bytes calldata proof) external view returns (bool) {
+        require(proof.length == PROOF_SIZE, "invalid proof size");
+        Transcript memory transcript = Transcript(TRANSCRIPT_PREFIX);
+        absorbFieldElement(transcript, instanceDigest(phi));
+        Challenges memory c = readProof(transcript, proof);
+        // z_I(0) != 0 for any subset of H
+        if (c.v3 == 0) {
+            return false;
+        }
+        return checkSubtable(proof, c) && checkW1(phi, proof, c) && checkW2(proof, c)
+            && checkW3(proof, c) && checkW4(proof, c);
+    }
+
+    // digest of the instance, with phi compressed as x in little-endian with
+    // the parity of y in the top bit
+    function instanceDigest(uint256[2] calldata phi) internal pure returns (uint256) {
+        uint256 compressed = 0;
+        if (phi[0] != 0 || phi[1] != 0) {
+            requireOnCurve([phi[0], phi[1]]);
+            compressed = reverseBytes(phi[0]) | ((phi[1] & 1) << 7);
+        }
+        bytes32 digest = keccak256(abi.encodePacked(INSTANCE_PREFIX, compressed));
+        return reverseBytes(uint256(digest)) % R;
+    }
+
+    function readProof(Transcript memory transcript, bytes calldata proof)
+        internal
+        pure
+        returns (Challenges memory c)
+    {
+        readG1(transcript, proof, XI);
+        readG2(transcript, proof, Z_I);
+        readG1(transcript, proof, T_I);
+        c.alpha = squeezeChallengeOutsideDomain(transcript);
+        readG1(transcript, proof, D);
+        readG1(transcript, proof, R_);
+        readG1(transcript, proof, Q_D);
+        c.beta = squeezeChallenge(transcript);
+        readG1(transcript, proof, E);
+        readG1(transcript, proof, Q_E);
+        c.gamma = squeezeChallenge(transcript);
+        c.zeta = squeezeChallengeOutsideDomain(transcript);
+        c.v1 = readFieldElement(transcript, proof, V1);
+        c.v2 = readFieldElement(transcript, proof, V2);
+        c.v3 = readFieldElement(transcript, proof, V3);
+        c.v4 = readFieldElement(transcript, proof, V4);
+        c.v5 = readFieldElement(transcript, proof, V5);
+        readG1(transcript, proof, A);
+        readG1(transcript, proof, W1);
+        readG1(transcript, proof, W2);
+        readG1(transcript, proof, W3);
+        readG1(transcript, proof, W4);
+    }
+
+    // e(t - t_I + γ * z_H, [1]2) = e(a, z_I)
+    function checkSubtable(bytes calldata proof, Challenges memory c) internal view returns (bool) {
+        uint256[2] memory lhs = ecAdd(ecAdd(g1(T_X, T_Y), negate(g1(proof, T_I))), ecMul(g1(Z_H_X, Z_H_Y), c.gamma));
+        uint256[] memory input = new uint256[](12);
+        pair(input, 0, lhs, g2(G2_X1, G2_X0, G2_Y1, G2_Y0));
+        pair(input, 1, negate(g1(proof, A)), g2(proof, Z_I));
+        return pairing(input);
+    }
+
+    // e(w1, [s]2) = e(E - v1 + γ(φ - v2), [x^(d-m+1)]2) * e(α * w1, [1]2)
+    function checkW1(uint256[2] calldata phi, bytes calldata proof, Challenges memory c)
+        internal
+        view
+        returns (bool)
+    {
+        uint256[2] memory w1 = g1(proof, W1);
+        uint256[2] memory rhs = ecAdd(
+            ecAdd(negate(g1(proof, E)), ecMul(g1(1, 2), addmod(c.v1, mulmod(c.gamma, c.v2, R), R))),
+            ecMul(g1(phi[0], phi[1]), neg(c.gamma))
+        );
+        uint256[] memory input = new uint256[](18);
+        pair(input, 0, w1, g2(S_G2_X1, S_G2_X0, S_G2_Y1, S_G2_Y0));
+        pair(input, 1, rhs, g2(X_D1_G2_X1, X_D1_G2_X0, X_D1_G2_Y1, X_D1_G2_Y0));
+        pair(input, 2, ecMul(w1, neg(c.alpha)), g2(G2_X1, G2_X0, G2_Y1, G2_Y0));
+        return pairing(input);
+    }
+
+    // e(w2, [s]2) = e([1]1 + γ^2 * x^(d-m+2), z_I)
+    //     * e(γ^3 * R - γ^2 * x^m, [x^(d-m+2)]2) * e(γ * R - v3, [1]2)
+    function checkW2(bytes calldata proof, Challenges memory c) internal view returns (bool) {
+        uint256 gamma2 = mulmod(c.gamma, c.gamma, R);
+        uint256 gamma3 = mulmod(gamma2, c.gamma, R);
+        uint256[2] memory r = g1(proof, R_);
+        uint256[] memory input = new uint256[](24);
+        pair(input, 0, g1(proof, W2), g2(S_G2_X1, S_G2_X0, S_G2_Y1, S_G2_Y0));
+        pair(input, 1, ecAdd(ecMul(g1(X_D2_X, X_D2_Y), neg(gamma2)), negate(g1(1, 2))), g2(proof, Z_I));
+        pair(
+            input,
+            2,
+            ecAdd(ecMul(r, neg(gamma3)), ecMul(g1(X_M_X, X_M_Y), gamma2)),
+            g2(X_D2_G2_X1, X_D2_G2_X0, X_D2_G2_Y1, X_D2_G2_Y0)
+        );
+        pair(input, 3, ecAdd(ecMul(r, neg(c.gamma)), ecMul(g1(1, 2), c.v3)), g2(G2_X1, G2_X0, G2_Y1, G2_Y0));
+        return pairing(input);
+    }
+
+    // P_D = v1 * t_I - v2 - R - v4 * Q_D
+    // e(w3, [s]2) = e(D + β * w3 - v1 - γ * v4 + γ^2 * P_D, [1]2) * e(γ, z_I)
+    function checkW3(bytes calldata proof, Challenges memory c) internal view returns (bool) {
+        uint256 gamma2 = mulmod(c.gamma, c.gamma, R);
+        uint256 scalar = addmod(addmod(c.v1, mulmod(c.gamma, c.v4, R), R), mulmod(gamma2, c.v2, R), R);
+        uint256[2] memory rhs = ecAdd(negate(g1(proof, D)), ecMul(g1(proof, W3), neg(c.beta)));
+        rhs = ecAdd(rhs, ecMul(g1(1, 2), scalar));
+        rhs = ecAdd(rhs, ecMul(g1(proof, T_I), neg(mulmod(gamma2, c.v1, R))));
+        rhs = ecAdd(rhs, ecMul(g1(proof, R_), gamma2));
+        rhs = ecAdd(rhs, ecMul(g1(proof, Q_D), mulmod(gamma2, c.v4, R)));
+        uint256[] memory input = new uint256[](18);
+        pair(input, 0, g1(proof, W3), g2(S_G2_X1, S_G2_X0, S_G2_Y1, S_G2_Y0));
+        pair(input, 1, rhs, g2(G2_X1, G2_X0, G2_Y1, G2_Y0));
+        pair(input, 2, ecMul(g1(1, 2), neg(c.gamma)), g2(proof, Z_I));
+        return pairing(input);
+    }
+
+    // P_E = v5 * β + (v4 / v3 - v5) * ξ - z_V(ζ) * Q_E
+    // e(w4, [s]2) = e(E + ζ * w4 + γ * P_E - v5, [1]2)
+    function checkW4(bytes calldata proof, Challenges memory c) internal view returns (bool) {
+        uint256 zVZeta = addmod(powM(c.zeta), R - 1, R);
+        uint256 scalar = addmod(c.v5, neg(mulmod(c.gamma, mulmod(c.v5, c.beta, R), R)), R);
+        uint256 xiScalar = neg(mulmod(c.gamma, addmod(neg(c.v5), mulmod(c.v4, invert(c.v3), R), R), R));
+        uint256[2] memory rhs = ecAdd(negate(g1(proof, E)), ecMul(g1(proof, W4), neg(c.zeta)));
+        rhs = ecAdd(rhs, ecMul(g1(1, 2), scalar));
+        rhs = ecAdd(rhs, ecMul(g1(proof, XI), xiScalar));
+        rhs = ecAdd(rhs, ecMul(g1(proof, Q_E), mulmod(c.gamma, zVZeta, R)));
+        uint256[] memory input = new uint256[](12);
+        pair(input, 0, g1(proof, W4), g2(S_G2_X1, S_G2_X0, S_G2_Y1, S_G2_Y0));
+        pair(input, 1, rhs, g2(G2_X1, G2_X0, G2_Y1, G2_Y0));
+        return pairing(input);
+    }
+
+    function absorb(Transcript memory transcript, uint256 value) internal pure {
+        transcript.state = bytes.concat(transcript.state, bytes32(value));
+    }
+
+    function absorbFieldElement(Transcript memory transcript, uint256 fe) internal pure {
+        absorb(transcript, reverseBytes(fe));
+    }
+
+    function squeezeChallenge(Transcript memory transcript) internal pure returns (uint256) {
+        bytes32 hash = keccak256(transcript.state);
+        transcript.state = bytes.concat(hash);
+        return reverseBytes(uint256(hash)) % R;
+    }
+
+    // re-squeeze with a counter until the challenge is outside the domains of
+    // orders m and t
+    function squeezeChallengeOutsideDomain(Transcript memory transcript)
+        internal
+        pure
+        returns (uint256 challenge)
+    {
+        challenge = squeezeChallenge(transcript);
+        uint256 counter = 0;
+        while (powM(challenge) == 1 || powT(challenge) == 1) {
+            counter += 1;
+            absorbFieldElement(transcript, counter);
+            challenge = squeezeChallenge(transcript);
+        }
+    }
+
+    function readFieldElement(Transcript memory transcript, bytes calldata proof, uint256 offset)
+        internal
+        pure
+        returns (uint256 fe)
+    {
+        fe = word(proof, offset);
+        require(fe < R, "invalid field element");
+        absorbFieldElement(transcript, fe);
+    }
+
+    function readG1(Transcript memory transcript, bytes calldata proof, uint256 offset) internal pure {
+        uint256[2] memory point = g1(proof, offset);
+        requireOnCurve(point);
+        absorbFieldElement(transcript, point[0]);
+        absorbFieldElement(transcript, point[1]);
+    }
+
+    // absorbed as c0 || c1 of each coordinate, the pairing precompile checks
+    // it's in G2
+    function readG2(Transcript memory transcript, bytes calldata proof, uint256 offset) internal pure {
+        uint256[4] memory point = g2(proof, offset);
+        for (uint256 i = 0; i < 4; i++) {
+            require(point[i] < Q, "invalid coordinate");
+        }
+        absorbFieldElement(transcript, point[1]);
+        absorbFieldElement(transcript, point[0]);
+        absorbFieldElement(transcript, point[3]);
+        absorbFieldElement(transcript, point[2]);
+    }
+
+    // the identity, which is only allowed for phi, isn't on the curve
+    function requireOnCurve(uint256[2] memory point) internal pure {
+        (uint256 x, uint256 y) = (point[0], point[1]);
+        require(x < Q && y < Q, "invalid coordinate");
+        require(mulmod(y, y, Q) == addmod(mulmod(mulmod(x, x, Q), x, Q), 3, Q), "point not on curve");
+    }
+
+    function word(bytes calldata proof, uint256 offset) internal pure returns (uint256) {
+        return uint256(bytes32(proof[offset:offset + 32]));
+    }
+
+    function g1(uint256 x, uint256 y) internal pure returns (uint256[2] memory) {
+        return [x, y];
+    }
+
+    function g1(bytes calldata proof, uint256 offset) internal pure returns (uint256[2] memory) {
+        return [word(proof, offset), word(proof, offset + 32)];
+    }
+
+    function g2(uint256 x1, uint256 x0, uint256 y1, uint256 y0) internal pure returns (uint256[4] memory) {
+        return [x1, x0, y1, y0];
+    }
+
+    function g2(bytes calldata proof, uint256 offset) internal pure returns (uint256[4] memory) {
+        return [word(proof, offset), word(proof, offset + 32), word(proof, offset + 64), word(proof, offset + 96)];
+    }
+
+    function neg(uint256 scalar) internal pure returns (uint256) {
+        return scalar == 0 ? uint256(0) : R - scalar;
+    }
+
+    function negate(uint256[2] memory point) internal pure returns (uint256[2] memory) {
+        return [point[0], point[1] == 0 ? uint256(0) : Q - point[1]];
+    }
+
+    function powM(uint256 x) internal pure returns (uint256) {
+        for (uint256 i = 0; i < LOG_M; i++) {
+            x = mulmod(x, x, R);
+        }
+        return x;
+    }
+
+    function powT(uint256 x) internal pure returns (uint256) {
+        for (uint256 i = 0; i < LOG_T; i++) {
+            x = mulmod(x, x, R);
+        }
+        return x;
+    }
+
+    function invert(uint256 x) internal view returns (uint256) {
+        uint256[6] memory input = [uint256(32), 32, 32, x, R - 2, R];
+        uint256[1] memory output;
+        bool success;
+        assembly {
+            success := staticcall(gas(), 0x05, input, 0xc0, output, 0x20)
+        }
+        require(success, "modexp failed");
+        return output[0];
+    }
+
+    function ecAdd(uint256[2] memory lhs, uint256[2] memory rhs) internal view returns (uint256[2] memory output) {
+        uint256[4] memory input = [lhs[0], lhs[1], rhs[0], rhs[1]];
+        bool success;
+        assembly {
+            success := staticcall(gas(), 0x06, input, 0x80, output, 0x40)
+        }
+        require(success, "ecAdd failed");
+    }
+
+    function ecMul(uint256[2] memory point, uint256 scalar) internal view returns (uint256[2] memory output) {
+        uint256[3] memory input = [point[0], point[1], scalar];
+        bool success;
+        assembly {
+            success := staticcall(gas(), 0x07, input, 0x60, output, 0x40)
+        }
+        require(success, "ecMul failed");
+    }
+
+    function pair(uint256[] memory input, uint256 idx, uint256[2] memory lhs, uint256[4] memory rhs) internal pure {
+        input[6 * idx] = lhs[0];
+        input[6 * idx + 1] = lhs[1];
+        for (uint256 i = 0; i < 4; i++) {
+            input[6 * idx + 2 + i] = rhs[i];
+        }
+    }
+
+    // whether the product of the pairings is the identity
+    function pairing(uint256[] memory input) internal view returns (bool) {
+        uint256[1] memory output;
+        bool success;
+        assembly {
+            success := staticcall(gas(), 0x08, add(input, 0x20), mul(mload(input), 0x20), output, 0x20)
+        }
+        require(success, "ecPairing failed");
+        return output[0] == 1;
+    }
+
+    function reverseBytes(uint256 v) internal pure returns (uint256) {
+        v = ((v & 0xff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00) >> 8)
+            | ((v & 0x00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff) << 8);
+        v = ((v & 0xffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000) >> 16)
+            | ((v & 0x0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff) << 16);
+        v = ((v & 0xffffffff00000000ffffffff00000000ffffffff00000000ffffffff00000000) >> 32)
+            | ((v & 0x00000000ffffffff00000000ffffffff00000000ffffffff00000000ffffffff) << 32);
+        v = ((v & 0xffffffffffffffff0000000000000000ffffffffffffffff0000000000000000) >> 64)
+            | ((v & 0x0000000000000000ffffffffffffffff0000000000000000ffffffffffffffff) << 64);
+        return (v >> 128) | (v << 128);
+    }
+}
+"#;
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        backend::baloo::{
+            proof::evm_words,
+            prover::Prover,
+            solidity::{
+                encode_calldata, generate_verifier, hex, instance_prefix, transcript_prefix,
+                VERIFY_SIGNATURE,
+            },
+            Baloo, Instance, PhiInstance,
+        },
+        backend::cq::generate_table_and_lookup,
+        backend::lookup,
+        util::{
+            arithmetic::{fe_mod_from_le_bytes, fe_to_bytes},
+            hash::{Hash, Keccak256},
+            transcript::{
+                FieldTranscript, G2TranscriptRead, InMemoryTranscript, Keccak256Transcript,
+                TranscriptRead,
+            },
+        },
+        Error,
+    };
+    use halo2_curves::{
+        bn256::{Fr, G1Affine, G2Affine},
+        group::GroupEncoding,
+    };
+
+    // little-endian integer of a big-endian word, as by `reverseBytes`
+    fn reverse(word: &[u8]) -> Vec<u8> {
+        word.iter().rev().copied().collect()
+    }
+
+    #[test]
+    fn replayed_transcript() {
+        let (table, lookup) = generate_table_and_lookup(8, 4);
+        let m = lookup.len();
+        let (param, pp, vp) = Baloo::preprocess(table.len(), m).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        let proof = Prover::new(&table, &param, &pp)
+            .prove(&lookup)
+            .unwrap()
+            .to_bytes();
+
+        // `instanceDigest`
+        let [x, y] = <[_; 2]>::try_from(evm_words(&phi_comm_1.0)).unwrap();
+        let mut compressed = reverse(&x);
+        compressed[31] |= (y[31] & 1) << 7;
+        assert_eq!(compressed, phi_comm_1.0.to_bytes().as_ref());
+        let instance = Instance {
+            m,
+            t_comm_1: vp.t_comm_1.clone(),
+            z_h_comm_1: vp.z_h_comm_1.clone(),
+            srs_digest: vp.srs_digest(),
+            phi: PhiInstance::Committed(phi_comm_1.clone()),
+        };
+        let digest: [u8; 32] =
+            Keccak256::digest([instance_prefix(&vp), compressed].concat()).into();
+        assert_eq!(digest, lookup::Instance::digest(&instance));
+
+        // `readProof` up to α, which has the G2 point of the proof absorbed
+        let digest_fe = fe_mod_from_le_bytes::<Fr>(digest);
+        let mut state = [transcript_prefix(), fe_to_bytes(digest_fe)].concat();
+        proof[..192].chunks(32).enumerate().for_each(|(idx, word)| {
+            // c0 before c1 for [z_I(x)]2
+            let word = match idx {
+                2..=5 => &proof[32 * (idx ^ 1)..][..32],
+                _ => word,
+            };
+            state.extend(reverse(word));
+        });
+        state.extend(proof[192..256].chunks(32).flat_map(reverse));
+        let alpha: Fr = fe_mod_from_le_bytes(Keccak256::digest(&state));
+
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        instance.absorb_into(&mut transcript);
+        let _: G1Affine = transcript.read_commitment().unwrap();
+        let _: G2Affine = transcript.read_commitment_g2().unwrap();
+        let _: G1Affine = transcript.read_commitment().unwrap();
+        let expected: Fr = transcript.squeeze_challenge();
+        assert_eq!(alpha, expected);
+    }
+
+    #[test]
+    fn calldata_and_source() {
+        let (table, lookup) = generate_table_and_lookup(8, 4);
+        let m = lookup.len();
+        let (param, pp, vp) = Baloo::preprocess(table.len(), m).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        let prover = Prover::new(&table, &param, &pp);
+
+        let proof = prover.prove(&lookup).unwrap();
+        let calldata = encode_calldata(&proof, &phi_comm_1).unwrap();
+        assert_eq!(calldata.len(), 4 + 4 * 32 + 1056);
+        assert_eq!(calldata[..4], Keccak256::digest(VERIFY_SIGNATURE)[..4]);
+        assert_eq!(calldata[4 + 4 * 32..], proof.to_bytes());
+        assert_eq!(
+            encode_calldata(&prover.prove_public(&lookup).unwrap(), &phi_comm_1),
+            Err(Error::InvalidSnark(
+                "Solidity verifier only supports proofs for committed φ".to_string()
+            ))
+        );
+
+        let source = generate_verifier(&vp);
+        assert!(!source.contains("{{"));
+        assert!(source.contains(&format!(
+            "uint256 internal constant T_X = 0x{};",
+            hex(&evm_words(&vp.t_comm_1.0)[0])
+        )));
+        assert!(source.contains("uint256 internal constant LOG_M = 2;"));
+        assert!(source.contains("uint256 internal constant LOG_T = 3;"));
+        assert!(source.contains("uint256 internal constant PROOF_SIZE = 1056;"));
+    }
+}
@@ -0,0 +1,278 @@
+//! Tables of a fixed structure which most circuits look up into, built once
+//! here instead of by hand in every project: ranges, bitwise XOR and AND,
+//! and byte arithmetic.
+//!
+//! A [`StructuredTable`] of several columns is looked up either with its
+//! columns compressed by a challenge, see [`tuple`], or as the single
+//! column of its canonical encoding, which packs the columns of a row at
+//! fixed bit offsets, e.g. `a + 2^bits b + 2^(2 bits) (a ^ b)`. The packing
+//! is injective only on values within the bits of their columns, so a lookup
+//! of encoded rows proves something about its columns only if they are
+//! range checked otherwise, e.g. by the circuit they come from.
+//!
+//! [`tuple`]: crate::backend::baloo::tuple
+
+use crate::{
+    backend::baloo::{
+        preprocessor::ProverTablePreprocessing, registry::TableRegistry, BalooEngine,
+    },
+    pcs::univariate::UnivariateKzgProverParam,
+    util::arithmetic::PrimeField,
+    Error,
+};
+use std::sync::Arc;
+
+/// Largest log2 of the number of rows of a table built here.
+pub const MAX_LOG_SIZE: usize = 32;
+
+/// Table of rows over columns of values of `bits[j]` bits each.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructuredTable<F> {
+    columns: Vec<Vec<F>>,
+    bits: Vec<usize>,
+}
+
+/// `[0, 2^k)`.
+pub fn range_table<F: PrimeField>(k: usize) -> Result<StructuredTable<F>, Error> {
+    check_log_size("range bits", k, MAX_LOG_SIZE)?;
+    let column = (0..1u64 << k).map(F::from).collect();
+    Ok(StructuredTable {
+        columns: vec![column],
+        bits: vec![k],
+    })
+}
+
+/// Rows `(a, b, a ^ b)` of every `a` and `b` of `bits` bits.
+pub fn xor_table<F: PrimeField>(bits: usize) -> Result<StructuredTable<F>, Error> {
+    bitwise_table(bits, |a, b| a ^ b)
+}
+
+/// Rows `(a, b, a & b)` of every `a` and `b` of `bits` bits.
+pub fn and_table<F: PrimeField>(bits: usize) -> Result<StructuredTable<F>, Error> {
+    bitwise_table(bits, |a, b| a & b)
+}
+
+/// Rows `(a, b, a + b, a * b)` of every pair of bytes, with the sum and the
+/// product not reduced.
+pub fn u8_arithmetic<F: PrimeField>() -> StructuredTable<F> {
+    StructuredTable::from_rows([8, 8, 9, 16], 16, |i| {
+        let (a, b) = (i & 0xff, i >> 8);
+        [a, b, a + b, a * b]
+    })
+}
+
+fn bitwise_table<F: PrimeField>(
+    bits: usize,
+    op: impl Fn(u64, u64) -> u64,
+) -> Result<StructuredTable<F>, Error> {
+    check_log_size("bitwise bits", bits, MAX_LOG_SIZE / 2)?;
+    let mask = (1 << bits) - 1;
+    Ok(StructuredTable::from_rows([bits; 3], 2 * bits, |i| {
+        let (a, b) = (i & mask, i >> bits);
+        [a, b, op(a, b)]
+    }))
+}
+
+fn check_log_size(name: &'static str, size: usize, max: usize) -> Result<(), Error> {
+    if size == 0 || size > max {
+        return Err(Error::SizeOutOfRange { name, size, max });
+    }
+    Ok(())
+}
+
+impl<F: PrimeField> StructuredTable<F> {
+    // table of the `row(i)` of every `i` in `[0, 2^log_size)`
+    fn from_rows<const N: usize>(
+        bits: [usize; N],
+        log_size: usize,
+        row: impl Fn(u64) -> [u64; N],
+    ) -> Self {
+        let mut columns = (0..N)
+            .map(|_| Vec::with_capacity(1 << log_size))
+            .collect::<Vec<_>>();
+        for i in 0..1u64 << log_size {
+            for (column, value) in columns.iter_mut().zip(row(i)) {
+                column.push(F::from(value));
+            }
+        }
+        Self {
+            columns,
+            bits: bits.to_vec(),
+        }
+    }
+
+    pub fn columns(&self) -> &[Vec<F>] {
+        &self.columns
+    }
+
+    /// Bits of the values of each column.
+    pub fn bits(&self) -> &[usize] {
+        &self.bits
+    }
+
+    pub fn len(&self) -> usize {
+        self.columns[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Canonical encoding of every row.
+    pub fn encoded(&self) -> Vec<F> {
+        self.encode(&self.columns).unwrap()
+    }
+
+    /// Canonical encoding of the rows of `columns`, e.g. of a lookup into
+    /// the table, with the value of column `j` shifted by the bits of the
+    /// columns before it.
+    pub fn encode(&self, columns: &[Vec<F>]) -> Result<Vec<F>, Error> {
+        if columns.len() != self.columns.len() {
+            return Err(Error::SizeMismatch {
+                name: "encoded columns",
+                expected: self.columns.len(),
+                got: columns.len(),
+            });
+        }
+        let n = columns[0].len();
+        if let Some(column) = columns.iter().find(|column| column.len() != n) {
+            return Err(Error::SizeMismatch {
+                name: "encoded column",
+                expected: n,
+                got: column.len(),
+            });
+        }
+
+        let mut encoded = vec![F::ZERO; n];
+        let mut shift = 0;
+        for (column, bits) in columns.iter().zip(&self.bits) {
+            let scalar = F::from(2).pow([shift as u64]);
+            for (encoded, value) in encoded.iter_mut().zip(column) {
+                *encoded += scalar * value;
+            }
+            shift += bits;
+        }
+        Ok(encoded)
+    }
+
+    /// Preprocessing of every column, e.g. for [`tuple::prove_tuples`],
+    /// cached in `registry`.
+    ///
+    /// [`tuple::prove_tuples`]: crate::backend::baloo::tuple::prove_tuples
+    pub fn preprocess<M: BalooEngine<Scalar = F>>(
+        &self,
+        registry: &mut TableRegistry<M>,
+        pp: &UnivariateKzgProverParam<M>,
+    ) -> Result<Vec<Arc<ProverTablePreprocessing<M>>>, Error> {
+        self.columns
+            .iter()
+            .map(|column| registry.get_or_preprocess(pp, column))
+            .collect()
+    }
+
+    /// Preprocessing of [`StructuredTable::encoded`], cached in `registry`.
+    pub fn preprocess_encoded<M: BalooEngine<Scalar = F>>(
+        &self,
+        registry: &mut TableRegistry<M>,
+        pp: &UnivariateKzgProverParam<M>,
+    ) -> Result<Arc<ProverTablePreprocessing<M>>, Error> {
+        registry.get_or_preprocess(pp, &self.encoded())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        backend::baloo::{preprocessor::preprocess, prover::Prover, tuple, Baloo},
+        pcs::univariate::UnivariateKzg,
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+    use std::collections::HashSet;
+
+    fn row(table: &StructuredTable<Fr>, i: usize) -> Vec<Fr> {
+        table.columns().iter().map(|column| column[i]).collect()
+    }
+
+    #[test]
+    fn test_structured_tables() {
+        let range = range_table::<Fr>(3).unwrap();
+        assert_eq!(
+            range.columns(),
+            &[(0..8u64).map(Fr::from).collect::<Vec<_>>()]
+        );
+        assert_eq!(range.encoded(), range.columns()[0]);
+
+        let xor = xor_table::<Fr>(2).unwrap();
+        assert_eq!(xor.len(), 16);
+        assert_eq!(row(&xor, 3 + 4), [3, 1, 2].map(Fr::from));
+        let and = and_table::<Fr>(2).unwrap();
+        assert_eq!(row(&and, 3 + 4), [3, 1, 1].map(Fr::from));
+        let u8_arithmetic = u8_arithmetic::<Fr>();
+        assert_eq!(u8_arithmetic.len(), 1 << 16);
+        assert_eq!(
+            row(&u8_arithmetic, 200 + 100 * 256),
+            [200, 100, 300, 20000].map(Fr::from)
+        );
+
+        // the encoding of every table is of distinct rows
+        for table in [&xor, &and, &u8_arithmetic] {
+            let encoded = table.encoded();
+            assert_eq!(encoded.iter().collect::<HashSet<_>>().len(), table.len());
+        }
+        assert_eq!(xor.encoded()[7], Fr::from(3 + (1 << 2) + (2 << 4)));
+
+        assert_eq!(
+            range_table::<Fr>(0),
+            Err(Error::SizeOutOfRange {
+                name: "range bits",
+                size: 0,
+                max: MAX_LOG_SIZE
+            })
+        );
+        assert!(xor_table::<Fr>(MAX_LOG_SIZE / 2 + 1).is_err());
+        assert!(xor.encode(&xor.columns()[..2]).is_err());
+    }
+
+    #[test]
+    fn test_structured_table_lookups() {
+        let xor = xor_table::<Fr>(2).unwrap();
+        let m = 4;
+        let (param, pp, vp) = preprocess::<Bn256>(xor.len(), m).unwrap();
+        let mut registry = TableRegistry::new();
+        let columns = xor.preprocess(&mut registry, &pp).unwrap();
+        assert_eq!(registry.len(), 3);
+        let encoded = xor.preprocess_encoded(&mut registry, &pp).unwrap();
+        // preprocessed once
+        assert!(Arc::ptr_eq(
+            &xor.preprocess_encoded(&mut registry, &pp).unwrap(),
+            &encoded
+        ));
+        assert_eq!(registry.len(), 4);
+
+        // 1 ^ 2, 3 ^ 3, 0 ^ 1, 2 ^ 3
+        let lookup = [[1, 3, 0, 2], [2, 3, 1, 3], [3, 0, 1, 1]]
+            .map(|column| column.map(Fr::from).to_vec())
+            .to_vec();
+
+        let columns = columns.iter().map(Arc::as_ref).collect::<Vec<_>>();
+        let proof =
+            tuple::prove_tuples::<Bn256, UnivariateKzg<Bn256>>(&columns, &param, &pp, &lookup)
+                .unwrap();
+        let vps = xor
+            .columns()
+            .iter()
+            .map(|column| Baloo::verifier_param(&param, &pp, &vp, column, m).unwrap())
+            .collect::<Vec<_>>();
+        let lookup_comms = Baloo::prepare_tuple_verification_data(&pp, &lookup);
+        assert_eq!(Baloo::verify_tuples(&vps, &proof, &lookup_comms, m), Ok(()));
+
+        let encoded_lookup = xor.encode(&lookup).unwrap();
+        let proof = Prover::new_with_preprocessed(&encoded, &param, &pp)
+            .prove(&encoded_lookup)
+            .unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &xor.encoded(), m).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &encoded_lookup);
+        assert_eq!(Baloo::verify(&vp, &proof, &phi_comm_1, m), Ok(()));
+    }
+}
@@ -0,0 +1,118 @@
+//! Invalid proofs made on purpose, so that downstream integrations of the
+//! verifier, e.g. a contract from [`solidity`] or a service, can assert they
+//! reject every class of [`Malformation`] and not only a valid proof.
+//!
+//! Each invalid proof is made from a valid one of the lookup and comes with
+//! the `[φ(x)]1` it should be verified against.
+//!
+//! [`solidity`]: crate::backend::baloo::solidity
+
+use crate::{
+    backend::baloo::{prover::Prover, BalooEngine, BalooPcs, BalooProof},
+    pcs::univariate::UnivariateKzgCommitment,
+    util::arithmetic::{Field, PrimeCurveAffine},
+    Error,
+};
+use std::{collections::HashSet, mem};
+
+/// What is wrong with an invalid proof.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Malformation {
+    /// `v1`, i.e. `v(α)`, is off by one.
+    WrongEvaluation,
+    /// `[a(x)]1` is shifted by `[1]1`.
+    TamperedCommitment,
+    /// `[v(x)]1` of round 1 and `[d(x)]1` of round 2 are swapped.
+    SwappedRounds,
+    /// `[φ(x)]1` is of the lookup with its first value replaced by one which
+    /// isn't in the table, while the proof is of the original lookup.
+    OutOfTableLookup,
+}
+
+impl Malformation {
+    pub const ALL: [Self; 4] = [
+        Self::WrongEvaluation,
+        Self::TamperedCommitment,
+        Self::SwappedRounds,
+        Self::OutOfTableLookup,
+    ];
+}
+
+/// A proof with its `[φ(x)]1`, which the verifier should reject.
+#[derive(Clone, Debug)]
+pub struct InvalidProof<M: BalooEngine> {
+    pub malformation: Malformation,
+    pub proof: BalooProof<M>,
+    pub phi_comm_1: UnivariateKzgCommitment<M::G1Affine>,
+}
+
+/// Prove `lookup` with `prover` and malform the proof by `malformation`.
+pub fn invalid_proof<M: BalooEngine, Pcs: BalooPcs<M>>(
+    prover: &Prover<M, Pcs>,
+    lookup: &[M::Scalar],
+    malformation: Malformation,
+) -> Result<InvalidProof<M>, Error> {
+    let mut proof = prover.prove(&lookup.to_vec())?;
+    let mut lookup = lookup.to_vec();
+    match malformation {
+        Malformation::WrongEvaluation => proof.v1 += M::Scalar::ONE,
+        Malformation::TamperedCommitment => {
+            proof.a_comm_1 = (proof.a_comm_1.to_curve() + M::G1Affine::generator()).into()
+        }
+        Malformation::SwappedRounds => mem::swap(&mut proof.v_comm_1, &mut proof.d_comm_1),
+        Malformation::OutOfTableLookup => lookup[0] = not_in_table(prover.table()),
+    }
+    Ok(InvalidProof {
+        malformation,
+        proof,
+        phi_comm_1: Pcs::commit_lagrange(prover.pp(), &lookup),
+    })
+}
+
+/// [`invalid_proof`] of every [`Malformation`].
+pub fn invalid_proofs<M: BalooEngine, Pcs: BalooPcs<M>>(
+    prover: &Prover<M, Pcs>,
+    lookup: &[M::Scalar],
+) -> Result<Vec<InvalidProof<M>>, Error> {
+    Malformation::ALL
+        .into_iter()
+        .map(|malformation| invalid_proof(prover, lookup, malformation))
+        .collect()
+}
+
+// the smallest of 1, 2, ... which isn't in `table`
+fn not_in_table<F: Field + std::hash::Hash>(table: &[F]) -> F {
+    let table = table.iter().collect::<HashSet<_>>();
+    let mut value = F::ONE;
+    while table.contains(&value) {
+        value += F::ONE;
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{
+        baloo::{preprocessor::preprocess, Baloo},
+        cq::generate_table_and_lookup,
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+
+    #[test]
+    fn test_invalid_proofs() {
+        let (table, lookup) = generate_table_and_lookup(8, 4);
+        let m = lookup.len();
+        let (param, pp, vp) = preprocess::<Bn256>(table.len(), m).unwrap();
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let prover = Prover::new(&table, &param, &pp);
+
+        let invalid_proofs = invalid_proofs(&prover, &lookup).unwrap();
+        assert_eq!(invalid_proofs.len(), Malformation::ALL.len());
+        for invalid in invalid_proofs {
+            let result = Baloo::verify(&vp, &invalid.proof, &invalid.phi_comm_1, m);
+            assert!(result.is_err(), "{:?} was accepted", invalid.malformation);
+        }
+        assert_eq!(not_in_table(&table), Fr::from(9));
+    }
+}
@@ -0,0 +1,144 @@
+//! Lookups of tuples `(a_i, b_i, ...)` into a table of tuples, with the
+//! columns of both compressed by a challenge `γ` into a single lookup
+//! `Σ_j γ^j a_j` into the table `Σ_j γ^j t_j`.
+//!
+//! `γ` is squeezed after absorbing the commitments of every table and lookup
+//! column, so the prover can't pick the rows after seeing it. Commitments are
+//! linear, so the verifier derives `[t(x)]1` and `[φ(x)]1` of the compressed
+//! table and lookup from the columns' ones, and then verifies the Baloo proof
+//! of the compressed lookup in the same transcript.
+
+use crate::{
+    backend::baloo::{
+        preprocessor::ProverTablePreprocessing, proof::BalooProof, prover::Prover,
+        verifier::Verifier, BalooEngine, BalooPcs, BalooVerifierParam, PhiInstance, VerifyError,
+    },
+    pcs::{univariate::UnivariateKzgCommitment, Additive},
+    util::{
+        arithmetic::{powers, CurveAffine},
+        transcript::{FieldTranscript, InMemoryTranscript, Transcript},
+    },
+    Error,
+};
+
+// label absorbed before the columns' commitments
+const TUPLE_LABEL: &[u8] = b"tuple";
+
+/// Prove that every row of `lookup`, given by its columns, is a row of the
+/// table whose columns are preprocessed in `table`.
+pub fn prove_tuples<M: BalooEngine, Pcs: BalooPcs<M>>(
+    table: &[&ProverTablePreprocessing<M>],
+    param: &Pcs::Param,
+    pp: &Pcs::ProverParam,
+    lookup: &[Vec<M::Scalar>],
+) -> Result<BalooProof<M>, Error> {
+    if table.is_empty() || lookup.len() != table.len() {
+        return Err(Error::InvalidSnark(format!(
+            "Expected a lookup column for each of the {} table columns but got {}",
+            table.len(),
+            lookup.len()
+        )));
+    }
+    let m = lookup[0].len();
+    if let Some(column) = lookup.iter().find(|column| column.len() != m) {
+        return Err(Error::SizeMismatch {
+            name: "lookup column",
+            expected: m,
+            got: column.len(),
+        });
+    }
+
+    let mut transcript = M::Transcript::new(());
+    let t_comms = table
+        .iter()
+        .map(|column| column.t_comm_1().clone())
+        .collect::<Vec<_>>();
+    let lookup_comms = lookup
+        .iter()
+        .map(|column| Pcs::commit_lagrange(pp, column))
+        .collect::<Vec<_>>();
+    let gamma = squeeze_gamma(&mut transcript, &t_comms, &lookup_comms)?;
+
+    let table = ProverTablePreprocessing::derive_combination(table, gamma)?;
+    let gammas = powers(gamma).take(lookup.len()).collect::<Vec<_>>();
+    let lookup: Vec<M::Scalar> = (0..m)
+        .map(|i| {
+            lookup
+                .iter()
+                .zip(&gammas)
+                .map(|(column, gamma)| column[i] * gamma)
+                .sum()
+        })
+        .collect();
+    Prover::<M, Pcs>::with_pcs_preprocessed(&table, param, pp).prove_with_transcript(
+        &lookup,
+        false,
+        &mut transcript,
+    )
+}
+
+/// Verify a proof of [`prove_tuples`] for lookups of size `m`, given the
+/// verifier param of each table column and the commitment of each lookup
+/// column, e.g. of [`Baloo::prepare_tuple_verification_data`].
+///
+/// [`Baloo::prepare_tuple_verification_data`]: crate::backend::baloo::Baloo::prepare_tuple_verification_data
+pub fn verify_tuples<M: BalooEngine, Pcs: BalooPcs<M>>(
+    vps: &[&BalooVerifierParam<M, Pcs>],
+    proof: &BalooProof<M>,
+    lookup_comms: &[UnivariateKzgCommitment<M::G1Affine>],
+    m: usize,
+) -> Result<(), VerifyError> {
+    if vps.is_empty() || lookup_comms.len() != vps.len() {
+        return Err(VerifyError::Instance(format!(
+            "Expected a lookup commitment for each of the {} table columns but got {}",
+            vps.len(),
+            lookup_comms.len()
+        )));
+    }
+    if vps[1..]
+        .iter()
+        .any(|vp| vp.m != vps[0].m || vp.z_h_comm_1 != vps[0].z_h_comm_1)
+    {
+        return Err(VerifyError::Instance(
+            "Table columns should be of the same size and for the same lookup size".to_string(),
+        ));
+    }
+    if proof.is_public() {
+        return Err(VerifyError::Instance(
+            "Proof should open φ(α) for committed φ".to_string(),
+        ));
+    }
+
+    let bytes = proof.to_bytes();
+    let mut transcript = M::Transcript::from_proof((), bytes.as_slice());
+    let t_comms = vps.iter().map(|vp| vp.t_comm_1.clone()).collect::<Vec<_>>();
+    let gamma = squeeze_gamma(&mut transcript, &t_comms, lookup_comms)?;
+
+    let gammas = powers(gamma).take(vps.len()).collect::<Vec<_>>();
+    let vp = BalooVerifierParam {
+        vp: vps[0].vp.clone(),
+        m: vps[0].m,
+        t: vps[0].t,
+        t_comm_1: UnivariateKzgCommitment::msm(&gammas, &t_comms),
+        z_h_comm_1: vps[0].z_h_comm_1.clone(),
+        x_m_exponent_poly_comm_1: vps[0].x_m_exponent_poly_comm_1.clone(),
+        x_exponent_poly_comm_2: vps[0].x_exponent_poly_comm_2.clone(),
+        x_exponent_poly_2_comm_1: vps[0].x_exponent_poly_2_comm_1.clone(),
+        x_exponent_poly_2_comm_2: vps[0].x_exponent_poly_2_comm_2.clone(),
+        prepared_g2: vps[0].prepared_g2.clone(),
+    };
+    let phi = PhiInstance::Committed(UnivariateKzgCommitment::msm(&gammas, lookup_comms));
+    Verifier::new(&vp).verify_with_transcript(&mut transcript, &phi, m)
+}
+
+fn squeeze_gamma<C: CurveAffine>(
+    transcript: &mut impl Transcript<C, C::Scalar>,
+    t_comms: &[UnivariateKzgCommitment<C>],
+    lookup_comms: &[UnivariateKzgCommitment<C>],
+) -> Result<C::Scalar, Error> {
+    transcript.common_label(TUPLE_LABEL)?;
+    for comm in t_comms.iter().chain(lookup_comms) {
+        transcript.common_commitment(&comm.0)?;
+    }
+    Ok(transcript.squeeze_challenge())
+}
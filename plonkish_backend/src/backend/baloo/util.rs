@@ -1,32 +1,37 @@
-use halo2_curves::{
-    bn256::{multi_miller_loop, Fr, G1Affine, G2Affine, G2Prepared, Gt},
-    pairing::MillerLoopResult,
-};
+use halo2_curves::pairing::MillerLoopResult;
 
 use crate::{
     poly::univariate::UnivariatePolynomial,
     util::{
-        arithmetic::Field,
-        transcript::InMemoryTranscript,
+        arithmetic::{
+            barycentric_weights, batch_invert, powers, root_of_unity, Field, MultiMillerLoop,
+            PrimeField,
+        },
+        metrics,
+        transcript::{FieldTranscript, InMemoryTranscript},
     },
+    Error,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    hash::Hash,
+    ops::Deref,
+    sync::Arc,
 };
 
-pub fn lagrange_interp(h_i_values: &[Fr], t_values_from_lookup: &[Fr]) -> UnivariatePolynomial<Fr> {
+pub fn lagrange_interp<F: Field>(
+    h_i_values: &[F],
+    t_values_from_lookup: &[F],
+) -> UnivariatePolynomial<F> {
     assert!(h_i_values.len() == t_values_from_lookup.len());
 
-    let vanishing_poly = UnivariatePolynomial::vanishing(h_i_values, Fr::one());
-    let mut bary_centric_weights = vec![Fr::one(); h_i_values.len()];
-    let mut sum = UnivariatePolynomial::monomial(vec![Fr::zero()]);
+    let vanishing_poly = UnivariatePolynomial::vanishing(h_i_values, F::ONE);
+    let bary_centric_weights = barycentric_weights(h_i_values);
+    let mut sum = UnivariatePolynomial::monomial(vec![F::ZERO]);
     for (idx, h_i) in h_i_values.iter().enumerate() {
-        for (jdx, h_j) in h_i_values.iter().enumerate() {
-            if h_i == h_j {
-                continue;
-            }
-            bary_centric_weights[idx] *= (h_i - h_j).invert().unwrap();
-        }
         let y_i = t_values_from_lookup[idx];
         // x - x_i
-        let v_poly = UnivariatePolynomial::monomial(vec![-h_i, Fr::one()]);
+        let v_poly = UnivariatePolynomial::monomial(vec![-*h_i, F::ONE]);
         let (v_poly_inv, _) = vanishing_poly.div_rem(&v_poly);
         let accu = &v_poly_inv * (y_i * bary_centric_weights[idx]);
         sum += accu;
@@ -34,13 +39,19 @@ pub fn lagrange_interp(h_i_values: &[Fr], t_values_from_lookup: &[Fr]) -> Univar
     sum
 }
 
-pub fn multi_pairing(g1: &[G1Affine], g2: &[G2Affine]) -> Gt {
+pub fn pairing<M: MultiMillerLoop>(g1: &M::G1Affine, g2: &M::G2Affine) -> M::Gt {
+    metrics::count_pairings(1);
+    M::pairing(g1, g2)
+}
+
+pub fn multi_pairing<M: MultiMillerLoop>(g1: &[M::G1Affine], g2: &[M::G2Affine]) -> M::Gt {
     assert_eq!(g1.len(), g2.len(), "Input slices must have the same length");
+    metrics::count_pairings(g1.len());
 
-    let g2_prepared: Vec<G2Prepared> = g2.iter().map(|&g| G2Prepared::from_affine(g)).collect();
-    let terms: Vec<(&G1Affine, &G2Prepared)> = g1.iter().zip(g2_prepared.iter()).collect();
+    let g2_prepared: Vec<M::G2Prepared> = g2.iter().map(|&g| g.into()).collect();
+    let terms: Vec<(&M::G1Affine, &M::G2Prepared)> = g1.iter().zip(g2_prepared.iter()).collect();
 
-    let u = multi_miller_loop(&terms);
+    let u = M::multi_miller_loop(&terms);
     u.final_exponentiation()
 }
 
@@ -54,16 +65,198 @@ pub fn log_2(n: usize) -> usize {
     }
 }
 
+/// The subtable of `table` used by `lookup`: `I`, the indexes in `table` of
+/// the distinct lookup values in ascending order (the first occurrence of a
+/// repeated table value), and `col`, the position in `I` of each lookup
+/// value. `None` if some lookup value is not in `table`.
+pub fn subtable_indices<F: Field + Hash>(
+    table: &[F],
+    lookup: &[F],
+) -> Option<(Vec<usize>, Vec<usize>)> {
+    let mut index = HashMap::new();
+    for (i, value) in table.iter().enumerate().rev() {
+        index.insert(*value, i);
+    }
+    subtable_indices_by(lookup, |value| index.get(value).copied())
+}
+
+/// [`subtable_indices`] with the table index of a value given by `index`,
+/// e.g. a preprocessed one, so that it costs `O(m log m)` whatever the table.
+pub fn subtable_indices_by<F>(
+    lookup: &[F],
+    index: impl Fn(&F) -> Option<usize>,
+) -> Option<(Vec<usize>, Vec<usize>)> {
+    let lookup_indices = lookup.iter().map(index).collect::<Option<Vec<_>>>()?;
+
+    let i_values = lookup_indices
+        .iter()
+        .copied()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let positions = i_values
+        .iter()
+        .enumerate()
+        .map(|(position, i)| (*i, position))
+        .collect::<BTreeMap<_, _>>();
+    let col_values = lookup_indices.iter().map(|i| positions[i]).collect();
+    Some((i_values, col_values))
+}
+
+/// Concatenation of the lookup `columns` into a single lookup, with column
+/// `j` at positions `[j·m, (j+1)·m)`, so that a Baloo proof of it shows every
+/// column to be in the table. The columns must be of the same size `m` and
+/// their number a power of two, for `k·m` to be one as well.
+pub fn concat_columns<F: Clone>(columns: &[Vec<F>]) -> Result<Vec<F>, Error> {
+    let k = columns.len();
+    if !k.is_power_of_two() {
+        return Err(Error::NotPowerOfTwo {
+            name: "lookup columns",
+            size: k,
+        });
+    }
+    let m = columns[0].len();
+    if let Some(column) = columns.iter().find(|column| column.len() != m) {
+        return Err(Error::SizeMismatch {
+            name: "lookup column",
+            expected: m,
+            got: column.len(),
+        });
+    }
+    Ok(columns.concat())
+}
+
 pub fn pow_2(n: usize) -> usize {
     // assert_ne!(n, 0);
     let p = 2_u32.pow(n as u32);
     p as usize
 }
 
+/// Squeeze a challenge outside of the multiplicative subgroups of the given
+/// orders, which is checked cheaply by `c^n != 1`.
+///
+/// On collision the counter `1, 2, ...` is absorbed and the challenge is
+/// squeezed again, so prover and verifier derive the same challenge as long
+/// as they pass the same orders. Baloo applies it to `α` and `ζ` with the
+/// orders of `V` and `H`: `φ(α)` is evaluated barycentrically over `V` for
+/// public lookups, `z_V(ζ)` must not vanish, and neither is to be a root of
+/// unity of the table either. `β` only enters `z_I(β)` multiplicatively, so
+/// it is squeezed as is.
+pub fn squeeze_challenge_outside_domains<F: PrimeField>(
+    transcript: &mut impl FieldTranscript<F>,
+    orders: &[usize],
+) -> F {
+    let mut challenge = transcript.squeeze_challenge();
+    let mut counter = 0u64;
+    while orders
+        .iter()
+        .any(|order| challenge.pow([*order as u64]) == F::ONE)
+    {
+        counter += 1;
+        transcript.common_field_element(&F::from(counter)).unwrap();
+        challenge = transcript.squeeze_challenge();
+    }
+    challenge
+}
+
+/// Field elements of their concatenated little-endian reprs, as passed to
+/// the bindings for other languages, failing on a trailing partial repr or a
+/// non-canonical one.
+pub fn field_elements_from_le_bytes<F: PrimeField>(bytes: &[u8]) -> Result<Vec<F>, Error> {
+    let repr_size = F::Repr::default().as_ref().len();
+    if bytes.len() % repr_size != 0 {
+        return Err(Error::Serialization(format!(
+            "Field elements should be of {repr_size} bytes each but got {} bytes",
+            bytes.len()
+        )));
+    }
+    bytes
+        .chunks(repr_size)
+        .map(|bytes| {
+            let mut repr = F::Repr::default();
+            repr.as_mut().copy_from_slice(bytes);
+            Option::from(F::from_repr(repr))
+                .ok_or_else(|| Error::Serialization("Invalid field element".to_string()))
+        })
+        .collect()
+}
+
+/// Evaluate at `x` the polynomial whose evaluations on the multiplicative
+/// subgroup of order `values.len()` are `values`, without interpolating it:
+/// `f(x) = (x^m - 1) / m * Σ_i f_i * ω^i / (x - ω^i)`.
+pub fn evaluate_on_roots_of_unity<F: PrimeField>(values: &[F], x: &F) -> F {
+    let m = values.len();
+    assert!(m.is_power_of_two());
+
+    let roots = powers(root_of_unity::<F>(log_2(m)))
+        .take(m)
+        .collect::<Vec<_>>();
+    if let Some(i) = roots.iter().position(|root| root == x) {
+        return values[i];
+    }
+
+    let mut denoms = roots.iter().map(|root| *x - root).collect::<Vec<_>>();
+    batch_invert(&mut denoms);
+    let sum = values
+        .iter()
+        .zip(roots.iter())
+        .zip(denoms.iter())
+        .map(|((value, root), denom_inv)| *value * root * denom_inv)
+        .sum::<F>();
+    let z_v_at_x = x.pow([m as u64]) - F::ONE;
+    sum * z_v_at_x * F::from(m as u64).invert().unwrap()
+}
+
+/// A value borrowed for `'b`, or owned through an [`Arc`] by a prover or
+/// verifier which outlives its caller, e.g. one shared by the threads of a
+/// server.
+#[derive(Debug)]
+pub(crate) enum Shared<'b, T> {
+    Borrowed(&'b T),
+    Owned(Arc<T>),
+}
+
+impl<T> Clone for Shared<'_, T> {
+    fn clone(&self) -> Self {
+        match self {
+            Shared::Borrowed(value) => Shared::Borrowed(value),
+            Shared::Owned(value) => Shared::Owned(value.clone()),
+        }
+    }
+}
+
+impl<T> Deref for Shared<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            Shared::Borrowed(value) => value,
+            Shared::Owned(value) => value,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::ops::Add;
+    use crate::{
+        backend::{
+            baloo::{prover::Prover, verifier::Verifier, Baloo, PhiInstance},
+            cq::generate_table_and_lookup,
+        },
+        pcs::{univariate::UnivariateKzg, PolynomialCommitmentScheme},
+        poly::Polynomial,
+        util::{
+            arithmetic::Group,
+            transcript::{
+                FieldTranscriptRead, FieldTranscriptWrite, G2TranscriptRead, G2TranscriptWrite,
+                Keccak256Transcript, Transcript, TranscriptRead, TranscriptWrite,
+            },
+        },
+    };
+    use halo2_curves::bn256::{Bn256, Fr, G1Affine, G2Affine, G1, G2};
+    use rand::rngs::OsRng;
+    use std::ops::{Add, Mul};
 
     type Pcs = UnivariateKzg<Bn256>;
 
@@ -107,12 +300,12 @@ mod tests {
         let quotient_comm_1 = Pcs::commit_and_write(&pp, &quotient_poly, &mut transcript).unwrap();
         print!("quotient_comm_1: {:?}\n", quotient_comm_1);
         let quotient_comm_1_affine: G1Affine = quotient_comm_1.to_affine();
-        let lhs = pairing(&quotient_comm_1_affine, &vp.s_g2());
+        let lhs = pairing::<Bn256>(&quotient_comm_1_affine, &vp.s_g2());
         let rhs_affine = test_comm_1_affine
             .add(&quotient_comm_1_affine.mul(alpha))
             .add(g1_affine.mul(test_at_alpha.neg()))
             .into();
-        let rhs = pairing(&rhs_affine, &g2_affine);
+        let rhs = pairing::<Bn256>(&rhs_affine, &g2_affine);
         assert_eq!(lhs, rhs);
         let rhs_terms_g1 = vec![
             test_comm_1_affine,
@@ -120,7 +313,7 @@ mod tests {
             g1_affine.mul(test_at_alpha.neg()).into(),
         ];
         let rhs_terms_g2 = vec![g2_affine, g2_affine, g2_affine];
-        let rhs = multi_pairing(&rhs_terms_g1, &rhs_terms_g2);
+        let rhs = multi_pairing::<Bn256>(&rhs_terms_g1, &rhs_terms_g2);
         assert_eq!(lhs, rhs);
     }
 
@@ -161,12 +354,12 @@ mod tests {
             Pcs::commit_and_write(&pp, &x_quotient_poly, &mut transcript).unwrap();
         let x_quotient_comm_1_affine: G1Affine = x_quotient_comm_1.to_affine();
 
-        let lhs = pairing(&x_quotient_comm_1_affine, &vp.s_g2());
+        let lhs = pairing::<Bn256>(&x_quotient_comm_1_affine, &vp.s_g2());
         let rhs_affine = test_x_comm_1_affine
             .add(&x_quotient_comm_1_affine.mul(alpha))
             .add(g1_affine.mul(test_x_at_alpha.neg()))
             .into();
-        let rhs = pairing(&rhs_affine, &g2_affine);
+        let rhs = pairing::<Bn256>(&rhs_affine, &g2_affine);
         assert_eq!(lhs, rhs);
 
         let rhs_terms_g1 = vec![
@@ -175,10 +368,32 @@ mod tests {
             g1_affine.mul(test_x_at_alpha.neg()).into(),
         ];
         let rhs_terms_g2 = vec![vp.s_g2(), g2_affine, g2_affine];
-        let rhs = multi_pairing(&rhs_terms_g1, &rhs_terms_g2);
+        let rhs = multi_pairing::<Bn256>(&rhs_terms_g1, &rhs_terms_g2);
         assert_eq!(lhs, rhs);
     }
 
+    #[test]
+    fn test_evaluate_on_roots_of_unity() {
+        let values = [3, 7, 3, 4].map(Fr::from).to_vec();
+        let poly = UnivariatePolynomial::lagrange(values.clone()).ifft();
+        let x = Fr::from(42);
+        assert_eq!(evaluate_on_roots_of_unity(&values, &x), poly.evaluate(&x));
+        let omega = root_of_unity::<Fr>(2);
+        assert_eq!(evaluate_on_roots_of_unity(&values, &omega), values[1]);
+    }
+
+    #[test]
+    fn test_subtable_indices() {
+        let table = [1, 2, 3, 4, 5, 3, 7, 8].map(Fr::from).to_vec();
+        let lookup = [7, 3, 3, 4].map(Fr::from).to_vec();
+        let (i_values, col_values) = subtable_indices(&table, &lookup).unwrap();
+        // ascending table index, first occurrence of the duplicated 3
+        assert_eq!(i_values, vec![2, 3, 6]);
+        assert_eq!(col_values, vec![2, 0, 0, 1]);
+
+        assert_eq!(subtable_indices(&table, &[Fr::from(9)]), None);
+    }
+
     #[test]
     fn test_pairing() {
         let mut rng = OsRng;
@@ -190,7 +405,7 @@ mod tests {
 
         let mut g2 = G2::generator();
         g1 = g1.mul(b);
-        let pair_ab = pairing(&G1Affine::from(g1), &G2Affine::from(g2));
+        let pair_ab = pairing::<Bn256>(&G1Affine::from(g1), &G2Affine::from(g2));
 
         g1 = G1::generator();
         g1 = g1.mul(b);
@@ -198,9 +413,143 @@ mod tests {
         g2 = G2::generator();
         g1 = g1.mul(a);
 
-        let pair_ba = pairing(&G1Affine::from(g1), &G2Affine::from(g2));
+        let pair_ba = pairing::<Bn256>(&G1Affine::from(g1), &G2Affine::from(g2));
 
         assert_eq!(pair_ab, pair_ba);
         println!("pairing: {:?}", pair_ab);
     }
+
+    // Transcript which replaces squeezed challenges by the given ones while
+    // keeping the inner state, to force collisions with the domains
+    struct CollidingTranscript<T> {
+        inner: T,
+        overrides: Vec<Option<Fr>>,
+    }
+
+    impl<T: FieldTranscript<Fr>> FieldTranscript<Fr> for CollidingTranscript<T> {
+        fn squeeze_challenge(&mut self) -> Fr {
+            let challenge = self.inner.squeeze_challenge();
+            if self.overrides.is_empty() {
+                return challenge;
+            }
+            self.overrides.remove(0).unwrap_or(challenge)
+        }
+
+        fn common_field_element(&mut self, fe: &Fr) -> Result<(), Error> {
+            self.inner.common_field_element(fe)
+        }
+
+        fn common_label(&mut self, label: &'static [u8]) -> Result<(), Error> {
+            self.inner.common_label(label)
+        }
+    }
+
+    impl<T: FieldTranscriptRead<Fr>> FieldTranscriptRead<Fr> for CollidingTranscript<T> {
+        fn read_field_element(&mut self) -> Result<Fr, Error> {
+            self.inner.read_field_element()
+        }
+    }
+
+    impl<T: FieldTranscriptWrite<Fr>> FieldTranscriptWrite<Fr> for CollidingTranscript<T> {
+        fn write_field_element(&mut self, fe: &Fr) -> Result<(), Error> {
+            self.inner.write_field_element(fe)
+        }
+    }
+
+    impl<T: Transcript<G1Affine, Fr>> Transcript<G1Affine, Fr> for CollidingTranscript<T> {
+        fn common_commitment(&mut self, comm: &G1Affine) -> Result<(), Error> {
+            self.inner.common_commitment(comm)
+        }
+    }
+
+    impl<T: TranscriptRead<G1Affine, Fr>> TranscriptRead<G1Affine, Fr> for CollidingTranscript<T> {
+        fn read_commitment(&mut self) -> Result<G1Affine, Error> {
+            self.inner.read_commitment()
+        }
+    }
+
+    impl<T: TranscriptWrite<G1Affine, Fr>> TranscriptWrite<G1Affine, Fr> for CollidingTranscript<T> {
+        fn write_commitment(&mut self, comm: &G1Affine) -> Result<(), Error> {
+            self.inner.write_commitment(comm)
+        }
+    }
+
+    impl<T: G2TranscriptRead<G2Affine, Fr>> G2TranscriptRead<G2Affine, Fr> for CollidingTranscript<T> {
+        fn read_commitment_g2(&mut self) -> Result<G2Affine, Error> {
+            self.inner.read_commitment_g2()
+        }
+    }
+
+    impl<T: G2TranscriptWrite<G2Affine, Fr>> G2TranscriptWrite<G2Affine, Fr>
+        for CollidingTranscript<T>
+    {
+        fn write_commitment_g2(&mut self, comm: &G2Affine) -> Result<(), Error> {
+            self.inner.write_commitment_g2(comm)
+        }
+    }
+
+    #[test]
+    fn test_squeeze_challenge_outside_domains() {
+        let m = 4;
+        let omega = root_of_unity::<Fr>(log_2(m));
+        let valid = Fr::from(7);
+        let mut transcript = CollidingTranscript {
+            inner: Keccak256Transcript::new(()),
+            overrides: vec![Some(omega), Some(valid)],
+        };
+        // ω_V of order m is skipped for the next challenge
+        assert_eq!(
+            squeeze_challenge_outside_domains(&mut transcript, &[m, 8]),
+            valid
+        );
+        assert!(transcript.overrides.is_empty());
+
+        // a root of unity of an order not dividing any of them is kept
+        let omega_8 = root_of_unity::<Fr>(3);
+        transcript.overrides = vec![Some(omega_8)];
+        assert_eq!(
+            squeeze_challenge_outside_domains(&mut transcript, &[m]),
+            omega_8
+        );
+    }
+
+    #[test]
+    fn test_baloo_challenge_collision() {
+        let (table, lookup) = generate_table_and_lookup(8, 4);
+        let m = lookup.len();
+        let t = table.len();
+        let (param, pp, vp) = Baloo::preprocess(t, m).unwrap();
+
+        // α = ω_V, which is re-squeezed, then β and γ, and ζ = 1, which is
+        // re-squeezed as well
+        let omega = root_of_unity::<Fr>(log_2(m));
+        let overrides = vec![Some(omega), None, None, None, Some(Fr::ONE)];
+
+        let mut transcript = CollidingTranscript {
+            inner: Keccak256Transcript::new(()),
+            overrides: overrides.clone(),
+        };
+        let baloo_proof = Prover::new(&table, &param, &pp)
+            .prove_with_transcript(&lookup, false, &mut transcript)
+            .unwrap();
+        assert!(transcript.overrides.is_empty());
+        let proof = transcript.inner.into_proof();
+        assert_eq!(baloo_proof.to_bytes(), proof);
+
+        let vp = Baloo::verifier_param(&param, &pp, &vp, &table, m).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        let mut transcript = CollidingTranscript {
+            inner: Keccak256Transcript::from_proof((), proof.as_slice()),
+            overrides,
+        };
+        assert_eq!(
+            Verifier::new(&vp).verify_with_transcript(
+                &mut transcript,
+                &PhiInstance::Committed(phi_comm_1),
+                m,
+            ),
+            Ok(())
+        );
+        assert!(transcript.overrides.is_empty());
+    }
 }
@@ -1,284 +1,615 @@
 use crate::{
-    backend::baloo::util::{log_2, multi_pairing},
+    backend::baloo::{
+        plan::{
+            batch_execute_with, G1Element, G1Source, G2Source, GtSource, PairingCheck,
+            PlanElements, PreparedG2, VerificationPlan,
+        },
+        proof::BalooProof,
+        util::{evaluate_on_roots_of_unity, log_2, squeeze_challenge_outside_domains, Shared},
+        BalooEngine, BalooPcs, BalooVerifierParam, Instance, PhiInstance,
+    },
     pcs::{
-        univariate::{
-            UnivariateKzg, UnivariateKzgCommitment,
-            UnivariateKzgVerifierParam,
-        }, PolynomialCommitmentScheme,
+        univariate::{UnivariateKzg, UnivariateKzgCommitment},
+        Additive,
     },
-    poly::{univariate::UnivariatePolynomial, Polynomial},
+    poly::univariate::Radix2Domain,
     util::{
-        arithmetic::{root_of_unity, variable_base_msm, Field},
+        arithmetic::{Field, PrimeCurveAffine},
         transcript::{
-            FieldTranscript, FieldTranscriptRead, G2TranscriptRead, InMemoryTranscript, Keccak256Transcript,
+            FieldTranscript, FieldTranscriptRead, G2TranscriptRead, InMemoryTranscript,
+            Keccak256Transcript, TranscriptRead,
         },
     },
+    Error,
+};
+use halo2_curves::bn256::Bn256;
+use rand::rngs::OsRng;
+use std::{
+    collections::BTreeMap,
+    io,
+    ops::{Mul, Neg},
+    sync::Arc,
 };
-use halo2_curves::bn256::{pairing, Bn256, Fr, G1Affine, G2Affine, G1, G2};
-use std::ops::Mul;
 
-type Pcs = UnivariateKzg<Bn256>;
+/// Reason why the Baloo verifier rejects a proof.
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum VerifyError {
+    /// The proof couldn't be read, e.g. by [`BalooProof::from_bytes`] as it's
+    /// truncated or has a point which isn't on the curve or in its subgroup
+    /// of prime order, e.g. a `[z_I]2` of a small subgroup of the twist.
+    #[error("Proof couldn't be read: {0}")]
+    Transcript(#[source] Error),
+    /// The instance is malformed, e.g. public lookup values not of size `m`.
+    #[error("Invalid instance: {0}")]
+    Instance(String),
+    /// `e([t]1 - [t_I]1 + γ [z_H]1, [1]2) = e([a]1, [z_I]2)`, so `t_I` isn't
+    /// the subtable of `t` at the roots of `z_I`.
+    #[error("subtable pairing check failed")]
+    Subtable,
+    /// The opening at `α` or `0` which also bounds the degrees of `E`, `φ`,
+    /// `z_I` and `R`, named as in the [`VerificationPlan`], i.e. `"w1"` or
+    /// `"w2"`.
+    #[error("{0} pairing check failed")]
+    Degree(&'static str),
+    /// The opening at `β` or `ζ` of the inner product and well-formation
+    /// relations, i.e. `"w3"` or `"w4"`.
+    #[error("{0} pairing check failed")]
+    Evaluation(&'static str),
+    /// `e([φ]1 - Σ_j a_j [c_j]1 - b [1]1, [1]2) = e([q]1, [z_V]2)`, so `φ`
+    /// isn't the [`Link`] of the outer columns on `V`.
+    ///
+    /// [`Link`]: crate::backend::baloo::link::Link
+    #[error("link pairing check failed")]
+    Link,
+    /// `e([φ']1 - d [1]1, [1]2) = e([f]1 - d [1]1, [s]2) e([q]1, [z_V]2)`,
+    /// so `φ'` isn't the lookup `f` with the rows off the selector `s` set to
+    /// `d`, see [`gated`].
+    ///
+    /// [`gated`]: crate::backend::baloo::gated
+    #[error("selector pairing check failed")]
+    Selector,
+}
+
+impl VerifyError {
+    fn failed_check(name: &'static str) -> Self {
+        match name {
+            "subtable" => VerifyError::Subtable,
+            "w1" | "w2" => VerifyError::Degree(name),
+            _ => VerifyError::Evaluation(name),
+        }
+    }
+}
+
+impl From<Error> for VerifyError {
+    fn from(err: Error) -> Self {
+        VerifyError::Transcript(err)
+    }
+}
+
+impl From<VerifyError> for Error {
+    fn from(err: VerifyError) -> Self {
+        match err {
+            VerifyError::Transcript(err) => err,
+            VerifyError::Instance(msg) => Error::InvalidSnark(msg),
+            err => Error::InvalidSnark(err.to_string()),
+        }
+    }
+}
 
-pub struct Verifier<'b> {
-    vp: &'b UnivariateKzgVerifierParam<Bn256>,
+pub struct Verifier<'b, M: BalooEngine = Bn256, Pcs: BalooPcs<M> = UnivariateKzg<M>> {
+    vp: Shared<'b, BalooVerifierParam<M, Pcs>>,
 }
 
-impl Verifier<'_> {
-    pub fn new(vp: &UnivariateKzgVerifierParam<Bn256>) -> Verifier<'_> {
-        Verifier { vp }
+/// Execute `plans` with [`batch_execute_with`] the `prepared` fixed `G2`
+/// points, or one by one if the batch fails to return the failing check of
+/// the first invalid plan.
+pub(crate) fn execute_plans<M: BalooEngine>(
+    plans: &[(VerificationPlan<M::Scalar>, PlanElements<M>)],
+    prepared: &PreparedG2<M>,
+) -> Result<(), VerifyError> {
+    let plan_refs = plans
+        .iter()
+        .map(|(plan, elements)| (plan, elements))
+        .collect::<Vec<_>>();
+    if batch_execute_with(&plan_refs, prepared, OsRng) {
+        return Ok(());
+    }
+    for (plan, elements) in plans.iter() {
+        plan.execute(elements).map_err(VerifyError::failed_check)?;
+    }
+    Ok(())
+}
+
+impl<M: BalooEngine, Pcs: BalooPcs<M>> Verifier<'_, M, Pcs> {
+    pub fn new(vp: &BalooVerifierParam<M, Pcs>) -> Verifier<'_, M, Pcs> {
+        Verifier {
+            vp: Shared::Borrowed(vp),
+        }
+    }
+
+    /// Verifier owning `vp`, which is `'static` and so can be kept across
+    /// requests and shared by threads, e.g. in an [`Arc`] itself.
+    pub fn from_arc(vp: Arc<BalooVerifierParam<M, Pcs>>) -> Verifier<'static, M, Pcs> {
+        Verifier {
+            vp: Shared::Owned(vp),
+        }
     }
 
     pub fn verify(
         &self,
-        proof: &Vec<u8>,
-        t_comm_1: &UnivariateKzgCommitment<G1Affine>,
-        z_h_comm_1: &UnivariateKzgCommitment<G1Affine>,
-        phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
-        x_m_exponent_poly_comm_1: &UnivariateKzgCommitment<G1Affine>,
-        x_exponent_poly_comm_2: &UnivariateKzgCommitment<G2Affine>,
-        x_exponent_poly_2_comm_1: &UnivariateKzgCommitment<G1Affine>,
-        x_exponent_poly_2_comm_2: &UnivariateKzgCommitment<G2Affine>,
+        proof: &BalooProof<M>,
+        phi_comm_1: &UnivariateKzgCommitment<M::G1Affine>,
+        m: usize,
+    ) -> Result<(), VerifyError> {
+        self.verify_with_phi(proof, &PhiInstance::Committed(phi_comm_1.clone()), m)
+    }
+
+    pub fn verify_with_phi(
+        &self,
+        proof: &BalooProof<M>,
+        phi: &PhiInstance<M::G1Affine>,
+        m: usize,
+    ) -> Result<(), VerifyError> {
+        Self::check_proof_shape(proof, phi)?;
+        let bytes = proof.to_bytes();
+        let mut transcript = M::Transcript::from_proof((), bytes.as_slice());
+        self.verify_with_transcript(&mut transcript, phi, m)
+    }
+
+    /// Verify a proof of [`Prover::prove_in`] with the same transcript `T`.
+    ///
+    /// [`Prover::prove_in`]: crate::backend::baloo::prover::Prover::prove_in
+    pub fn verify_in<T>(
+        &self,
+        proof: &BalooProof<M>,
+        phi_comm_1: &UnivariateKzgCommitment<M::G1Affine>,
+        m: usize,
+    ) -> Result<(), VerifyError>
+    where
+        T: InMemoryTranscript<Param = ()>
+            + TranscriptRead<M::G1Affine, M::Scalar>
+            + G2TranscriptRead<M::G2Affine, M::Scalar>,
+    {
+        let phi = PhiInstance::Committed(phi_comm_1.clone());
+        Self::check_proof_shape(proof, &phi)?;
+        let bytes = proof.to_bytes();
+        let mut transcript = T::from_proof((), bytes.as_slice());
+        self.verify_with_transcript(&mut transcript, &phi, m)
+    }
+
+    /// Verify a proof of [`Prover::prove_in_transcript`] read from
+    /// `transcript`, in the same state as the prover's one before proving.
+    ///
+    /// [`Prover::prove_in_transcript`]: crate::backend::baloo::prover::Prover::prove_in_transcript
+    pub fn verify_in_transcript(
+        &self,
+        transcript: &mut (impl TranscriptRead<M::G1Affine, M::Scalar>
+                  + G2TranscriptRead<M::G2Affine, M::Scalar>),
+        phi_comm_1: &UnivariateKzgCommitment<M::G1Affine>,
+        m: usize,
+    ) -> Result<(), VerifyError> {
+        self.verify_with_transcript(transcript, &PhiInstance::Committed(phi_comm_1.clone()), m)
+    }
+
+    /// Verify a proof read from `reader` round by round as the verifier
+    /// needs it, e.g. from a socket, instead of buffering it first. The proof
+    /// is of the Keccak256 transcript, as of [`Verifier::verify`] for
+    /// [`Bn256`], and any bytes after it are left in `reader`.
+    pub fn verify_stream<R: io::Read>(
+        &self,
+        reader: R,
+        phi_comm_1: &UnivariateKzgCommitment<M::G1Affine>,
         m: usize,
-    ) -> bool {
-        let scalar_0 = Fr::from(0_u64);
-        let scalar_1 = Fr::from(1_u64);
-        let vp = self.vp;
-        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+    ) -> Result<(), VerifyError>
+    where
+        Keccak256Transcript<R>:
+            TranscriptRead<M::G1Affine, M::Scalar> + G2TranscriptRead<M::G2Affine, M::Scalar>,
+    {
+        let mut transcript = Keccak256Transcript::from_stream(reader);
+        self.verify_in_transcript(&mut transcript, phi_comm_1, m)
+    }
+
+    /// [`Verifier::batch_verify`] of proofs read one after the other from
+    /// `reader`, e.g. of an aggregation of many, reading each only when the
+    /// previous one is planned so that only their pairing checks are kept.
+    pub fn batch_verify_stream<R: io::Read>(
+        &self,
+        mut reader: R,
+        phi_comms: &[UnivariateKzgCommitment<M::G1Affine>],
+        m: usize,
+    ) -> Result<(), VerifyError>
+    where
+        for<'r> Keccak256Transcript<&'r mut R>:
+            TranscriptRead<M::G1Affine, M::Scalar> + G2TranscriptRead<M::G2Affine, M::Scalar>,
+    {
+        let plans = phi_comms
+            .iter()
+            .map(|phi_comm_1| {
+                let mut transcript = Keccak256Transcript::from_stream(&mut reader);
+                let phi = PhiInstance::Committed(phi_comm_1.clone());
+                self.plan_with_transcript(&mut transcript, &phi, m)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        execute_plans(&plans, self.vp.prepared_g2())
+    }
+
+    /// Verify a proof of [`Prover::prove_columns`] for columns of size `m`,
+    /// given the commitment of each column at its positions in the
+    /// concatenation, see [`Baloo::prepare_column_verification_data`]. These
+    /// sum up to `[φ(x)]1` of the concatenation, which is of size `k·m`.
+    ///
+    /// [`Prover::prove_columns`]: crate::backend::baloo::prover::Prover::prove_columns
+    /// [`Baloo::prepare_column_verification_data`]: crate::backend::baloo::Baloo::prepare_column_verification_data
+    pub fn verify_columns(
+        &self,
+        proof: &BalooProof<M>,
+        column_comms: &[UnivariateKzgCommitment<M::G1Affine>],
+        m: usize,
+    ) -> Result<(), VerifyError> {
+        if column_comms.is_empty() {
+            return Err(VerifyError::Instance(
+                "Expected at least one lookup column".to_string(),
+            ));
+        }
+        let ones = vec![M::Scalar::ONE; column_comms.len()];
+        let phi_comm_1 = UnivariateKzgCommitment::msm(&ones, column_comms);
+        self.verify(proof, &phi_comm_1, column_comms.len() * m)
+    }
+
+    /// Verify `proofs` of lookups of size `m`, each against the `[φ(x)]1` at
+    /// the same position in `phi_comms`, with a single pairing product, see
+    /// [`batch_execute_with`]. If the batch fails, the proofs are checked one
+    /// by one to return the failing check of the first invalid proof.
+    pub fn batch_verify(
+        &self,
+        proofs: &[BalooProof<M>],
+        phi_comms: &[UnivariateKzgCommitment<M::G1Affine>],
+        m: usize,
+    ) -> Result<(), VerifyError> {
+        if proofs.len() != phi_comms.len() {
+            return Err(VerifyError::Instance(format!(
+                "Expected a [φ(x)]1 for each of the {} proofs but got {}",
+                proofs.len(),
+                phi_comms.len()
+            )));
+        }
+        let plans = proofs
+            .iter()
+            .zip(phi_comms)
+            .map(|(proof, phi_comm_1)| {
+                self.plan(proof, &PhiInstance::Committed(phi_comm_1.clone()), m)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        execute_plans(&plans, self.vp.prepared_g2())
+    }
+
+    /// Reject a proof which opens `φ(α)` for public `φ`, or doesn't for
+    /// committed one, before deriving any challenge.
+    fn check_proof_shape(
+        proof: &BalooProof<M>,
+        phi: &PhiInstance<M::G1Affine>,
+    ) -> Result<(), VerifyError> {
+        match (phi, proof.is_public()) {
+            (PhiInstance::Committed(_), true) => Err(VerifyError::Instance(
+                "Proof should open φ(α) for committed φ".to_string(),
+            )),
+            (PhiInstance::Public(_), false) => Err(VerifyError::Instance(
+                "Proof shouldn't open φ(α) for public φ".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Read the proof and derive the pairing checks which the verifier
+    /// executes, without executing them.
+    pub fn plan(
+        &self,
+        proof: &BalooProof<M>,
+        phi: &PhiInstance<M::G1Affine>,
+        m: usize,
+    ) -> Result<(VerificationPlan<M::Scalar>, PlanElements<M>), VerifyError> {
+        Self::check_proof_shape(proof, phi)?;
+        let bytes = proof.to_bytes();
+        let mut transcript = M::Transcript::from_proof((), bytes.as_slice());
+        self.plan_with_transcript(&mut transcript, phi, m)
+    }
+
+    pub(crate) fn plan_with_transcript(
+        &self,
+        transcript: &mut (impl TranscriptRead<M::G1Affine, M::Scalar>
+                  + G2TranscriptRead<M::G2Affine, M::Scalar>),
+        phi: &PhiInstance<M::G1Affine>,
+        m: usize,
+    ) -> Result<(VerificationPlan<M::Scalar>, PlanElements<M>), VerifyError> {
+        let scalar_1 = M::Scalar::from(1_u64);
+        let BalooVerifierParam {
+            vp,
+            t,
+            t_comm_1,
+            z_h_comm_1,
+            x_m_exponent_poly_comm_1,
+            x_exponent_poly_comm_2,
+            x_exponent_poly_2_comm_1,
+            x_exponent_poly_2_comm_2,
+            ..
+        } = &*self.vp;
+        if !m.is_power_of_two() {
+            return Err(VerifyError::Instance(format!(
+                "Lookup size should be a power of two but got {m}"
+            )));
+        }
+        if m != self.vp.m {
+            return Err(VerifyError::Instance(format!(
+                "Verifier param is for lookups of size {} but got {m}",
+                self.vp.m
+            )));
+        }
+        if let PhiInstance::Public(lookup) = phi {
+            if lookup.len() != m {
+                return Err(VerifyError::Instance(format!(
+                    "Public lookup should be of size {m} but got {}",
+                    lookup.len()
+                )));
+            }
+        }
+        // bind the instance, which includes the public lookup values if any
+        Instance {
+            m,
+            t_comm_1: t_comm_1.clone(),
+            z_h_comm_1: z_h_comm_1.clone(),
+            srs_digest: self.vp.srs_digest(),
+            phi: phi.clone(),
+        }
+        .absorb_into(transcript);
 
         // read pi_1 = (v_comm_1.clone(), z_i_comm_2.clone(), t_i_comm_1.clone());
-        let v_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let v_comm_1 = Pcs::read_commitment(vp, transcript)?;
         // g2
-        let z_i_comm_2: G2Affine = transcript.read_commitment_g2().unwrap();
+        let z_i_comm_2: M::G2Affine = transcript.read_commitment_g2()?;
 
-        let t_i_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let t_i_comm_1 = Pcs::read_commitment(vp, transcript)?;
 
-        let alpha: Fr = transcript.squeeze_challenge();
+        let alpha = squeeze_challenge_outside_domains(transcript, &[m, *t]);
 
         // read pi_2 = (d_comm_1.clone(), r_comm_1.clone(), q_d_comm_1.clone());
-        let d_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let d_comm_1 = Pcs::read_commitment(vp, transcript)?;
 
-        let r_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let r_comm_1 = Pcs::read_commitment(vp, transcript)?;
 
-        let q_d_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let q_d_comm_1 = Pcs::read_commitment(vp, transcript)?;
 
-        let beta: Fr = transcript.squeeze_challenge();
+        let beta: M::Scalar = transcript.squeeze_challenge();
 
         // read pi_3 = (e_comm_1.clone(), q_e_comm_1.clone());
-        let e_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let e_comm_1 = Pcs::read_commitment(vp, transcript)?;
 
-        let q_e_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let q_e_comm_1 = Pcs::read_commitment(vp, transcript)?;
 
-        let gamma: Fr = transcript.squeeze_challenge();
-        let zeta: Fr = transcript.squeeze_challenge();
+        let gamma: M::Scalar = transcript.squeeze_challenge();
+        let zeta = squeeze_challenge_outside_domains(transcript, &[m, *t]);
         let gamma_2 = gamma.mul(gamma);
         let gamma_3 = gamma_2.mul(gamma);
 
         // read pi_4 = (v1, v2, v3, v4, v5, a_comm_1.clone(), w1_comm_1.clone(), w2_comm_1.clone(), w3_comm_1.clone(), w4_comm_1.clone());
-        let v1: Fr = transcript.read_field_element().unwrap();
-
-        let v2: Fr = transcript.read_field_element().unwrap();
+        let v1: M::Scalar = transcript.read_field_element()?;
 
-        let v3: Fr = transcript.read_field_element().unwrap();
+        // φ(α) is evaluated directly if φ is public
+        let v2: M::Scalar = match phi {
+            PhiInstance::Committed(_) => transcript.read_field_element()?,
+            PhiInstance::Public(lookup) => evaluate_on_roots_of_unity(lookup, &alpha),
+        };
 
-        let v4: Fr = transcript.read_field_element().unwrap();
+        let v3: M::Scalar = transcript.read_field_element()?;
+        // z_I(0) ≠ 0 for any subset of H
+        let v3_inv = Option::<M::Scalar>::from(v3.invert()).ok_or(VerifyError::Evaluation("w4"))?;
 
-        let v5: Fr = transcript.read_field_element().unwrap();
+        let v4: M::Scalar = transcript.read_field_element()?;
 
-        let a_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let v5: M::Scalar = transcript.read_field_element()?;
 
-        let w1_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let a_comm_1 = Pcs::read_commitment(vp, transcript)?;
 
-        let w2_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let w1_comm_1 = Pcs::read_commitment(vp, transcript)?;
 
-        let w3_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let w2_comm_1 = Pcs::read_commitment(vp, transcript)?;
 
-        let w4_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let w3_comm_1 = Pcs::read_commitment(vp, transcript)?;
 
-        // Construct X^m - 1, [-1, 0, 0, ..., 1], m - 1 0s in between
-        let z_v_values: Vec<Fr> = vec![scalar_1.neg()]
-            .into_iter()
-            .chain((0..m - 1).map(|_| scalar_0))
-            .chain(vec![scalar_1])
-            .collect();
-        assert_eq!(z_v_values.len(), m + 1);
+        let w4_comm_1 = Pcs::read_commitment(vp, transcript)?;
 
-        let log_m = log_2(m);
-        let v_root_of_unity = root_of_unity::<Fr>(log_m);
-        // X^m - 1
-        let z_v_poly = UnivariatePolynomial::monomial(z_v_values);
-        assert_eq!(z_v_poly.evaluate(&v_root_of_unity), scalar_0);
-        let z_v_zeta = z_v_poly.evaluate(&zeta);
+        // z_V(ζ) = ζ^m - 1
+        let v_domain = Radix2Domain::<M::Scalar>::new(log_2(m));
+        let z_v_zeta = v_domain.evaluate_vanishing(&zeta);
 
         /************
         Verification
         ************/
-        let g1 = G1::generator();
-        let g2 = G2::generator();
-        let g1_affine = G1Affine::from(g1);
-        let g2_affine = G2Affine::from(g2);
-        let z_i_comm_2_affine = z_i_comm_2;
+        let g1_affine = M::G1Affine::generator();
+        let g2_affine = M::G2Affine::generator();
+        if Pcs::g2(vp) != g2_affine {
+            return Err(VerifyError::Instance(
+                "Verifier param should be of the generator of G2".to_string(),
+            ));
+        }
+
+        let elements = PlanElements {
+            proof_g1: BTreeMap::from_iter([
+                ("xi", v_comm_1.to_affine()),
+                ("t_I", t_i_comm_1.to_affine()),
+                ("D", d_comm_1.to_affine()),
+                ("R", r_comm_1.to_affine()),
+                ("Q_D", q_d_comm_1.to_affine()),
+                ("E", e_comm_1.to_affine()),
+                ("Q_E", q_e_comm_1.to_affine()),
+                ("a", a_comm_1.to_affine()),
+                ("w1", w1_comm_1.to_affine()),
+                ("w2", w2_comm_1.to_affine()),
+                ("w3", w3_comm_1.to_affine()),
+                ("w4", w4_comm_1.to_affine()),
+            ]),
+            proof_g2: BTreeMap::from_iter([("z_I", z_i_comm_2)]),
+            instance_g1: BTreeMap::from_iter(
+                [
+                    ("t", t_comm_1.clone().to_affine()),
+                    ("z_H", z_h_comm_1.clone().to_affine()),
+                    ("x^m", x_m_exponent_poly_comm_1.clone().to_affine()),
+                    ("x^(d-m+2)", x_exponent_poly_2_comm_1.clone().to_affine()),
+                ]
+                .into_iter()
+                .chain(match phi {
+                    PhiInstance::Committed(phi_comm_1) => {
+                        Some(("phi", phi_comm_1.clone().to_affine()))
+                    }
+                    PhiInstance::Public(_) => None,
+                }),
+            ),
+            instance_g2: BTreeMap::from_iter([
+                ("x^(d-m+1)", x_exponent_poly_comm_2.clone().to_affine()),
+                ("x^(d-m+2)", x_exponent_poly_2_comm_2.clone().to_affine()),
+            ]),
+            g1: g1_affine,
+            g2: g2_affine,
+            s_g2: Pcs::s_g2(vp),
+        };
+
+        use G1Element::{Generator, Proof};
+        let mut checks = Vec::new();
+
         // 1. verify subtable
-        // subtable_lhs = ec_lincomb([
-        //     (t_comm_1, scalar_one),
-        //     (t_I_comm_1, -scalar_one),
-        //     (z_H_comm_1, gamma),
-        // ])
-        // subtable_rhs = ec_lincomb([
-        //     (w5_comm_1, scalar_one),
-        //     (w6_comm_1, gamma),
-        // ])
-        let subtable_msm_lhs = variable_base_msm(
-            &[scalar_1, -scalar_1, gamma],
-            &[
-                t_comm_1.clone().to_affine(),
-                t_i_comm_1.clone().to_affine(),
-                z_h_comm_1.clone().to_affine(),
+        // e(t - t_I + γ * z_H, [1]2) = e(a, z_I)
+        checks.push(PairingCheck {
+            name: "subtable",
+            lhs: vec![
+                (
+                    G1Source::Lincomb(vec![
+                        (scalar_1, G1Element::Instance("t")),
+                        (-scalar_1, Proof("t_I")),
+                        (gamma, G1Element::Instance("z_H")),
+                    ]),
+                    G2Source::Generator,
+                ),
+                (
+                    G1Source::Lincomb(vec![(-scalar_1, Proof("a"))]),
+                    G2Source::Proof("z_I"),
+                ),
             ],
-        )
-        .into();
-        let subtable_msm_rhs = a_comm_1.clone().to_affine();
-        let subtable_pairing_lhs = pairing(&subtable_msm_lhs, &g2_affine.clone());
-        let subtable_pairing_rhs = pairing(&subtable_msm_rhs, &z_i_comm_2_affine.clone());
-        assert_eq!(subtable_pairing_lhs, subtable_pairing_rhs);
-        println!("Finished to verify: subtable");
+            rhs: GtSource::Identity,
+        });
 
         // 2. verify w1 for X = α
         // # w1 = X^(d-m+1) * (E(X) - e(α) + (φ(X) - a(α))γ) / X - α
-        let w1_comm_1_affine: G1Affine = w1_comm_1.to_affine();
-        // calculate left hand side pairing
-        let w1_lhs = pairing(&w1_comm_1_affine, &vp.s_g2());
-        // calculate right hand side pairing
-        let w1_rhs1: G1Affine = variable_base_msm(
-            &[scalar_1, -v1, gamma, -gamma.mul(v2)],
-            &[
-                e_comm_1.clone().to_affine(),
-                g1_affine,
-                phi_comm_1.clone().to_affine(),
-                g1_affine,
+        // e(w1, [s]2) = e(E - v1 + γ(φ - v2), [x^(d-m+1)]2) * e(α * w1, [1]2),
+        // where the φ term is dropped if φ is public
+        let w1_rhs1 = match phi {
+            PhiInstance::Committed(_) => vec![
+                (-scalar_1, Proof("E")),
+                (v1 + gamma.mul(v2), Generator),
+                (-gamma, G1Element::Instance("phi")),
             ],
-        )
-        .into();
-        let w1_rhs2: G1Affine = variable_base_msm(&[alpha], &[w1_comm_1_affine]).into();
-        let x_exponent_poly_comm_2_affine = x_exponent_poly_comm_2.clone().to_affine();
-        assert_eq!(vp.g2(), g2_affine);
-        let g1_terms = vec![w1_rhs1, w1_rhs2];
-        let g2_terms = vec![x_exponent_poly_comm_2_affine, g2_affine];
-        let w1_rhs = multi_pairing(&g1_terms, &g2_terms);
-
-        assert_eq!(w1_lhs, w1_rhs);
-
-        println!("Finished to verify: w1");
+            PhiInstance::Public(_) => vec![(-scalar_1, Proof("E")), (v1, Generator)],
+        };
+        checks.push(PairingCheck {
+            name: "w1",
+            lhs: vec![
+                (G1Source::Element(Proof("w1")), G2Source::Tau),
+                (G1Source::Lincomb(w1_rhs1), G2Source::Instance("x^(d-m+1)")),
+                (
+                    G1Source::Lincomb(vec![(-alpha, Proof("w1"))]),
+                    G2Source::Generator,
+                ),
+            ],
+            rhs: GtSource::Identity,
+        });
 
         // 3. verify w2 for X = 0
-        // to affine
-        let x_exponent_poly_2_comm_1_affine = x_exponent_poly_2_comm_1.clone().to_affine();
-        let x_exponent_poly_2_comm_2_affine = x_exponent_poly_2_comm_2.clone().to_affine();
-        let x_m_exponent_poly_comm_1_affine = x_m_exponent_poly_comm_1.clone().to_affine();
-        let r_comm_1_affine = r_comm_1.to_affine();
-        let w2_comm_1_affine: G1Affine = w2_comm_1.to_affine();
-
-        // w2_rhs1 = ec_lincomb([
-        //     (b.G1, scalar_one),
-        //     (x_exp_poly_2_comm_1, gamma ** 2),
-        // ])
-        let w2_rhs1 = variable_base_msm(
-            &[gamma_2, scalar_1],
-            &[x_exponent_poly_2_comm_1_affine, g1_affine],
-        )
-        .into();
-
-        // w2_rhs2 = ec_lincomb([
-        //     (R_comm_1, gamma ** 3),
-        //     (x_m_exp_poly_comm_1, -gamma ** 2),
-        // ])
-        let w2_rhs2 = variable_base_msm(
-            &[gamma_3, -gamma_2],
-            &[
-                r_comm_1_affine,
-                x_m_exponent_poly_comm_1_affine,
+        // e(w2, [s]2) = e([1]1 + γ^2 * x^(d-m+2), z_I)
+        //     * e(γ^3 * R - γ^2 * x^m, [x^(d-m+2)]2) * e(γ * R - v3, [1]2)
+        checks.push(PairingCheck {
+            name: "w2",
+            lhs: vec![
+                (G1Source::Element(Proof("w2")), G2Source::Tau),
+                (
+                    G1Source::Lincomb(vec![
+                        (-gamma_2, G1Element::Instance("x^(d-m+2)")),
+                        (-scalar_1, Generator),
+                    ]),
+                    G2Source::Proof("z_I"),
+                ),
+                (
+                    G1Source::Lincomb(vec![
+                        (-gamma_3, Proof("R")),
+                        (gamma_2, G1Element::Instance("x^m")),
+                    ]),
+                    G2Source::Instance("x^(d-m+2)"),
+                ),
+                (
+                    G1Source::Lincomb(vec![(-gamma, Proof("R")), (v3, Generator)]),
+                    G2Source::Generator,
+                ),
             ],
-        )
-        .into();
-
-        // w2_rhs3 = ec_lincomb([
-        //     (R_comm_1, gamma),
-        //     (b.G1, -v3),
-        // ])
-        let w2_rhs3 =
-            variable_base_msm(&[gamma, -v3], &[r_comm_1_affine, g1_affine]).into();
-        // assert b.pairing(x2, w2_comm_1) == b.pairing(z_I_comm_2, w2_rhs1) * b.pairing(
-        //     x_exp_poly_2_comm_2, w2_rhs2) * b.pairing(b.G2, w2_rhs3), "w2 paring check failed"
-        // print("Finished to verify: w2")
-
-        let w2_lhs = pairing(&w2_comm_1_affine, &vp.s_g2());
-        let w2_pairing_g1_terms = vec![w2_rhs1, w2_rhs2, w2_rhs3];
-        let w2_pairing_g2_terms = vec![
-            z_i_comm_2_affine,
-            x_exponent_poly_2_comm_2_affine,
-            g2_affine,
-        ];
-        let rhs = multi_pairing(&w2_pairing_g1_terms, &w2_pairing_g2_terms);
-        assert_eq!(w2_lhs, rhs);
-        println!("Finished to verify: w2");
+            rhs: GtSource::Identity,
+        });
 
         // 4. verify w3 for X = β
-        let w3_comm_1_affine: G1Affine = w3_comm_1.to_affine();
-        // # calculate commitment [P_D(X)]1
-        let p_d_comm_1_affine: G1Affine = variable_base_msm(
-            &[v1, -v2, -scalar_1, -v4],
-            &[
-                t_i_comm_1.clone().to_affine(),
-                g1_affine,
-                r_comm_1_affine,
-                q_d_comm_1.clone().to_affine(),
+        // P_D = v1 * t_I - v2 - R - v4 * Q_D
+        // e(w3, [s]2) = e(D + β * w3 - v1 - γ * v4 + γ^2 * P_D, [1]2) * e(γ, z_I)
+        checks.push(PairingCheck {
+            name: "w3",
+            lhs: vec![
+                (G1Source::Element(Proof("w3")), G2Source::Tau),
+                (
+                    G1Source::Lincomb(vec![
+                        (-scalar_1, Proof("D")),
+                        (-beta, Proof("w3")),
+                        (v1 + gamma.mul(v4) + gamma_2.mul(v2), Generator),
+                        (-gamma_2.mul(v1), Proof("t_I")),
+                        (gamma_2, Proof("R")),
+                        (gamma_2.mul(v4), Proof("Q_D")),
+                    ]),
+                    G2Source::Generator,
+                ),
+                (
+                    G1Source::Lincomb(vec![(-gamma, Generator)]),
+                    G2Source::Proof("z_I"),
+                ),
             ],
-        )
-        .into();
-        let w3_rhs1 = variable_base_msm(
-            &[scalar_1, beta, -v1 - gamma.mul(v4), gamma_2],
-            &[
-                d_comm_1.clone().to_affine(),
-                w3_comm_1_affine,
-                g1_affine,
-                p_d_comm_1_affine,
-            ],
-        )
-        .into();
-        let w3_rhs2 = variable_base_msm(&[gamma], &[g1_affine]).into();
-
-        let w3_lhs = pairing(&w3_comm_1_affine, &vp.s_g2());
-        let w3_pairing_g1_terms = vec![w3_rhs1, w3_rhs2];
-        let w3_pairing_g2_terms = vec![g2_affine, z_i_comm_2_affine];
-        let w3_rhs = multi_pairing(&w3_pairing_g1_terms, &w3_pairing_g2_terms);
-        assert_eq!(w3_lhs, w3_rhs);
-        println!("Finished to verify: w3");
+            rhs: GtSource::Identity,
+        });
 
         // 5. verify w4 for X = ζ
-        let p_e_comm_1_affine: G1Affine = variable_base_msm(
-            &[v5.mul(beta), -v5 + v4 * v3.invert().unwrap(), -z_v_zeta],
-            &[
-                g1_affine,
-                v_comm_1.clone().to_affine(),
-                q_e_comm_1.clone().to_affine(),
+        // P_E = v5 * β + (v4 / v3 - v5) * ξ - z_V(ζ) * Q_E
+        // e(w4, [s]2) = e(E + ζ * w4 + γ * P_E - v5, [1]2)
+        checks.push(PairingCheck {
+            name: "w4",
+            lhs: vec![
+                (G1Source::Element(Proof("w4")), G2Source::Tau),
+                (
+                    G1Source::Lincomb(vec![
+                        (-scalar_1, Proof("E")),
+                        (-zeta, Proof("w4")),
+                        (v5 - gamma.mul(v5.mul(beta)), Generator),
+                        (-gamma.mul(-v5 + v4 * v3_inv), Proof("xi")),
+                        (gamma.mul(z_v_zeta), Proof("Q_E")),
+                    ]),
+                    G2Source::Generator,
+                ),
             ],
-        )
-        .into();
-        let w4_comm_1_affine: G1Affine = w4_comm_1.to_affine();
-        let w4_msm_rhs = variable_base_msm(
-            &[scalar_1, zeta, gamma, -v5],
-            &[
-                e_comm_1.clone().to_affine(),
-                w4_comm_1_affine,
-                p_e_comm_1_affine,
-                g1_affine,
-            ],
-        )
-        .into();
-        let w4_pairing_lhs = pairing(&w4_comm_1_affine, &vp.s_g2());
-        let w4_pairing_rhs = pairing(&w4_msm_rhs, &g2_affine.clone());
-        assert_eq!(w4_pairing_lhs, w4_pairing_rhs);
-        println!("Finished to verify: w4");
-
-        true
+            rhs: GtSource::Identity,
+        });
+
+        Ok((VerificationPlan { checks }, elements))
+    }
+
+    pub(crate) fn verify_with_transcript(
+        &self,
+        transcript: &mut (impl TranscriptRead<M::G1Affine, M::Scalar>
+                  + G2TranscriptRead<M::G2Affine, M::Scalar>),
+        phi: &PhiInstance<M::G1Affine>,
+        m: usize,
+    ) -> Result<(), VerifyError> {
+        // all checks in a single Miller loop and final exponentiation, with
+        // the fixed `G2` points prepared once per verifier param
+        let plan = self.plan_with_transcript(transcript, phi, m)?;
+        execute_plans(&[plan], self.vp.prepared_g2())
     }
 }
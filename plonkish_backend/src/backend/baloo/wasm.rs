@@ -0,0 +1,131 @@
+//! Bindings of Baloo over BN254 for JavaScript, of the `cdylib` built with
+//! `cargo rustc --release --target wasm32-unknown-unknown --crate-type cdylib
+//! --no-default-features --features wasm-bindgen` then passed to the
+//! `wasm-bindgen` CLI. Field elements are passed as concatenated 32-byte
+//! little-endian reprs, proofs as [`BalooProof::to_versioned_bytes`] and
+//! everything else in bincode.
+//!
+//! Only paths which need no randomness are exposed: the SRS is given instead
+//! of sampled, proofs aren't blinded and verification isn't batched.
+
+use crate::{
+    backend::baloo::{
+        preprocessor::preprocess_with_param, proof::BalooProof, prover::Prover,
+        util::field_elements_from_le_bytes, verifier::Verifier, Baloo, BalooVerifierParam,
+    },
+    pcs::{
+        univariate::{UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam},
+        PolynomialCommitmentScheme,
+    },
+    util::{DeserializeOwned, Serialize},
+    Error,
+};
+use halo2_curves::bn256::{Bn256, Fr, G1Affine};
+use wasm_bindgen::prelude::*;
+
+/// Prove that `lookup` is in `table` with the SRS `srs`, a bincode-encoded
+/// `UnivariateKzgParam<Bn256>`.
+#[wasm_bindgen]
+pub fn prove(table: &[u8], lookup: &[u8], srs: &[u8]) -> Result<Vec<u8>, JsError> {
+    prove_bytes(table, lookup, srs).map_err(js_error)
+}
+
+/// Verifier param of lookups of size `m` into `table` for [`verify`].
+#[wasm_bindgen(js_name = verifierParam)]
+pub fn verifier_param(table: &[u8], m: usize, srs: &[u8]) -> Result<Vec<u8>, JsError> {
+    verifier_param_bytes(table, m, srs).map_err(js_error)
+}
+
+/// `[φ(x)]1` of `lookup` for [`verify`].
+#[wasm_bindgen(js_name = commitLookup)]
+pub fn commit_lookup(lookup: &[u8], srs: &[u8]) -> Result<Vec<u8>, JsError> {
+    commit_lookup_bytes(lookup, srs).map_err(js_error)
+}
+
+/// Whether `proof` is valid for `phi` of [`commit_lookup`] against `vp` of
+/// [`verifier_param`], failing only if any of them is malformed.
+#[wasm_bindgen]
+pub fn verify(proof: &[u8], phi: &[u8], vp: &[u8]) -> Result<bool, JsError> {
+    verify_bytes(proof, phi, vp).map_err(js_error)
+}
+
+fn prove_bytes(table: &[u8], lookup: &[u8], srs: &[u8]) -> Result<Vec<u8>, Error> {
+    let table: Vec<Fr> = field_elements_from_le_bytes(table)?;
+    let lookup: Vec<Fr> = field_elements_from_le_bytes(lookup)?;
+    let (param, pp, _) = preprocess_with_param(deserialize(srs)?, table.len(), lookup.len())?;
+    let proof = Prover::new(&table, &param, &pp).prove(&lookup)?;
+    Ok(proof.to_versioned_bytes())
+}
+
+fn verifier_param_bytes(table: &[u8], m: usize, srs: &[u8]) -> Result<Vec<u8>, Error> {
+    let table: Vec<Fr> = field_elements_from_le_bytes(table)?;
+    let (param, pp, vp) = preprocess_with_param(deserialize(srs)?, table.len(), m)?;
+    serialize(&Baloo::verifier_param(&param, &pp, &vp, &table, m)?)
+}
+
+fn commit_lookup_bytes(lookup: &[u8], srs: &[u8]) -> Result<Vec<u8>, Error> {
+    let lookup: Vec<Fr> = field_elements_from_le_bytes(lookup)?;
+    let param: UnivariateKzgParam<Bn256> = deserialize(srs)?;
+    let (pp, _) = UnivariateKzg::trim(&param, lookup.len().next_power_of_two(), 1)?;
+    serialize(&Baloo::prepare_verification_data(&pp, &lookup))
+}
+
+fn verify_bytes(proof: &[u8], phi: &[u8], vp: &[u8]) -> Result<bool, Error> {
+    let proof = BalooProof::from_versioned_bytes(proof)?;
+    let phi_comm_1: UnivariateKzgCommitment<G1Affine> = deserialize(phi)?;
+    let vp: BalooVerifierParam = deserialize(vp)?;
+    Ok(Verifier::new(&vp)
+        .verify(&proof, &phi_comm_1, vp.m())
+        .is_ok())
+}
+
+fn serialize(value: &impl Serialize) -> Result<Vec<u8>, Error> {
+    bincode::serialize(value).map_err(|err| Error::Serialization(err.to_string()))
+}
+
+fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    bincode::deserialize(bytes).map_err(|err| Error::Serialization(err.to_string()))
+}
+
+fn js_error(err: Error) -> JsError {
+    JsError::new(&format!("{err:?}"))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        backend::{
+            baloo::wasm::{
+                commit_lookup_bytes, prove_bytes, serialize, verifier_param_bytes, verify_bytes,
+            },
+            cq::generate_table_and_lookup,
+        },
+        pcs::{univariate::UnivariateKzg, PolynomialCommitmentScheme},
+        util::arithmetic::fe_to_bytes,
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+    use rand::rngs::OsRng;
+
+    fn to_bytes(values: &[Fr]) -> Vec<u8> {
+        values.iter().flat_map(fe_to_bytes).collect()
+    }
+
+    #[test]
+    fn prove_and_verify() {
+        let (table, lookup) = generate_table_and_lookup(8, 4);
+        let param = UnivariateKzg::<Bn256>::setup(16, 1, OsRng).unwrap();
+        let srs = serialize(&param).unwrap();
+        let (table, lookup) = (to_bytes(&table), to_bytes(&lookup));
+
+        let proof = prove_bytes(&table, &lookup, &srs).unwrap();
+        let phi = commit_lookup_bytes(&lookup, &srs).unwrap();
+        let vp = verifier_param_bytes(&table, 4, &srs).unwrap();
+        assert_eq!(verify_bytes(&proof, &phi, &vp), Ok(true));
+
+        let other_lookup = to_bytes(&[1, 1, 1, 1].map(Fr::from));
+        let other_phi = commit_lookup_bytes(&other_lookup, &srs).unwrap();
+        assert_eq!(verify_bytes(&proof, &other_phi, &vp), Ok(false));
+        assert!(verify_bytes(&proof[1..], &phi, &vp).is_err());
+        assert!(prove_bytes(&table[1..], &lookup, &srs).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{
+    backend::lookup::witness::{LookupWitness, MissingValues},
+    util::{arithmetic::PrimeField, Serialize},
+    Error,
+};
+use halo2_curves::bn256::Fr;
+
+/// Summary of a satisfiable lookup witness.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct WitnessReport {
+    pub table_size: usize,
+    pub lookup_size: usize,
+    /// Table index of each lookup value, the first occurrence if the value is
+    /// repeated in the table.
+    pub indices: Vec<usize>,
+    /// `(table index, number of lookup values using it)` in ascending table
+    /// index, which is the canonical order of the subtable `t_I`.
+    pub counts: Vec<(usize, usize)>,
+}
+
+/// Reason why a lookup witness is unsatisfiable before any proving.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub enum WitnessError<F = Fr> {
+    EmptyTable,
+    EmptyLookup,
+    /// Baloo interpolates over multiplicative subgroups, so both sizes have to
+    /// be powers of two.
+    NonPowerOfTwoSize { name: &'static str, size: usize },
+    /// `(position in lookup, value)` of every lookup value missing in table.
+    MissingValues(Vec<(usize, F)>),
+}
+
+impl<F> From<WitnessError<F>> for Error {
+    fn from(err: WitnessError<F>) -> Self {
+        match err {
+            WitnessError::EmptyTable => Error::InvalidSnark("Table is empty".to_string()),
+            WitnessError::EmptyLookup => Error::InvalidSnark("Lookup is empty".to_string()),
+            WitnessError::NonPowerOfTwoSize { name, size } => Error::NotPowerOfTwo { name, size },
+            WitnessError::MissingValues(missing) => Error::NotInTable {
+                positions: missing.into_iter().map(|(position, _)| position).collect(),
+            },
+        }
+    }
+}
+
+/// Check that every lookup value appears in table, without any SRS or
+/// proving, and report how the table is used.
+pub fn check_witness<F: PrimeField>(
+    table: &[F],
+    lookup: &[F],
+) -> Result<WitnessReport, WitnessError<F>> {
+    if table.is_empty() {
+        return Err(WitnessError::EmptyTable);
+    }
+    if lookup.is_empty() {
+        return Err(WitnessError::EmptyLookup);
+    }
+    for (name, size) in [("table", table.len()), ("lookup", lookup.len())] {
+        if !size.is_power_of_two() {
+            return Err(WitnessError::NonPowerOfTwoSize { name, size });
+        }
+    }
+
+    let witness = LookupWitness::new(table, lookup)
+        .map_err(|MissingValues(missing)| WitnessError::MissingValues(missing))?;
+    Ok(WitnessReport {
+        table_size: table.len(),
+        lookup_size: lookup.len(),
+        counts: witness
+            .subtable_indices
+            .iter()
+            .map(|idx| (*idx, witness.multiplicities[*idx]))
+            .collect(),
+        indices: witness.indices,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::baloo::witness::{check_witness, WitnessError, WitnessReport};
+    use halo2_curves::bn256::Fr;
+
+    #[test]
+    fn test_check_witness() {
+        let table = [1, 2, 3, 4].map(Fr::from).to_vec();
+        let lookup = [3, 1, 3, 3].map(Fr::from).to_vec();
+        assert_eq!(
+            check_witness(&table, &lookup),
+            Ok(WitnessReport {
+                table_size: 4,
+                lookup_size: 4,
+                indices: vec![2, 0, 2, 2],
+                counts: vec![(0, 1), (2, 3)],
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_witness_missing_values() {
+        let table = [1, 2, 3, 4].map(Fr::from).to_vec();
+        let lookup = [5, 1, 3, 7].map(Fr::from).to_vec();
+        assert_eq!(
+            check_witness(&table, &lookup),
+            Err(WitnessError::MissingValues(vec![
+                (0, Fr::from(5)),
+                (3, Fr::from(7))
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_check_witness_empty() {
+        let table = [1, 2].map(Fr::from).to_vec();
+        assert_eq!(check_witness(&[], &table), Err(WitnessError::EmptyTable));
+        assert_eq!(check_witness(&table, &[]), Err(WitnessError::EmptyLookup));
+        assert_eq!(
+            check_witness(&[Fr::one(); 3], &table),
+            Err(WitnessError::NonPowerOfTwoSize {
+                name: "table",
+                size: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_witness_duplicated_table() {
+        let table = [1, 2, 2, 1].map(Fr::from).to_vec();
+        let lookup = [2, 2, 1, 2].map(Fr::from).to_vec();
+        let report = check_witness(&table, &lookup).unwrap();
+        // first occurrences
+        assert_eq!(report.indices, vec![1, 1, 0, 1]);
+        assert_eq!(report.counts, vec![(0, 1), (1, 3)]);
+    }
+}
@@ -0,0 +1,192 @@
+use halo2_curves::bn256::{Bn256, Fr, G1Affine};
+
+use crate::{
+    pcs::univariate::{
+        UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam, UnivariateKzgProverParam,
+        UnivariateKzgVerifierParam,
+    },
+    poly::univariate::UnivariatePolynomial,
+    Error,
+};
+
+pub mod preprocessor;
+pub mod prover;
+pub mod util;
+pub mod verifier;
+
+use prover::Prover;
+use verifier::Verifier;
+
+type Pcs = UnivariateKzg<Bn256>;
+
+/// Caulk lookup, with the positions of the lookup values in table hidden
+/// from the verifier by blinding.
+#[derive(Clone, Debug)]
+pub struct Caulk;
+
+impl Caulk {
+    pub fn preprocess(
+        t: usize,
+        m: usize,
+    ) -> Result<
+        (
+            UnivariateKzgParam<Bn256>,
+            UnivariateKzgProverParam<Bn256>,
+            UnivariateKzgVerifierParam<Bn256>,
+        ),
+        Error,
+    > {
+        preprocessor::preprocess(t, m)
+    }
+
+    pub fn prove(
+        table: &Vec<Fr>,
+        param: &UnivariateKzgParam<Bn256>,
+        pp: &UnivariateKzgProverParam<Bn256>,
+        lookup: &Vec<Fr>,
+    ) -> Result<Vec<u8>, Error> {
+        Prover::new(table, param, pp).prove(lookup)
+    }
+
+    pub fn verify(
+        vp: &UnivariateKzgVerifierParam<Bn256>,
+        proof: &Vec<u8>,
+        t_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        m: usize,
+        t: usize,
+    ) -> bool {
+        Verifier::new(vp).verify(proof, t_comm_1, phi_comm_1, m, t)
+    }
+
+    // Helper method to prepare verification data
+    pub fn prepare_verification_data(
+        pp: &UnivariateKzgProverParam<Bn256>,
+        table: &[Fr],
+        lookup: &[Fr],
+    ) -> (
+        UnivariateKzgCommitment<G1Affine>, // t_comm_1
+        UnivariateKzgCommitment<G1Affine>, // phi_comm_1
+    ) {
+        let t_poly = UnivariatePolynomial::lagrange(table.to_vec()).ifft();
+        let phi_poly = UnivariatePolynomial::lagrange(lookup.to_vec()).ifft();
+        (
+            Pcs::commit_monomial(pp, t_poly.coeffs()),
+            Pcs::commit_monomial(pp, phi_poly.coeffs()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backend::caulk::{verifier::Verifier, Caulk},
+        pcs::univariate::{UnivariateKzgCommitment, UnivariateKzgVerifierParam},
+        util::transcript::{InMemoryTranscript, Keccak256Transcript, TranscriptWrite},
+        Error,
+    };
+    use halo2_curves::bn256::{Bn256, Fr, G1Affine};
+
+    // offset in the proof of C_I'(v1) of π3, after π1 = ([z_I']1, [C_I']1,
+    // [u']1, [H']2, [U_1]1, ..., [U_{log t - 1}]1), π2 = ([H1]1, [H2]1) and 5
+    // other evaluations, which is followed by U_1(α), ..., U_{log t - 1}(α)
+    // and the batch opening ([Q]1, [π]1)
+    fn c_i_eval(log_t: usize) -> usize {
+        3 * 64 + 128 + (log_t - 1) * 64 + 2 * 64 + 5 * 32
+    }
+
+    fn proof_size(log_t: usize) -> usize {
+        c_i_eval(log_t) + 32 + (log_t - 1) * 32 + 2 * 64
+    }
+
+    fn verify(
+        vp: &UnivariateKzgVerifierParam<Bn256>,
+        proof: &[u8],
+        t_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        m: usize,
+        t: usize,
+    ) -> Result<(), Error> {
+        let mut transcript = Keccak256Transcript::from_proof((), proof);
+        Verifier::new(vp).verify_with_transcript(t_comm_1, phi_comm_1, m, t, &mut transcript)
+    }
+
+    fn run_caulk(table: Vec<Fr>, lookup: Vec<Fr>) {
+        let (m, t) = (lookup.len(), table.len());
+        let (param, pp, vp) = Caulk::preprocess(t, m).unwrap();
+        let proof = Caulk::prove(&table, &param, &pp, &lookup).unwrap();
+        let (t_comm_1, phi_comm_1) = Caulk::prepare_verification_data(&pp, &table, &lookup);
+        assert!(Caulk::verify(&vp, &proof, &t_comm_1, &phi_comm_1, m, t));
+
+        // proof doesn't verify against another lookup
+        let mut other_lookup = lookup.clone();
+        other_lookup.reverse();
+        other_lookup[0] += Fr::one();
+        let (_, other_phi_comm_1) = Caulk::prepare_verification_data(&pp, &table, &other_lookup);
+        assert!(!Caulk::verify(&vp, &proof, &t_comm_1, &other_phi_comm_1, m, t));
+    }
+
+    #[test]
+    fn test_caulk() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        run_caulk(table, [3, 7, 3, 4].map(Fr::from).to_vec());
+    }
+
+    #[test]
+    fn test_caulk_single_value() {
+        let table = (1..=16).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        run_caulk(table, vec![Fr::from(16); 2]);
+    }
+
+    #[test]
+    fn test_caulk_smallest_table() {
+        run_caulk(vec![Fr::from(5), Fr::from(9)], vec![Fr::from(9), Fr::from(5)]);
+    }
+
+    #[test]
+    fn test_caulk_tampered() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = [3, 7, 3, 4].map(Fr::from).to_vec();
+        let (m, t, log_t) = (lookup.len(), table.len(), 3);
+        let (param, pp, vp) = Caulk::preprocess(t, m).unwrap();
+        let proof = Caulk::prove(&table, &param, &pp, &lookup).unwrap();
+        let (t_comm_1, phi_comm_1) = Caulk::prepare_verification_data(&pp, &table, &lookup);
+        let verify = |proof: &[u8]| verify(&vp, proof, &t_comm_1, &phi_comm_1, m, t);
+        assert_eq!(proof.len(), proof_size(log_t));
+        assert_eq!(verify(&proof), Ok(()));
+
+        // C_I'(v1) off by one, which fails the composition check at α
+        let mut tampered = proof.clone();
+        tampered[c_i_eval(log_t) + 31] ^= 1;
+        assert_eq!(
+            verify(&tampered),
+            Err(Error::InvalidSnark(
+                "Lookup values should be the subtable at u".to_string()
+            ))
+        );
+
+        // U_1(α) off by one, which fails the check of u' being roots of unity
+        let mut tampered = proof.clone();
+        tampered[c_i_eval(log_t) + 32 + 31] ^= 1;
+        assert_eq!(
+            verify(&tampered),
+            Err(Error::InvalidSnark(
+                "u should evaluate to roots of unity of the table domain on V".to_string()
+            ))
+        );
+
+        // [π]1 replaced by another valid point, which passes the checks of
+        // Caulk as it's read after them but fails the opening
+        let mut transcript = Keccak256Transcript::new(());
+        TranscriptWrite::<G1Affine, Fr>::write_commitment(&mut transcript, &pp.g1()).unwrap();
+        let mut tampered = proof.clone();
+        tampered[proof.len() - 64..].copy_from_slice(&transcript.into_proof());
+        assert_ne!(tampered, proof);
+        assert_eq!(
+            verify(&tampered),
+            Err(Error::InvalidPcsOpen(
+                "Invalid univariate KZG open".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use crate::pcs::univariate::{
+    UnivariateKzg, UnivariateKzgParam, UnivariateKzgProverParam, UnivariateKzgVerifierParam,
+};
+use crate::{pcs::PolynomialCommitmentScheme, Error};
+use halo2_curves::bn256::Bn256;
+use rand::rngs::OsRng;
+use std::cmp::max;
+
+type Pcs = UnivariateKzg<Bn256>;
+
+/// Poly size of the SRS for table size `t` and lookup size `m`.
+///
+/// Besides the table, Caulk commits to `(z_I(u(X)) + χ(C_I(u(X)) - φ(X))) / z_V(X)`,
+/// whose degree is quadratic in `m` (`deg(C_I) ≤ m + 2` and `deg(u) ≤ m + 2`
+/// with blinders), which is where its `O(m^2)` prover comes from.
+pub fn poly_size(t: usize, m: usize) -> usize {
+    max(
+        t.next_power_of_two() * 2,
+        ((m + 2) * (m + 2) + 1).next_power_of_two(),
+    )
+}
+
+pub fn preprocess(
+    t: usize,
+    m: usize,
+) -> Result<
+    (
+        UnivariateKzgParam<Bn256>,
+        UnivariateKzgProverParam<Bn256>,
+        UnivariateKzgVerifierParam<Bn256>,
+    ),
+    Error,
+> {
+    let mut rng = OsRng;
+    let poly_size = poly_size(t, m);
+    let param = Pcs::setup(poly_size, 1, &mut rng)?;
+    let (pp, vp) = Pcs::trim(&param, poly_size, 1)?;
+
+    Ok((param, pp, vp))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::caulk::preprocessor::{poly_size, preprocess};
+
+    #[test]
+    fn test_preprocess() {
+        assert_eq!(poly_size(8, 4), 64);
+        assert_eq!(poly_size(1 << 10, 4), 1 << 11);
+
+        let (param, pp, _) = preprocess(8, 4).unwrap();
+        assert_eq!(param.degree(), 63);
+        assert_eq!(pp.degree(), 63);
+    }
+}
@@ -0,0 +1,254 @@
+use crate::{
+    backend::{
+        baloo::util::{lagrange_interp, log_2, squeeze_challenge_outside_domains, subtable_indices},
+        caulk::util::{compose, vanishing_poly},
+    },
+    pcs::{
+        univariate::{UnivariateKzg, UnivariateKzgParam, UnivariateKzgProverParam},
+        Evaluation, PolynomialCommitmentScheme,
+    },
+    poly::univariate::UnivariatePolynomial,
+    util::{
+        arithmetic::{powers, root_of_unity, Field},
+        transcript::{
+            FieldTranscript, FieldTranscriptWrite, G2TranscriptWrite, InMemoryTranscript,
+            Keccak256Transcript, Transcript, TranscriptWrite,
+        },
+    },
+    Error,
+};
+use halo2_curves::bn256::{Bn256, Fr, G1Affine, G2Affine};
+use rand::{rngs::OsRng, RngCore};
+use std::iter;
+
+type Pcs = UnivariateKzg<Bn256>;
+
+pub struct Prover<'b> {
+    table: &'b Vec<Fr>,
+    param: &'b UnivariateKzgParam<Bn256>,
+    pp: &'b UnivariateKzgProverParam<Bn256>,
+}
+
+impl Prover<'_> {
+    pub fn new<'a>(
+        table: &'a Vec<Fr>,
+        param: &'a UnivariateKzgParam<Bn256>,
+        pp: &'a UnivariateKzgProverParam<Bn256>,
+    ) -> Prover<'a> {
+        Prover { table, param, pp }
+    }
+
+    pub fn prove(&self, lookup: &Vec<Fr>) -> Result<Vec<u8>, Error> {
+        let mut transcript = Keccak256Transcript::new(());
+        self.prove_with_transcript(lookup, OsRng, &mut transcript)?;
+        Ok(transcript.into_proof())
+    }
+
+    pub(crate) fn prove_with_transcript(
+        &self,
+        lookup: &Vec<Fr>,
+        mut rng: impl RngCore,
+        transcript: &mut (impl TranscriptWrite<G1Affine, Fr> + G2TranscriptWrite<G2Affine, Fr>),
+    ) -> Result<(), Error> {
+        let table = self.table;
+        let pp = self.pp;
+        let m = lookup.len();
+        let t = table.len();
+        if !m.is_power_of_two() || !t.is_power_of_two() || t < 2 {
+            return Err(Error::InvalidSnark(format!(
+                "Lookup and table sizes should be powers of two (table at least 2) but got {m} and {t}"
+            )));
+        }
+
+        /************
+          Round 1
+        ************/
+        /*
+        Same subtable as Baloo: the distinct lookup values at I ⊆ [0, t) in the
+        table, H_I = {ω^i}, and col(j) the position in I of lookup value j.
+
+        C_I(X) interpolates table[I] over H_I, z_I(X) = Π_{i ∈ I}(X - ω^i),
+        u(X) interpolates ω^{I[col(j)]} over V, so that on V
+            z_I(u(X)) = 0 and C_I(u(X)) = φ(X),
+        and C(X) - C_I(X) = z_I(X) * H(X) binds C_I to the table.
+
+        Positions are hidden by blinding as in the Caulk paper:
+            z_I'(X) = r1 * z_I(X)
+            C_I'(X) = C_I(X) + (r2 + r3 X + r4 X^2) * z_I(X)
+            u'(X) = u(X) + (r5 + r6 X + r7 X^2) * z_V(X)
+        which keep all identities above, with H'(X) = (H(X) - (r2 + r3 X + r4 X^2)) / r1.
+        */
+        let (i_values, col_values) = subtable_indices(table, lookup)
+            .ok_or_else(|| Error::InvalidSnark("Lookup values should be in table".to_string()))?;
+        let log_t = log_2(t);
+        let t_root_of_unity = root_of_unity::<Fr>(log_t);
+        let h_i = i_values
+            .iter()
+            .map(|&i| t_root_of_unity.pow([i as u64]))
+            .collect::<Vec<_>>();
+        let c_i_values = i_values.iter().map(|&i| table[i]).collect::<Vec<_>>();
+
+        // C(X)
+        let t_poly = UnivariatePolynomial::lagrange(table.clone()).ifft();
+        // φ(X)
+        let phi_poly = UnivariatePolynomial::lagrange(lookup.clone()).ifft();
+        // bind the instance ([C]1, [φ]1)
+        let t_comm_1 = Pcs::commit_monomial(pp, t_poly.coeffs());
+        let phi_comm_1 = Pcs::commit_monomial(pp, phi_poly.coeffs());
+        transcript.common_commitments(&[t_comm_1.0, phi_comm_1.0])?;
+        // z_I(X), C_I(X), u(X)
+        let z_i_poly = UnivariatePolynomial::vanishing(&h_i, Fr::one());
+        let c_i_poly = lagrange_interp(&h_i, &c_i_values);
+        let u_values = col_values.iter().map(|&col| h_i[col]).collect::<Vec<_>>();
+        let u_poly = UnivariatePolynomial::lagrange(u_values.clone()).ifft();
+        // z_V(X) = X^m - 1
+        let z_v_poly = vanishing_poly(m);
+        // H(X) = (C(X) - C_I(X)) / z_I(X)
+        let h_poly = &(&t_poly - &c_i_poly) / &z_i_poly;
+
+        let blinders = iter::repeat_with(|| Fr::random(&mut rng))
+            .take(7)
+            .collect::<Vec<_>>();
+        let c_i_blinder = UnivariatePolynomial::monomial(blinders[1..4].to_vec());
+        let u_blinder = UnivariatePolynomial::monomial(blinders[4..7].to_vec());
+        let z_i_poly_blinded = &z_i_poly * blinders[0];
+        let c_i_poly_blinded = &c_i_poly + c_i_blinder.poly_mul(z_i_poly.clone());
+        let u_poly_blinded = &u_poly + u_blinder.poly_mul(z_v_poly.clone());
+        let h_poly_blinded = &(&h_poly - &c_i_blinder) * blinders[0].invert().unwrap();
+
+        /*
+        u(ν_j) ∈ H is shown by the chain U_0 = u', U_s = U_{s-1}^2 on V for
+        s in [1, log t) and U_{log t - 1}^2 = 1 on V, each U_s blinded by a
+        random linear multiple of z_V(X).
+        */
+        let mut unity_values = u_values;
+        let unity_polys = (1..log_t)
+            .map(|_| {
+                unity_values.iter_mut().for_each(|value| *value = value.square());
+                let blinder = UnivariatePolynomial::monomial(vec![
+                    Fr::random(&mut rng),
+                    Fr::random(&mut rng),
+                ]);
+                &UnivariatePolynomial::lagrange(unity_values.clone()).ifft()
+                    + blinder.poly_mul(z_v_poly.clone())
+            })
+            .collect::<Vec<_>>();
+
+        // π1 = ([z_I']1, [C_I']1, [u']1, [H']2, [U_1]1, ..., [U_{log t - 1}]1)
+        let z_i_comm_1 = Pcs::commit_and_write(pp, &z_i_poly_blinded, transcript)?;
+        let c_i_comm_1 = Pcs::commit_and_write(pp, &c_i_poly_blinded, transcript)?;
+        let u_comm_1 = Pcs::commit_and_write(pp, &u_poly_blinded, transcript)?;
+        let h_comm_2 = Pcs::commit_monomial_g2(self.param, h_poly_blinded.coeffs());
+        transcript.write_commitment_g2(&h_comm_2.to_affine())?;
+        let unity_comms = unity_polys
+            .iter()
+            .map(|poly| Pcs::commit_and_write(pp, poly, transcript))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let chi: Fr = transcript.squeeze_challenge();
+        let sigma: Fr = transcript.squeeze_challenge();
+
+        /************
+          Round 2
+        ************/
+        // H1(X) = (z_I'(u'(X)) + χ(C_I'(u'(X)) - φ(X))) / z_V(X)
+        let h1_poly = &(&compose(&z_i_poly_blinded, &u_poly_blinded)
+            + &(&(&compose(&c_i_poly_blinded, &u_poly_blinded) - &phi_poly) * chi))
+            / &z_v_poly;
+        // H2(X) = (Σ_s σ^(s-1) (U_{s-1}(X)^2 - U_s(X)) + σ^(log t - 1) (U_{log t - 1}(X)^2 - 1)) / z_V(X)
+        let unity_chain = iter::once(&u_poly_blinded)
+            .chain(unity_polys.iter())
+            .collect::<Vec<_>>();
+        let h2_poly = &powers(sigma)
+            .zip(0..log_t)
+            .map(|(scalar, s)| {
+                let squared = unity_chain[s].poly_mul(unity_chain[s].clone());
+                let diff = match unity_chain.get(s + 1) {
+                    Some(next) => &squared - *next,
+                    None => squared + -Fr::one(),
+                };
+                &diff * scalar
+            })
+            .sum::<UnivariatePolynomial<Fr>>()
+            / &z_v_poly;
+
+        // π2 = ([H1]1, [H2]1)
+        let h1_comm_1 = Pcs::commit_and_write(pp, &h1_poly, transcript)?;
+        let h2_comm_1 = Pcs::commit_and_write(pp, &h2_poly, transcript)?;
+
+        // α must not be in V for z_V(α) to be invertible
+        let alpha = squeeze_challenge_outside_domains(transcript, &[m]);
+
+        /************
+          Round 3
+        ************/
+        // v1 = u'(α), the point at which z_I' and C_I' are opened
+        let v1 = u_poly_blinded.evaluate(&alpha);
+        let polys = [
+            &u_poly_blinded,
+            &phi_poly,
+            &h1_poly,
+            &h2_poly,
+            &z_i_poly_blinded,
+            &c_i_poly_blinded,
+        ]
+        .into_iter()
+        .chain(unity_polys.iter())
+        .collect::<Vec<_>>();
+        let comms = [
+            &u_comm_1,
+            &phi_comm_1,
+            &h1_comm_1,
+            &h2_comm_1,
+            &z_i_comm_1,
+            &c_i_comm_1,
+        ]
+        .into_iter()
+        .chain(unity_comms.iter())
+        .collect::<Vec<_>>();
+        let points = [alpha, v1];
+        // z_I' and C_I' at v1, everything else at α
+        let evals = polys
+            .iter()
+            .enumerate()
+            .map(|(idx, poly)| {
+                let point = if idx == 4 || idx == 5 { 1 } else { 0 };
+                Evaluation::new(idx, point, poly.evaluate(&points[point]))
+            })
+            .collect::<Vec<_>>();
+
+        // π3 = (u'(α), φ(α), H1(α), H2(α), z_I'(v1), C_I'(v1), U_s(α), batch opening)
+        for eval in evals.iter() {
+            transcript.write_field_element(eval.value())?;
+        }
+        Pcs::batch_open(pp, polys, comms, &points, &evals, transcript)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::caulk::{preprocessor::preprocess, prover::Prover};
+    use crate::Error;
+    use halo2_curves::bn256::Fr;
+
+    #[test]
+    fn test_prove() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = vec![Fr::from(4), Fr::from(3), Fr::from(4), Fr::from(8)];
+        let (param, pp, _) = preprocess(table.len(), lookup.len()).unwrap();
+        let prover = Prover::new(&table, &param, &pp);
+        let proof = prover.prove(&lookup).unwrap();
+        // blinded, so proofs of the same lookup differ
+        assert_ne!(proof, prover.prove(&lookup).unwrap());
+
+        let lookup = vec![Fr::from(4), Fr::from(9), Fr::from(4), Fr::from(8)];
+        assert_eq!(
+            prover.prove(&lookup),
+            Err(Error::InvalidSnark(
+                "Lookup values should be in table".to_string()
+            ))
+        );
+    }
+}
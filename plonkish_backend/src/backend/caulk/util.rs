@@ -0,0 +1,58 @@
+use crate::poly::univariate::UnivariatePolynomial;
+use halo2_curves::bn256::Fr;
+
+/// `outer(inner(X))` by Horner's rule, each step being an FFT multiplication.
+pub fn compose(
+    outer: &UnivariatePolynomial<Fr>,
+    inner: &UnivariatePolynomial<Fr>,
+) -> UnivariatePolynomial<Fr> {
+    let mut coeffs = outer.coeffs().iter().rev();
+    let leading = match coeffs.next() {
+        Some(leading) => *leading,
+        None => return UnivariatePolynomial::zero(),
+    };
+    coeffs.fold(
+        UnivariatePolynomial::monomial(vec![leading]),
+        |acc, coeff| acc.poly_mul(inner.clone()) + *coeff,
+    )
+}
+
+/// `X^n - 1`
+pub fn vanishing_poly(n: usize) -> UnivariatePolynomial<Fr> {
+    let mut coeffs = vec![Fr::zero(); n + 1];
+    coeffs[0] = -Fr::one();
+    coeffs[n] = Fr::one();
+    UnivariatePolynomial::monomial(coeffs)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backend::caulk::util::{compose, vanishing_poly},
+        poly::univariate::UnivariatePolynomial,
+    };
+    use halo2_curves::bn256::Fr;
+
+    #[test]
+    fn test_compose() {
+        // 1 + 2X + 3X^2
+        let outer = UnivariatePolynomial::monomial(vec![Fr::from(1), Fr::from(2), Fr::from(3)]);
+        // 5 + X
+        let inner = UnivariatePolynomial::monomial(vec![Fr::from(5), Fr::from(1)]);
+        let composed = compose(&outer, &inner);
+        for x in [0, 1, 7] {
+            let x = Fr::from(x);
+            assert_eq!(composed.evaluate(&x), outer.evaluate(&inner.evaluate(&x)));
+        }
+        assert_eq!(composed.degree(), 2);
+        assert!(compose(&UnivariatePolynomial::zero(), &inner).is_empty());
+    }
+
+    #[test]
+    fn test_vanishing_poly() {
+        let z = vanishing_poly(4);
+        assert_eq!(z.degree(), 4);
+        assert_eq!(z.evaluate(&-Fr::one()), Fr::zero());
+        assert_eq!(z.evaluate(&Fr::from(2)), Fr::from(15));
+    }
+}
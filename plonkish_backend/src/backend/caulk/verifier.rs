@@ -0,0 +1,145 @@
+use crate::{
+    backend::baloo::util::{log_2, multi_pairing, squeeze_challenge_outside_domains},
+    pcs::{
+        univariate::{UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgVerifierParam},
+        Evaluation, PolynomialCommitmentScheme,
+    },
+    util::{
+        arithmetic::{powers, Field, Group},
+        transcript::{
+            FieldTranscript, FieldTranscriptRead, G2TranscriptRead, InMemoryTranscript,
+            Keccak256Transcript, Transcript, TranscriptRead,
+        },
+    },
+    Error,
+};
+use halo2_curves::bn256::{Bn256, Fr, G1Affine, G2Affine, Gt};
+use std::iter;
+
+type Pcs = UnivariateKzg<Bn256>;
+
+pub struct Verifier<'b> {
+    vp: &'b UnivariateKzgVerifierParam<Bn256>,
+}
+
+impl Verifier<'_> {
+    pub fn new(vp: &UnivariateKzgVerifierParam<Bn256>) -> Verifier<'_> {
+        Verifier { vp }
+    }
+
+    pub fn verify(
+        &self,
+        proof: &Vec<u8>,
+        t_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        m: usize,
+        t: usize,
+    ) -> bool {
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        self.verify_with_transcript(t_comm_1, phi_comm_1, m, t, &mut transcript)
+            .is_ok()
+    }
+
+    pub(crate) fn verify_with_transcript(
+        &self,
+        t_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        m: usize,
+        t: usize,
+        transcript: &mut (impl TranscriptRead<G1Affine, Fr> + G2TranscriptRead<G2Affine, Fr>),
+    ) -> Result<(), Error> {
+        let vp = self.vp;
+        if !m.is_power_of_two() || !t.is_power_of_two() || t < 2 {
+            return Err(Error::InvalidSnark(format!(
+                "Lookup and table sizes should be powers of two (table at least 2) but got {m} and {t}"
+            )));
+        }
+        let log_t = log_2(t);
+        transcript.common_commitments(&[t_comm_1.0, phi_comm_1.0])?;
+
+        // read π1 = ([z_I']1, [C_I']1, [u']1, [H']2, [U_1]1, ..., [U_{log t - 1}]1)
+        let z_i_comm_1 = Pcs::read_commitment(vp, transcript)?;
+        let c_i_comm_1 = Pcs::read_commitment(vp, transcript)?;
+        let u_comm_1 = Pcs::read_commitment(vp, transcript)?;
+        let h_comm_2: G2Affine = transcript.read_commitment_g2()?;
+        let unity_comms = Pcs::read_commitments(vp, log_t - 1, transcript)?;
+
+        let chi: Fr = transcript.squeeze_challenge();
+        let sigma: Fr = transcript.squeeze_challenge();
+
+        // read π2 = ([H1]1, [H2]1)
+        let h1_comm_1 = Pcs::read_commitment(vp, transcript)?;
+        let h2_comm_1 = Pcs::read_commitment(vp, transcript)?;
+
+        let alpha = squeeze_challenge_outside_domains(transcript, &[m]);
+
+        // read π3 = (u'(α), φ(α), H1(α), H2(α), z_I'(v1), C_I'(v1), U_s(α), batch opening)
+        let [u_eval, phi_eval, h1_eval, h2_eval, z_i_eval, c_i_eval]: [Fr; 6] = transcript
+            .read_field_elements(6)?
+            .try_into()
+            .unwrap();
+        let unity_evals = transcript.read_field_elements(log_t - 1)?;
+        let v1 = u_eval;
+
+        // z_I'(u'(α)) + χ(C_I'(u'(α)) - φ(α)) = H1(α) * z_V(α)
+        let z_v_at_alpha = alpha.pow([m as u64]) - Fr::one();
+        if z_i_eval + chi * (c_i_eval - phi_eval) != h1_eval * z_v_at_alpha {
+            return Err(Error::InvalidSnark(
+                "Lookup values should be the subtable at u".to_string(),
+            ));
+        }
+
+        // Σ_s σ^(s-1) (U_{s-1}(α)^2 - U_s(α)) + σ^(log t - 1) (U_{log t - 1}(α)^2 - 1) = H2(α) * z_V(α)
+        let unity_chain = iter::once(u_eval)
+            .chain(unity_evals.iter().copied())
+            .collect::<Vec<_>>();
+        let unity_sum = powers(sigma)
+            .zip(0..log_t)
+            .map(|(scalar, s)| {
+                let next = unity_chain.get(s + 1).copied().unwrap_or(Fr::one());
+                scalar * (unity_chain[s].square() - next)
+            })
+            .sum::<Fr>();
+        if unity_sum != h2_eval * z_v_at_alpha {
+            return Err(Error::InvalidSnark(
+                "u should evaluate to roots of unity of the table domain on V".to_string(),
+            ));
+        }
+
+        // e([C]1 - [C_I']1, [1]2) = e([z_I']1, [H']2)
+        let t_minus_c_i: G1Affine = (t_comm_1.0 - c_i_comm_1.0).into();
+        let subtable_check = multi_pairing::<Bn256>(
+            &[t_minus_c_i, -z_i_comm_1.0],
+            &[vp.g2(), h_comm_2],
+        );
+        if subtable_check != Gt::identity() {
+            return Err(Error::InvalidSnark(
+                "Subtable should be bound to the table".to_string(),
+            ));
+        }
+
+        // z_I' and C_I' at v1, everything else at α
+        let comms = [
+            &u_comm_1,
+            phi_comm_1,
+            &h1_comm_1,
+            &h2_comm_1,
+            &z_i_comm_1,
+            &c_i_comm_1,
+        ]
+        .into_iter()
+        .chain(unity_comms.iter())
+        .collect::<Vec<_>>();
+        let points = [alpha, v1];
+        let evals = [u_eval, phi_eval, h1_eval, h2_eval, z_i_eval, c_i_eval]
+            .into_iter()
+            .chain(unity_evals)
+            .enumerate()
+            .map(|(idx, value)| {
+                let point = if idx == 4 || idx == 5 { 1 } else { 0 };
+                Evaluation::new(idx, point, value)
+            })
+            .collect::<Vec<_>>();
+        Pcs::batch_verify(vp, comms, &points, &evals, transcript)
+    }
+}
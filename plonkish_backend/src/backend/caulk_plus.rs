@@ -0,0 +1,269 @@
+use halo2_curves::bn256::{Bn256, Fr, G1Affine};
+
+use crate::{
+    pcs::univariate::{
+        UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam, UnivariateKzgProverParam,
+        UnivariateKzgVerifierParam,
+    },
+    poly::univariate::UnivariatePolynomial,
+    Error,
+};
+
+pub mod preprocessor;
+pub mod prover;
+pub mod verifier;
+
+use preprocessor::TablePreprocessing;
+use prover::Prover;
+use verifier::Verifier;
+
+type Pcs = UnivariateKzg<Bn256>;
+
+/// Caulk+ lookup: Caulk with the roots of unity shown by `z_I | z_H`, so
+/// that after preprocessing the table the prover is independent of the table
+/// size, leaving the `O(m^2)` composition as its main cost.
+#[derive(Clone, Debug)]
+pub struct CaulkPlus;
+
+impl CaulkPlus {
+    /// Trim `param`, which may be shared with Baloo or Caulk, and preprocess
+    /// `table` for lookups of size `m`.
+    pub fn preprocess(
+        param: &UnivariateKzgParam<Bn256>,
+        table: &[Fr],
+        m: usize,
+    ) -> Result<
+        (
+            UnivariateKzgProverParam<Bn256>,
+            UnivariateKzgVerifierParam<Bn256>,
+            TablePreprocessing,
+        ),
+        Error,
+    > {
+        let (pp, vp) = preprocessor::preprocess(param, table.len(), m)?;
+        let preprocessing = TablePreprocessing::new(&pp, table)?;
+        Ok((pp, vp, preprocessing))
+    }
+
+    pub fn prove(
+        param: &UnivariateKzgParam<Bn256>,
+        pp: &UnivariateKzgProverParam<Bn256>,
+        preprocessing: &TablePreprocessing,
+        lookup: &Vec<Fr>,
+    ) -> Result<Vec<u8>, Error> {
+        Prover::new(param, pp, preprocessing).prove(lookup)
+    }
+
+    pub fn verify(
+        vp: &UnivariateKzgVerifierParam<Bn256>,
+        proof: &Vec<u8>,
+        t_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        z_h_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        m: usize,
+    ) -> bool {
+        Verifier::new(vp).verify(proof, t_comm_1, z_h_comm_1, phi_comm_1, m)
+    }
+
+    // Helper method to prepare verification data
+    pub fn prepare_verification_data(
+        pp: &UnivariateKzgProverParam<Bn256>,
+        preprocessing: &TablePreprocessing,
+        lookup: &[Fr],
+    ) -> (
+        UnivariateKzgCommitment<G1Affine>, // t_comm_1
+        UnivariateKzgCommitment<G1Affine>, // z_h_comm_1
+        UnivariateKzgCommitment<G1Affine>, // phi_comm_1
+    ) {
+        let phi_poly = UnivariatePolynomial::lagrange(lookup.to_vec()).ifft();
+        (
+            preprocessing.t_comm_1().clone(),
+            preprocessing.z_h_comm_1().clone(),
+            Pcs::commit_monomial(pp, phi_poly.coeffs()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backend::{
+            baloo::Baloo,
+            caulk::preprocessor::poly_size,
+            caulk_plus::{verifier::Verifier, CaulkPlus},
+        },
+        pcs::{
+            univariate::{UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgVerifierParam},
+            PolynomialCommitmentScheme,
+        },
+        util::transcript::{InMemoryTranscript, Keccak256Transcript, TranscriptWrite},
+        Error,
+    };
+    use halo2_curves::bn256::{Bn256, Fr, G1Affine};
+    use rand::rngs::OsRng;
+
+    type Pcs = UnivariateKzg<Bn256>;
+
+    // offsets in the proof of [W]1 of π2, after π1 = ([z_I']1, [z_I']2,
+    // [C_I']1, [u']1), and of φ(α) of π4, after [H1]1 of π3 and u'(α)
+    const W_COMM_1: usize = 64 + 128 + 64 + 64;
+    const PHI_EVAL: usize = W_COMM_1 + 64 + 64 + 32;
+
+    fn table(t: usize) -> Vec<Fr> {
+        (1..=t).map(|i| Fr::from(i as u64)).collect()
+    }
+
+    fn verify(
+        vp: &UnivariateKzgVerifierParam<Bn256>,
+        proof: &[u8],
+        t_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        z_h_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        m: usize,
+    ) -> Result<(), Error> {
+        let mut transcript = Keccak256Transcript::from_proof((), proof);
+        Verifier::new(vp).verify_with_transcript(
+            t_comm_1,
+            z_h_comm_1,
+            phi_comm_1,
+            m,
+            &mut transcript,
+        )
+    }
+
+    fn run_caulk_plus(table: Vec<Fr>, lookup: Vec<Fr>) {
+        let m = lookup.len();
+        let param = Pcs::setup(poly_size(table.len(), m), 1, OsRng).unwrap();
+        let (pp, vp, preprocessing) = CaulkPlus::preprocess(&param, &table, m).unwrap();
+        let proof = CaulkPlus::prove(&param, &pp, &preprocessing, &lookup).unwrap();
+        let (t_comm_1, z_h_comm_1, phi_comm_1) =
+            CaulkPlus::prepare_verification_data(&pp, &preprocessing, &lookup);
+        assert!(CaulkPlus::verify(
+            &vp,
+            &proof,
+            &t_comm_1,
+            &z_h_comm_1,
+            &phi_comm_1,
+            m
+        ));
+
+        // proof doesn't verify against another lookup
+        let mut other_lookup = lookup.clone();
+        other_lookup.reverse();
+        other_lookup[0] += Fr::one();
+        let (_, _, other_phi_comm_1) =
+            CaulkPlus::prepare_verification_data(&pp, &preprocessing, &other_lookup);
+        assert!(!CaulkPlus::verify(
+            &vp,
+            &proof,
+            &t_comm_1,
+            &z_h_comm_1,
+            &other_phi_comm_1,
+            m
+        ));
+    }
+
+    #[test]
+    fn test_caulk_plus() {
+        run_caulk_plus(table(8), [3, 7, 3, 4].map(Fr::from).to_vec());
+    }
+
+    #[test]
+    fn test_caulk_plus_single_value() {
+        run_caulk_plus(table(16), vec![Fr::from(16); 2]);
+    }
+
+    #[test]
+    fn test_caulk_plus_tampered() {
+        let (table, lookup) = (table(8), [3, 7, 3, 4].map(Fr::from).to_vec());
+        let m = lookup.len();
+        let param = Pcs::setup(poly_size(table.len(), m), 1, OsRng).unwrap();
+        let (pp, vp, preprocessing) = CaulkPlus::preprocess(&param, &table, m).unwrap();
+        let proof = CaulkPlus::prove(&param, &pp, &preprocessing, &lookup).unwrap();
+        let (t_comm_1, z_h_comm_1, phi_comm_1) =
+            CaulkPlus::prepare_verification_data(&pp, &preprocessing, &lookup);
+        let verify = |proof: &[u8], t_comm_1: &UnivariateKzgCommitment<G1Affine>| {
+            verify(&vp, proof, t_comm_1, &z_h_comm_1, &phi_comm_1, m)
+        };
+        assert_eq!(verify(&proof, &t_comm_1), Ok(()));
+
+        // φ(α) off by one, which fails the composition check at α first
+        let mut tampered = proof.clone();
+        tampered[PHI_EVAL + 31] ^= 1;
+        assert_eq!(
+            verify(&tampered, &t_comm_1),
+            Err(Error::InvalidSnark(
+                "Lookup values should be the subtable at u".to_string()
+            ))
+        );
+
+        // [W]1 replaced by another valid point
+        let mut transcript = Keccak256Transcript::new(());
+        TranscriptWrite::<G1Affine, Fr>::write_commitment(&mut transcript, &pp.g1()).unwrap();
+        let mut tampered = proof.clone();
+        tampered[W_COMM_1..][..64].copy_from_slice(&transcript.into_proof());
+        assert_ne!(tampered, proof);
+        assert!(verify(&tampered, &t_comm_1).is_err());
+
+        // against the commitment of another table which also has the lookup
+        let other_table = table
+            .iter()
+            .map(|value| value + Fr::one())
+            .collect::<Vec<_>>();
+        let (other_pp, _, other_preprocessing) =
+            CaulkPlus::preprocess(&param, &other_table, m).unwrap();
+        let (other_t_comm_1, other_z_h_comm_1, _) =
+            CaulkPlus::prepare_verification_data(&other_pp, &other_preprocessing, &lookup);
+        assert_ne!(other_t_comm_1, t_comm_1);
+        assert_eq!(other_z_h_comm_1, z_h_comm_1);
+        assert!(verify(&proof, &other_t_comm_1).is_err());
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_caulk_plus_prover_cost_independent_of_table_size() {
+        use crate::util::metrics;
+
+        let lookup = [3, 7, 3, 4].map(Fr::from).to_vec();
+        let m = lookup.len();
+        let costs = [16, 256].map(|t| {
+            let table = table(t);
+            let param = Pcs::setup(poly_size(t, m), 1, OsRng).unwrap();
+            let (pp, _, preprocessing) = CaulkPlus::preprocess(&param, &table, m).unwrap();
+            metrics::reset();
+            CaulkPlus::prove(&param, &pp, &preprocessing, &lookup).unwrap();
+            let counters = metrics::snapshot();
+            (counters.msm_count, counters.msm_scalars, counters.fft_count)
+        });
+        assert_eq!(costs[0], costs[1]);
+    }
+
+    #[test]
+    fn test_caulk_plus_shares_srs_with_baloo() {
+        let table = table(16);
+        let lookup = [5, 1, 16, 5].map(Fr::from).to_vec();
+        let (m, t) = (lookup.len(), table.len());
+        let param = Pcs::setup(poly_size(t, m), 1, OsRng).unwrap();
+
+        // Baloo trims the same SRS to all of its powers, for its degree checks
+        let (baloo_pp, baloo_vp) = Pcs::trim(&param, param.degree() + 1, 1).unwrap();
+        let proof = Baloo::prove(&table, &param, &baloo_pp, &lookup).unwrap();
+        let baloo_vp = Baloo::verifier_param(&param, &baloo_pp, &baloo_vp, &table, m).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&baloo_pp, &lookup);
+        assert_eq!(Baloo::verify(&baloo_vp, &proof, &phi_comm_1, m), Ok(()));
+
+        let (pp, vp, preprocessing) = CaulkPlus::preprocess(&param, &table, m).unwrap();
+        let proof = CaulkPlus::prove(&param, &pp, &preprocessing, &lookup).unwrap();
+        // the table commitments agree across backends
+        assert_eq!(preprocessing.t_comm_1(), baloo_vp.t_comm_1());
+        assert_eq!(preprocessing.z_h_comm_1(), baloo_vp.z_h_comm_1());
+        assert!(CaulkPlus::verify(
+            &vp,
+            &proof,
+            baloo_vp.t_comm_1(),
+            baloo_vp.z_h_comm_1(),
+            &phi_comm_1,
+            m
+        ));
+    }
+}
@@ -0,0 +1,149 @@
+use crate::pcs::univariate::{
+    UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam, UnivariateKzgProverParam,
+    UnivariateKzgVerifierParam,
+};
+use crate::{
+    backend::{
+        baloo::preprocessor::TablePreprocessing as BalooTablePreprocessing,
+        caulk::preprocessor::poly_size,
+        cq::{
+            preprocessor::{precompute_with_fk, TablePreprocessing as CqTablePreprocessing},
+            util::log_2,
+        },
+    },
+    pcs::PolynomialCommitmentScheme,
+    util::arithmetic::{root_of_unity, Field},
+    Error,
+};
+use halo2_curves::bn256::{Bn256, Fr, G1Affine};
+
+type Pcs = UnivariateKzg<Bn256>;
+
+/// Trim `param` for table size `t` and lookup size `m`.
+///
+/// Caulk+ doesn't set up its own SRS: any `param` large enough for
+/// [`poly_size`], e.g. the one driving Baloo or Caulk on the same table, will
+/// do, and `param` stays the one committing in G2.
+pub fn preprocess(
+    param: &UnivariateKzgParam<Bn256>,
+    t: usize,
+    m: usize,
+) -> Result<
+    (
+        UnivariateKzgProverParam<Bn256>,
+        UnivariateKzgVerifierParam<Bn256>,
+    ),
+    Error,
+> {
+    Pcs::trim(param, poly_size(t, m), 1)
+}
+
+/// Table-dependent artifacts of Caulk+, after which proving only touches the
+/// table entries used by the lookup.
+///
+/// For each `i`, both `[(C(x) - c_i) / (x - ω^i)]1` and
+/// `[z_H(x) / (x - ω^i)]1` are `N·ω^-i` times the cq cached quotient
+/// `[Q_i(x)]1` and Lagrange commitment `[L_i(x)]1`, so they are read off the
+/// cq preprocessing of the table.
+#[derive(Clone, Debug)]
+pub struct TablePreprocessing {
+    // [C(x)]1 and [z_H(x)]1, as preprocessed for Baloo
+    table: BalooTablePreprocessing,
+    // cached quotients and Lagrange commitments, as preprocessed for cq
+    cached: CqTablePreprocessing,
+}
+
+impl TablePreprocessing {
+    pub fn new(pp: &UnivariateKzgProverParam<Bn256>, table: &[Fr]) -> Result<Self, Error> {
+        let t = table.len();
+        if t < 2 {
+            return Err(Error::InvalidPcsParam(format!(
+                "Table size should be at least 2 but got {t}"
+            )));
+        }
+        let table_preprocessing = BalooTablePreprocessing::new(pp, table)?;
+        let q_t_comms = precompute_with_fk(&table.to_vec(), &mut pp.monomial_g1()[..t].to_vec());
+
+        Ok(Self {
+            table: table_preprocessing,
+            cached: CqTablePreprocessing::new(pp, table, q_t_comms),
+        })
+    }
+
+    pub fn table(&self) -> &[Fr] {
+        self.table.table()
+    }
+
+    pub fn t_comm_1(&self) -> &UnivariateKzgCommitment<G1Affine> {
+        self.table.t_comm_1()
+    }
+
+    pub fn z_h_comm_1(&self) -> &UnivariateKzgCommitment<G1Affine> {
+        self.table.z_h_comm_1()
+    }
+
+    pub fn index(&self, value: &Fr) -> Option<usize> {
+        self.cached.index(value)
+    }
+
+    /// `[(C(x) - c_i) / (x - ω^i)]1` and `[z_H(x) / (x - ω^i)]1`.
+    pub fn quotient_comms(&self, i: usize) -> (G1Affine, G1Affine) {
+        let t = self.cached.table_size();
+        let root_inv = root_of_unity::<Fr>(log_2(t)).invert().unwrap();
+        let scale = Fr::from(t as u64) * root_inv.pow([i as u64]);
+        (
+            (self.cached.q_t_comm(i) * scale).into(),
+            (self.cached.lagrange_comm(i) * scale).into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backend::caulk_plus::preprocessor::{preprocess, TablePreprocessing},
+        pcs::{univariate::UnivariateKzg, PolynomialCommitmentScheme},
+        poly::univariate::UnivariatePolynomial,
+        util::arithmetic::{root_of_unity, Field},
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+    use rand::rngs::OsRng;
+
+    type Pcs = UnivariateKzg<Bn256>;
+
+    #[test]
+    fn test_preprocess() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        // an SRS set up for a larger table drives a smaller instance
+        let param = Pcs::setup(1 << 7, 1, OsRng).unwrap();
+        let (pp, _) = preprocess(&param, table.len(), 4).unwrap();
+        assert_eq!(pp.degree(), 63);
+        assert!(preprocess(&param, table.len(), 16).is_err());
+
+        let preprocessing = TablePreprocessing::new(&pp, &table).unwrap();
+        let t_poly = UnivariatePolynomial::lagrange(table.clone()).ifft();
+        assert_eq!(
+            preprocessing.t_comm_1(),
+            &Pcs::commit_monomial(&pp, t_poly.coeffs())
+        );
+
+        let omega = root_of_unity::<Fr>(3);
+        let mut z_h = vec![Fr::zero(); 9];
+        z_h[0] = -Fr::one();
+        z_h[8] = Fr::one();
+        let z_h_poly = UnivariatePolynomial::monomial(z_h);
+        for i in [0, 3, 7] {
+            let x_minus_root =
+                UnivariatePolynomial::monomial(vec![-omega.pow([i as u64]), Fr::one()]);
+            let opening = &(t_poly.clone() + -table[i]) / &x_minus_root;
+            let vanishing_quotient = &z_h_poly / &x_minus_root;
+            assert_eq!(
+                preprocessing.quotient_comms(i),
+                (
+                    Pcs::commit_monomial(&pp, opening.coeffs()).0,
+                    Pcs::commit_monomial(&pp, vanishing_quotient.coeffs()).0
+                )
+            );
+        }
+    }
+}
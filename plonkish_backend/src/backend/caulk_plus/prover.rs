@@ -0,0 +1,245 @@
+use crate::{
+    backend::{
+        baloo::util::{
+            lagrange_interp, log_2, squeeze_challenge_outside_domains, subtable_indices_by,
+        },
+        caulk::util::{compose, vanishing_poly},
+        caulk_plus::preprocessor::TablePreprocessing,
+    },
+    pcs::{
+        univariate::{
+            UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam, UnivariateKzgProverParam,
+        },
+        Evaluation, PolynomialCommitmentScheme,
+    },
+    poly::univariate::UnivariatePolynomial,
+    util::{
+        arithmetic::{barycentric_weights, root_of_unity, variable_base_msm, Field},
+        transcript::{
+            FieldTranscript, FieldTranscriptWrite, G2TranscriptWrite, InMemoryTranscript,
+            Keccak256Transcript, Transcript, TranscriptWrite,
+        },
+    },
+    Error,
+};
+use halo2_curves::bn256::{Bn256, Fr, G1Affine, G2Affine};
+use rand::{rngs::OsRng, RngCore};
+use std::iter;
+
+type Pcs = UnivariateKzg<Bn256>;
+
+pub struct Prover<'b> {
+    param: &'b UnivariateKzgParam<Bn256>,
+    pp: &'b UnivariateKzgProverParam<Bn256>,
+    preprocessing: &'b TablePreprocessing,
+}
+
+impl Prover<'_> {
+    pub fn new<'a>(
+        param: &'a UnivariateKzgParam<Bn256>,
+        pp: &'a UnivariateKzgProverParam<Bn256>,
+        preprocessing: &'a TablePreprocessing,
+    ) -> Prover<'a> {
+        Prover {
+            param,
+            pp,
+            preprocessing,
+        }
+    }
+
+    pub fn prove(&self, lookup: &Vec<Fr>) -> Result<Vec<u8>, Error> {
+        let mut transcript = Keccak256Transcript::new(());
+        self.prove_with_transcript(lookup, OsRng, &mut transcript)?;
+        Ok(transcript.into_proof())
+    }
+
+    pub(crate) fn prove_with_transcript(
+        &self,
+        lookup: &Vec<Fr>,
+        mut rng: impl RngCore,
+        transcript: &mut (impl TranscriptWrite<G1Affine, Fr> + G2TranscriptWrite<G2Affine, Fr>),
+    ) -> Result<(), Error> {
+        let table = self.preprocessing.table();
+        let pp = self.pp;
+        let m = lookup.len();
+        let t = table.len();
+        if !m.is_power_of_two() {
+            return Err(Error::InvalidSnark(format!(
+                "Lookup size should be a power of two but got {m}"
+            )));
+        }
+
+        /************
+          Round 1
+        ************/
+        /*
+        Same subtable, blinded polynomials and composition as Caulk:
+            z_I'(X) = r1 * z_I(X)
+            C_I'(X) = C_I(X) + (r2 + r3 X + r4 X^2) * z_I(X)
+            u'(X) = u(X) + (r5 + r6 X + r7 X^2) * z_V(X)
+        z_I' is committed in both groups, the G2 one for the subtable check.
+        */
+        let (i_values, col_values) =
+            subtable_indices_by(lookup, |value| self.preprocessing.index(value)).ok_or_else(
+                || Error::InvalidSnark("Lookup values should be in table".to_string()),
+            )?;
+        let t_root_of_unity = root_of_unity::<Fr>(log_2(t));
+        let h_i = i_values
+            .iter()
+            .map(|&i| t_root_of_unity.pow([i as u64]))
+            .collect::<Vec<_>>();
+        let c_i_values = i_values.iter().map(|&i| table[i]).collect::<Vec<_>>();
+
+        // φ(X)
+        let phi_poly = UnivariatePolynomial::lagrange(lookup.clone()).ifft();
+        // bind the instance ([C]1, [φ]1)
+        let phi_comm_1 = Pcs::commit_monomial(pp, phi_poly.coeffs());
+        transcript.common_commitments(&[self.preprocessing.t_comm_1().0, phi_comm_1.0])?;
+        // z_I(X), C_I(X), u(X)
+        let z_i_poly = UnivariatePolynomial::vanishing(&h_i, Fr::one());
+        let c_i_poly = lagrange_interp(&h_i, &c_i_values);
+        let u_values = col_values.iter().map(|&col| h_i[col]).collect::<Vec<_>>();
+        let u_poly = UnivariatePolynomial::lagrange(u_values).ifft();
+        // z_V(X) = X^m - 1
+        let z_v_poly = vanishing_poly(m);
+
+        let blinders = iter::repeat_with(|| Fr::random(&mut rng))
+            .take(7)
+            .collect::<Vec<_>>();
+        let c_i_blinder = UnivariatePolynomial::monomial(blinders[1..4].to_vec());
+        let u_blinder = UnivariatePolynomial::monomial(blinders[4..7].to_vec());
+        let z_i_poly_blinded = &z_i_poly * blinders[0];
+        let c_i_poly_blinded = &c_i_poly + c_i_blinder.poly_mul(z_i_poly);
+        let u_poly_blinded = &u_poly + u_blinder.poly_mul(z_v_poly.clone());
+
+        // π1 = ([z_I']1, [z_I']2, [C_I']1, [u']1)
+        let z_i_comm_1 = Pcs::commit_and_write(pp, &z_i_poly_blinded, transcript)?;
+        let z_i_comm_2 = Pcs::commit_monomial_g2(self.param, z_i_poly_blinded.coeffs());
+        transcript.write_commitment_g2(&z_i_comm_2.to_affine())?;
+        let c_i_comm_1 = Pcs::commit_and_write(pp, &c_i_poly_blinded, transcript)?;
+        let u_comm_1 = Pcs::commit_and_write(pp, &u_poly_blinded, transcript)?;
+
+        let chi: Fr = transcript.squeeze_challenge();
+
+        /************
+          Round 2
+        ************/
+        /*
+        Caulk+ replaces the unity chain of Caulk by z_I | z_H, which together
+        with C(X) - C_I(X) = z_I(X) * H(X) is checked in one pairing:
+            C(X) - C_I'(X) + χ z_H(X) = z_I'(X) * W(X),
+            W(X) = (H(X) + χ z_H(X) / z_I(X) - (r2 + r3 X + r4 X^2)) / r1.
+        With λ_i the barycentric weights of H_I,
+            H(X) = Σ_{i ∈ I} λ_i (C(X) - c_i) / (X - ω^i),
+            z_H(X) / z_I(X) = Σ_{i ∈ I} λ_i z_H(X) / (X - ω^i),
+        so [W]1 is an MSM over the preprocessed quotients of size |I| ≤ m,
+        independent of the table size.
+        */
+        let weights = barycentric_weights(&h_i);
+        let (scalars, bases): (Vec<_>, Vec<_>) = i_values
+            .iter()
+            .zip(weights.iter())
+            .flat_map(|(&i, weight)| {
+                let (opening_comm, vanishing_quotient_comm) = self.preprocessing.quotient_comms(i);
+                [
+                    (*weight, opening_comm),
+                    (*weight * chi, vanishing_quotient_comm),
+                ]
+            })
+            .chain(
+                blinders[1..4]
+                    .iter()
+                    .zip(pp.monomial_g1())
+                    .map(|(blinder, base)| (-*blinder, *base)),
+            )
+            .unzip();
+        let r1_inv = blinders[0].invert().unwrap();
+        let w_comm_1: G1Affine = (variable_base_msm(&scalars, &bases) * r1_inv).into();
+        // π2 = [W]1
+        transcript.write_commitment(&w_comm_1)?;
+
+        let chi_2: Fr = transcript.squeeze_challenge();
+
+        // H1(X) = (z_I'(u'(X)) + χ2 (C_I'(u'(X)) - φ(X))) / z_V(X)
+        let h1_poly = &(&compose(&z_i_poly_blinded, &u_poly_blinded)
+            + &(&(&compose(&c_i_poly_blinded, &u_poly_blinded) - &phi_poly) * chi_2))
+            / &z_v_poly;
+
+        // π3 = [H1]1
+        let h1_comm_1 = Pcs::commit_and_write(pp, &h1_poly, transcript)?;
+
+        // α must not be in V for z_V(α) to be invertible
+        let alpha = squeeze_challenge_outside_domains(transcript, &[m]);
+
+        /************
+          Round 3
+        ************/
+        // v1 = u'(α), the point at which z_I' and C_I' are opened
+        let v1 = u_poly_blinded.evaluate(&alpha);
+        let polys = [
+            &u_poly_blinded,
+            &phi_poly,
+            &h1_poly,
+            &z_i_poly_blinded,
+            &c_i_poly_blinded,
+        ];
+        let comms: [&UnivariateKzgCommitment<G1Affine>; 5] =
+            [&u_comm_1, &phi_comm_1, &h1_comm_1, &z_i_comm_1, &c_i_comm_1];
+        let points = [alpha, v1];
+        // z_I' and C_I' at v1, everything else at α
+        let evals = polys
+            .iter()
+            .enumerate()
+            .map(|(idx, poly)| {
+                let point = if idx >= 3 { 1 } else { 0 };
+                Evaluation::new(idx, point, poly.evaluate(&points[point]))
+            })
+            .collect::<Vec<_>>();
+
+        // π4 = (u'(α), φ(α), H1(α), z_I'(v1), C_I'(v1), batch opening)
+        for eval in evals.iter() {
+            transcript.write_field_element(eval.value())?;
+        }
+        Pcs::batch_open(pp, polys, comms, &points, &evals, transcript)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::caulk_plus::{
+        preprocessor::{preprocess, TablePreprocessing},
+        prover::Prover,
+    };
+    use crate::{
+        backend::caulk::preprocessor::poly_size,
+        pcs::{univariate::UnivariateKzg, PolynomialCommitmentScheme},
+        Error,
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+    use rand::rngs::OsRng;
+
+    type Pcs = UnivariateKzg<Bn256>;
+
+    #[test]
+    fn test_prove() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = vec![Fr::from(4), Fr::from(3), Fr::from(4), Fr::from(8)];
+        let param = Pcs::setup(poly_size(table.len(), lookup.len()), 1, OsRng).unwrap();
+        let (pp, _) = preprocess(&param, table.len(), lookup.len()).unwrap();
+        let preprocessing = TablePreprocessing::new(&pp, &table).unwrap();
+        let prover = Prover::new(&param, &pp, &preprocessing);
+        let proof = prover.prove(&lookup).unwrap();
+        // blinded, so proofs of the same lookup differ
+        assert_ne!(proof, prover.prove(&lookup).unwrap());
+
+        let lookup = vec![Fr::from(4), Fr::from(9), Fr::from(4), Fr::from(8)];
+        assert_eq!(
+            prover.prove(&lookup),
+            Err(Error::InvalidSnark(
+                "Lookup values should be in table".to_string()
+            ))
+        );
+    }
+}
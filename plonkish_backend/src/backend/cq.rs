@@ -1,6 +1,7 @@
-use halo2_curves::bn256::{Bn256, Fr, G2Affine, G1};
+use halo2_curves::bn256::{Bn256, Fr, G1Affine, G2Affine, G1};
 
 use crate::{
+    backend::lookup,
     pcs::{
         univariate::{
             UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam, UnivariateKzgProverParam,
@@ -11,7 +12,7 @@ use crate::{
     poly::Polynomial,
     util::{
         arithmetic::Field,
-        transcript::InMemoryTranscript,
+        transcript::{InMemoryTranscript, Keccak256Transcript, TranscriptRead},
     },
     Error,
 };
@@ -20,12 +21,14 @@ pub mod preprocessor;
 pub mod prover;
 pub mod util;
 pub mod verifier;
+use preprocessor::TablePreprocessing;
 use prover::Prover;
 use verifier::Verifier;
 type Pcs = UnivariateKzg<Bn256>;
 
 use rand::seq::SliceRandom;
 use rand::thread_rng;
+use rand::{rngs::OsRng, RngCore};
 use std::cmp::max;
 use std::collections::HashSet;
 
@@ -41,6 +44,7 @@ pub fn test_cq_by_input(table: Vec<Fr>, lookup: Vec<Fr>) -> Vec<String> {
     // 1. setup and preprocess
     let start = Instant::now();
     let (param, pp, vp, q_t_comm_poly_coeffs) = preprocessor::preprocess(t, m, &table).unwrap();
+    let preprocessing = TablePreprocessing::new(&pp, &table, q_t_comm_poly_coeffs);
     assert_eq!(poly_size, 2_usize.pow(pp.k() as u32));
     let duration1 = start.elapsed();
     timings.push(format!(
@@ -55,9 +59,10 @@ pub fn test_cq_by_input(table: Vec<Fr>, lookup: Vec<Fr>) -> Vec<String> {
     // 2. generate proof
     let start = Instant::now();
     let prover = Prover::new(&table, &param, &pp);
-    let proof = prover.prove(&lookup, &q_t_comm_poly_coeffs);
+    let proof = prover.prove_with_preprocessing(&lookup, &preprocessing);
     let duration2 = start.elapsed();
     timings.push(format!("k={k}, prove time: {}ms", duration2.as_millis()));
+    timings.push(format!("k={k}, proof size: {}bytes", proof.len()));
     println!("------------prove: {}ms------------", duration2.as_millis());
 
     let scalar_0 = Fr::from(0_u64);
@@ -163,11 +168,18 @@ pub struct CqProverParam {
     pp: UnivariateKzgProverParam<Bn256>,
     table: Vec<Fr>,
     q_t_comm_poly_coeffs: Vec<G1>,
+    preprocessing: TablePreprocessing,
 }
 
 #[derive(Clone, Debug)]
 pub struct CqVerifierParam {
     vp: UnivariateKzgVerifierParam<Bn256>,
+    // of `Cq::prepare_verification_data`
+    t_comm_2: UnivariateKzgCommitment<G2Affine>,
+    z_v_comm_2: UnivariateKzgCommitment<G2Affine>,
+    x_exponent_poly_comm_2: UnivariateKzgCommitment<G2Affine>,
+    m: usize,
+    t: usize,
 }
 
 #[derive(Clone, Debug)]
@@ -227,20 +239,40 @@ impl Cq {
         Error,
     > {
         let m = info.lookup.len();
-        let t = info.table.len();
-
-        let (param, pp, vp, q_t_comm_poly_coeffs) = preprocessor::preprocess(t, m, &info.table)?;
-
-        Ok((
-            CqProverParam {
-                param: param.clone(),
-                pp,
-                table: info.table.clone(),
-                q_t_comm_poly_coeffs: q_t_comm_poly_coeffs.clone(),
-            },
-            CqVerifierParam { vp },
+        let param = Pcs::setup(preprocessor::poly_size(info.table.len(), m), 1, OsRng)?;
+        let (pp, vp) = Self::preprocess_with_srs(param, &info.table, m)?;
+        let q_t_comm_poly_coeffs = pp.q_t_comm_poly_coeffs.clone();
+        Ok((pp, vp, q_t_comm_poly_coeffs))
+    }
+
+    /// Preprocess `table` for lookups of size `m` with the SRS given, e.g.
+    /// shared with other tables.
+    pub fn preprocess_with_srs(
+        param: UnivariateKzgParam<Bn256>,
+        table: &[Fr],
+        m: usize,
+    ) -> Result<(CqProverParam, CqVerifierParam), Error> {
+        let (table, t) = (table.to_vec(), table.len());
+        let (pp, vp, q_t_comm_poly_coeffs) =
+            preprocessor::preprocess_with_param(&param, m, &table)?;
+        let (t_comm_2, z_v_comm_2, x_exponent_poly_comm_2) =
+            Self::prepare_verification_data(&param, &table, m, t);
+        let pp = CqProverParam {
+            preprocessing: TablePreprocessing::new(&pp, &table, q_t_comm_poly_coeffs.clone()),
+            param,
+            pp,
+            table,
             q_t_comm_poly_coeffs,
-        ))
+        };
+        let vp = CqVerifierParam {
+            vp,
+            t_comm_2,
+            z_v_comm_2,
+            x_exponent_poly_comm_2,
+            m,
+            t,
+        };
+        Ok((pp, vp))
     }
 
     pub fn prove(
@@ -256,7 +288,7 @@ impl Cq {
 
     pub fn prove_with_param(pp: &CqProverParam, lookup: &Vec<Fr>) -> Vec<u8> {
         let prover = Prover::new(&pp.table, &pp.param, &pp.pp);
-        prover.prove(lookup, &pp.q_t_comm_poly_coeffs)
+        prover.prove_with_preprocessing(lookup, &pp.preprocessing)
     }
 
     pub fn verify(
@@ -341,12 +373,14 @@ impl Cq {
         // 1. Setup using the original API
         let start = std::time::Instant::now();
         let (param, pp, vp, q_t_comm_poly_coeffs) = Cq::preprocess(t, m, &table).unwrap();
+        let preprocessing = TablePreprocessing::new(&pp, &table, q_t_comm_poly_coeffs);
         let duration1 = start.elapsed();
         timings.push(format!("Setup and preprocess: {}ms", duration1.as_millis()));
 
         // 2. Generate proof
         let start = std::time::Instant::now();
-        let proof = Cq::prove(&table, &param, &pp, &lookup, &q_t_comm_poly_coeffs);
+        let proof =
+            Prover::new(&table, &param, &pp).prove_with_preprocessing(&lookup, &preprocessing);
         let duration2 = start.elapsed();
         timings.push(format!("Prove: {}ms", duration2.as_millis()));
 
@@ -377,6 +411,68 @@ impl Cq {
     }
 }
 
+impl lookup::LookupScheme<Fr> for Cq {
+    type Param = UnivariateKzgParam<Bn256>;
+    type ProverParam = CqProverParam;
+    type VerifierParam = CqVerifierParam;
+    /// `[f(x)]1` of the lookup, which the proof carries.
+    type LookupCommitment = UnivariateKzgCommitment<G1Affine>;
+    type Proof = Vec<u8>;
+
+    fn setup(t: usize, m: usize, rng: impl RngCore) -> Result<Self::Param, Error> {
+        Pcs::setup(preprocessor::poly_size(t, m), 1, rng)
+    }
+
+    fn preprocess(
+        param: &Self::Param,
+        table: &[Fr],
+        m: usize,
+    ) -> Result<(CqProverParam, CqVerifierParam), Error> {
+        Self::preprocess_with_srs(param.clone(), table, m)
+    }
+
+    fn commit_lookup(pp: &CqProverParam, lookup: &[Fr]) -> Result<Self::LookupCommitment, Error> {
+        let f_poly = UnivariatePolynomial::lagrange(lookup.to_vec()).ifft();
+        Pcs::commit(&pp.pp, &f_poly)
+    }
+
+    fn prove(pp: &CqProverParam, lookup: &[Fr]) -> Result<Vec<u8>, Error> {
+        Ok(Self::prove_with_param(pp, &lookup.to_vec()))
+    }
+
+    fn proof_to_bytes(proof: &Vec<u8>) -> Vec<u8> {
+        proof.clone()
+    }
+
+    fn verify(
+        vp: &CqVerifierParam,
+        f_comm_1: &Self::LookupCommitment,
+        proof: &Vec<u8>,
+    ) -> Result<(), Error> {
+        // [f(x)]1 follows [m(x)]1, [A(x)]1, [Q_A(x)]1 and [B_0(x)]1
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        let comms: Vec<G1Affine> = transcript.read_commitments(5)?;
+        if comms[4] != f_comm_1.0 {
+            return Err(Error::InvalidSnark(
+                "Proof isn't of the lookup commitment".to_string(),
+            ));
+        }
+        let verified = Self::verify_with_param(
+            vp,
+            proof,
+            &vp.t_comm_2,
+            &vp.z_v_comm_2,
+            &vp.x_exponent_poly_comm_2,
+            vp.m,
+            vp.t,
+        );
+        if !verified {
+            return Err(Error::InvalidSnark("Invalid cq proof".to_string()));
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -10,7 +10,7 @@ use crate::{
 use halo2_curves::bn256::{Bn256, Fq, Fr, G1Affine, G1};
 use rand::rngs::OsRng;
 use std::time::Instant;
-use std::{cmp::max, iter};
+use std::{cmp::max, collections::HashMap, iter};
 
 type Pcs = UnivariateKzg<Bn256>;
 type Scalar = Fr;
@@ -29,14 +29,45 @@ pub fn preprocess(
     Error,
 > {
     let mut rng = OsRng;
-    let poly_size = max(t.next_power_of_two() * 2, m.next_power_of_two() * 2);
 
     // let start = Instant::now();
-    let param = Pcs::setup(poly_size, 1, &mut rng).unwrap();
+    let param = Pcs::setup(poly_size(t, m), 1, &mut rng).unwrap();
     // let duration1 = start.elapsed();
     // println!("\n ------------Setup: {}ms----------- \n",duration1.as_millis());
 
-    let (pp, vp) = Pcs::trim(&param, poly_size, 1).unwrap();
+    let (pp, vp, q_t_comm_poly_coeffs) = preprocess_with_param(&param, m, table)?;
+    Ok((param, pp, vp, q_t_comm_poly_coeffs))
+}
+
+/// Size of the SRS for tables of size `t` and lookups of size `m`.
+pub fn poly_size(t: usize, m: usize) -> usize {
+    max(t.next_power_of_two() * 2, m.next_power_of_two() * 2)
+}
+
+/// [`preprocess`] with the SRS given, e.g. shared with other tables.
+pub fn preprocess_with_param(
+    param: &UnivariateKzgParam<Bn256>,
+    m: usize,
+    table: &Vec<Fr>,
+) -> Result<
+    (
+        UnivariateKzgProverParam<Bn256>,
+        UnivariateKzgVerifierParam<Bn256>,
+        Vec<G1>,
+    ),
+    Error,
+> {
+    // the degree check of `B_0` fails to bound it if the prover holds powers
+    // beyond `param`, e.g. the rest of a ceremony
+    if param.srs_degree() > param.degree() {
+        return Err(Error::InvalidPcsParam(format!(
+            "Degree checks need all powers of the SRS of degree {} but got {}",
+            param.srs_degree(),
+            param.degree()
+        )));
+    }
+    let t = table.len();
+    let (pp, vp) = Pcs::trim(param, poly_size(t, m), 1)?;
     let mut powers_of_x = pp.monomial_g1()[..t].to_vec();
 
     // let start = Instant::now();
@@ -44,7 +75,74 @@ pub fn preprocess(
     // let duration2 = start.elapsed();
     // println!("\n ------------precompute_with_fk: {}ms----------- \n",duration2.as_millis());
 
-    Ok((param, pp, vp, q_t_comm_poly_coeffs))
+    Ok((pp, vp, q_t_comm_poly_coeffs))
+}
+
+/// Table-dependent artifacts of cq, after which proving only touches the
+/// table entries used by the lookup.
+#[derive(Clone, Debug)]
+pub struct TablePreprocessing {
+    // index of each table value, the first occurrence if repeated
+    index: HashMap<Fr, usize>,
+    // [L_i(x)]1, Lagrange polynomials over the table domain
+    lagrange_comms: Vec<G1Affine>,
+    // [(L_i(x) - L_i(0)) / x]1
+    lagrange_0_comms: Vec<G1Affine>,
+    // [Q_i(x)]1, cached quotients from `precompute_with_fk`
+    q_t_comms: Vec<G1>,
+}
+
+impl TablePreprocessing {
+    pub fn new(pp: &UnivariateKzgProverParam<Bn256>, table: &[Fr], q_t_comms: Vec<G1>) -> Self {
+        let t = table.len();
+        assert_eq!(q_t_comms.len(), t, "cached quotients should match table");
+
+        let mut index = HashMap::new();
+        for (i, value) in table.iter().enumerate().rev() {
+            index.insert(*value, i);
+        }
+
+        // [L_i(x)]1 = ifft([1]1, [x]1, ..., [x^(N-1)]1)_i
+        let lagrange_comms = ec_ifft(&mut pp.monomial_g1()[..t].to_vec());
+        // (L_i(X) - 1/N) / X = ω^-i * L_i(X) - X^(N-1) / N
+        let root_inv = root_of_unity::<Fr>(log_2(t)).invert().unwrap();
+        let t_inv = Fr::from(t as u64).invert().unwrap();
+        let x_t_minus_1 = pp.monomial_g1()[t - 1];
+        let lagrange_0_comms = lagrange_comms
+            .iter()
+            .enumerate()
+            .map(|(i, lagrange)| {
+                (lagrange * root_inv.pow([i as u64]) - x_t_minus_1 * t_inv).into()
+            })
+            .collect();
+
+        Self {
+            index,
+            lagrange_comms,
+            lagrange_0_comms,
+            q_t_comms,
+        }
+    }
+
+    pub fn index(&self, value: &Fr) -> Option<usize> {
+        self.index.get(value).copied()
+    }
+
+    pub fn lagrange_comm(&self, i: usize) -> G1Affine {
+        self.lagrange_comms[i]
+    }
+
+    pub fn lagrange_0_comm(&self, i: usize) -> G1Affine {
+        self.lagrange_0_comms[i]
+    }
+
+    pub fn q_t_comm(&self, i: usize) -> G1 {
+        self.q_t_comms[i]
+    }
+
+    pub fn table_size(&self) -> usize {
+        self.lagrange_comms.len()
+    }
 }
 
 fn fk(coeffs: &mut Vec<Fr>, powers_of_x: &mut Vec<G1Affine>) -> Vec<G1> {
@@ -168,7 +266,10 @@ pub fn precompute_with_fk(table: &Vec<Fr>, powers_of_x: &mut Vec<G1Affine>) -> V
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::backend::cq::preprocessor::preprocess;
+    use crate::{
+        backend::cq::preprocessor::preprocess, poly::univariate::UnivariatePolynomial,
+        poly::Polynomial,
+    };
 
     #[test]
     fn test_preprocess() {
@@ -179,4 +280,34 @@ mod tests {
         println!("vp: {:?}", vp);
         println!("q_t_comm_poly_coeffs: {:?}", q_t_comm_poly_coeffs);
     }
+
+    #[test]
+    fn test_table_preprocessing() {
+        let table = vec![Fr::from(1), Fr::from(2), Fr::from(3), Fr::from(4)];
+        let t = table.len();
+        let (_, pp, _, q_t_comm_poly_coeffs) = preprocess(t, 4, &table).unwrap();
+        let preprocessing = TablePreprocessing::new(&pp, &table, q_t_comm_poly_coeffs);
+
+        let root = root_of_unity::<Fr>(log_2(t));
+        let x_poly = UnivariatePolynomial::monomial(vec![Fr::zero(), Fr::one()]);
+        for i in 0..t {
+            // L_i(X) from the i-th unit vector
+            let mut values = vec![Fr::zero(); t];
+            values[i] = Fr::one();
+            let lagrange = UnivariatePolynomial::lagrange(values).ifft();
+            assert_eq!(lagrange.evaluate(&root.pow([i as u64])), Fr::one());
+            let lagrange_0 = &(lagrange.clone() + lagrange.evaluate(&Fr::zero()).neg()) / &x_poly;
+
+            assert_eq!(
+                preprocessing.lagrange_comm(i),
+                Pcs::commit_monomial(&pp, lagrange.coeffs()).to_affine()
+            );
+            assert_eq!(
+                preprocessing.lagrange_0_comm(i),
+                Pcs::commit_monomial(&pp, lagrange_0.coeffs()).to_affine()
+            );
+            assert_eq!(preprocessing.index(&table[i]), Some(i));
+        }
+        assert_eq!(preprocessing.index(&Fr::from(5)), None);
+    }
 }
@@ -1,4 +1,5 @@
 use crate::{
+    backend::cq::preprocessor::TablePreprocessing,
     pcs::{
         univariate::{
             UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam, UnivariateKzgProverParam,
@@ -7,15 +8,15 @@ use crate::{
     },
     poly::univariate::UnivariatePolynomial,
     util::{
-        arithmetic::Field,
+        arithmetic::{variable_base_msm, Field, Group},
         transcript::{
             FieldTranscript, FieldTranscriptWrite, InMemoryTranscript, Keccak256Transcript,
             TranscriptWrite,
         },
     },
 };
-use halo2_curves::bn256::{Bn256, Fq, Fr, G1Affine, G1};
-use std::{collections::hash_map::Entry, collections::HashMap};
+use halo2_curves::bn256::{Bn256, Fr, G1Affine, G1};
+use std::collections::BTreeMap;
 
 type Pcs = UnivariateKzg<Bn256>;
 type Scalar = Fr;
@@ -43,9 +44,21 @@ impl Prover<'_> {
     }
 
     pub fn prove(&self, lookup: &Vec<Fr>, q_t_comm: &Vec<G1>) -> Vec<u8> {
-        let table = self.table.clone();
-        let param = self.param.clone();
-        let pp = self.pp.clone();
+        let preprocessing = TablePreprocessing::new(self.pp, self.table, q_t_comm.clone());
+        self.prove_with_preprocessing(lookup, &preprocessing)
+    }
+
+    /// Prove with the table preprocessed once, in time independent of the
+    /// table size.
+    pub fn prove_with_preprocessing(
+        &self,
+        lookup: &Vec<Fr>,
+        preprocessing: &TablePreprocessing,
+    ) -> Vec<u8> {
+        assert_eq!(preprocessing.table_size(), self.table.len());
+        let table = self.table;
+        let param = self.param;
+        let pp = self.pp;
         let d = self.d;
 
         let m = lookup.len();
@@ -63,31 +76,28 @@ impl Prover<'_> {
 
         // initialize transcript
         let mut transcript = Keccak256Transcript::new(());
-        // compute m
-        let mut duplicates: HashMap<Fr, Fr> = HashMap::new();
-        for &value in lookup {
-            match duplicates.entry(value) {
-                Entry::Occupied(mut entry) => *entry.get_mut() += Fr::from(1),
-                Entry::Vacant(entry) => {
-                    entry.insert(Fr::from(1));
-                }
+        // compute m sparsely, as (table index, multiplicity) over the table
+        // entries used by the lookup, in ascending table index. Lookup values
+        // missing in table are left out, so the proof fails to verify.
+        let mut multiplicities: BTreeMap<usize, Fr> = BTreeMap::new();
+        for value in lookup {
+            if let Some(i) = preprocessing.index(value) {
+                *multiplicities.entry(i).or_insert(Fr::from(0)) += Fr::from(1);
             }
         }
+        let (indices, m_values): (Vec<usize>, Vec<Fr>) = multiplicities.into_iter().unzip();
 
-        // get m_values
-        let m_values: Vec<Fr> = table
+        // [m(x)]1 = Σ_i m_i * [L_i(x)]1
+        let lagrange_comms: Vec<G1Affine> = indices
             .iter()
-            .map(|&val| *duplicates.get(&val).unwrap_or(&Fr::from(0)))
+            .map(|&i| preprocessing.lagrange_comm(i))
             .collect();
-        // println!("m_values: {:?}", m_values);
-
-        // m(X)
-        let m_poly = UnivariatePolynomial::lagrange(m_values.clone()).ifft();
-        // println!("m_poly: {:?}", m_poly);
-        // [m(x)]1
-        let m_comm_1: UnivariateKzgCommitment<G1Affine> =
-            Pcs::commit_and_write(&pp, &m_poly, &mut transcript).unwrap();
-        // println!("m_comm_1: {:?}", m_comm_1);
+        let m_comm_1 = UnivariateKzgCommitment::<G1Affine>(
+            variable_base_msm(&m_values, &lagrange_comms).into(),
+        );
+        transcript
+            .write_commitment(&m_comm_1.clone().to_affine())
+            .unwrap();
 
         let beta: Fr = transcript.squeeze_challenge();
 
@@ -102,45 +112,27 @@ impl Prover<'_> {
         */
 
         // 1. commit A(X): Step 1-3 in the paper
-        // 1.a. compute A_i values
+        // 1.a. compute A_i values, which are 0 outside of the used indices
         //  a_i = m_i / (β + t_i)
         let mut a_values: Vec<Fr> = Vec::new();
-        for (i, t_i) in table.iter().enumerate() {
-            let a_i = m_values[i] * ((&beta + *t_i).invert().unwrap());
+        for (&i, m_i) in indices.iter().zip(m_values.iter()) {
+            let a_i = m_i * ((&beta + table[i]).invert().unwrap());
             a_values.push(a_i);
             // sanity check
-            assert_eq!(a_i * (&beta + *t_i), m_values[i], "A: not equal");
+            assert_eq!(a_i * (&beta + table[i]), *m_i, "A: not equal");
         }
 
-        // println!("a_values: {:?}", a_values);
-
-        // println!("A_values: {:?}", a_values);
-
-        // 1.b. compute A(X) from A_i values
-        let a_poly = UnivariatePolynomial::lagrange(a_values.clone()).ifft();
-
-        // 1.c. commit A(X)
-        let a_comm_1: UnivariateKzgCommitment<G1Affine> =
-            Pcs::commit_and_write(&pp, &a_poly, &mut transcript).unwrap();
-
-        // 2. commit Q_A(X): Step 4 in the paper
-        // 2.a. T(X) in lagrange form
-        let t_poly = UnivariatePolynomial::lagrange(table.clone()).ifft();
+        // 1.b. commit A(X) = Σ_i A_i * L_i(X)
+        let a_comm_1 = UnivariateKzgCommitment::<G1Affine>(
+            variable_base_msm(&a_values, &lagrange_comms).into(),
+        );
+        transcript
+            .write_commitment(&a_comm_1.clone().to_affine())
+            .unwrap();
 
-        // 2.b. vanishing polynomial: X^N - 1, N = group_order_N - 1
-        // group_order_N = len(table) = t
-        // X^N - 1 : [-1, 0, ... , 0, 1] with t-1 0s in between
         let scalar_0 = Fr::from(0);
         let scalar_1 = Fr::from(1);
 
-        let z_v_poly_coeffs = vec![scalar_1.neg()]
-            .into_iter()
-            .chain(vec![scalar_0; t - 1])
-            .chain(vec![scalar_1])
-            .collect();
-        let z_v_poly = UnivariatePolynomial::monomial(z_v_poly_coeffs);
-        // println!("z_h_poly: {:?}", z_h_poly);
-
         // vanishing polynomial: X^n - 1, n = group_order_n - 1
         // group_order_n = len(lookup) = m
         // X^n - 1 : [-1, 0, ... , 0, 1] with m-1 0s in between
@@ -152,40 +144,15 @@ impl Prover<'_> {
         let z_h_poly = UnivariatePolynomial::monomial(z_h_poly_coeffs);
         // println!("z_h_poly: {:?}", z_h_poly);
 
-        // commit Q_A(X), there are two methods:
-
-        // // ===============Method 1=====================
-        // // Method 1: use polynomial to commit Q_A(X)
-        // // A(X) * (T(X) + beta)
-        // let a_t_poly = &a_poly.poly_mul(t_poly.clone() + beta);
-        // // - m(X)
-        // let neg_m_poly = &m_poly * (Fr::from(1).neg());
-
-        // // Q_A(X), R(X) = (A(X) * (T(X) + beta) - m(X)) / z_v(X)
-        // let add_poly = &(a_t_poly + neg_m_poly);
-        // let (q_a_poly, r_poly) = add_poly.div_rem(&z_v_poly);
-        // assert_eq!(r_poly.evaluate(&scalar_0), scalar_0);
-
-        // // commit Q_A(X)
-        // let q_a_comm_1: UnivariateKzgCommitment<G1Affine> = Pcs::commit_and_write(&pp, &q_a_poly, &mut transcript).unwrap();
-        // println!("q_a_comm_1: {:?}", q_a_comm_1.clone().to_affine());
-
-        // ===============Method 2====================
-        // Here we use method 2: commit Q_A(X) with FK
-        // implement FK algorithm to compute q_a_comm_1
-
-        let mut q_a_comm_1_fk = G1Affine {
-            x: Fq::zero(),
-            y: Fq::zero(),
-        };
-        for (i, &a_val) in a_values.iter().enumerate() {
-            if a_val == scalar_0 {
-                continue;
-            }
-
-            let a_times_q_t_comm = q_t_comm[i] * a_val;
-            q_a_comm_1_fk = (q_a_comm_1_fk + a_times_q_t_comm).into();
-        }
+        // 2. commit Q_A(X): Step 4 in the paper, with the cached quotients
+        // [Q_A(x)]1 = Σ_i A_i * [Q_i(x)]1
+        let q_a_comm_1_fk: G1Affine = indices
+            .iter()
+            .zip(a_values.iter())
+            .fold(G1::identity(), |acc, (&i, &a_val)| {
+                acc + preprocessing.q_t_comm(i) * a_val
+            })
+            .into();
 
         transcript.write_commitment(&q_a_comm_1_fk);
 
@@ -284,7 +251,8 @@ impl Prover<'_> {
         let f_at_gamma = f_poly.evaluate(&gamma);
         transcript.write_field_element(&f_at_gamma).unwrap();
         // 3. compute a_at_0: Step 3 in the paper
-        let a_at_0 = a_poly.evaluate(&scalar_0);
+        // A(0) = Σ_i A_i * L_i(0) = Σ_i A_i / N
+        let a_at_0 = a_values.iter().sum::<Fr>() * Fr::from(t as u64).invert().unwrap();
         transcript.write_field_element(&a_at_0).unwrap();
         // 4. compute b_at_0: Step 4 in the paper
         // b0 := (N·a0)/n
@@ -318,12 +286,17 @@ impl Prover<'_> {
             Pcs::commit_and_write(&pp, &h_poly, &mut transcript).unwrap();
         // 3.7 commit A_0(X): Step 7 in the paper
         // (a) compute a_0_comm_1
-        let (a_0_poly, r_a_0_poly) = (a_poly + a_at_0.neg()).div_rem(&x_poly);
-        // println!("a_0_poly: {:?}", a_0_poly);
-        // println!("r_a_0_poly: {:?}", r_a_0_poly);
-        assert_eq!(r_a_0_poly.evaluate(&scalar_0), scalar_0);
-        let a_0_comm_1: UnivariateKzgCommitment<G1Affine> =
-            Pcs::commit_and_write(&pp, &a_0_poly, &mut transcript).unwrap();
+        // [A_0(x)]1 = Σ_i A_i * [(L_i(x) - L_i(0)) / x]1
+        let lagrange_0_comms: Vec<G1Affine> = indices
+            .iter()
+            .map(|&i| preprocessing.lagrange_0_comm(i))
+            .collect();
+        let a_0_comm_1 = UnivariateKzgCommitment::<G1Affine>(
+            variable_base_msm(&a_values, &lagrange_0_comms).into(),
+        );
+        transcript
+            .write_commitment(&a_0_comm_1.clone().to_affine())
+            .unwrap();
         // println!("a_0_comm_1: {:?}", a_0_comm_1);
 
         // π3 = (b_0_at_gamma, f_at_gamma, a_at_0, pi_gamma, a_0_comm_1)
@@ -348,7 +321,7 @@ impl Prover<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::backend::cq::preprocessor::preprocess;
+    use crate::backend::cq::{preprocessor::preprocess, Cq};
     use halo2_curves::bn256::Fr;
 
     #[test]
@@ -364,4 +337,35 @@ mod tests {
         let prover = Prover::new(&table, &param, &pp);
         let proof = prover.prove(&lookup, &q_t_comm_poly_coeffs);
     }
+
+    #[test]
+    fn test_prove_with_preprocessing() {
+        let table = (1..=16).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let t = table.len();
+        let (param, pp, vp, q_t_comm_poly_coeffs) = preprocess(t, 4, &table).unwrap();
+        let preprocessing = TablePreprocessing::new(&pp, &table, q_t_comm_poly_coeffs.clone());
+
+        // the same preprocessing serves every lookup
+        let prover = Prover::new(&table, &param, &pp);
+        for lookup in [
+            vec![Fr::from(1), Fr::from(2), Fr::from(1), Fr::from(3)],
+            vec![Fr::from(16), Fr::from(16), Fr::from(9), Fr::from(16)],
+        ] {
+            let m = lookup.len();
+            let proof = prover.prove_with_preprocessing(&lookup, &preprocessing);
+            assert_eq!(proof, prover.prove(&lookup, &q_t_comm_poly_coeffs));
+
+            let (t_comm_2, z_v_comm_2, x_exponent_poly_comm_2) =
+                Cq::prepare_verification_data(&param, &table, m, t);
+            assert!(Cq::verify(
+                &vp,
+                &proof,
+                &t_comm_2,
+                &z_v_comm_2,
+                &x_exponent_poly_comm_2,
+                m,
+                t
+            ));
+        }
+    }
 }
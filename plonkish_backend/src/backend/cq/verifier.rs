@@ -11,6 +11,7 @@ use crate::{
             TranscriptRead,
         },
     },
+    Error,
 };
 use halo2_curves::bn256::{pairing, Bn256, Fr, G1Affine, G2Affine, G1, G2};
 
@@ -25,6 +26,7 @@ impl Verifier<'_> {
         Verifier { vp }
     }
 
+    /// Whether `proof` is valid, false if it's malformed too.
     pub fn verify(
         &self,
         proof: &Vec<u8>,
@@ -34,45 +36,58 @@ impl Verifier<'_> {
         m: usize,
         t: usize,
     ) -> bool {
+        self.try_verify(proof, t_comm_2, z_v_comm_2, x_exponent_poly_comm_2, m, t)
+            .unwrap_or(false)
+    }
+
+    fn try_verify(
+        &self,
+        proof: &[u8],
+        t_comm_2: &UnivariateKzgCommitment<G2Affine>,
+        z_v_comm_2: &UnivariateKzgCommitment<G2Affine>,
+        x_exponent_poly_comm_2: &UnivariateKzgCommitment<G2Affine>,
+        m: usize,
+        t: usize,
+    ) -> Result<bool, Error> {
         println!("Start to verify proof");
         let scalar_0 = Fr::from(0_u64);
         let scalar_1 = Fr::from(1_u64);
         let vp = self.vp;
-        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        let mut transcript = Keccak256Transcript::from_proof((), proof);
 
         // get commitments
         // read π1 = ([m(x)]1)
-        let m_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let m_comm_1 = Pcs::read_commitment(vp, &mut transcript)?;
 
         let beta: Fr = transcript.squeeze_challenge();
 
         // read π2 = ([A(X)]1, [Q_A(X)]1, [B_0(X)]1, [f(X)]1, [Q_B(X)]1, [P(X)]1)
-        let a_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let a_comm_1 = Pcs::read_commitment(vp, &mut transcript)?;
 
-        let q_a_comm_1_fk: G1Affine = transcript.read_commitment().unwrap();
+        let q_a_comm_1_fk: G1Affine = transcript.read_commitment()?;
 
-        let b_0_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let b_0_comm_1 = Pcs::read_commitment(vp, &mut transcript)?;
 
-        let f_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let f_comm_1 = Pcs::read_commitment(vp, &mut transcript)?;
 
-        let q_b_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let q_b_comm_1 = Pcs::read_commitment(vp, &mut transcript)?;
 
-        let p_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let p_comm_1 = Pcs::read_commitment(vp, &mut transcript)?;
 
         let randomness: Vec<Fr> = transcript.squeeze_challenges(2);
         let gamma = randomness[0];
         let eta = randomness[1];
 
         // read π3 = (b_0_at_gamma, f_at_gamma, a_at_0, pi_gamma, a_0_comm_1)
-        let b_0_at_gamma: Fr = transcript.read_field_element().unwrap();
+        let b_0_at_gamma: Fr = transcript.read_field_element()?;
 
-        let f_at_gamma: Fr = transcript.read_field_element().unwrap();
+        let f_at_gamma: Fr = transcript.read_field_element()?;
 
-        let a_at_0: Fr = transcript.read_field_element().unwrap();
+        let a_at_0: Fr = transcript.read_field_element()?;
 
-        let pi_gamma = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let pi_gamma = Pcs::read_commitment(vp, &mut transcript)?;
 
-        let a_0_comm_1 = Pcs::read_commitment(vp, &mut transcript).unwrap();
+        let a_0_comm_1 = Pcs::read_commitment(vp, &mut transcript)?;
 
         /************
         Verification
@@ -98,10 +113,13 @@ impl Verifier<'_> {
         let a_check_lhs = pairing(&a_comm_1.clone().to_affine(), &t_comm_2.clone().to_affine());
         let a_check_pairing_g1_terms = vec![q_a_comm_1_fk, comb];
         let a_check_pairing_g2_terms = vec![z_v_comm_2.clone().to_affine(), g2_affine];
-        let a_check_rhs = multi_pairing(&a_check_pairing_g1_terms, &a_check_pairing_g2_terms);
+        let a_check_rhs =
+            multi_pairing::<Bn256>(&a_check_pairing_g1_terms, &a_check_pairing_g2_terms);
         // println!("a_check_lhs: {:?}", a_check_lhs);
         // println!("a_check_rhs: {:?}", a_check_rhs);
-        assert_eq!(a_check_lhs, a_check_rhs);
+        if a_check_lhs != a_check_rhs {
+            return Ok(false);
+        }
         println!("=== Finished Check 1: round 2.11: A encodes the correct values ===");
 
         // Check 2: round 2.12: B_0 has the appropriate degree
@@ -114,7 +132,9 @@ impl Verifier<'_> {
         let b_0_check_rhs = pairing(&p_comm_1.clone().to_affine(), &g2_affine);
         // println!("b_0_check_lhs: {:?}", b_0_check_lhs);
         // println!("b_0_check_rhs: {:?}", b_0_check_rhs);
-        assert_eq!(b_0_check_lhs, b_0_check_rhs);
+        if b_0_check_lhs != b_0_check_rhs {
+            return Ok(false);
+        }
         println!("=== Finished Check 2: round 2.12: B_0 has the appropriate degree ===");
 
         // Check 3: 3.6 (c)
@@ -167,7 +187,9 @@ impl Verifier<'_> {
         let batch_check_rhs = pairing(&pi_gamma.clone().to_affine(), &vp.s_g2());
         // println!("batch_check_lhs: {:?}", batch_check_lhs);
         // println!("batch_check_rhs: {:?}", batch_check_rhs);
-        assert_eq!(batch_check_lhs, batch_check_rhs);
+        if batch_check_lhs != batch_check_rhs {
+            return Ok(false);
+        }
         println!("=== Finished Check 3: batched KZG check for the correctness of b_0_at_gamma, f_at_gamma, Q_b_at_gamma ===");
 
         // Check 4: 3.7 (b)
@@ -187,11 +209,13 @@ impl Verifier<'_> {
         let a_0_check_rhs = pairing(&a_0_comm_1.clone().to_affine(), &vp.s_g2());
         // println!("a_0_check_lhs: {:?}", a_0_check_lhs);
         // println!("a_0_check_rhs: {:?}", a_0_check_rhs);
-        assert_eq!(a_0_check_lhs, a_0_check_rhs);
+        if a_0_check_lhs != a_0_check_rhs {
+            return Ok(false);
+        }
         println!("=== Finished Check 4: KZG check for the correctness of a_at_0 ===");
 
         println!("Finished to verify proof");
-        true
+        Ok(true)
     }
 
     // random linear combination
@@ -0,0 +1,290 @@
+use halo2_curves::bn256::{Bn256, Fr, G1Affine};
+
+use crate::{
+    pcs::univariate::{
+        UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam, UnivariateKzgProverParam,
+        UnivariateKzgVerifierParam,
+    },
+    poly::univariate::UnivariatePolynomial,
+    Error,
+};
+
+pub mod prover;
+pub mod verifier;
+
+use crate::backend::caulk_plus::preprocessor::{self, TablePreprocessing};
+use prover::Prover;
+use verifier::Verifier;
+
+type Pcs = UnivariateKzg<Bn256>;
+
+/// flookup: the subtable `C_I` at roots `z_I` is extracted from the table
+/// with the pairing `e([C]1 - [C_I]1, [1]2) = e([H]1, [z_I]2)`, where `[H]1`
+/// comes from quotients cached for the table, after which the lookup only
+/// has to be shown to be in the subtable, of size at most `m`.
+///
+/// Without blinding, so proofs are deterministic and not zero-knowledge. The
+/// table preprocessing, including the Baloo one for `[C]1` and `[z_H]1`, is
+/// the one of Caulk+, so a [`TablePreprocessing`] serves both backends.
+#[derive(Clone, Debug)]
+pub struct Flookup;
+
+impl Flookup {
+    /// Trim `param`, which may be shared with Baloo, Caulk or Caulk+, and
+    /// preprocess `table` for lookups of size `m`.
+    pub fn preprocess(
+        param: &UnivariateKzgParam<Bn256>,
+        table: &[Fr],
+        m: usize,
+    ) -> Result<
+        (
+            UnivariateKzgProverParam<Bn256>,
+            UnivariateKzgVerifierParam<Bn256>,
+            TablePreprocessing,
+        ),
+        Error,
+    > {
+        let (pp, vp) = preprocessor::preprocess(param, table.len(), m)?;
+        let preprocessing = TablePreprocessing::new(&pp, table)?;
+        Ok((pp, vp, preprocessing))
+    }
+
+    pub fn prove(
+        param: &UnivariateKzgParam<Bn256>,
+        pp: &UnivariateKzgProverParam<Bn256>,
+        preprocessing: &TablePreprocessing,
+        lookup: &Vec<Fr>,
+    ) -> Result<Vec<u8>, Error> {
+        Prover::new(param, pp, preprocessing).prove(lookup)
+    }
+
+    pub fn verify(
+        vp: &UnivariateKzgVerifierParam<Bn256>,
+        proof: &Vec<u8>,
+        t_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        z_h_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        m: usize,
+    ) -> bool {
+        Verifier::new(vp).verify(proof, t_comm_1, z_h_comm_1, phi_comm_1, m)
+    }
+
+    // Helper method to prepare verification data
+    pub fn prepare_verification_data(
+        pp: &UnivariateKzgProverParam<Bn256>,
+        preprocessing: &TablePreprocessing,
+        lookup: &[Fr],
+    ) -> (
+        UnivariateKzgCommitment<G1Affine>, // t_comm_1
+        UnivariateKzgCommitment<G1Affine>, // z_h_comm_1
+        UnivariateKzgCommitment<G1Affine>, // phi_comm_1
+    ) {
+        let phi_poly = UnivariatePolynomial::lagrange(lookup.to_vec()).ifft();
+        (
+            preprocessing.t_comm_1().clone(),
+            preprocessing.z_h_comm_1().clone(),
+            Pcs::commit_monomial(pp, phi_poly.coeffs()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backend::{
+            caulk::preprocessor::poly_size,
+            caulk_plus::CaulkPlus,
+            flookup::{verifier::Verifier, Flookup},
+        },
+        pcs::{
+            univariate::{UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgVerifierParam},
+            PolynomialCommitmentScheme,
+        },
+        util::transcript::{InMemoryTranscript, Keccak256Transcript, TranscriptWrite},
+        Error,
+    };
+    use halo2_curves::bn256::{Bn256, Fr, G1Affine};
+    use rand::rngs::OsRng;
+
+    type Pcs = UnivariateKzg<Bn256>;
+
+    // offsets in the proof of C_I(v1), the last evaluation of π4 after π1 =
+    // ([z_I]1, [z_I]2, [C_I]1, [u]1), π2 = [W]1, π3 = [H1]1 and 4 others, and
+    // of the KZG opening [π]1 of the batch opening, after its quotient [Q]1
+    const C_I_EVAL: usize = 64 + 128 + 64 + 64 + 64 + 64 + 4 * 32;
+    const OPENING: usize = C_I_EVAL + 32 + 64;
+    const PROOF_SIZE: usize = OPENING + 64;
+
+    fn verify(
+        vp: &UnivariateKzgVerifierParam<Bn256>,
+        proof: &[u8],
+        t_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        z_h_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        m: usize,
+    ) -> Result<(), Error> {
+        let mut transcript = Keccak256Transcript::from_proof((), proof);
+        Verifier::new(vp).verify_with_transcript(
+            t_comm_1,
+            z_h_comm_1,
+            phi_comm_1,
+            m,
+            &mut transcript,
+        )
+    }
+
+    fn run_flookup(table: Vec<Fr>, lookup: Vec<Fr>) {
+        let m = lookup.len();
+        let param = Pcs::setup(poly_size(table.len(), m), 1, OsRng).unwrap();
+        let (pp, vp, preprocessing) = Flookup::preprocess(&param, &table, m).unwrap();
+        let proof = Flookup::prove(&param, &pp, &preprocessing, &lookup).unwrap();
+        let (t_comm_1, z_h_comm_1, phi_comm_1) =
+            Flookup::prepare_verification_data(&pp, &preprocessing, &lookup);
+        assert!(Flookup::verify(
+            &vp,
+            &proof,
+            &t_comm_1,
+            &z_h_comm_1,
+            &phi_comm_1,
+            m
+        ));
+
+        // proof doesn't verify against another lookup
+        let mut other_lookup = lookup.clone();
+        other_lookup.reverse();
+        other_lookup[0] += Fr::one();
+        let (_, _, other_phi_comm_1) =
+            Flookup::prepare_verification_data(&pp, &preprocessing, &other_lookup);
+        assert!(!Flookup::verify(
+            &vp,
+            &proof,
+            &t_comm_1,
+            &z_h_comm_1,
+            &other_phi_comm_1,
+            m
+        ));
+    }
+
+    #[test]
+    fn test_flookup() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        run_flookup(table, [3, 7, 3, 4].map(Fr::from).to_vec());
+    }
+
+    #[test]
+    fn test_flookup_single_value() {
+        let table = (1..=16).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        run_flookup(table, vec![Fr::from(16); 2]);
+    }
+
+    #[test]
+    fn test_flookup_tampered() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = [3, 7, 3, 4].map(Fr::from).to_vec();
+        let m = lookup.len();
+        let param = Pcs::setup(poly_size(table.len(), m), 1, OsRng).unwrap();
+        let (pp, vp, preprocessing) = Flookup::preprocess(&param, &table, m).unwrap();
+        let proof = Flookup::prove(&param, &pp, &preprocessing, &lookup).unwrap();
+        let (t_comm_1, z_h_comm_1, phi_comm_1) =
+            Flookup::prepare_verification_data(&pp, &preprocessing, &lookup);
+        let verify = |proof: &[u8]| verify(&vp, proof, &t_comm_1, &z_h_comm_1, &phi_comm_1, m);
+        assert_eq!(proof.len(), PROOF_SIZE);
+        assert_eq!(verify(&proof), Ok(()));
+
+        // C_I(v1) off by one, which fails the composition check at α
+        let mut tampered = proof.clone();
+        tampered[C_I_EVAL + 31] ^= 1;
+        assert_eq!(
+            verify(&tampered),
+            Err(Error::InvalidSnark(
+                "Lookup values should be the subtable at u".to_string()
+            ))
+        );
+
+        // [π]1 replaced by another valid point, which passes both pairing
+        // checks of flookup as it's read after them but fails the opening
+        let mut transcript = Keccak256Transcript::new(());
+        TranscriptWrite::<G1Affine, Fr>::write_commitment(&mut transcript, &pp.g1()).unwrap();
+        let mut tampered = proof.clone();
+        tampered[OPENING..].copy_from_slice(&transcript.into_proof());
+        assert_ne!(tampered, proof);
+        assert_eq!(
+            verify(&tampered),
+            Err(Error::InvalidPcsOpen(
+                "Invalid univariate KZG open".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_flookup_wrong_subtable() {
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = [3, 7, 3, 4].map(Fr::from).to_vec();
+        let m = lookup.len();
+        let param = Pcs::setup(poly_size(table.len(), m), 1, OsRng).unwrap();
+        let (pp, vp, preprocessing) = Flookup::preprocess(&param, &table, m).unwrap();
+
+        // no subtable of the table has a value out of it
+        assert_eq!(
+            Flookup::prove(
+                &param,
+                &pp,
+                &preprocessing,
+                &[3, 9, 3, 4].map(Fr::from).to_vec()
+            ),
+            Err(Error::InvalidSnark(
+                "Lookup values should be in table".to_string()
+            ))
+        );
+
+        // the subtable of another table with the lookup in it, at other
+        // roots of unity and with other values elsewhere, isn't of this one
+        let other_table = table.iter().rev().copied().collect::<Vec<_>>();
+        let (_, _, other_preprocessing) = Flookup::preprocess(&param, &other_table, m).unwrap();
+        let proof = Flookup::prove(&param, &pp, &other_preprocessing, &lookup).unwrap();
+        let (t_comm_1, z_h_comm_1, phi_comm_1) =
+            Flookup::prepare_verification_data(&pp, &preprocessing, &lookup);
+        let (other_t_comm_1, _, _) =
+            Flookup::prepare_verification_data(&pp, &other_preprocessing, &lookup);
+        assert_eq!(
+            verify(&vp, &proof, &other_t_comm_1, &z_h_comm_1, &phi_comm_1, m),
+            Ok(())
+        );
+        assert!(verify(&vp, &proof, &t_comm_1, &z_h_comm_1, &phi_comm_1, m).is_err());
+    }
+
+    #[test]
+    fn test_flookup_shares_preprocessing_with_caulk_plus() {
+        let table = (1..=16).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = [5, 1, 16, 5].map(Fr::from).to_vec();
+        let m = lookup.len();
+        let param = Pcs::setup(poly_size(table.len(), m), 1, OsRng).unwrap();
+        let (pp, vp, preprocessing) = CaulkPlus::preprocess(&param, &table, m).unwrap();
+        let (t_comm_1, z_h_comm_1, phi_comm_1) =
+            CaulkPlus::prepare_verification_data(&pp, &preprocessing, &lookup);
+
+        let proof = CaulkPlus::prove(&param, &pp, &preprocessing, &lookup).unwrap();
+        assert!(CaulkPlus::verify(
+            &vp,
+            &proof,
+            &t_comm_1,
+            &z_h_comm_1,
+            &phi_comm_1,
+            m
+        ));
+        let proof = Flookup::prove(&param, &pp, &preprocessing, &lookup).unwrap();
+        assert!(Flookup::verify(
+            &vp,
+            &proof,
+            &t_comm_1,
+            &z_h_comm_1,
+            &phi_comm_1,
+            m
+        ));
+        // without blinding, proving is deterministic
+        assert_eq!(
+            Flookup::prove(&param, &pp, &preprocessing, &lookup).unwrap(),
+            Flookup::prove(&param, &pp, &preprocessing, &lookup).unwrap()
+        );
+    }
+}
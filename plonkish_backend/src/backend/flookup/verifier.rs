@@ -0,0 +1,121 @@
+use crate::{
+    backend::baloo::util::{multi_pairing, squeeze_challenge_outside_domains},
+    pcs::{
+        univariate::{UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgVerifierParam},
+        Evaluation, PolynomialCommitmentScheme,
+    },
+    util::{
+        arithmetic::{Field, Group},
+        transcript::{
+            FieldTranscript, FieldTranscriptRead, G2TranscriptRead, InMemoryTranscript,
+            Keccak256Transcript, Transcript, TranscriptRead,
+        },
+    },
+    Error,
+};
+use halo2_curves::bn256::{Bn256, Fr, G1Affine, G2Affine, Gt};
+
+type Pcs = UnivariateKzg<Bn256>;
+
+pub struct Verifier<'b> {
+    vp: &'b UnivariateKzgVerifierParam<Bn256>,
+}
+
+impl Verifier<'_> {
+    pub fn new(vp: &UnivariateKzgVerifierParam<Bn256>) -> Verifier<'_> {
+        Verifier { vp }
+    }
+
+    pub fn verify(
+        &self,
+        proof: &Vec<u8>,
+        t_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        z_h_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        m: usize,
+    ) -> bool {
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        self.verify_with_transcript(t_comm_1, z_h_comm_1, phi_comm_1, m, &mut transcript)
+            .is_ok()
+    }
+
+    pub(crate) fn verify_with_transcript(
+        &self,
+        t_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        z_h_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        phi_comm_1: &UnivariateKzgCommitment<G1Affine>,
+        m: usize,
+        transcript: &mut (impl TranscriptRead<G1Affine, Fr> + G2TranscriptRead<G2Affine, Fr>),
+    ) -> Result<(), Error> {
+        let vp = self.vp;
+        if !m.is_power_of_two() {
+            return Err(Error::InvalidSnark(format!(
+                "Lookup size should be a power of two but got {m}"
+            )));
+        }
+        transcript.common_commitments(&[t_comm_1.0, phi_comm_1.0])?;
+
+        // read π1 = ([z_I]1, [z_I]2, [C_I]1, [u]1)
+        let z_i_comm_1 = Pcs::read_commitment(vp, transcript)?;
+        let z_i_comm_2: G2Affine = transcript.read_commitment_g2()?;
+        let c_i_comm_1 = Pcs::read_commitment(vp, transcript)?;
+        let u_comm_1 = Pcs::read_commitment(vp, transcript)?;
+
+        let chi: Fr = transcript.squeeze_challenge();
+
+        // read π2 = [W]1
+        let w_comm_1: G1Affine = transcript.read_commitment()?;
+
+        let chi_2: Fr = transcript.squeeze_challenge();
+
+        // read π3 = [H1]1
+        let h1_comm_1 = Pcs::read_commitment(vp, transcript)?;
+
+        let alpha = squeeze_challenge_outside_domains(transcript, &[m]);
+
+        // read π4 = (u(α), φ(α), H1(α), z_I(v1), C_I(v1), batch opening)
+        let [u_eval, phi_eval, h1_eval, z_i_eval, c_i_eval]: [Fr; 5] =
+            transcript.read_field_elements(5)?.try_into().unwrap();
+        let v1 = u_eval;
+
+        // z_I(u(α)) + χ2 (C_I(u(α)) - φ(α)) = H1(α) * z_V(α)
+        let z_v_at_alpha = alpha.pow([m as u64]) - Fr::one();
+        if z_i_eval + chi_2 * (c_i_eval - phi_eval) != h1_eval * z_v_at_alpha {
+            return Err(Error::InvalidSnark(
+                "Lookup values should be the subtable at u".to_string(),
+            ));
+        }
+
+        // e([z_I]1, [1]2) = e([1]1, [z_I]2)
+        let z_i_check =
+            multi_pairing::<Bn256>(&[z_i_comm_1.0, -vp.g1()], &[vp.g2(), z_i_comm_2]);
+        if z_i_check != Gt::identity() {
+            return Err(Error::InvalidSnark(
+                "z_I should be committed the same in both groups".to_string(),
+            ));
+        }
+
+        // e([C]1 - [C_I]1 + χ [z_H]1, [1]2) = e([W]1, [z_I]2)
+        let lhs: G1Affine = (t_comm_1.0 - c_i_comm_1.0 + z_h_comm_1.0 * chi).into();
+        let subtable_check =
+            multi_pairing::<Bn256>(&[lhs, -w_comm_1], &[vp.g2(), z_i_comm_2]);
+        if subtable_check != Gt::identity() {
+            return Err(Error::InvalidSnark(
+                "Subtable should be bound to the table at roots of unity".to_string(),
+            ));
+        }
+
+        // z_I and C_I at v1, everything else at α
+        let comms = [&u_comm_1, phi_comm_1, &h1_comm_1, &z_i_comm_1, &c_i_comm_1];
+        let points = [alpha, v1];
+        let evals = [u_eval, phi_eval, h1_eval, z_i_eval, c_i_eval]
+            .into_iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                let point = if idx >= 3 { 1 } else { 0 };
+                Evaluation::new(idx, point, value)
+            })
+            .collect::<Vec<_>>();
+        Pcs::batch_verify(vp, comms, &points, &evals, transcript)
+    }
+}
@@ -0,0 +1,201 @@
+//! Estimates of the gas of verifying a proof on the EVM, to compare lookup
+//! arguments for on-chain deployment before writing any Solidity.
+//!
+//! Only what dominates the cost is counted: the calldata of EIP-2028, the
+//! `ecAdd`, `ecMul` and `ecPairing` precompiles of EIP-1108, `modexp` of
+//! EIP-2565 for inversions and `keccak256` of the transcript. Field
+//! arithmetic, memory and the base cost of the transaction aren't, which are
+//! a few thousand gas for either verifier.
+
+use crate::{
+    backend::baloo::BalooProof,
+    util::{Deserialize, Serialize},
+};
+use halo2_curves::bn256::Bn256;
+
+pub const CALLDATA_ZERO_BYTE_GAS: u64 = 4;
+pub const CALLDATA_NONZERO_BYTE_GAS: u64 = 16;
+pub const EC_ADD_GAS: u64 = 150;
+pub const EC_MUL_GAS: u64 = 6000;
+pub const PAIRING_BASE_GAS: u64 = 45000;
+pub const PAIRING_PER_PAIR_GAS: u64 = 34000;
+// minimum of EIP-2565, which an inversion by `x^(r-2) mod r` of 32 bytes
+// operands costs
+pub const MODEXP_GAS: u64 = 200;
+pub const KECCAK_BASE_GAS: u64 = 30;
+pub const KECCAK_WORD_GAS: u64 = 6;
+
+// selector, `phi` inline, and the offset and length of `proof` of
+// `verify(uint256[2],bytes)` of `solidity::generate_verifier`
+const BALOO_CALLDATA_HEAD: usize = 4 + 4 * 32;
+// `ecMul`, `ecAdd` and `modexp` of the checks of `solidity::generate_verifier`
+const BALOO_NUM_EC_MULS: usize = 19;
+const BALOO_NUM_EC_ADDS: usize = 16;
+const BALOO_NUM_MODEXPS: usize = 1;
+// the instance digest, and the challenges `α`, `β`, `γ` and `ζ` each of the
+// digest or previous challenge and then `8`, `6`, `4` and `0` words of the
+// proof, with the prefixes of labels and parameters left out
+const BALOO_NUM_HASHES: usize = 5;
+const BALOO_NUM_HASHED_WORDS: usize = 1 + 9 + 7 + 5 + 1;
+
+// selector, and the offset and length of `proof` of a `verify(bytes)`
+const CQ_CALLDATA_HEAD: usize = 4 + 2 * 32;
+// 9 G1 points and 3 field elements
+const CQ_PROOF_SIZE: usize = 9 * 64 + 3 * 32;
+// pairings of the checks of `cq::verifier::Verifier`, i.e. 3 + 2 + 2 + 2
+const CQ_NUM_PAIRING_CHECKS: usize = 4;
+const CQ_NUM_PAIRINGS: usize = 9;
+// terms with a scalar other than 1 of its 4 linear combinations
+const CQ_NUM_EC_MULS: usize = 6;
+const CQ_NUM_EC_ADDS: usize = 6;
+// `1 / Z_H(γ)`, `1 / m` being a constant
+const CQ_NUM_MODEXPS: usize = 1;
+// `β` of `[m(x)]1`, and `γ` of `β` and the 6 points of `π2`, then `η` of `γ`
+const CQ_NUM_HASHES: usize = 3;
+const CQ_NUM_HASHED_WORDS: usize = 2 + 13 + 1;
+
+/// What the EVM verifier of a lookup argument does, with the calldata
+/// assumed to be all nonzero bytes until [`VerifierCost::with_calldata`] of
+/// the actual one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifierCost {
+    pub calldata_zero_bytes: usize,
+    pub calldata_nonzero_bytes: usize,
+    /// Calls of `ecPairing`, each of some of the pairings.
+    pub num_pairing_checks: usize,
+    pub num_pairings: usize,
+    pub num_ec_muls: usize,
+    pub num_ec_adds: usize,
+    pub num_modexps: usize,
+    pub num_hashes: usize,
+    pub num_hashed_words: usize,
+}
+
+impl VerifierCost {
+    /// Cost of the contract of [`generate_verifier`], which verifies proofs
+    /// for committed `φ`.
+    ///
+    /// [`generate_verifier`]: crate::backend::baloo::solidity::generate_verifier
+    pub fn baloo() -> Self {
+        let stats = BalooProof::<Bn256>::stats_of(false);
+        Self {
+            calldata_zero_bytes: 0,
+            calldata_nonzero_bytes: BALOO_CALLDATA_HEAD + stats.size_in_bytes,
+            // one per check, which batches its pairings
+            num_pairing_checks: 5,
+            num_pairings: stats.num_pairings,
+            num_ec_muls: BALOO_NUM_EC_MULS,
+            num_ec_adds: BALOO_NUM_EC_ADDS,
+            num_modexps: BALOO_NUM_MODEXPS,
+            num_hashes: BALOO_NUM_HASHES,
+            num_hashed_words: BALOO_NUM_HASHED_WORDS,
+        }
+    }
+
+    /// Cost of a contract doing what [`cq::verifier::Verifier`] does, with
+    /// the commitments of the table as constants.
+    ///
+    /// [`cq::verifier::Verifier`]: crate::backend::cq::verifier::Verifier
+    pub fn cq() -> Self {
+        Self {
+            calldata_zero_bytes: 0,
+            calldata_nonzero_bytes: CQ_CALLDATA_HEAD + CQ_PROOF_SIZE,
+            num_pairing_checks: CQ_NUM_PAIRING_CHECKS,
+            num_pairings: CQ_NUM_PAIRINGS,
+            num_ec_muls: CQ_NUM_EC_MULS,
+            num_ec_adds: CQ_NUM_EC_ADDS,
+            num_modexps: CQ_NUM_MODEXPS,
+            num_hashes: CQ_NUM_HASHES,
+            num_hashed_words: CQ_NUM_HASHED_WORDS,
+        }
+    }
+
+    /// This cost with the bytes of `calldata`, e.g. of [`encode_calldata`] of
+    /// a proof, instead of the worst case.
+    ///
+    /// [`encode_calldata`]: crate::backend::baloo::solidity::encode_calldata
+    pub fn with_calldata(self, calldata: &[u8]) -> Self {
+        let calldata_zero_bytes = calldata.iter().filter(|byte| **byte == 0).count();
+        Self {
+            calldata_zero_bytes,
+            calldata_nonzero_bytes: calldata.len() - calldata_zero_bytes,
+            ..self
+        }
+    }
+
+    pub fn calldata_size(&self) -> usize {
+        self.calldata_zero_bytes + self.calldata_nonzero_bytes
+    }
+
+    pub fn estimate(&self) -> GasEstimate {
+        let gas = |count: usize, gas: u64| count as u64 * gas;
+        GasEstimate {
+            calldata: gas(self.calldata_zero_bytes, CALLDATA_ZERO_BYTE_GAS)
+                + gas(self.calldata_nonzero_bytes, CALLDATA_NONZERO_BYTE_GAS),
+            pairings: gas(self.num_pairing_checks, PAIRING_BASE_GAS)
+                + gas(self.num_pairings, PAIRING_PER_PAIR_GAS),
+            ec_arithmetic: gas(self.num_ec_muls, EC_MUL_GAS) + gas(self.num_ec_adds, EC_ADD_GAS),
+            modexps: gas(self.num_modexps, MODEXP_GAS),
+            hashing: gas(self.num_hashes, KECCAK_BASE_GAS)
+                + gas(self.num_hashed_words, KECCAK_WORD_GAS),
+        }
+    }
+}
+
+/// Gas of a [`VerifierCost`] by what it's spent on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasEstimate {
+    pub calldata: u64,
+    pub pairings: u64,
+    pub ec_arithmetic: u64,
+    pub modexps: u64,
+    pub hashing: u64,
+}
+
+impl GasEstimate {
+    pub fn total(&self) -> u64 {
+        self.calldata + self.pairings + self.ec_arithmetic + self.modexps + self.hashing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::{
+        baloo::{preprocessor::preprocess, prover::Prover, solidity::encode_calldata, Baloo},
+        gas::{GasEstimate, VerifierCost},
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+
+    #[test]
+    fn test_gas_estimate() {
+        let baloo = VerifierCost::baloo();
+        let cq = VerifierCost::cq();
+        assert_eq!(baloo.calldata_size(), 1188);
+        assert_eq!(cq.calldata_size(), 740);
+        assert_eq!(
+            baloo.estimate(),
+            GasEstimate {
+                calldata: 1188 * 16,
+                pairings: 5 * 45000 + 14 * 34000,
+                ec_arithmetic: 19 * 6000 + 16 * 150,
+                modexps: 200,
+                hashing: 5 * 30 + 23 * 6,
+            }
+        );
+        // cq pairs fewer points and needs fewer scalar multiplications
+        assert!(cq.estimate().total() < baloo.estimate().total());
+
+        // zero bytes of the actual calldata are cheaper
+        let table = (1..=8).map(|i| Fr::from(i as u64)).collect::<Vec<_>>();
+        let lookup = vec![Fr::from(7), Fr::from(3), Fr::from(3), Fr::from(4)];
+        let (param, pp, _) = preprocess::<Bn256>(table.len(), lookup.len()).unwrap();
+        let proof = Prover::new(&table, &param, &pp).prove(&lookup).unwrap();
+        let phi_comm_1 = Baloo::prepare_verification_data(&pp, &lookup);
+        let calldata = encode_calldata(&proof, &phi_comm_1).unwrap();
+        let actual = baloo.with_calldata(&calldata);
+        assert_eq!(actual.calldata_size(), baloo.calldata_size());
+        assert!(actual.calldata_zero_bytes > 0);
+        assert!(actual.estimate().calldata < baloo.estimate().calldata);
+        assert_eq!(actual.estimate().pairings, baloo.estimate().pairings);
+    }
+}
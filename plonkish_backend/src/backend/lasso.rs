@@ -0,0 +1,208 @@
+use crate::{
+    backend::lasso::table::{AndTable, DecomposableTable, RangeTable},
+    pcs::{multilinear::MultilinearKzg, PolynomialCommitmentScheme},
+    poly::multilinear::MultilinearPolynomial,
+    util::{
+        arithmetic::PrimeField,
+        test::std_rng,
+        transcript::{InMemoryTranscript, Keccak256Transcript, TranscriptRead, TranscriptWrite},
+    },
+    Error,
+};
+use halo2_curves::bn256::{Bn256, Fr};
+use rand::RngCore;
+use std::{fmt::Debug, marker::PhantomData, time::Instant};
+
+pub mod preprocessor;
+pub mod prover;
+pub mod table;
+pub mod verifier;
+
+#[derive(Clone, Debug)]
+pub struct LassoProverParam<F, Pcs, T>
+where
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F>,
+{
+    /// For polynomials over the lookups, with `num_vars` variables.
+    lookup_pcs: Pcs::ProverParam,
+    /// For multiplicities of subtables, with `table.subtable_num_vars()`.
+    subtable_pcs: Pcs::ProverParam,
+    num_vars: usize,
+    table: T,
+    subtables: Vec<Vec<F>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct LassoVerifierParam<F, Pcs, T>
+where
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F>,
+{
+    lookup_pcs: Pcs::VerifierParam,
+    subtable_pcs: Pcs::VerifierParam,
+    num_vars: usize,
+    table: T,
+}
+
+/// Lasso-style lookup into a [`DecomposableTable`] over a multilinear `Pcs`.
+///
+/// A sumcheck reduces `a(r) = Σ_x eq(r, x) g(E_0(x), ..., E_{c-1}(x))` to
+/// openings of the subtable reads `E_i`, and each `(dim_i, E_i)` is checked
+/// to be read from `T_i` with LogUp over a GKR fractional sumcheck. Only the
+/// subtables are ever committed to, so the table can be as large as `2^128`.
+#[derive(Clone, Debug)]
+pub struct Lasso<F, Pcs, T>(PhantomData<F>, PhantomData<Pcs>, PhantomData<T>);
+
+impl<F, Pcs, T> Lasso<F, Pcs, T>
+where
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = MultilinearPolynomial<F>>,
+    T: DecomposableTable<F>,
+{
+    pub fn preprocess(
+        param: &Pcs::Param,
+        table: T,
+        num_vars: usize,
+    ) -> Result<(LassoProverParam<F, Pcs, T>, LassoVerifierParam<F, Pcs, T>), Error> {
+        preprocessor::preprocess(param, table, num_vars)
+    }
+
+    /// Look up the entries at `indices`, returning the lookup vector `a`
+    /// committed to first in the proof.
+    pub fn prove(
+        pp: &LassoProverParam<F, Pcs, T>,
+        indices: &[u128],
+        transcript: &mut impl TranscriptWrite<Pcs::CommitmentChunk, F>,
+    ) -> Result<Vec<F>, Error> {
+        prover::prove(pp, indices, transcript)
+    }
+
+    pub fn verify(
+        vp: &LassoVerifierParam<F, Pcs, T>,
+        transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, F>,
+    ) -> Result<Pcs::Commitment, Error> {
+        verifier::verify(vp, transcript)
+    }
+}
+
+// Concrete implementation for BN256/Fr
+impl<T: DecomposableTable<Fr>> Lasso<Fr, MultilinearKzg<Bn256>, T> {
+    // Run the full Lasso protocol with given table and lookup indices
+    pub fn test_lasso_by_input(table: T, indices: Vec<u128>) -> Vec<String> {
+        let mut timings: Vec<String> = vec![];
+
+        let start_total = Instant::now();
+
+        // 1. Setup
+        let num_vars = indices.len().next_power_of_two().ilog2().max(1) as usize;
+        let poly_size = 1 << num_vars.max(table.subtable_num_vars());
+
+        let start = Instant::now();
+        let mut rng = std_rng();
+        let param = MultilinearKzg::<Bn256>::setup(poly_size, 1, &mut rng).unwrap();
+        let (pp, vp) = Self::preprocess(&param, table, num_vars).unwrap();
+        let duration1 = start.elapsed();
+        timings.push(format!("Setup and preprocess: {}ms", duration1.as_millis()));
+
+        // 2. Prove
+        let start = Instant::now();
+        let mut transcript = Keccak256Transcript::new(());
+        Self::prove(&pp, &indices, &mut transcript).unwrap();
+        let proof = transcript.into_proof();
+        let duration2 = start.elapsed();
+        timings.push(format!("Prove: {}ms", duration2.as_millis()));
+        timings.push(format!("Proof size: {}bytes", proof.len()));
+
+        // 3. Verify
+        let start = Instant::now();
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        Self::verify(&vp, &mut transcript).unwrap();
+        let duration3 = start.elapsed();
+        timings.push(format!("Verify: {}ms", duration3.as_millis()));
+
+        let total_duration = start_total.elapsed();
+        timings.push(format!("Total time: {}ms", total_duration.as_millis()));
+
+        timings
+    }
+}
+
+impl Lasso<Fr, MultilinearKzg<Bn256>, RangeTable> {
+    // Run the full Lasso protocol with 2^k lookups into the range [0, 2^64)
+    pub fn test_lasso_by_k(k: usize) -> Vec<String> {
+        let mut rng = std_rng();
+        let indices = (0..1 << k).map(|_| rng.next_u64() as u128).collect();
+        Self::test_lasso_by_input(RangeTable::new(8, 8), indices)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{pcs::Commitment, util::Itertools};
+
+    type Pcs = MultilinearKzg<Bn256>;
+
+    fn run_lasso<T: DecomposableTable<Fr>>(
+        table: T,
+        num_vars: usize,
+        indices: &[u128],
+    ) -> Result<(Vec<Fr>, Commitment<Fr, Pcs>), Error> {
+        let mut rng = std_rng();
+        let poly_size = 1 << num_vars.max(table.subtable_num_vars());
+        let param = Pcs::setup(poly_size, 1, &mut rng).unwrap();
+        let (pp, vp) = Lasso::<Fr, Pcs, T>::preprocess(&param, table, num_vars).unwrap();
+
+        let mut transcript = Keccak256Transcript::new(());
+        let lookup = Lasso::prove(&pp, indices, &mut transcript)?;
+        let proof = transcript.into_proof();
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        let lookup_comm = Lasso::verify(&vp, &mut transcript)?;
+        Ok((lookup, lookup_comm))
+    }
+
+    #[test]
+    fn test_e2e_range() {
+        let indices = [0, 1, 1 << 40, u64::MAX as u128, 12345].to_vec();
+        let (lookup, lookup_comm) = run_lasso(RangeTable::new(8, 8), 3, &indices).unwrap();
+        assert_eq!(
+            lookup[..5],
+            indices
+                .iter()
+                .map(|index| Fr::from(*index as u64))
+                .collect_vec()
+        );
+        // the lookup vector is padded with its last value
+        assert_eq!(lookup[7], Fr::from(12345));
+        // and the verifier ends up with the commitment to it
+        let (pp, _) = {
+            let param = Pcs::setup(1 << 8, 1, std_rng()).unwrap();
+            Pcs::trim(&param, 1 << 3, 1).unwrap()
+        };
+        let lookup_poly = MultilinearPolynomial::new(lookup);
+        assert_eq!(Pcs::commit(&pp, &lookup_poly).unwrap().0, lookup_comm.0);
+
+        assert_eq!(
+            run_lasso(RangeTable::new(8, 8), 3, &[1 << 64]).unwrap_err(),
+            Error::InvalidSnark("Lookup index should be in table".to_string())
+        );
+    }
+
+    #[test]
+    fn test_e2e_and() {
+        let table = AndTable::new(4, 4);
+        let operands = [(0xffff, 0x1234), (0xbeef, 0xf00d), (0, 0xffff), (7, 3)];
+        let indices = operands.map(|(a, b)| table.index(a, b)).to_vec();
+        let (lookup, _) = run_lasso(table, 2, &indices).unwrap();
+        assert_eq!(lookup, operands.map(|(a, b)| Fr::from(a & b)).to_vec());
+    }
+
+    #[test]
+    fn test_lasso_by_k() {
+        let timings = Lasso::<Fr, Pcs, RangeTable>::test_lasso_by_k(4);
+        assert!(timings
+            .iter()
+            .any(|timing| timing.starts_with("Proof size: ")));
+    }
+}
@@ -0,0 +1,42 @@
+use super::{table::DecomposableTable, LassoProverParam, LassoVerifierParam};
+use crate::{
+    pcs::PolynomialCommitmentScheme, poly::multilinear::MultilinearPolynomial,
+    util::arithmetic::PrimeField, Error,
+};
+
+pub(super) fn preprocess<
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = MultilinearPolynomial<F>>,
+    T: DecomposableTable<F>,
+>(
+    param: &Pcs::Param,
+    table: T,
+    num_vars: usize,
+) -> Result<(LassoProverParam<F, Pcs, T>, LassoVerifierParam<F, Pcs, T>), Error> {
+    let subtable_num_vars = table.subtable_num_vars();
+    if num_vars == 0 || subtable_num_vars == 0 {
+        return Err(Error::InvalidPcsParam(
+            "Lookups and subtables should have at least 1 variable".to_string(),
+        ));
+    }
+    let num_chunks = table.num_chunks();
+    // a, dim_i and E_i for lookups, and m_i for subtables
+    let (lookup_pcs_pp, lookup_pcs_vp) = Pcs::trim(param, 1 << num_vars, 1 + 2 * num_chunks)?;
+    let (subtable_pcs_pp, subtable_pcs_vp) = Pcs::trim(param, 1 << subtable_num_vars, num_chunks)?;
+    let subtables = (0..num_chunks).map(|chunk| table.subtable(chunk)).collect();
+
+    let pp = LassoProverParam {
+        lookup_pcs: lookup_pcs_pp,
+        subtable_pcs: subtable_pcs_pp,
+        num_vars,
+        table: table.clone(),
+        subtables,
+    };
+    let vp = LassoVerifierParam {
+        lookup_pcs: lookup_pcs_vp,
+        subtable_pcs: subtable_pcs_vp,
+        num_vars,
+        table,
+    };
+    Ok((pp, vp))
+}
@@ -0,0 +1,246 @@
+use super::{table::DecomposableTable, LassoProverParam};
+use crate::{
+    backend::lookup::witness::{pad_with_last, LookupWitness},
+    pcs::{Evaluation, PolynomialCommitmentScheme},
+    piop::{
+        gkr::{fraction_sum, prove_fractional_sum_check},
+        sum_check::{
+            classic::{ClassicSumCheck, EvaluationsProver},
+            SumCheck as _, VirtualPolynomial,
+        },
+    },
+    poly::multilinear::MultilinearPolynomial,
+    util::{
+        arithmetic::PrimeField, chain, expression::Expression, izip, izip_eq,
+        transcript::TranscriptWrite, Itertools,
+    },
+    Error,
+};
+use std::iter;
+
+type SumCheck<F> = ClassicSumCheck<EvaluationsProver<F>>;
+
+pub(super) fn prove<
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = MultilinearPolynomial<F>>,
+    T: DecomposableTable<F>,
+>(
+    pp: &LassoProverParam<F, Pcs, T>,
+    indices: &[u128],
+    transcript: &mut impl TranscriptWrite<Pcs::CommitmentChunk, F>,
+) -> Result<Vec<F>, Error> {
+    let n = 1 << pp.num_vars;
+    // pad the lookups to n with the last index
+    let indices = pad_with_last(indices, n)?;
+    let table_num_vars = pp.table.num_vars();
+    if table_num_vars < 128 && indices.iter().any(|index| index >> table_num_vars != 0) {
+        return Err(Error::InvalidSnark(
+            "Lookup index should be in table".to_string(),
+        ));
+    }
+
+    // round 1
+    let (dims, reads) = compute_subtable_reads(&pp.subtables, &pp.table, &indices);
+    let lookup = (0..n)
+        .map(|j| {
+            pp.table
+                .combine(&reads.iter().map(|read| read[j]).collect_vec())
+        })
+        .collect_vec();
+    // counted at the subtable index read, as subtables may repeat values
+    let ms = dims
+        .iter()
+        .zip(&pp.subtables)
+        .map(|(dim, subtable)| LookupWitness::from_indices(subtable, dim.clone()))
+        .map(|witness| witness.field_multiplicities())
+        .collect_vec();
+    let lookup_poly = MultilinearPolynomial::new(lookup.clone());
+    let dim_polys = dims
+        .iter()
+        .map(|dim| MultilinearPolynomial::new(dim.iter().map(|r| F::from(*r as u64)).collect()))
+        .collect_vec();
+    let read_polys = reads
+        .into_iter()
+        .map(MultilinearPolynomial::new)
+        .collect_vec();
+    let m_polys = ms.into_iter().map(MultilinearPolynomial::new).collect_vec();
+    let lookup_polys = chain![[&lookup_poly], &dim_polys, &read_polys].collect_vec();
+    let lookup_comms =
+        Pcs::batch_commit_and_write(&pp.lookup_pcs, lookup_polys.iter().copied(), transcript)?;
+    let m_comms = Pcs::batch_commit_and_write(&pp.subtable_pcs, &m_polys, transcript)?;
+
+    // round 2
+    // a(r) = Σ_x eq(r, x) g(E_0(x), ..., E_{c-1}(x))
+    let r = transcript.squeeze_challenges(pp.num_vars);
+    let lookup_eval = lookup_poly.evaluate(&r);
+    transcript.write_field_element(&lookup_eval)?;
+    let expression = Expression::eq_xy(0) * pp.table.combine_expression();
+    let (_, x, _) = SumCheck::prove(
+        &(),
+        pp.num_vars,
+        VirtualPolynomial::new(&expression, &read_polys, &[], &[r.clone()]),
+        lookup_eval,
+        transcript,
+    )?;
+    let read_evals = read_polys
+        .iter()
+        .map(|poly| poly.evaluate(&x))
+        .collect_vec();
+    transcript.write_field_elements(&read_evals)?;
+
+    // round 3
+    // Σ_x 1 / (β + dim_i(x) + γ E_i(x)) = Σ_y m_i(y) / (β + y + γ T_i(y))
+    let beta = transcript.squeeze_challenge();
+    let gamma = transcript.squeeze_challenge();
+    let ones = MultilinearPolynomial::new(vec![F::ONE; n]);
+    let lookup_qs = compute_fingerprints(&beta, &gamma, &dim_polys, &read_polys);
+    let subtable_polys = pp
+        .subtables
+        .iter()
+        .map(|subtable| {
+            let ids = (0..subtable.len() as u64).map(F::from).collect();
+            (
+                MultilinearPolynomial::new(ids),
+                MultilinearPolynomial::new(subtable.clone()),
+            )
+        })
+        .collect_vec();
+    let (id_polys, t_polys): (Vec<_>, Vec<_>) = subtable_polys.into_iter().unzip();
+    let subtable_qs = compute_fingerprints(&beta, &gamma, &id_polys, &t_polys);
+    let lookup_fractions = lookup_qs
+        .iter()
+        .map(|q| fraction_sum(&ones, q))
+        .collect_vec();
+    let subtable_fractions = izip_eq!(&m_polys, &subtable_qs)
+        .map(|(m, q)| fraction_sum(m, q))
+        .collect_vec();
+    let fractions = chain![&lookup_fractions, &subtable_fractions].collect_vec();
+    transcript.write_field_elements(fractions.iter().flat_map(|(p, q)| [p, q]))?;
+
+    let (_, _, x_lookup) = prove_fractional_sum_check(
+        lookup_fractions.iter().map(|(p, _)| Some(*p)),
+        lookup_fractions.iter().map(|(_, q)| Some(*q)),
+        iter::repeat(&ones).take(lookup_qs.len()),
+        &lookup_qs,
+        transcript,
+    )?;
+    let dim_evals = dim_polys
+        .iter()
+        .map(|poly| poly.evaluate(&x_lookup))
+        .collect_vec();
+    let read_lookup_evals = read_polys
+        .iter()
+        .map(|poly| poly.evaluate(&x_lookup))
+        .collect_vec();
+    transcript.write_field_elements(chain![&dim_evals, &read_lookup_evals])?;
+
+    let (_, _, x_subtable) = prove_fractional_sum_check(
+        subtable_fractions.iter().map(|(p, _)| Some(*p)),
+        subtable_fractions.iter().map(|(_, q)| Some(*q)),
+        &m_polys,
+        &subtable_qs,
+        transcript,
+    )?;
+    let m_evals = m_polys
+        .iter()
+        .map(|poly| poly.evaluate(&x_subtable))
+        .collect_vec();
+    transcript.write_field_elements(&m_evals)?;
+
+    // round 4
+    // lookup polys [a, dim_0, .., dim_{c-1}, E_0, .., E_{c-1}] at r, x and x_lookup
+    let num_chunks = dim_polys.len();
+    let points = [r, x, x_lookup];
+    let evals = chain![
+        [Evaluation::new(0, 0, lookup_eval)],
+        izip!(1 + num_chunks.., read_evals).map(|(idx, eval)| Evaluation::new(idx, 1, eval)),
+        izip!(1.., chain![dim_evals, read_lookup_evals])
+            .map(|(idx, eval)| Evaluation::new(idx, 2, eval)),
+    ]
+    .collect_vec();
+    Pcs::batch_open(
+        &pp.lookup_pcs,
+        lookup_polys,
+        &lookup_comms,
+        &points,
+        &evals,
+        transcript,
+    )?;
+    let evals = m_evals
+        .into_iter()
+        .enumerate()
+        .map(|(idx, eval)| Evaluation::new(idx, 0, eval))
+        .collect_vec();
+    Pcs::batch_open(
+        &pp.subtable_pcs,
+        &m_polys,
+        &m_comms,
+        &[x_subtable],
+        &evals,
+        transcript,
+    )?;
+
+    Ok(lookup)
+}
+
+/// For each chunk, the subtable indices `dim_i` its lookups read at, and the
+/// entries `E_i` read.
+fn compute_subtable_reads<F: PrimeField, T: DecomposableTable<F>>(
+    subtables: &[Vec<F>],
+    table: &T,
+    indices: &[u128],
+) -> (Vec<Vec<usize>>, Vec<Vec<F>>) {
+    let mut dims = vec![Vec::with_capacity(indices.len()); subtables.len()];
+    for index in indices {
+        for (dim, r) in dims.iter_mut().zip(table.split(*index)) {
+            dim.push(r);
+        }
+    }
+    let reads = dims
+        .iter()
+        .zip(subtables)
+        .map(|(dim, subtable)| dim.iter().map(|r| subtable[*r]).collect())
+        .collect();
+    (dims, reads)
+}
+
+/// `β + u + γ v` for each pair of polynomials `(u, v)`.
+fn compute_fingerprints<F: PrimeField>(
+    beta: &F,
+    gamma: &F,
+    us: &[MultilinearPolynomial<F>],
+    vs: &[MultilinearPolynomial<F>],
+) -> Vec<MultilinearPolynomial<F>> {
+    us.iter()
+        .zip(vs)
+        .map(|(u, v)| {
+            let evals = u
+                .evals()
+                .iter()
+                .zip(v.evals())
+                .map(|(u, v)| *beta + u + *gamma * v)
+                .collect();
+            MultilinearPolynomial::new(evals)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::lasso::table::RangeTable;
+    use halo2_curves::bn256::Fr;
+
+    #[test]
+    fn test_compute_subtable_reads() {
+        let table = RangeTable::new(2, 4);
+        let subtables = vec![DecomposableTable::<Fr>::subtable(&table, 0); 2];
+        let (dims, reads) = compute_subtable_reads(&subtables, &table, &[0x12, 0xf3, 0x13]);
+        assert_eq!(dims, vec![vec![2, 3, 3], vec![1, 15, 1]]);
+        assert_eq!(reads[1], [1, 15, 1].map(Fr::from).to_vec());
+        let witness = LookupWitness::from_indices(&subtables[1], dims[1].clone());
+        assert_eq!(witness.multiplicities[1], 2);
+        assert_eq!(witness.multiplicities[0], 0);
+        assert_eq!(witness.subtable, [1, 15].map(Fr::from).to_vec());
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{
+    piop::sum_check::identity_eval,
+    poly::multilinear::MultilinearPolynomial,
+    util::{
+        arithmetic::{powers, PrimeField},
+        expression::{Expression, Query, Rotation},
+        Itertools,
+    },
+};
+use std::fmt::Debug;
+
+/// Table with `2^num_vars` entries, where the entry at `index` is
+/// `g(T_0[r_0], ..., T_{c-1}[r_{c-1}])` for the chunks `r_i` of `index` and
+/// small subtables `T_i`, so it never has to be materialized.
+pub trait DecomposableTable<F: PrimeField>: Clone + Debug {
+    /// Number of bits of an index of the table.
+    fn num_vars(&self) -> usize;
+
+    /// Number of chunks `c` an index is split into.
+    fn num_chunks(&self) -> usize;
+
+    /// Number of bits `ℓ` of a chunk, so every subtable has `2^ℓ` entries.
+    fn subtable_num_vars(&self) -> usize;
+
+    /// Entries of the subtable read by `chunk`.
+    fn subtable(&self, chunk: usize) -> Vec<F>;
+
+    /// Evaluation at `x` of the multilinear extension of the subtable read by
+    /// `chunk`, which the verifier computes itself, so structured subtables
+    /// should override it with a closed form.
+    fn evaluate_subtable(&self, chunk: usize, x: &[F]) -> F {
+        MultilinearPolynomial::new(self.subtable(chunk)).evaluate(x)
+    }
+
+    /// Indices into the subtables of the chunks of `index`.
+    fn split(&self, index: u128) -> Vec<usize>;
+
+    /// `g` in terms of `Expression::Polynomial(Query::new(i, Rotation::cur()))`
+    /// for the entry read from `T_i`.
+    fn combine_expression(&self) -> Expression<F>;
+
+    fn combine(&self, subtable_entries: &[F]) -> F {
+        self.combine_expression().evaluate(
+            &|scalar| scalar,
+            &|_| unreachable!(),
+            &|query| subtable_entries[query.poly()],
+            &|_| unreachable!(),
+            &|value| -value,
+            &|lhs, rhs| lhs + &rhs,
+            &|lhs, rhs| lhs * &rhs,
+            &|value, scalar| scalar * value,
+        )
+    }
+
+    /// Entry of the table at `index`.
+    fn entry(&self, index: u128) -> F {
+        let entries = self
+            .split(index)
+            .into_iter()
+            .enumerate()
+            .map(|(chunk, r)| self.subtable(chunk)[r])
+            .collect_vec();
+        self.combine(&entries)
+    }
+}
+
+/// `Σ_i 2^{ℓ i} E_i`, shared by tables whose chunks are placed side by side.
+fn concat_expression<F: PrimeField>(num_chunks: usize, chunk_bits: usize) -> Expression<F> {
+    powers(F::from(1 << chunk_bits))
+        .take(num_chunks)
+        .enumerate()
+        .map(|(i, scalar)| Expression::Polynomial(Query::new(i, Rotation::cur())) * scalar)
+        .sum()
+}
+
+/// Range table `[0, 2^{c ℓ})`, e.g. `2^64` with 4 chunks of 16 bits, whose
+/// subtables are all the identity over `ℓ` bits.
+#[derive(Clone, Debug)]
+pub struct RangeTable {
+    num_chunks: usize,
+    chunk_bits: usize,
+}
+
+impl RangeTable {
+    pub fn new(num_chunks: usize, chunk_bits: usize) -> Self {
+        assert!(chunk_bits > 0 && chunk_bits < 32 && num_chunks * chunk_bits <= 128);
+        Self {
+            num_chunks,
+            chunk_bits,
+        }
+    }
+}
+
+impl<F: PrimeField> DecomposableTable<F> for RangeTable {
+    fn num_vars(&self) -> usize {
+        self.num_chunks * self.chunk_bits
+    }
+
+    fn num_chunks(&self) -> usize {
+        self.num_chunks
+    }
+
+    fn subtable_num_vars(&self) -> usize {
+        self.chunk_bits
+    }
+
+    fn subtable(&self, _: usize) -> Vec<F> {
+        (0..1u64 << self.chunk_bits).map(F::from).collect()
+    }
+
+    fn evaluate_subtable(&self, _: usize, x: &[F]) -> F {
+        identity_eval(x)
+    }
+
+    fn split(&self, index: u128) -> Vec<usize> {
+        let mask = (1 << self.chunk_bits) - 1;
+        (0..self.num_chunks)
+            .map(|i| ((index >> (i * self.chunk_bits)) & mask) as usize)
+            .collect()
+    }
+
+    fn combine_expression(&self) -> Expression<F> {
+        concat_expression(self.num_chunks, self.chunk_bits)
+    }
+}
+
+/// Bitwise AND of two operands of `c w` bits, indexed by `a | b << c w`.
+/// Chunk `i` reads `a_i & b_i` from a subtable indexed by `a_i | b_i << w`.
+#[derive(Clone, Debug)]
+pub struct AndTable {
+    num_chunks: usize,
+    chunk_bits: usize,
+}
+
+impl AndTable {
+    pub fn new(num_chunks: usize, chunk_bits: usize) -> Self {
+        assert!(chunk_bits > 0 && chunk_bits < 16 && num_chunks * chunk_bits <= 64);
+        Self {
+            num_chunks,
+            chunk_bits,
+        }
+    }
+
+    pub fn index(&self, a: u64, b: u64) -> u128 {
+        a as u128 | (b as u128) << (self.num_chunks * self.chunk_bits)
+    }
+}
+
+impl<F: PrimeField> DecomposableTable<F> for AndTable {
+    fn num_vars(&self) -> usize {
+        2 * self.num_chunks * self.chunk_bits
+    }
+
+    fn num_chunks(&self) -> usize {
+        self.num_chunks
+    }
+
+    fn subtable_num_vars(&self) -> usize {
+        2 * self.chunk_bits
+    }
+
+    fn subtable(&self, _: usize) -> Vec<F> {
+        let mask = (1 << self.chunk_bits) - 1;
+        (0..1u64 << (2 * self.chunk_bits))
+            .map(|r| F::from(r & (r >> self.chunk_bits) & mask))
+            .collect()
+    }
+
+    fn evaluate_subtable(&self, _: usize, x: &[F]) -> F {
+        let (a, b) = x.split_at(self.chunk_bits);
+        let bits = a.iter().zip(b).map(|(a, b)| *a * b).collect_vec();
+        identity_eval(&bits)
+    }
+
+    fn split(&self, index: u128) -> Vec<usize> {
+        let width = self.num_chunks * self.chunk_bits;
+        let mask = (1 << self.chunk_bits) - 1;
+        let (a, b) = (index & ((1 << width) - 1), index >> width);
+        (0..self.num_chunks)
+            .map(|i| {
+                let shift = i * self.chunk_bits;
+                let (a_i, b_i) = ((a >> shift) & mask, (b >> shift) & mask);
+                (a_i | b_i << self.chunk_bits) as usize
+            })
+            .collect()
+    }
+
+    fn combine_expression(&self) -> Expression<F> {
+        concat_expression(self.num_chunks, self.chunk_bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test::{rand_vec, seeded_std_rng};
+    use halo2_curves::bn256::Fr;
+
+    fn assert_subtable_mle<T: DecomposableTable<Fr>>(table: &T) {
+        let x = rand_vec(table.subtable_num_vars(), seeded_std_rng());
+        for chunk in 0..table.num_chunks() {
+            assert_eq!(
+                table.evaluate_subtable(chunk, &x),
+                MultilinearPolynomial::new(table.subtable(chunk)).evaluate(&x)
+            );
+        }
+    }
+
+    #[test]
+    fn test_range_table() {
+        let table = RangeTable::new(4, 16);
+        assert_eq!(DecomposableTable::<Fr>::num_vars(&table), 64);
+        let index = u64::MAX as u128 - 12345;
+        assert_eq!(
+            DecomposableTable::<Fr>::entry(&table, index),
+            Fr::from(u64::MAX - 12345)
+        );
+        assert_subtable_mle::<RangeTable>(&RangeTable::new(2, 4));
+    }
+
+    #[test]
+    fn test_and_table() {
+        let table = AndTable::new(4, 4);
+        let (a, b) = (0xbeef, 0x1234);
+        assert_eq!(
+            DecomposableTable::<Fr>::entry(&table, table.index(a, b)),
+            Fr::from(a & b)
+        );
+        assert_subtable_mle::<AndTable>(&table);
+    }
+}
@@ -0,0 +1,146 @@
+use super::{table::DecomposableTable, LassoVerifierParam};
+use crate::{
+    pcs::{Evaluation, PolynomialCommitmentScheme},
+    piop::{
+        gkr::verify_fractional_sum_check,
+        sum_check::{
+            classic::{ClassicSumCheck, EvaluationsProver},
+            evaluate, identity_eval, SumCheck as _,
+        },
+    },
+    poly::multilinear::MultilinearPolynomial,
+    util::{
+        arithmetic::PrimeField,
+        chain,
+        expression::{Expression, Query, Rotation},
+        izip, izip_eq,
+        transcript::TranscriptRead,
+        Itertools,
+    },
+    Error,
+};
+
+type SumCheck<F> = ClassicSumCheck<EvaluationsProver<F>>;
+
+/// Returns the commitment to the lookup vector, which is then known to only
+/// contain entries of the table.
+pub(super) fn verify<
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = MultilinearPolynomial<F>>,
+    T: DecomposableTable<F>,
+>(
+    vp: &LassoVerifierParam<F, Pcs, T>,
+    transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, F>,
+) -> Result<Pcs::Commitment, Error> {
+    let num_chunks = vp.table.num_chunks();
+    let lookup_comms = Pcs::read_commitments(&vp.lookup_pcs, 1 + 2 * num_chunks, transcript)?;
+    let m_comms = Pcs::read_commitments(&vp.subtable_pcs, num_chunks, transcript)?;
+
+    // a(r) = Σ_x eq(r, x) g(E_0(x), ..., E_{c-1}(x))
+    let r = transcript.squeeze_challenges(vp.num_vars);
+    let lookup_eval = transcript.read_field_element()?;
+    let expression = Expression::eq_xy(0) * vp.table.combine_expression();
+    let (x_eval, x) = SumCheck::verify(
+        &(),
+        vp.num_vars,
+        expression.degree(),
+        lookup_eval,
+        transcript,
+    )?;
+    let read_evals = transcript.read_field_elements(num_chunks)?;
+    let query_eval = {
+        let queries = (0..).map(|idx| Query::new(idx, Rotation::cur()));
+        let evals = izip!(queries, read_evals.iter().cloned()).collect();
+        evaluate::<_, usize>(&expression, vp.num_vars, &evals, &[], &[&r], &x)
+    };
+    if x_eval != query_eval {
+        return Err(err_unmatched_sum_check_output());
+    }
+
+    // Σ_x 1 / (β + dim_i(x) + γ E_i(x)) = Σ_y m_i(y) / (β + y + γ T_i(y))
+    let beta = transcript.squeeze_challenge();
+    let gamma = transcript.squeeze_challenge();
+    let fractions = transcript.read_field_elements(4 * num_chunks)?;
+    let (lookup_fractions, subtable_fractions) = fractions.split_at(2 * num_chunks);
+    for ((p_lookup, q_lookup), (p_subtable, q_subtable)) in izip_eq!(
+        lookup_fractions.iter().tuples(),
+        subtable_fractions.iter().tuples()
+    ) {
+        if *q_lookup == F::ZERO
+            || *q_subtable == F::ZERO
+            || *p_lookup * q_subtable != *p_subtable * q_lookup
+        {
+            return Err(Error::InvalidSnark(
+                "Unmatched between lookup and subtable fractions".to_string(),
+            ));
+        }
+    }
+
+    let (p_xs, q_xs, x_lookup) = verify_fractional_sum_check(
+        vp.num_vars,
+        lookup_fractions.iter().step_by(2).copied().map(Some),
+        lookup_fractions
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .copied()
+            .map(Some),
+        transcript,
+    )?;
+    let lookup_evals = transcript.read_field_elements(2 * num_chunks)?;
+    let (dim_evals, read_lookup_evals) = lookup_evals.split_at(num_chunks);
+    for (p_x, q_x, dim_eval, read_eval) in izip_eq!(&p_xs, &q_xs, dim_evals, read_lookup_evals) {
+        if *p_x != F::ONE || *q_x != beta + dim_eval + gamma * read_eval {
+            return Err(err_unmatched_sum_check_output());
+        }
+    }
+
+    let subtable_num_vars = vp.table.subtable_num_vars();
+    let (p_ys, q_ys, x_subtable) = verify_fractional_sum_check(
+        subtable_num_vars,
+        subtable_fractions.iter().step_by(2).copied().map(Some),
+        subtable_fractions
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .copied()
+            .map(Some),
+        transcript,
+    )?;
+    let m_evals = transcript.read_field_elements(num_chunks)?;
+    let id_eval = identity_eval(&x_subtable);
+    for (chunk, (p_y, q_y, m_eval)) in izip_eq!(&p_ys, &q_ys, &m_evals).enumerate() {
+        let t_eval = vp.table.evaluate_subtable(chunk, &x_subtable);
+        if p_y != m_eval || *q_y != beta + id_eval + gamma * t_eval {
+            return Err(err_unmatched_sum_check_output());
+        }
+    }
+
+    // lookup polys [a, dim_0, .., dim_{c-1}, E_0, .., E_{c-1}] at r, x and x_lookup
+    let points = [r, x, x_lookup];
+    let evals = chain![
+        [Evaluation::new(0, 0, lookup_eval)],
+        izip!(1 + num_chunks.., read_evals).map(|(idx, eval)| Evaluation::new(idx, 1, eval)),
+        izip!(1.., lookup_evals).map(|(idx, eval)| Evaluation::new(idx, 2, eval)),
+    ]
+    .collect_vec();
+    Pcs::batch_verify(&vp.lookup_pcs, &lookup_comms, &points, &evals, transcript)?;
+    let evals = m_evals
+        .into_iter()
+        .enumerate()
+        .map(|(idx, eval)| Evaluation::new(idx, 0, eval))
+        .collect_vec();
+    Pcs::batch_verify(
+        &vp.subtable_pcs,
+        &m_comms,
+        &[x_subtable],
+        &evals,
+        transcript,
+    )?;
+
+    Ok(lookup_comms.into_iter().next().unwrap())
+}
+
+fn err_unmatched_sum_check_output() -> Error {
+    Error::InvalidSumcheck("Unmatched between sum_check output and query evaluation".to_string())
+}
@@ -0,0 +1,248 @@
+use crate::{
+    backend::cq::generate_table_and_lookup,
+    pcs::PolynomialCommitmentScheme,
+    poly::univariate::UnivariatePolynomial,
+    util::{
+        arithmetic::PrimeField,
+        transcript::{InMemoryTranscript, Keccak256Transcript, TranscriptRead, TranscriptWrite},
+        Deserialize, DeserializeOwned, Serialize,
+    },
+    Error,
+};
+use halo2_curves::ff::WithSmallOrderMulGroup;
+use std::{fmt::Debug, hash::Hash, marker::PhantomData, time::Instant};
+
+pub mod preprocessor;
+pub mod prover;
+pub mod verifier;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LogupProverParam<F, Pcs>
+where
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F>,
+{
+    pcs: Pcs::ProverParam,
+    g: F,
+    table: Vec<F>,
+    t_poly: UnivariatePolynomial<F>,
+    t_comm: Pcs::Commitment,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LogupVerifierParam<F, Pcs>
+where
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F>,
+{
+    pcs: Pcs::VerifierParam,
+    g: F,
+    n: usize,
+    t_comm: Pcs::Commitment,
+}
+
+/// LogUp lookup, proving with multiplicities `m` that
+/// `Σ_i m_i / (β + t_i) = Σ_i 1 / (β + f_i)`.
+///
+/// The identity itself only needs field arithmetic, so this works over any
+/// `PrimeField` with a univariate `Pcs`.
+#[derive(Clone, Debug)]
+pub struct Logup<F, Pcs>(PhantomData<F>, PhantomData<Pcs>);
+
+impl<F, Pcs> Logup<F, Pcs>
+where
+    F: PrimeField + WithSmallOrderMulGroup<3> + Hash + Serialize + DeserializeOwned,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
+{
+    pub fn preprocess(
+        param: &Pcs::Param,
+        table: &[F],
+    ) -> Result<(LogupProverParam<F, Pcs>, LogupVerifierParam<F, Pcs>), Error> {
+        preprocessor::preprocess(param, table)
+    }
+
+    pub fn prove(
+        pp: &LogupProverParam<F, Pcs>,
+        lookup: &[F],
+        transcript: &mut impl TranscriptWrite<Pcs::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        prover::prove(pp, lookup, transcript)
+    }
+
+    pub fn verify(
+        vp: &LogupVerifierParam<F, Pcs>,
+        transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        verifier::verify(vp, transcript)
+    }
+}
+
+// Concrete implementation for BN256/Fr
+use crate::{
+    backend::lookup::{self, witness::pad_with_last},
+    pcs::univariate::{UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam},
+};
+use halo2_curves::bn256::{Bn256, Fr, G1Affine};
+use rand::RngCore;
+
+impl Logup<Fr, UnivariateKzg<Bn256>> {
+    // Run the full LogUp protocol with given table and lookup
+    pub fn test_logup_by_input(table: Vec<Fr>, lookup: Vec<Fr>) -> Vec<String> {
+        let mut timings: Vec<String> = vec![];
+
+        let start_total = Instant::now();
+
+        // 1. Setup
+        let start = Instant::now();
+        let mut rng = crate::util::test::std_rng();
+        let param = UnivariateKzg::<Bn256>::setup(table.len(), 1, &mut rng).unwrap();
+        let (pp, vp) = Self::preprocess(&param, &table).unwrap();
+        let duration1 = start.elapsed();
+        timings.push(format!("Setup and preprocess: {}ms", duration1.as_millis()));
+
+        // 2. Prove
+        let start = Instant::now();
+        let mut transcript = Keccak256Transcript::new(());
+        Self::prove(&pp, &lookup, &mut transcript).unwrap();
+        let proof = transcript.into_proof();
+        let duration2 = start.elapsed();
+        timings.push(format!("Prove: {}ms", duration2.as_millis()));
+        timings.push(format!("Proof size: {}bytes", proof.len()));
+
+        // 3. Verify
+        let start = Instant::now();
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        Self::verify(&vp, &mut transcript).unwrap();
+        let duration3 = start.elapsed();
+        timings.push(format!("Verify: {}ms", duration3.as_millis()));
+
+        let total_duration = start_total.elapsed();
+        timings.push(format!("Total time: {}ms", total_duration.as_millis()));
+
+        timings
+    }
+
+    // Run the full LogUp protocol with table and lookup generated based on k
+    pub fn test_logup_by_k(k: usize) -> Vec<String> {
+        let (table, lookup) =
+            generate_table_and_lookup(2_usize.pow(k as u32), 2_usize.pow((k - 1) as u32));
+        Self::test_logup_by_input(table, lookup)
+    }
+}
+
+impl lookup::LookupScheme<Fr> for Logup<Fr, UnivariateKzg<Bn256>> {
+    type Param = UnivariateKzgParam<Bn256>;
+    type ProverParam = LogupProverParam<Fr, UnivariateKzg<Bn256>>;
+    type VerifierParam = LogupVerifierParam<Fr, UnivariateKzg<Bn256>>;
+    /// `[f(x)]1` of the padded lookup, which the proof carries.
+    type LookupCommitment = UnivariateKzgCommitment<G1Affine>;
+    type Proof = Vec<u8>;
+
+    fn setup(t: usize, _: usize, rng: impl RngCore) -> Result<Self::Param, Error> {
+        UnivariateKzg::<Bn256>::setup(t.next_power_of_two(), 1, rng)
+    }
+
+    /// The lookup size is only bounded by the table size, which it should be
+    /// at most.
+    fn preprocess(
+        param: &Self::Param,
+        table: &[Fr],
+        _: usize,
+    ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
+        Self::preprocess(param, table)
+    }
+
+    fn commit_lookup(
+        pp: &Self::ProverParam,
+        lookup: &[Fr],
+    ) -> Result<Self::LookupCommitment, Error> {
+        let f = pad_with_last(lookup, pp.table.len())?;
+        UnivariateKzg::<Bn256>::commit(&pp.pcs, &UnivariatePolynomial::lagrange(f).ifft())
+    }
+
+    fn prove(pp: &Self::ProverParam, lookup: &[Fr]) -> Result<Vec<u8>, Error> {
+        let mut transcript = Keccak256Transcript::new(());
+        Self::prove(pp, lookup, &mut transcript)?;
+        Ok(transcript.into_proof())
+    }
+
+    fn proof_to_bytes(proof: &Vec<u8>) -> Vec<u8> {
+        proof.clone()
+    }
+
+    fn verify(
+        vp: &Self::VerifierParam,
+        f_comm: &Self::LookupCommitment,
+        proof: &Vec<u8>,
+    ) -> Result<(), Error> {
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        let proof_f_comm: G1Affine = transcript.read_commitment()?;
+        if proof_f_comm != f_comm.0 {
+            return Err(Error::InvalidSnark(
+                "Proof isn't of the lookup commitment".to_string(),
+            ));
+        }
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        Self::verify(vp, &mut transcript)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        pcs::univariate::UnivariateKzg,
+        util::{test::std_rng, transcript::Keccak256Transcript},
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+
+    type Pcs = UnivariateKzg<Bn256>;
+    type Lb = Logup<Fr, Pcs>;
+
+    fn run_logup(table: Vec<Fr>, lookup: Vec<Fr>) -> Result<(), Error> {
+        let mut rng = std_rng();
+        let param = Pcs::setup(table.len(), 1, &mut rng).unwrap();
+        let (pp, vp) = Lb::preprocess(&param, &table).unwrap();
+
+        let mut transcript = Keccak256Transcript::new(());
+        Lb::prove(&pp, &lookup, &mut transcript)?;
+        let proof = transcript.into_proof();
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        Lb::verify(&vp, &mut transcript)
+    }
+
+    #[test]
+    fn test_e2e() {
+        let table = [1, 2, 3, 4, 5, 6, 7, 8].map(Fr::from).to_vec();
+        run_logup(table.clone(), [3, 3, 8, 1].map(Fr::from).to_vec()).unwrap();
+        // lookup as large as the table, all of one value
+        run_logup(table.clone(), vec![Fr::from(5); 8]).unwrap();
+        // repeated table values
+        run_logup(
+            [1, 1, 2, 2].map(Fr::from).to_vec(),
+            [2, 1, 2].map(Fr::from).to_vec(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            run_logup(table.clone(), Vec::new()),
+            Err(Error::InvalidPcsParam(
+                "Lookup size should be in [1, 8] but got 0".to_string()
+            ))
+        );
+        assert_eq!(
+            run_logup(table, [3, 9].map(Fr::from).to_vec()),
+            Err(Error::InvalidSnark(
+                "Lookup values should be in table".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_logup_by_k() {
+        let timings = Lb::test_logup_by_k(4);
+        assert!(timings
+            .iter()
+            .any(|timing| timing.starts_with("Proof size: ")));
+    }
+}
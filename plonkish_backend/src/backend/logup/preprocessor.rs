@@ -0,0 +1,68 @@
+use super::{LogupProverParam, LogupVerifierParam};
+use crate::{
+    pcs::PolynomialCommitmentScheme,
+    poly::univariate::UnivariatePolynomial,
+    util::arithmetic::{root_of_unity, PrimeField},
+    Error,
+};
+use halo2_curves::ff::WithSmallOrderMulGroup;
+
+pub(super) fn preprocess<
+    F: PrimeField + WithSmallOrderMulGroup<3>,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
+>(
+    param: &Pcs::Param,
+    table: &[F],
+) -> Result<(LogupProverParam<F, Pcs>, LogupVerifierParam<F, Pcs>), Error> {
+    let n = table.len();
+    if !n.is_power_of_two() {
+        return Err(Error::InvalidPcsParam(format!(
+            "Table size should be a power of two but got {n}"
+        )));
+    }
+    // every polynomial, the quotient included, has degree less than n
+    let (pcs_pp, pcs_vp) = Pcs::trim(param, n, 1)?;
+    let g = root_of_unity(n.ilog2() as usize);
+    let t_poly = UnivariatePolynomial::lagrange(table.to_vec()).ifft();
+    let t_comm = Pcs::commit(&pcs_pp, &t_poly)?;
+
+    let pp = LogupProverParam {
+        pcs: pcs_pp,
+        g,
+        table: table.to_vec(),
+        t_poly,
+        t_comm: t_comm.clone(),
+    };
+    let vp = LogupVerifierParam {
+        pcs: pcs_vp,
+        g,
+        n,
+        t_comm,
+    };
+    Ok((pp, vp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pcs::univariate::UnivariateKzg, util::test::std_rng};
+    use halo2_curves::{
+        bn256::{Bn256, Fr},
+        ff::Field,
+    };
+
+    type Pcs = UnivariateKzg<Bn256>;
+
+    #[test]
+    fn test_preprocess() {
+        let mut rng = std_rng();
+        let table = vec![Fr::one(), Fr::from(2), Fr::from(3), Fr::from(4)];
+        let param = Pcs::setup(8, 1, &mut rng).unwrap();
+        let (pp, vp) = preprocess::<Fr, Pcs>(&param, &table).unwrap();
+        assert_eq!(pp.g, vp.g);
+        assert_eq!(vp.g.pow([4]), Fr::one());
+        assert_eq!(pp.t_poly.evaluate(&vp.g), table[1]);
+
+        assert!(preprocess::<Fr, Pcs>(&param, &table[..3]).is_err());
+    }
+}
@@ -0,0 +1,219 @@
+use super::LogupProverParam;
+use crate::{
+    backend::lookup::witness::{pad_with_last, LookupWitness},
+    pcs::{Evaluation, PolynomialCommitmentScheme},
+    poly::univariate::UnivariatePolynomial,
+    util::{
+        arithmetic::{batch_invert, PrimeField},
+        transcript::TranscriptWrite,
+    },
+    Error,
+};
+use halo2_curves::ff::WithSmallOrderMulGroup;
+use std::hash::Hash;
+
+pub(super) fn prove<
+    F: PrimeField + WithSmallOrderMulGroup<3> + Hash,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
+>(
+    pp: &LogupProverParam<F, Pcs>,
+    lookup: &[F],
+    transcript: &mut impl TranscriptWrite<Pcs::CommitmentChunk, F>,
+) -> Result<(), Error> {
+    let n = pp.table.len();
+
+    // round 1
+    // pad f to n with its last value, which is then counted as looked up,
+    // whose polynomial `f(X)` is the first commitment of the proof
+    let f = pad_with_last(lookup, n)?;
+    let m = compute_multiplicities(&pp.table, &f)
+        .ok_or_else(|| Error::InvalidSnark("Lookup values should be in table".to_string()))?;
+    let f_poly = UnivariatePolynomial::lagrange(f.clone()).ifft();
+    let m_poly = UnivariatePolynomial::lagrange(m.clone()).ifft();
+    let f_comm = Pcs::commit_and_write(&pp.pcs, &f_poly, transcript)?;
+    let m_comm = Pcs::commit_and_write(&pp.pcs, &m_poly, transcript)?;
+
+    // round 2
+    let beta = transcript.squeeze_challenge();
+    let (a, b) = compute_inverses(&beta, &pp.table, &f, &m);
+    let z = compute_running_sum(&a, &b);
+    let a_poly = UnivariatePolynomial::lagrange(a).ifft();
+    let b_poly = UnivariatePolynomial::lagrange(b).ifft();
+    let z_poly = UnivariatePolynomial::lagrange(z.clone()).ifft();
+    let a_comm = Pcs::commit_and_write(&pp.pcs, &a_poly, transcript)?;
+    let b_comm = Pcs::commit_and_write(&pp.pcs, &b_poly, transcript)?;
+    let z_comm = Pcs::commit_and_write(&pp.pcs, &z_poly, transcript)?;
+
+    // round 3
+    let delta = transcript.squeeze_challenge();
+    let q_poly = compute_quotient_polynomial(
+        &beta, &delta, &z, &pp.t_poly, &f_poly, &m_poly, &a_poly, &b_poly, &z_poly,
+    );
+    let q_comm = Pcs::commit_and_write(&pp.pcs, &q_poly, transcript)?;
+
+    // round 4
+    let zeta = transcript.squeeze_challenge();
+    let g_zeta = pp.g * zeta;
+    let polys = [
+        &pp.t_poly, &f_poly, &m_poly, &a_poly, &b_poly, &z_poly, &q_poly,
+    ];
+    // [t_eval, f_eval, m_eval, a_eval, b_eval, z_eval], as q_eval is derived
+    let evals = polys
+        .iter()
+        .map(|poly| poly.evaluate(&zeta))
+        .collect::<Vec<_>>();
+    transcript.write_field_elements(&evals[..6])?;
+    let z_g_eval = z_poly.evaluate(&g_zeta);
+    transcript.write_field_element(&z_g_eval)?;
+
+    // round 5
+    let batch_comms = [
+        &pp.t_comm, &f_comm, &m_comm, &a_comm, &b_comm, &z_comm, &q_comm,
+    ];
+    let batch_points = [zeta, g_zeta];
+    let batch_evals = evals
+        .into_iter()
+        .enumerate()
+        .map(|(idx, eval)| Evaluation::new(idx, 0, eval))
+        .chain(Some(Evaluation::new(5, 1, z_g_eval)))
+        .collect::<Vec<_>>();
+    Pcs::batch_open(
+        &pp.pcs,
+        polys,
+        batch_comms,
+        &batch_points,
+        &batch_evals,
+        transcript,
+    )?;
+    Ok(())
+}
+
+/// `m_i`, the number of times `table[i]` is looked up, all counted at the
+/// first occurrence of a repeated table value. `None` if some lookup value
+/// is not in `table`.
+fn compute_multiplicities<F: PrimeField>(table: &[F], lookup: &[F]) -> Option<Vec<F>> {
+    LookupWitness::new(table, lookup)
+        .ok()
+        .map(|witness| witness.field_multiplicities())
+}
+
+/// `a_i = m_i / (β + t_i)` and `b_i = 1 / (β + f_i)`, with all `2n`
+/// denominators inverted at once.
+fn compute_inverses<F: PrimeField>(beta: &F, t: &[F], f: &[F], m: &[F]) -> (Vec<F>, Vec<F>) {
+    let mut denoms = t
+        .iter()
+        .chain(f.iter())
+        .map(|value| *beta + value)
+        .collect::<Vec<_>>();
+    batch_invert(&mut denoms);
+    let (t_inv, f_inv) = denoms.split_at(t.len());
+    let a = t_inv.iter().zip(m).map(|(inv, m)| *inv * m).collect();
+    (a, f_inv.to_vec())
+}
+
+/// `z_0 = 0` and `z_{i+1} = z_i + a_i - b_i`, which wraps around to `z_0`
+/// exactly when `Σ_i a_i = Σ_i b_i`.
+fn compute_running_sum<F: PrimeField>(a: &[F], b: &[F]) -> Vec<F> {
+    let mut z = Vec::with_capacity(a.len());
+    let mut acc = F::ZERO;
+    for (a, b) in a.iter().zip(b) {
+        z.push(acc);
+        acc += *a - b;
+    }
+    assert!(acc == F::ZERO);
+    z
+}
+
+/// `q(X) = (C_1 + δ C_2 + δ^2 C_3 + δ^3 C_4)(X) / (X^n - 1)` for the
+/// constraints on `H`:
+///   C_1 = A(X) (β + t(X)) - m(X)
+///   C_2 = B(X) (β + f(X)) - 1
+///   C_3 = L_0(X) Z(X)
+///   C_4 = Z(gX) - Z(X) - A(X) + B(X)
+fn compute_quotient_polynomial<F: PrimeField + WithSmallOrderMulGroup<3>>(
+    beta: &F,
+    delta: &F,
+    z: &[F],
+    t_poly: &UnivariatePolynomial<F>,
+    f_poly: &UnivariatePolynomial<F>,
+    m_poly: &UnivariatePolynomial<F>,
+    a_poly: &UnivariatePolynomial<F>,
+    b_poly: &UnivariatePolynomial<F>,
+    z_poly: &UnivariatePolynomial<F>,
+) -> UnivariatePolynomial<F> {
+    let n = z.len();
+    let l0_poly = {
+        let mut values = vec![F::ZERO; n];
+        values[0] = F::ONE;
+        UnivariatePolynomial::lagrange(values).ifft()
+    };
+    let z_shift_poly = {
+        let values = z[1..].iter().chain(z[..1].iter()).copied().collect();
+        UnivariatePolynomial::lagrange(values).ifft()
+    };
+    let c1 = &a_poly.poly_mul(t_poly.clone() + *beta) - m_poly;
+    let c2 = b_poly.poly_mul(f_poly.clone() + *beta) + -F::ONE;
+    let c3 = l0_poly.poly_mul(z_poly.clone());
+    let c4 = &(&(&z_shift_poly - z_poly) - a_poly) + b_poly;
+    let delta_2 = delta.square();
+    let agg = [c1, &c2 * delta, &c3 * delta_2, &c4 * (delta_2 * delta)]
+        .into_iter()
+        .sum::<UnivariatePolynomial<F>>();
+    let vanish = {
+        // x^n - 1
+        let mut coeffs = vec![F::ZERO; n + 1];
+        coeffs[0] = -F::ONE;
+        coeffs[n] = F::ONE;
+        UnivariatePolynomial::monomial(coeffs)
+    };
+    &agg / &vanish
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_curves::{bn256::Fr, ff::Field};
+
+    #[test]
+    fn test_compute_multiplicities() {
+        let table = [1, 2, 3, 2].map(Fr::from).to_vec();
+        let lookup = [2, 2, 4].map(Fr::from).to_vec();
+        assert_eq!(compute_multiplicities(&table, &lookup), None);
+        let lookup = [2, 3, 2, 2].map(Fr::from).to_vec();
+        assert_eq!(
+            compute_multiplicities(&table, &lookup),
+            Some([0, 3, 1, 0].map(Fr::from).to_vec())
+        );
+    }
+
+    #[test]
+    fn test_compute_running_sum() {
+        let table = [1, 2, 3, 4].map(Fr::from).to_vec();
+        let f = [4, 4, 1, 4].map(Fr::from).to_vec();
+        let m = compute_multiplicities(&table, &f).unwrap();
+        let beta = Fr::from(7);
+        let (a, b) = compute_inverses(&beta, &table, &f, &m);
+        assert_eq!(a[3], Fr::from(3) * Fr::from(11).invert().unwrap());
+        assert_eq!(b[2], Fr::from(8).invert().unwrap());
+        let z = compute_running_sum(&a, &b);
+        assert_eq!(z[0], Fr::zero());
+        assert_eq!(z[1], a[0] - b[0]);
+    }
+
+    #[test]
+    fn test_compute_quotient_polynomial() {
+        let table = [1, 2, 3, 4].map(Fr::from).to_vec();
+        let f = [2, 2, 3, 2].map(Fr::from).to_vec();
+        let m = compute_multiplicities(&table, &f).unwrap();
+        let (beta, delta) = (Fr::from(5), Fr::from(3));
+        let (a, b) = compute_inverses(&beta, &table, &f, &m);
+        let z = compute_running_sum(&a, &b);
+        let [t_poly, f_poly, m_poly, a_poly, b_poly, z_poly] = [table, f, m, a, b, z.clone()]
+            .map(|values| UnivariatePolynomial::lagrange(values).ifft());
+        // divides exactly, or `/` would panic
+        let q_poly = compute_quotient_polynomial(
+            &beta, &delta, &z, &t_poly, &f_poly, &m_poly, &a_poly, &b_poly, &z_poly,
+        );
+        assert!(q_poly.degree() < 4);
+    }
+}
@@ -0,0 +1,77 @@
+use super::LogupVerifierParam;
+use crate::{
+    pcs::{Evaluation, PolynomialCommitmentScheme},
+    poly::univariate::UnivariatePolynomial,
+    util::{arithmetic::PrimeField, transcript::TranscriptRead},
+    Error,
+};
+
+pub(super) fn verify<
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
+>(
+    vp: &LogupVerifierParam<F, Pcs>,
+    transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, F>,
+) -> Result<(), Error> {
+    let f_comm = Pcs::read_commitment(&vp.pcs, transcript)?;
+    let m_comm = Pcs::read_commitment(&vp.pcs, transcript)?;
+    let beta = transcript.squeeze_challenge();
+    let a_comm = Pcs::read_commitment(&vp.pcs, transcript)?;
+    let b_comm = Pcs::read_commitment(&vp.pcs, transcript)?;
+    let z_comm = Pcs::read_commitment(&vp.pcs, transcript)?;
+    let delta = transcript.squeeze_challenge();
+    let q_comm = Pcs::read_commitment(&vp.pcs, transcript)?;
+    let zeta = transcript.squeeze_challenge();
+    // [t_eval, f_eval, m_eval, a_eval, b_eval, z_eval]
+    let evals = transcript.read_field_elements(6)?;
+    let z_g_eval = transcript.read_field_element()?;
+    let q_eval = compute_quotient_polynomial_eval(vp.n, &beta, &delta, &zeta, &evals, &z_g_eval)?;
+
+    let batch_comms = [
+        &vp.t_comm, &f_comm, &m_comm, &a_comm, &b_comm, &z_comm, &q_comm,
+    ];
+    let batch_points = [zeta, vp.g * zeta];
+    let batch_evals = evals
+        .into_iter()
+        .chain(Some(q_eval))
+        .enumerate()
+        .map(|(idx, eval)| Evaluation::new(idx, 0, eval))
+        .chain(Some(Evaluation::new(5, 1, z_g_eval)))
+        .collect::<Vec<_>>();
+    Pcs::batch_verify(
+        &vp.pcs,
+        batch_comms,
+        &batch_points,
+        &batch_evals,
+        transcript,
+    )
+}
+
+/// `q(ζ)` from the claimed evaluations, with `L_0(ζ) = (ζ^n - 1) / (n (ζ - 1))`
+/// and `1 / (ζ^n - 1)` sharing a single inversion.
+fn compute_quotient_polynomial_eval<F: PrimeField>(
+    n: usize,
+    beta: &F,
+    delta: &F,
+    zeta: &F,
+    evals: &[F],
+    z_g_eval: &F,
+) -> Result<F, Error> {
+    let [t_eval, f_eval, m_eval, a_eval, b_eval, z_eval]: [F; 6] = evals.try_into().unwrap();
+    let vanish_eval = zeta.pow([n as u64]) - F::ONE;
+    let denom_inv =
+        Option::<F>::from((vanish_eval * (F::from(n as u64) * (*zeta - F::ONE))).invert())
+            .ok_or_else(|| {
+                Error::InvalidSnark("ζ should not be in the table domain".to_string())
+            })?;
+    let l0_eval = vanish_eval.square() * denom_inv;
+
+    let c1 = a_eval * (*beta + t_eval) - m_eval;
+    let c2 = b_eval * (*beta + f_eval) - F::ONE;
+    let c3 = l0_eval * z_eval;
+    let c4 = *z_g_eval - z_eval - a_eval + b_eval;
+    let delta_2 = delta.square();
+    let agg = c1 + c2 * delta + c3 * delta_2 + c4 * delta_2 * delta;
+    let vanish_inv = F::from(n as u64) * (*zeta - F::ONE) * denom_inv;
+    Ok(agg * vanish_inv)
+}
@@ -0,0 +1,49 @@
+use super::{LogupGkrProverParam, LogupGkrVerifierParam};
+use crate::{
+    backend::lookup::witness::pad_to_power_of_two, pcs::PolynomialCommitmentScheme,
+    poly::multilinear::MultilinearPolynomial, util::arithmetic::PrimeField, Error,
+};
+
+pub(super) fn preprocess<
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = MultilinearPolynomial<F>>,
+>(
+    param: &Pcs::Param,
+    table: &[F],
+    num_vars: usize,
+) -> Result<(LogupGkrProverParam<F, Pcs>, LogupGkrVerifierParam<F, Pcs>), Error> {
+    let last = *table
+        .last()
+        .ok_or_else(|| Error::InvalidPcsParam("Table should be non-empty".to_string()))?;
+    // repeated table values are never looked up past their first occurrence,
+    // so padding with the last one keeps the table the same set
+    let table = pad_to_power_of_two(table, last);
+    let table_num_vars = table.len().ilog2() as usize;
+    if num_vars == 0 || table_num_vars == 0 {
+        return Err(Error::InvalidPcsParam(
+            "Lookup and table should have at least 1 variable".to_string(),
+        ));
+    }
+    // f for lookups, and m and t for the table
+    let (lookup_pcs_pp, lookup_pcs_vp) = Pcs::trim(param, 1 << num_vars, 1)?;
+    let (table_pcs_pp, table_pcs_vp) = Pcs::trim(param, table.len(), 2)?;
+    let t_poly = MultilinearPolynomial::new(table.clone());
+    let t_comm = Pcs::commit(&table_pcs_pp, &t_poly)?;
+
+    let pp = LogupGkrProverParam {
+        lookup_pcs: lookup_pcs_pp,
+        table_pcs: table_pcs_pp,
+        num_vars,
+        table,
+        t_poly,
+        t_comm: t_comm.clone(),
+    };
+    let vp = LogupGkrVerifierParam {
+        lookup_pcs: lookup_pcs_vp,
+        table_pcs: table_pcs_vp,
+        num_vars,
+        table_num_vars,
+        t_comm,
+    };
+    Ok((pp, vp))
+}
@@ -0,0 +1,97 @@
+use super::LogupGkrProverParam;
+use crate::{
+    backend::lookup::witness::{pad_with_last, LookupWitness},
+    pcs::{Evaluation, PolynomialCommitmentScheme},
+    piop::gkr::{fraction_sum, prove_fractional_sum_check},
+    poly::multilinear::MultilinearPolynomial,
+    util::{arithmetic::PrimeField, transcript::TranscriptWrite},
+    Error,
+};
+
+pub(super) fn prove<
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = MultilinearPolynomial<F>>,
+>(
+    pp: &LogupGkrProverParam<F, Pcs>,
+    lookup: &[F],
+    transcript: &mut impl TranscriptWrite<Pcs::CommitmentChunk, F>,
+) -> Result<(), Error> {
+    let n = 1 << pp.num_vars;
+
+    // round 1
+    // pad f to n with its last value, which is then counted as looked up
+    let f = pad_with_last(lookup, n)?;
+    let m = LookupWitness::new(&pp.table, lookup)?
+        .pad_with_last(n)
+        .field_multiplicities();
+    let f_poly = MultilinearPolynomial::new(f);
+    let m_poly = MultilinearPolynomial::new(m);
+    let f_comm = Pcs::commit_and_write(&pp.lookup_pcs, &f_poly, transcript)?;
+    let m_comm = Pcs::commit_and_write(&pp.table_pcs, &m_poly, transcript)?;
+
+    // round 2
+    // Σ_x 1 / (β + f(x)) = Σ_y m(y) / (β + t(y))
+    let beta = transcript.squeeze_challenge();
+    let ones = MultilinearPolynomial::new(vec![F::ONE; n]);
+    let f_q = compute_fingerprint(&beta, &f_poly);
+    let t_q = compute_fingerprint(&beta, &pp.t_poly);
+    let lookup_fraction = fraction_sum(&ones, &f_q);
+    let table_fraction = fraction_sum(&m_poly, &t_q);
+    transcript.write_field_elements([
+        &lookup_fraction.0,
+        &lookup_fraction.1,
+        &table_fraction.0,
+        &table_fraction.1,
+    ])?;
+
+    let (_, _, x_lookup) = prove_fractional_sum_check(
+        [Some(lookup_fraction.0)],
+        [Some(lookup_fraction.1)],
+        [&ones],
+        [&f_q],
+        transcript,
+    )?;
+    let f_eval = f_poly.evaluate(&x_lookup);
+    transcript.write_field_element(&f_eval)?;
+
+    let (_, _, x_table) = prove_fractional_sum_check(
+        [Some(table_fraction.0)],
+        [Some(table_fraction.1)],
+        [&m_poly],
+        [&t_q],
+        transcript,
+    )?;
+    let m_eval = m_poly.evaluate(&x_table);
+    let t_eval = pp.t_poly.evaluate(&x_table);
+    transcript.write_field_elements([&m_eval, &t_eval])?;
+
+    // round 3
+    // f at x_lookup, and m and t at x_table
+    Pcs::open(
+        &pp.lookup_pcs,
+        &f_poly,
+        &f_comm,
+        &x_lookup,
+        &f_eval,
+        transcript,
+    )?;
+    let evals = [Evaluation::new(0, 0, m_eval), Evaluation::new(1, 0, t_eval)];
+    Pcs::batch_open(
+        &pp.table_pcs,
+        [&m_poly, &pp.t_poly],
+        [&m_comm, &pp.t_comm],
+        &[x_table],
+        &evals,
+        transcript,
+    )?;
+
+    Ok(())
+}
+
+/// `β + u` of a polynomial `u`.
+fn compute_fingerprint<F: PrimeField>(
+    beta: &F,
+    u: &MultilinearPolynomial<F>,
+) -> MultilinearPolynomial<F> {
+    MultilinearPolynomial::new(u.evals().iter().map(|u| *beta + u).collect())
+}
@@ -0,0 +1,66 @@
+use super::LogupGkrVerifierParam;
+use crate::{
+    pcs::{Evaluation, PolynomialCommitmentScheme},
+    piop::gkr::verify_fractional_sum_check,
+    poly::multilinear::MultilinearPolynomial,
+    util::{arithmetic::PrimeField, transcript::TranscriptRead},
+    Error,
+};
+
+/// Returns the commitment to the lookup, which is then known to only contain
+/// values of the table.
+pub(super) fn verify<
+    F: PrimeField,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = MultilinearPolynomial<F>>,
+>(
+    vp: &LogupGkrVerifierParam<F, Pcs>,
+    transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, F>,
+) -> Result<Pcs::Commitment, Error> {
+    let f_comm = Pcs::read_commitment(&vp.lookup_pcs, transcript)?;
+    let m_comm = Pcs::read_commitment(&vp.table_pcs, transcript)?;
+
+    // Σ_x 1 / (β + f(x)) = Σ_y m(y) / (β + t(y))
+    let beta = transcript.squeeze_challenge();
+    let fractions = transcript.read_field_elements(4)?;
+    let [p_lookup, q_lookup, p_table, q_table] = fractions.try_into().unwrap();
+    if q_lookup == F::ZERO || q_table == F::ZERO || p_lookup * q_table != p_table * q_lookup {
+        return Err(Error::InvalidSnark(
+            "Unmatched between lookup and table fractions".to_string(),
+        ));
+    }
+
+    let (p_xs, q_xs, x_lookup) =
+        verify_fractional_sum_check(vp.num_vars, [Some(p_lookup)], [Some(q_lookup)], transcript)?;
+    let f_eval = transcript.read_field_element()?;
+    if p_xs[0] != F::ONE || q_xs[0] != beta + f_eval {
+        return Err(err_unmatched_sum_check_output());
+    }
+
+    let (p_ys, q_ys, x_table) = verify_fractional_sum_check(
+        vp.table_num_vars,
+        [Some(p_table)],
+        [Some(q_table)],
+        transcript,
+    )?;
+    let [m_eval, t_eval] = transcript.read_field_elements(2)?.try_into().unwrap();
+    if p_ys[0] != m_eval || q_ys[0] != beta + t_eval {
+        return Err(err_unmatched_sum_check_output());
+    }
+
+    // f at x_lookup, and m and t at x_table
+    Pcs::verify(&vp.lookup_pcs, &f_comm, &x_lookup, &f_eval, transcript)?;
+    let evals = [Evaluation::new(0, 0, m_eval), Evaluation::new(1, 0, t_eval)];
+    Pcs::batch_verify(
+        &vp.table_pcs,
+        [&m_comm, &vp.t_comm],
+        &[x_table],
+        &evals,
+        transcript,
+    )?;
+
+    Ok(f_comm)
+}
+
+fn err_unmatched_sum_check_output() -> Error {
+    Error::InvalidSumcheck("Unmatched between sum_check output and query evaluation".to_string())
+}
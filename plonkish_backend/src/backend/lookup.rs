@@ -0,0 +1,122 @@
+use crate::{
+    util::{
+        arithmetic::Field,
+        hash::{Hash, Keccak256},
+    },
+    Error,
+};
+use rand::RngCore;
+use std::fmt::Debug;
+
+pub mod witness;
+
+/// Lookup argument of a vector of size `m` into a table, with the table
+/// preprocessed once for any number of lookups, so that applications and
+/// benchmarks are generic over the protocol.
+pub trait LookupScheme<F: Field>: Clone + Debug {
+    type Param: Clone + Debug;
+    type ProverParam: Clone + Debug;
+    type VerifierParam: Clone + Debug;
+    /// What the verifier is given of the lookup, e.g. a commitment to it.
+    type LookupCommitment: Clone + Debug;
+    type Proof: Clone + Debug;
+
+    /// Sample an SRS for tables of size `t` and lookups of size `m`.
+    fn setup(t: usize, m: usize, rng: impl RngCore) -> Result<Self::Param, Error>;
+
+    fn preprocess(
+        param: &Self::Param,
+        table: &[F],
+        m: usize,
+    ) -> Result<(Self::ProverParam, Self::VerifierParam), Error>;
+
+    fn commit_lookup(pp: &Self::ProverParam, lookup: &[F])
+        -> Result<Self::LookupCommitment, Error>;
+
+    fn prove(pp: &Self::ProverParam, lookup: &[F]) -> Result<Self::Proof, Error>;
+
+    /// Encoding of `proof` as sent to the verifier, e.g. to measure its size.
+    fn proof_to_bytes(proof: &Self::Proof) -> Vec<u8>;
+
+    fn verify(
+        vp: &Self::VerifierParam,
+        lookup_comm: &Self::LookupCommitment,
+        proof: &Self::Proof,
+    ) -> Result<(), Error>;
+}
+
+/// Public instance of a lookup argument, with a canonical encoding, so that
+/// prover and verifier bind the very same statement, e.g. in the transcript or
+/// as a cache key of verification results.
+pub trait Instance {
+    /// Identifier of the lookup argument.
+    const PROTOCOL_ID: &'static [u8];
+    /// Version of the encoding, bumped on any change of [`Instance::write_bytes`].
+    const VERSION: u8;
+
+    /// Write the canonical encoding of the instance itself.
+    fn write_bytes(&self, bytes: &mut Vec<u8>);
+
+    /// `len(PROTOCOL_ID) || PROTOCOL_ID || VERSION || instance`.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.push(Self::PROTOCOL_ID.len() as u8);
+        bytes.extend_from_slice(Self::PROTOCOL_ID);
+        bytes.push(Self::VERSION);
+        self.write_bytes(&mut bytes);
+        bytes
+    }
+
+    fn digest(&self) -> [u8; 32] {
+        Keccak256::digest(self.to_bytes()).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        backend::{
+            baloo::Baloo,
+            cq::{generate_table_and_lookup, Cq},
+            logup::Logup,
+            lookup::LookupScheme,
+            plookup::Plookup,
+        },
+        pcs::univariate::UnivariateKzg,
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+    use rand::rngs::OsRng;
+
+    fn run_lookup_scheme<S: LookupScheme<Fr>>() {
+        let (table, lookup) = generate_table_and_lookup(16, 8);
+        let param = S::setup(table.len(), lookup.len(), OsRng).unwrap();
+        let (pp, vp) = S::preprocess(&param, &table, lookup.len()).unwrap();
+        let proof = S::prove(&pp, &lookup).unwrap();
+        assert!(!S::proof_to_bytes(&proof).is_empty());
+        assert!(S::verify(&vp, &S::commit_lookup(&pp, &lookup).unwrap(), &proof).is_ok());
+
+        let other_lookup = vec![table[0]; lookup.len()];
+        let other_comm = S::commit_lookup(&pp, &other_lookup).unwrap();
+        assert!(S::verify(&vp, &other_comm, &proof).is_err());
+    }
+
+    #[test]
+    fn baloo() {
+        run_lookup_scheme::<Baloo>();
+    }
+
+    #[test]
+    fn cq() {
+        run_lookup_scheme::<Cq>();
+    }
+
+    #[test]
+    fn plookup() {
+        run_lookup_scheme::<Plookup<Fr, UnivariateKzg<Bn256>>>();
+    }
+
+    #[test]
+    fn logup() {
+        run_lookup_scheme::<Logup<Fr, UnivariateKzg<Bn256>>>();
+    }
+}
@@ -0,0 +1,207 @@
+//! Witness of a lookup into a table which every lookup argument derives the
+//! same way: the table index of each lookup value, the multiplicities, and
+//! the deduplicated subtable of the used table values.
+
+use crate::{
+    util::{arithmetic::PrimeField, Serialize},
+    Error,
+};
+use std::collections::BTreeMap;
+
+/// Witness of a lookup, with repeated table values all used at their first
+/// occurrence.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LookupWitness<F> {
+    /// Table index of each lookup value.
+    pub indices: Vec<usize>,
+    /// Number of lookup values at each table index, e.g. `m` of LogUp.
+    pub multiplicities: Vec<usize>,
+    /// Table indices used at least once in ascending order, e.g. `I` of
+    /// Baloo.
+    pub subtable_indices: Vec<usize>,
+    /// Table values at `subtable_indices`, e.g. `t_I` of Baloo.
+    pub subtable: Vec<F>,
+}
+
+/// `(position in lookup, value)` of every lookup value missing in the table.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MissingValues<F>(pub Vec<(usize, F)>);
+
+impl<F> From<MissingValues<F>> for Error {
+    fn from(MissingValues(missing): MissingValues<F>) -> Self {
+        Error::NotInTable {
+            positions: missing.into_iter().map(|(position, _)| position).collect(),
+        }
+    }
+}
+
+impl<F: PrimeField> LookupWitness<F> {
+    pub fn new(table: &[F], lookup: &[F]) -> Result<Self, MissingValues<F>> {
+        // not every field implements Hash nor Ord, so index the table by its repr
+        let mut positions = BTreeMap::new();
+        for (idx, value) in table.iter().enumerate().rev() {
+            positions.insert(value.to_repr().as_ref().to_vec(), idx);
+        }
+
+        let mut missing = Vec::new();
+        let mut indices = Vec::with_capacity(lookup.len());
+        let mut multiplicities = vec![0; table.len()];
+        for (position, value) in lookup.iter().enumerate() {
+            match positions.get(value.to_repr().as_ref()) {
+                Some(idx) => {
+                    indices.push(*idx);
+                    multiplicities[*idx] += 1;
+                }
+                None => missing.push((position, *value)),
+            }
+        }
+        if !missing.is_empty() {
+            return Err(MissingValues(missing));
+        }
+
+        Ok(Self::with_multiplicities(table, indices, multiplicities))
+    }
+
+    /// Witness of a lookup of the table indices `indices`, e.g. into a
+    /// subtable of Lasso, which may repeat values at the indices it reads.
+    pub fn from_indices(table: &[F], indices: Vec<usize>) -> Self {
+        let mut multiplicities = vec![0; table.len()];
+        for idx in &indices {
+            multiplicities[*idx] += 1;
+        }
+        Self::with_multiplicities(table, indices, multiplicities)
+    }
+
+    fn with_multiplicities(table: &[F], indices: Vec<usize>, multiplicities: Vec<usize>) -> Self {
+        let subtable_indices = (0..table.len())
+            .filter(|idx| multiplicities[*idx] != 0)
+            .collect::<Vec<_>>();
+        let subtable = subtable_indices.iter().map(|idx| table[*idx]).collect();
+        Self {
+            indices,
+            multiplicities,
+            subtable_indices,
+            subtable,
+        }
+    }
+
+    /// Witness of the lookup padded to `n` values with its last one, which
+    /// is then counted as looked up as many more times, see [`pad_with_last`].
+    pub fn pad_with_last(mut self, n: usize) -> Self {
+        if let Some(&last) = self.indices.last() {
+            let padding = n.saturating_sub(self.indices.len());
+            self.indices.resize(self.indices.len() + padding, last);
+            self.multiplicities[last] += padding;
+        }
+        self
+    }
+
+    /// [`LookupWitness::multiplicities`] as field elements, e.g. to be
+    /// interpolated into `m(X)`.
+    pub fn field_multiplicities(&self) -> Vec<F> {
+        self.multiplicities
+            .iter()
+            .map(|m| F::from(*m as u64))
+            .collect()
+    }
+}
+
+/// `values` of a lookup padded to `n` with their last one, which fails if
+/// there are none or more than `n`.
+pub fn pad_with_last<T: Copy>(values: &[T], n: usize) -> Result<Vec<T>, Error> {
+    let Some(last) = values.last().filter(|_| values.len() <= n) else {
+        return Err(Error::InvalidPcsParam(format!(
+            "Lookup size should be in [1, {n}] but got {}",
+            values.len()
+        )));
+    };
+    let mut padded = values.to_vec();
+    padded.resize(n, *last);
+    Ok(padded)
+}
+
+/// `values` padded with `padding` to the next power of two, e.g. a lookup
+/// with a table value to the size of a multiplicative subgroup.
+pub fn pad_to_power_of_two<F: Copy>(values: &[F], padding: F) -> Vec<F> {
+    let mut padded = values.to_vec();
+    padded.resize(values.len().max(1).next_power_of_two(), padding);
+    padded
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        backend::lookup::witness::{
+            pad_to_power_of_two, pad_with_last, LookupWitness, MissingValues,
+        },
+        Error,
+    };
+    use halo2_curves::bn256::Fr;
+
+    #[test]
+    fn lookup_witness() {
+        let table = [1, 2, 3, 2].map(Fr::from).to_vec();
+        let lookup = [2, 3, 2, 2].map(Fr::from).to_vec();
+        assert_eq!(
+            LookupWitness::new(&table, &lookup),
+            Ok(LookupWitness {
+                indices: vec![1, 2, 1, 1],
+                multiplicities: vec![0, 3, 1, 0],
+                subtable_indices: vec![1, 2],
+                subtable: [2, 3].map(Fr::from).to_vec(),
+            })
+        );
+    }
+
+    #[test]
+    fn from_indices() {
+        // of repeated subtable values, counted at the indices read
+        let table = [0, 1, 0, 1].map(Fr::from).to_vec();
+        let witness = LookupWitness::from_indices(&table, vec![2, 3, 2]);
+        assert_eq!(witness.multiplicities, vec![0, 0, 2, 1]);
+        assert_eq!(witness.subtable, [0, 1].map(Fr::from).to_vec());
+        assert_eq!(
+            witness.field_multiplicities(),
+            [0, 0, 2, 1].map(Fr::from).to_vec()
+        );
+    }
+
+    #[test]
+    fn missing_values() {
+        let table = [1, 2, 3, 4].map(Fr::from).to_vec();
+        let lookup = [5, 1, 3, 7].map(Fr::from).to_vec();
+        let err = LookupWitness::new(&table, &lookup).unwrap_err();
+        assert_eq!(err, MissingValues(vec![(0, Fr::from(5)), (3, Fr::from(7))]));
+        assert_eq!(
+            Error::from(err),
+            Error::NotInTable {
+                positions: vec![0, 3]
+            }
+        );
+    }
+
+    #[test]
+    fn pad() {
+        let values = [3, 1, 2].map(Fr::from);
+        assert_eq!(
+            pad_to_power_of_two(&values, Fr::from(1)),
+            [3, 1, 2, 1].map(Fr::from).to_vec()
+        );
+        assert_eq!(pad_to_power_of_two(&values[..2], Fr::from(1)).len(), 2);
+        assert_eq!(pad_to_power_of_two(&[], Fr::from(1)).len(), 1);
+
+        assert_eq!(
+            pad_with_last(&values, 5),
+            Ok([3, 1, 2, 2, 2].map(Fr::from).to_vec())
+        );
+        assert!(pad_with_last(&values, 2).is_err());
+        assert!(pad_with_last::<Fr>(&[], 2).is_err());
+
+        let table = [1, 2, 3, 4].map(Fr::from).to_vec();
+        let witness = LookupWitness::new(&table, &[2, 4, 2].map(Fr::from))
+            .unwrap()
+            .pad_with_last(8);
+        assert_eq!(witness.indices, vec![1, 3, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(witness.multiplicities, vec![0, 7, 0, 1]);
+    }
+}
@@ -0,0 +1,245 @@
+//! Multiset equality of two committed vectors `a` and `b` of size `n`, i.e.
+//! that `b` is a permutation of `a`, by the grand product argument shared by
+//! plookup and the permutation checks of PLONK.
+//!
+//! The vectors are committed as the polynomials interpolating them over the
+//! subgroup `H = <g>` of order `n`. Given a challenge `γ`, the prover commits
+//! to the grand product `z` with `z(1) = 1` and
+//! `z(g^(i+1)) = z(g^i) (a_i + γ) / (b_i + γ)`, which wraps around to `1` iff
+//! `∏ (a_i + γ) = ∏ (b_i + γ)`, and shows that `z` is of this form by
+//!     q = (L_0 (z - 1) + δ (z(gX) (b + γ) - z (a + γ))) / Z_H,
+//! with `a`, `b`, `z` and `q` opened at `ζ` and `z` at `gζ`.
+
+use crate::{
+    pcs::{Evaluation, PolynomialCommitmentScheme},
+    poly::univariate::UnivariatePolynomial,
+    util::{
+        arithmetic::{batch_invert, root_of_unity, Field, PrimeField},
+        transcript::{TranscriptRead, TranscriptWrite},
+        Itertools,
+    },
+    Error,
+};
+use halo2_curves::ff::WithSmallOrderMulGroup;
+
+/// `z_i = ∏_{j < i} numers[j] / denoms[j]` for every `i` up to and including
+/// `numers.len()`, so that the last one is the whole product.
+pub fn grand_product<F: Field>(numers: &[F], denoms: &[F]) -> Vec<F> {
+    assert_eq!(numers.len(), denoms.len());
+
+    let mut denoms = denoms.to_vec();
+    batch_invert(&mut denoms);
+    let mut z = Vec::with_capacity(numers.len() + 1);
+    z.push(F::ONE);
+    for (numer, denom_inv) in numers.iter().zip(denoms) {
+        z.push(*z.last().unwrap() * numer * denom_inv);
+    }
+    z
+}
+
+/// Prove that `b` is a permutation of `a`, of the commitments `a_comm` and
+/// `b_comm` to their interpolations over `H`, which the verifier knows.
+pub fn prove_multiset_equality<F, Pcs>(
+    pp: &Pcs::ProverParam,
+    a: &[F],
+    b: &[F],
+    a_comm: &Pcs::Commitment,
+    b_comm: &Pcs::Commitment,
+    transcript: &mut impl TranscriptWrite<Pcs::CommitmentChunk, F>,
+) -> Result<(), Error>
+where
+    F: PrimeField + WithSmallOrderMulGroup<3>,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
+{
+    let n = check_sizes(a.len(), b.len())?;
+    transcript.common_label(b"multiset")?;
+    transcript.common_commitments(a_comm.as_ref())?;
+    transcript.common_commitments(b_comm.as_ref())?;
+
+    // round 1
+    let gamma = transcript.squeeze_challenge();
+    let numers = a.iter().map(|a| *a + gamma).collect_vec();
+    let denoms = b.iter().map(|b| *b + gamma).collect_vec();
+    let mut z = grand_product(&numers, &denoms);
+    if z.pop() != Some(F::ONE) {
+        return Err(Error::InvalidSnark(
+            "Vectors aren't permutations of each other".to_string(),
+        ));
+    }
+    let z_poly = UnivariatePolynomial::lagrange(z.clone()).into_ifft();
+    let z_comm = Pcs::commit_and_write(pp, &z_poly, transcript)?;
+
+    // round 2
+    let delta = transcript.squeeze_challenge();
+    let a_poly = UnivariatePolynomial::lagrange(a.to_vec()).into_ifft();
+    let b_poly = UnivariatePolynomial::lagrange(b.to_vec()).into_ifft();
+    // z(gX) interpolates z rotated by one
+    z.rotate_left(1);
+    let z_g_poly = UnivariatePolynomial::lagrange(z).into_ifft();
+    let l0_poly = {
+        let mut values = vec![F::ZERO; n];
+        values[0] = F::ONE;
+        UnivariatePolynomial::lagrange(values).into_ifft()
+    };
+    let mut poly = &z_g_poly * &(b_poly.clone() + gamma);
+    poly -= &z_poly * &(a_poly.clone() + gamma);
+    poly *= &delta;
+    poly += &l0_poly * &(z_poly.clone() + -F::ONE);
+    let q_poly = poly.divide_by_vanishing_poly(n)?;
+    let q_comm = Pcs::commit_and_write(pp, &q_poly, transcript)?;
+
+    // round 3
+    let zeta = transcript.squeeze_challenge();
+    let g_zeta = root_of_unity::<F>(n.ilog2() as usize) * zeta;
+    let evals = [&a_poly, &b_poly, &z_poly].map(|poly| poly.evaluate(&zeta));
+    let z_g_eval = z_poly.evaluate(&g_zeta);
+    transcript.write_field_elements(evals.iter().chain([&z_g_eval]))?;
+
+    let batch_evals = [
+        Evaluation::new(0, 0, evals[0]),
+        Evaluation::new(1, 0, evals[1]),
+        Evaluation::new(2, 0, evals[2]),
+        Evaluation::new(3, 0, q_poly.evaluate(&zeta)),
+        Evaluation::new(2, 1, z_g_eval),
+    ];
+    Pcs::batch_open(
+        pp,
+        [&a_poly, &b_poly, &z_poly, &q_poly],
+        [a_comm, b_comm, &z_comm, &q_comm],
+        &[zeta, g_zeta],
+        &batch_evals,
+        transcript,
+    )
+}
+
+/// Verify a proof of [`prove_multiset_equality`] of the vectors of size `n`
+/// of `a_comm` and `b_comm`.
+pub fn verify_multiset_equality<F, Pcs>(
+    vp: &Pcs::VerifierParam,
+    n: usize,
+    a_comm: &Pcs::Commitment,
+    b_comm: &Pcs::Commitment,
+    transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, F>,
+) -> Result<(), Error>
+where
+    F: PrimeField + WithSmallOrderMulGroup<3>,
+    Pcs: PolynomialCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
+{
+    check_sizes(n, n)?;
+    transcript.common_label(b"multiset")?;
+    transcript.common_commitments(a_comm.as_ref())?;
+    transcript.common_commitments(b_comm.as_ref())?;
+
+    let gamma = transcript.squeeze_challenge();
+    let z_comm = Pcs::read_commitment(vp, transcript)?;
+    let delta = transcript.squeeze_challenge();
+    let q_comm = Pcs::read_commitment(vp, transcript)?;
+    let zeta = transcript.squeeze_challenge();
+    // [a_eval, b_eval, z_eval, z_g_eval]
+    let evals = transcript.read_field_elements(4)?;
+
+    let z_h_eval = zeta.pow([n as u64]) - F::ONE;
+    let Some(z_h_eval_inv) = Option::<F>::from(z_h_eval.invert()) else {
+        return Err(Error::InvalidSnark("Challenge ζ is in H".to_string()));
+    };
+    // L_0(ζ) = (ζ^n - 1) / (n (ζ - 1))
+    let l0_eval = z_h_eval * (F::from(n as u64) * (zeta - F::ONE)).invert().unwrap();
+    let q_eval = (l0_eval * (evals[2] - F::ONE)
+        + delta * (evals[3] * (evals[1] + gamma) - evals[2] * (evals[0] + gamma)))
+        * z_h_eval_inv;
+
+    let batch_evals = [
+        Evaluation::new(0, 0, evals[0]),
+        Evaluation::new(1, 0, evals[1]),
+        Evaluation::new(2, 0, evals[2]),
+        Evaluation::new(3, 0, q_eval),
+        Evaluation::new(2, 1, evals[3]),
+    ];
+    Pcs::batch_verify(
+        vp,
+        [a_comm, b_comm, &z_comm, &q_comm],
+        &[zeta, root_of_unity::<F>(n.ilog2() as usize) * zeta],
+        &batch_evals,
+        transcript,
+    )
+}
+
+fn check_sizes(a_len: usize, b_len: usize) -> Result<usize, Error> {
+    if !a_len.is_power_of_two() {
+        return Err(Error::NotPowerOfTwo {
+            name: "multiset",
+            size: a_len,
+        });
+    }
+    if b_len != a_len {
+        return Err(Error::SizeMismatch {
+            name: "multiset",
+            expected: a_len,
+            got: b_len,
+        });
+    }
+    Ok(a_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pcs::univariate::UnivariateKzg,
+        util::{
+            test::std_rng,
+            transcript::{InMemoryTranscript, Keccak256Transcript},
+        },
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+
+    type Pcs = UnivariateKzg<Bn256>;
+
+    #[test]
+    fn test_multiset_equality() {
+        let mut rng = std_rng();
+        let n = 8;
+        let param = Pcs::setup(n, 1, &mut rng).unwrap();
+        let (pp, vp) = Pcs::trim(&param, n, 1).unwrap();
+        let a = [1, 2, 3, 4, 5, 5, 6, 7].map(Fr::from).to_vec();
+        let b = [5, 3, 7, 1, 5, 4, 2, 6].map(Fr::from).to_vec();
+        let commit = |values: &[Fr]| {
+            let poly = UnivariatePolynomial::lagrange(values.to_vec()).into_ifft();
+            Pcs::commit(&pp, &poly).unwrap()
+        };
+        let (a_comm, b_comm) = (commit(&a), commit(&b));
+
+        let mut transcript = Keccak256Transcript::new(());
+        prove_multiset_equality::<Fr, Pcs>(&pp, &a, &b, &a_comm, &b_comm, &mut transcript).unwrap();
+        let proof = transcript.into_proof();
+        let verify = |a_comm: &_, b_comm: &_| {
+            let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+            verify_multiset_equality::<Fr, Pcs>(&vp, n, a_comm, b_comm, &mut transcript)
+        };
+        assert_eq!(verify(&a_comm, &b_comm), Ok(()));
+        // the multiset of b but not the vector
+        let c = [3, 5, 7, 1, 5, 4, 2, 6].map(Fr::from).to_vec();
+        assert!(verify(&a_comm, &commit(&c)).is_err());
+
+        // 5 twice in a, once in d
+        let d = [5, 3, 7, 1, 8, 4, 2, 6].map(Fr::from).to_vec();
+        let mut transcript = Keccak256Transcript::new(());
+        assert!(prove_multiset_equality::<Fr, Pcs>(
+            &pp,
+            &a,
+            &d,
+            &a_comm,
+            &commit(&d),
+            &mut transcript
+        )
+        .is_err());
+        assert_eq!(
+            grand_product(&[2, 3].map(Fr::from), &[3, 2].map(Fr::from)),
+            [
+                Fr::ONE,
+                Fr::from(2) * Fr::from(3).invert().unwrap(),
+                Fr::ONE
+            ]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    backend::cq::generate_table_and_lookup,
     pcs::PolynomialCommitmentScheme,
     poly::univariate::UnivariatePolynomial,
     util::{
@@ -46,6 +47,15 @@ pub struct PlookupInfo<F> {
     lookup: Vec<F>,
 }
 
+impl<F> PlookupInfo<F> {
+    /// Info for looking up `lookup` in `table`, whose size should be a power
+    /// of two and larger than the lookup size.
+    pub fn new(table: Vec<F>, lookup: Vec<F>) -> Self {
+        let k = table.len().next_power_of_two().ilog2();
+        Self { k, table, lookup }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Plookup<F, Pcs>(PhantomData<F>, PhantomData<Pcs>);
 
@@ -54,21 +64,21 @@ where
     F: PrimeField + WithSmallOrderMulGroup<3> + Hash + Serialize + DeserializeOwned,
     Pcs: PolynomialCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
 {
-    fn preprocess(
+    pub fn preprocess(
         param: &Pcs::Param,
         info: &PlookupInfo<F>,
     ) -> Result<(PlookupProverParam<F, Pcs>, PlookupVerifierParam<F, Pcs>), Error> {
         preprocessor::preprocess(param, info)
     }
 
-    fn prove(
+    pub fn prove(
         pp: PlookupProverParam<F, Pcs>,
         transcript: &mut (impl TranscriptWrite<Pcs::CommitmentChunk, F> + InMemoryTranscript),
     ) -> Result<(), Error> {
         prover::prove(pp, transcript)
     }
 
-    fn verify(
+    pub fn verify(
         vp: PlookupVerifierParam<F, Pcs>,
         transcript: &mut (impl TranscriptRead<Pcs::CommitmentChunk, F> + InMemoryTranscript),
     ) -> Result<(), Error> {
@@ -77,8 +87,12 @@ where
 }
 
 // Concrete implementation for BN256/Fr
-use crate::pcs::univariate::UnivariateKzg;
-use halo2_curves::bn256::{Bn256, Fr};
+use crate::{
+    backend::lookup,
+    pcs::univariate::{UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam},
+};
+use halo2_curves::bn256::{Bn256, Fr, G1Affine};
+use rand::RngCore;
 
 impl Plookup<Fr, UnivariateKzg<Bn256>> {
     // Run the full Plookup protocol with given table and lookup
@@ -87,17 +101,9 @@ impl Plookup<Fr, UnivariateKzg<Bn256>> {
 
         let start_total = Instant::now();
 
-        // Calculate k value based on max of table and lookup size
-        let size = std::cmp::max(table.len(), lookup.len()).next_power_of_two();
-        let k = (size as f64).log2() as u32;
-        let n = 1 << k;
-
         // 1. Setup
-        let info = PlookupInfo {
-            k,
-            table: table.clone(),
-            lookup: lookup.clone(),
-        };
+        let info = PlookupInfo::new(table, lookup);
+        let n = 1 << info.k;
 
         let start = Instant::now();
         let mut rng = crate::util::test::std_rng();
@@ -113,6 +119,7 @@ impl Plookup<Fr, UnivariateKzg<Bn256>> {
         let proof = transcript.into_proof();
         let duration2 = start.elapsed();
         timings.push(format!("Prove: {}ms", duration2.as_millis()));
+        timings.push(format!("Proof size: {}bytes", proof.len()));
 
         // 3. Verify
         let start = Instant::now();
@@ -126,6 +133,74 @@ impl Plookup<Fr, UnivariateKzg<Bn256>> {
 
         timings
     }
+
+    // Run the full Plookup protocol with table and lookup generated based on k
+    pub fn test_plookup_by_k(k: usize) -> Vec<String> {
+        let (table, lookup) =
+            generate_table_and_lookup(2_usize.pow(k as u32), 2_usize.pow((k - 1) as u32));
+        Self::test_plookup_by_input(table, lookup)
+    }
+}
+
+impl lookup::LookupScheme<Fr> for Plookup<Fr, UnivariateKzg<Bn256>> {
+    type Param = UnivariateKzgParam<Bn256>;
+    type ProverParam = PlookupProverParam<Fr, UnivariateKzg<Bn256>>;
+    type VerifierParam = PlookupVerifierParam<Fr, UnivariateKzg<Bn256>>;
+    /// `[f(x)]1` of the padded lookup, which the proof carries.
+    type LookupCommitment = UnivariateKzgCommitment<G1Affine>;
+    type Proof = Vec<u8>;
+
+    fn setup(t: usize, _: usize, rng: impl RngCore) -> Result<Self::Param, Error> {
+        UnivariateKzg::<Bn256>::setup(t.next_power_of_two() * 4, 1, rng)
+    }
+
+    /// The lookup size is only bounded by the table size, which it should be
+    /// less than.
+    fn preprocess(
+        param: &Self::Param,
+        table: &[Fr],
+        _: usize,
+    ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
+        Self::preprocess(param, &PlookupInfo::new(table.to_vec(), Vec::new()))
+    }
+
+    fn commit_lookup(
+        pp: &Self::ProverParam,
+        lookup: &[Fr],
+    ) -> Result<Self::LookupCommitment, Error> {
+        let f_poly = prover::lookup_poly(lookup, pp.table.len())?;
+        UnivariateKzg::<Bn256>::commit(&pp.pcs, &f_poly)
+    }
+
+    fn prove(pp: &Self::ProverParam, lookup: &[Fr]) -> Result<Vec<u8>, Error> {
+        let pp = PlookupProverParam {
+            lookup: lookup.to_vec(),
+            ..pp.clone()
+        };
+        let mut transcript = Keccak256Transcript::new(());
+        Self::prove(pp, &mut transcript)?;
+        Ok(transcript.into_proof())
+    }
+
+    fn proof_to_bytes(proof: &Vec<u8>) -> Vec<u8> {
+        proof.clone()
+    }
+
+    fn verify(
+        vp: &Self::VerifierParam,
+        f_comm: &Self::LookupCommitment,
+        proof: &Vec<u8>,
+    ) -> Result<(), Error> {
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        let proof_f_comm: G1Affine = transcript.read_commitment()?;
+        if proof_f_comm != f_comm.0 {
+            return Err(Error::InvalidSnark(
+                "Proof isn't of the lookup commitment".to_string(),
+            ));
+        }
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        Self::verify(vp.clone(), &mut transcript)
+    }
 }
 
 #[cfg(test)]
@@ -164,6 +239,29 @@ mod test {
         Pb::verify(vp, &mut transcript).unwrap();
     }
 
+    #[test]
+    fn test_empty_lookup() {
+        let table = [1, 2, 3, 4].map(Fr::from).to_vec();
+        let param = <Pb as lookup::LookupScheme<Fr>>::setup(table.len(), 1, std_rng()).unwrap();
+        let (pp, _) = <Pb as lookup::LookupScheme<Fr>>::preprocess(&param, &table, 1).unwrap();
+        let err = Error::InvalidPcsParam("Lookup size should be in [1, 3] but got 0".to_string());
+        assert_eq!(
+            <Pb as lookup::LookupScheme<Fr>>::commit_lookup(&pp, &[]).unwrap_err(),
+            err
+        );
+        assert_eq!(
+            <Pb as lookup::LookupScheme<Fr>>::prove(&pp, &[]).unwrap_err(),
+            err
+        );
+    }
+
+    #[test]
+    fn test_plookup_by_k() {
+        let timings = Pb::test_plookup_by_k(4);
+        // proof size is reported next to the timings for comparison with Baloo
+        assert!(timings.iter().any(|timing| timing.starts_with("Proof size: ")));
+    }
+
     #[test]
     fn test_plookup_by_input() {
         let table_size = 2_usize.pow(6);
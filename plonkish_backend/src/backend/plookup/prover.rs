@@ -1,5 +1,6 @@
 use super::{util::aggregate_poly, PlookupProverParam};
 use crate::{
+    backend::multiset::grand_product,
     pcs::{Evaluation, PolynomialCommitmentScheme},
     poly::univariate::UnivariatePolynomial,
     util::{
@@ -19,12 +20,7 @@ pub(super) fn prove<
     transcript: &mut (impl TranscriptWrite<Pcs::CommitmentChunk, F> + InMemoryTranscript),
 ) -> Result<(), Error> {
     let order = pp.table.len();
-    if pp.lookup.len() >= order {
-        // TODO: add another entry to Error for lookup itself?
-        return Err(Error::InvalidPcsParam(String::from(
-            "lookup length too long",
-        )));
-    }
+    let f_poly = lookup_poly(&pp.lookup, order)?;
     let t = pp.table.clone();
     let mut f = pp.lookup.clone();
 
@@ -38,9 +34,6 @@ pub(super) fn prove<
     let h1 = s[..order].to_vec();
     let h2 = s[order - 1..].to_vec();
     let t_poly = UnivariatePolynomial::lagrange(t.clone()).ifft();
-    let mut f_clone = f.clone();
-    f_clone.push(last); // pad to length order for polynomial
-    let f_poly = UnivariatePolynomial::lagrange(f_clone).ifft();
     let h1_poly = UnivariatePolynomial::lagrange(h1.clone()).ifft();
     let h2_poly = UnivariatePolynomial::lagrange(h2.clone()).ifft();
     let f_comm = Pcs::commit_and_write(&pp.pcs, &f_poly, transcript)?;
@@ -114,6 +107,25 @@ pub(super) fn prove<
     Ok(())
 }
 
+/// `f(X)` of `lookup` padded with its last value to the table size `order`,
+/// whose commitment is the first one of the proof. The lookup has to be
+/// shorter than the table, for the sorted `s` to be split in two.
+pub(super) fn lookup_poly<F: PrimeField + WithSmallOrderMulGroup<3>>(
+    lookup: &[F],
+    order: usize,
+) -> Result<UnivariatePolynomial<F>, Error> {
+    let Some(last) = lookup.last().filter(|_| lookup.len() < order) else {
+        return Err(Error::InvalidPcsParam(format!(
+            "Lookup size should be in [1, {}] but got {}",
+            order.saturating_sub(1),
+            lookup.len()
+        )));
+    };
+    let mut f = lookup.to_vec();
+    f.resize(order, *last);
+    Ok(UnivariatePolynomial::lagrange(f).ifft())
+}
+
 fn sorted_by_table<F: PrimeField>(table: &Vec<F>, witness: &Vec<F>) -> Vec<F> {
     let n = table.len();
     let count = {
@@ -155,27 +167,20 @@ fn compute_inner_table<F: PrimeField>(
     s: &Vec<F>,
 ) -> Vec<F> {
     let n = t.len();
-    let mut numer = F::ONE;
-    let mut denom = F::ONE;
-    
-    {
-        let mut ret: Vec<F> = Vec::with_capacity(n);
-        ret.push(F::ONE);
-        let mut i = 1;
-        while i < n {
-            numer = numer
-                * (F::ONE + *beta)
+    let beta_plus_1 = F::ONE + *beta;
+    let (numers, denoms): (Vec<_>, Vec<_>) = (1..n)
+        .map(|i| {
+            let numer = beta_plus_1
                 * (*gamma + f[i - 1])
-                * (*gamma * (F::ONE + *beta) + t[i - 1] + *beta * t[i]);
-            denom = denom
-                * (*gamma * (F::ONE + *beta) + s[i - 1] + *beta * s[i])
-                * (*gamma * (F::ONE + *beta) + s[n + i - 2] + *beta * s[n + i - 1]);
-            i += 1;
-            ret.push(numer * F::invert(&denom).unwrap());
-        }
-        assert!(ret[n - 1] == F::ONE);
-        ret
-    }
+                * (*gamma * beta_plus_1 + t[i - 1] + *beta * t[i]);
+            let denom = (*gamma * beta_plus_1 + s[i - 1] + *beta * s[i])
+                * (*gamma * beta_plus_1 + s[n + i - 2] + *beta * s[n + i - 1]);
+            (numer, denom)
+        })
+        .unzip();
+    let ret = grand_product(&numers, &denoms);
+    assert!(ret[n - 1] == F::ONE);
+    ret
 }
 
 /// send both table and polynomial (in monomial basis) for efficiency
@@ -0,0 +1,228 @@
+//! Range checks of values in `[0, 2^k)` without a table of size `2^k`: each
+//! value is decomposed into limbs of `limb_bits` bits, and every limb column
+//! is looked up by [`Baloo`] in the table `[0, 2^limb_bits)`.
+//!
+//! The verifier is given `[v(x)]1` of the values, and the prover sends
+//! `[l_j(x)]1` of each limb column, which recompose into it by linearity as
+//! `[v(x)]1 = Σ_j 2^(j·limb_bits) [l_j(x)]1`, together with the Baloo proofs
+//! of the limb columns, verified in a single batch. If `limb_bits` doesn't
+//! divide `k`, the top limb of `r` bits is also looked up shifted by
+//! `limb_bits - r` bits, which bounds it by `2^r`.
+
+use crate::{
+    backend::{
+        baloo::{Baloo, BalooProof, BalooProverParam, BalooVerifierParam},
+        lookup::LookupScheme,
+    },
+    pcs::{univariate::UnivariateKzgCommitment, Additive},
+    util::arithmetic::{div_ceil, powers, Field, PrimeField},
+    Error,
+};
+use halo2_curves::bn256::{Fr, G1Affine};
+use rand::RngCore;
+use std::cmp::min;
+
+#[derive(Clone, Debug)]
+pub struct RangeProverParam {
+    k: usize,
+    limb_bits: usize,
+    pp: BalooProverParam,
+}
+
+#[derive(Clone, Debug)]
+pub struct RangeVerifierParam {
+    k: usize,
+    limb_bits: usize,
+    vp: BalooVerifierParam,
+}
+
+/// `[l_j(x)]1` of each limb column and the Baloo proof of each of them, then
+/// of the shifted top limb if any.
+#[derive(Clone, Debug)]
+pub struct RangeProof {
+    limb_comms: Vec<UnivariateKzgCommitment<G1Affine>>,
+    proofs: Vec<BalooProof>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Range;
+
+impl Range {
+    /// Preprocess range checks of `m` values in `[0, 2^k)` by limbs of
+    /// `limb_bits` bits, which is at most `k`.
+    pub fn preprocess(
+        k: usize,
+        limb_bits: usize,
+        m: usize,
+        rng: impl RngCore,
+    ) -> Result<(RangeProverParam, RangeVerifierParam), Error> {
+        if k >= Fr::NUM_BITS as usize || limb_bits == 0 || limb_bits > k {
+            return Err(Error::InvalidSnark(format!(
+                "Range should be of less than {} bits and limbs of 1 to {k} bits but got limbs of {limb_bits} bits",
+                Fr::NUM_BITS
+            )));
+        }
+        let table = (0..1u64 << limb_bits).map(Fr::from).collect::<Vec<_>>();
+        let param = <Baloo as LookupScheme<Fr>>::setup(table.len(), m, rng)?;
+        let (pp, vp) = <Baloo as LookupScheme<Fr>>::preprocess(&param, &table, m)?;
+        Ok((
+            RangeProverParam { k, limb_bits, pp },
+            RangeVerifierParam { k, limb_bits, vp },
+        ))
+    }
+
+    /// `[v(x)]1` of `values` the verifier is given.
+    pub fn commit(
+        pp: &RangeProverParam,
+        values: &[Fr],
+    ) -> Result<UnivariateKzgCommitment<G1Affine>, Error> {
+        <Baloo as LookupScheme<Fr>>::commit_lookup(&pp.pp, values)
+    }
+
+    pub fn prove(pp: &RangeProverParam, values: &[Fr]) -> Result<RangeProof, Error> {
+        let limbs = limb_columns(pp.k, pp.limb_bits, values)?;
+        let limb_comms = limbs
+            .iter()
+            .map(|column| <Baloo as LookupScheme<Fr>>::commit_lookup(&pp.pp, column))
+            .collect::<Result<_, _>>()?;
+
+        let shifted_top = top_shift(pp.k, pp.limb_bits).map(|shift| {
+            let scalar = Fr::from(2).pow([shift as u64]);
+            limbs
+                .last()
+                .unwrap()
+                .iter()
+                .map(|limb| *limb * scalar)
+                .collect()
+        });
+        let proofs = limbs
+            .iter()
+            .chain(shifted_top.as_ref())
+            .map(|lookup| <Baloo as LookupScheme<Fr>>::prove(&pp.pp, lookup))
+            .collect::<Result<_, _>>()?;
+        Ok(RangeProof { limb_comms, proofs })
+    }
+
+    /// Verify that the values committed in `value_comm` are in `[0, 2^k)`.
+    pub fn verify(
+        vp: &RangeVerifierParam,
+        value_comm: &UnivariateKzgCommitment<G1Affine>,
+        proof: &RangeProof,
+    ) -> Result<(), Error> {
+        let num_limbs = div_ceil(vp.k, vp.limb_bits);
+        let shift = top_shift(vp.k, vp.limb_bits);
+        if proof.limb_comms.len() != num_limbs
+            || proof.proofs.len() != num_limbs + shift.is_some() as usize
+        {
+            return Err(Error::InvalidSnark(format!(
+                "Range proof should be of {num_limbs} limbs"
+            )));
+        }
+
+        let limb_scalars = powers(Fr::from(2).pow([vp.limb_bits as u64]))
+            .take(num_limbs)
+            .collect::<Vec<_>>();
+        if UnivariateKzgCommitment::msm(&limb_scalars, &proof.limb_comms) != *value_comm {
+            return Err(Error::InvalidSnark(
+                "Limbs should recompose into the values".to_string(),
+            ));
+        }
+
+        let mut lookup_comms = proof.limb_comms.clone();
+        if let Some(shift) = shift {
+            let scalar = Fr::from(2).pow([shift as u64]);
+            lookup_comms.push(UnivariateKzgCommitment::msm(
+                &[scalar],
+                proof.limb_comms.last(),
+            ));
+        }
+        Ok(Baloo::batch_verify(
+            &vp.vp,
+            &proof.proofs,
+            &lookup_comms,
+            vp.vp.m(),
+        )?)
+    }
+}
+
+// shift which bounds the top limb by `2^r` for `r = k mod limb_bits`, if any
+fn top_shift(k: usize, limb_bits: usize) -> Option<usize> {
+    let r = k % limb_bits;
+    (r != 0).then_some(limb_bits - r)
+}
+
+// limb columns of `values`, from the least significant one
+fn limb_columns(k: usize, limb_bits: usize, values: &[Fr]) -> Result<Vec<Vec<Fr>>, Error> {
+    let mut columns = vec![Vec::with_capacity(values.len()); div_ceil(k, limb_bits)];
+    for (i, value) in values.iter().enumerate() {
+        let repr = value.to_repr();
+        let bit = |j: usize| (repr.as_ref()[j / 8] >> (j % 8)) & 1 == 1;
+        if (k..Fr::NUM_BITS as usize).any(bit) {
+            return Err(Error::InvalidSnark(format!(
+                "Value {i} should be in [0, 2^{k})"
+            )));
+        }
+        for (j, column) in columns.iter_mut().enumerate() {
+            let limb = (j * limb_bits..min((j + 1) * limb_bits, k))
+                .rev()
+                .fold(0, |limb, bit_index| (limb << 1) | bit(bit_index) as u64);
+            column.push(Fr::from(limb));
+        }
+    }
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        backend::{
+            baloo::Baloo,
+            lookup::LookupScheme,
+            range::{limb_columns, Range},
+        },
+        Error,
+    };
+    use halo2_curves::bn256::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn range() {
+        let values = [0, 1, 1023, 517].map(Fr::from).to_vec();
+        let (pp, vp) = Range::preprocess(10, 4, values.len(), OsRng).unwrap();
+        let proof = Range::prove(&pp, &values).unwrap();
+        assert_eq!(
+            Range::verify(&vp, &Range::commit(&pp, &values).unwrap(), &proof),
+            Ok(())
+        );
+
+        let other_values = [0, 1, 1023, 518].map(Fr::from).to_vec();
+        assert_eq!(
+            Range::verify(&vp, &Range::commit(&pp, &other_values).unwrap(), &proof),
+            Err(Error::InvalidSnark(
+                "Limbs should recompose into the values".to_string()
+            ))
+        );
+        let out_of_range = [0, 1, 1024, 517].map(Fr::from).to_vec();
+        assert_eq!(
+            Range::prove(&pp, &out_of_range).map(|_| ()),
+            Err(Error::InvalidSnark(
+                "Value 2 should be in [0, 2^10)".to_string()
+            ))
+        );
+        assert_eq!(
+            Range::prove(&pp, &[-Fr::from(1); 4]).map(|_| ()),
+            Err(Error::InvalidSnark(
+                "Value 0 should be in [0, 2^10)".to_string()
+            ))
+        );
+
+        // 1024 as limbs (0, 0, 4) of 4 bits, whose top limb is in the table
+        // but not once shifted by 2 bits
+        assert_eq!(
+            limb_columns(12, 4, &[Fr::from(1024)]).unwrap(),
+            vec![vec![Fr::from(0)], vec![Fr::from(0)], vec![Fr::from(4)]]
+        );
+        assert!(<Baloo as LookupScheme<Fr>>::prove(&pp.pp, &[Fr::from(4); 4]).is_ok());
+        assert!(<Baloo as LookupScheme<Fr>>::prove(&pp.pp, &[Fr::from(16); 4]).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+//! Univariate sum check of Aurora: `f` sums to `σ` over the subgroup `H` of
+//! order `n` iff `f = X g + σ / n + Z_H h` for some `g` of degree less than
+//! `n - 1` and some `h`, since `Σ_{x ∈ H} x^i` is `n` if `n | i` and `0`
+//! otherwise.
+//!
+//! The prover commits to `g` and `h`, and opens `f`, `g` and `h` at a
+//! challenge `ζ`, `g` with a proof of its degree bound, for the verifier to
+//! check the decomposition at `ζ`.
+
+use crate::{
+    pcs::{DegreeBoundCommitmentScheme, Evaluation, PolynomialCommitmentScheme},
+    poly::univariate::UnivariatePolynomial,
+    util::{
+        arithmetic::{Field, PrimeField},
+        transcript::{TranscriptRead, TranscriptWrite},
+    },
+    Error,
+};
+
+/// `(σ, g, h)` of `f = X g + σ / n + Z_H h`, for `H` of order `n`.
+pub fn decompose<F: Field>(
+    poly: &UnivariatePolynomial<F>,
+    n: usize,
+) -> (F, UnivariatePolynomial<F>, UnivariatePolynomial<F>) {
+    assert!(n.is_power_of_two());
+
+    // X^i = X^(i-n) (X^n - 1) + X^(i-n), from the leading coefficient
+    let mut remainder = poly.coeffs().to_vec();
+    let mut quotient = vec![F::ZERO; remainder.len().saturating_sub(n)];
+    for i in (n..remainder.len()).rev() {
+        let coeff = remainder[i];
+        quotient[i - n] = coeff;
+        remainder[i - n] += coeff;
+    }
+    remainder.resize(n, F::ZERO);
+    let sum = remainder[0] * F::from(n as u64);
+    let g = UnivariatePolynomial::monomial(remainder[1..].to_vec());
+    (sum, g, UnivariatePolynomial::monomial(quotient))
+}
+
+/// Whether `f(ζ) = ζ g(ζ) + σ / n + (ζ^n - 1) h(ζ)`.
+pub fn check_decomposition<F: PrimeField>(
+    n: usize,
+    sum: &F,
+    point: &F,
+    f_eval: &F,
+    g_eval: &F,
+    h_eval: &F,
+) -> bool {
+    let z_h_eval = point.pow([n as u64]) - F::ONE;
+    let n_inv = F::from(n as u64).invert().unwrap();
+    *f_eval == *point * g_eval + *sum * n_inv + z_h_eval * h_eval
+}
+
+/// Prove the sum of `poly` over `H` of order `n`, of its commitment
+/// `comm` the verifier knows, and return the sum, which the verifier is
+/// given too.
+pub fn prove_sum<F, Pcs>(
+    pp: &Pcs::ProverParam,
+    n: usize,
+    poly: &UnivariatePolynomial<F>,
+    comm: &Pcs::Commitment,
+    transcript: &mut impl TranscriptWrite<Pcs::CommitmentChunk, F>,
+) -> Result<F, Error>
+where
+    F: PrimeField,
+    Pcs: DegreeBoundCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
+{
+    check_domain_size(n)?;
+    let (sum, g_poly, h_poly) = decompose(poly, n);
+    transcript.common_label(b"univariate_sum_check")?;
+    transcript.common_commitments(comm.as_ref())?;
+    transcript.common_field_element(&sum)?;
+
+    let g_comm = Pcs::commit_and_write(pp, &g_poly, transcript)?;
+    let h_comm = Pcs::commit_and_write(pp, &h_poly, transcript)?;
+    let zeta = transcript.squeeze_challenge();
+    let evals = [poly, &g_poly, &h_poly].map(|poly| poly.evaluate(&zeta));
+    transcript.write_field_elements(&evals)?;
+
+    Pcs::batch_open(
+        pp,
+        [poly, &h_poly],
+        [comm, &h_comm],
+        &[zeta],
+        &[
+            Evaluation::new(0, 0, evals[0]),
+            Evaluation::new(1, 0, evals[2]),
+        ],
+        transcript,
+    )?;
+    Pcs::open_with_degree_bound(pp, &g_poly, &g_comm, n - 2, &zeta, &evals[1], transcript)?;
+    Ok(sum)
+}
+
+/// Verify a proof of [`prove_sum`] that the polynomial of `comm` sums to
+/// `sum` over `H` of order `n`, given the param of the degree bound `n - 2`.
+pub fn verify_sum<F, Pcs>(
+    vp: &Pcs::VerifierParam,
+    degree_bound_param: &Pcs::DegreeBoundParam,
+    n: usize,
+    comm: &Pcs::Commitment,
+    sum: &F,
+    transcript: &mut impl TranscriptRead<Pcs::CommitmentChunk, F>,
+) -> Result<(), Error>
+where
+    F: PrimeField,
+    Pcs: DegreeBoundCommitmentScheme<F, Polynomial = UnivariatePolynomial<F>>,
+{
+    check_domain_size(n)?;
+    transcript.common_label(b"univariate_sum_check")?;
+    transcript.common_commitments(comm.as_ref())?;
+    transcript.common_field_element(sum)?;
+
+    let g_comm = Pcs::read_commitment(vp, transcript)?;
+    let h_comm = Pcs::read_commitment(vp, transcript)?;
+    let zeta = transcript.squeeze_challenge();
+    // [f_eval, g_eval, h_eval]
+    let evals = transcript.read_field_elements(3)?;
+    if !check_decomposition(n, sum, &zeta, &evals[0], &evals[1], &evals[2]) {
+        return Err(Error::InvalidSnark(
+            "Invalid univariate sum check decomposition".to_string(),
+        ));
+    }
+
+    Pcs::batch_verify(
+        vp,
+        [comm, &h_comm],
+        &[zeta],
+        &[
+            Evaluation::new(0, 0, evals[0]),
+            Evaluation::new(1, 0, evals[2]),
+        ],
+        transcript,
+    )?;
+    Pcs::verify_degree_bound(
+        vp,
+        degree_bound_param,
+        &g_comm,
+        &zeta,
+        &evals[1],
+        transcript,
+    )
+}
+
+// g is of degree at most n - 2
+fn check_domain_size(n: usize) -> Result<(), Error> {
+    if !n.is_power_of_two() || n < 2 {
+        return Err(Error::NotPowerOfTwo {
+            name: "sum check domain",
+            size: n,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pcs::univariate::UnivariateKzg,
+        poly::Polynomial,
+        util::{
+            arithmetic::{powers, root_of_unity},
+            test::std_rng,
+            transcript::{InMemoryTranscript, Keccak256Transcript},
+        },
+    };
+    use halo2_curves::bn256::{Bn256, Fr};
+
+    type Pcs = UnivariateKzg<Bn256>;
+
+    #[test]
+    fn test_univariate_sum_check() {
+        let mut rng = std_rng();
+        let (n, poly_size) = (8, 32);
+        let poly = UnivariatePolynomial::rand(3 * n - 1, &mut rng);
+        let sum = powers(root_of_unity::<Fr>(3))
+            .take(n)
+            .map(|x| poly.evaluate(&x))
+            .sum::<Fr>();
+        let (decomposed_sum, g, h) = decompose(&poly, n);
+        assert_eq!(decomposed_sum, sum);
+        assert!(g.degree() <= n - 2);
+        let point = Fr::from(5);
+        assert!(check_decomposition(
+            n,
+            &sum,
+            &point,
+            &poly.evaluate(&point),
+            &g.evaluate(&point),
+            &h.evaluate(&point)
+        ));
+
+        let param = Pcs::setup(poly_size, 1, &mut rng).unwrap();
+        let (pp, vp) = Pcs::trim(&param, poly_size, 1).unwrap();
+        let dp = Pcs::trim_degree_bound(&param, poly_size, n - 2).unwrap();
+        let comm = Pcs::commit(&pp, &poly).unwrap();
+        let mut transcript = Keccak256Transcript::new(());
+        assert_eq!(
+            prove_sum::<Fr, Pcs>(&pp, n, &poly, &comm, &mut transcript),
+            Ok(sum)
+        );
+        let proof = transcript.into_proof();
+        let verify = |sum: Fr| {
+            let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+            verify_sum::<Fr, Pcs>(&vp, &dp, n, &comm, &sum, &mut transcript)
+        };
+        assert_eq!(verify(sum), Ok(()));
+        assert!(verify(sum + Fr::ONE).is_err());
+        assert!(matches!(
+            prove_sum::<Fr, Pcs>(&pp, 6, &poly, &comm, &mut Keccak256Transcript::new(())),
+            Err(Error::NotPowerOfTwo { .. })
+        ));
+    }
+}
@@ -0,0 +1,261 @@
+//! Prove and verify workloads of any [`LookupScheme`] run programmatically,
+//! e.g. from the integration tests of a downstream crate, with reports in
+//! CSV or JSON instead of the criterion output of `benches/`.
+//!
+//! Times are means over the iterations of a [`Workload`], in milliseconds.
+//! The time of each prover round is the one between the marks of the rounds
+//! in `util::metrics`, so it's only reported by the schemes marking them,
+//! e.g. Baloo, of which the first duration is the interpolation before round
+//! 1.
+
+use crate::{
+    backend::{cq::generate_table_and_lookup, lookup::LookupScheme},
+    util::metrics,
+    Error,
+};
+use halo2_curves::bn256::Fr;
+use rand::RngCore;
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+const CSV_HEADER: &str = "scheme,log_table_size,log_lookup_size,iterations,setup_ms,\
+    preprocess_ms,prove_ms,round_ms,verify_ms,proof_size";
+
+/// Lookup of size `2^log_lookup_size` into a table of size
+/// `2^log_table_size`, proved and verified `iterations` times.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Workload {
+    pub log_table_size: usize,
+    pub log_lookup_size: usize,
+    pub iterations: usize,
+}
+
+impl Workload {
+    pub fn new(log_table_size: usize, log_lookup_size: usize) -> Self {
+        Self {
+            log_table_size,
+            log_lookup_size,
+            iterations: 1,
+        }
+    }
+
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+}
+
+/// Measurements of a [`Workload`] of a scheme.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub scheme: String,
+    pub workload: Workload,
+    pub setup_ms: f64,
+    pub preprocess_ms: f64,
+    pub prove_ms: f64,
+    /// Time of each prover round, empty if the scheme doesn't mark them.
+    pub round_ms: Vec<f64>,
+    pub verify_ms: f64,
+    /// Size of the proof as encoded by [`LookupScheme::proof_to_bytes`].
+    pub proof_size: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    pub records: Vec<Record>,
+}
+
+impl Report {
+    /// Run `workloads` of `S`, recorded as `scheme`.
+    pub fn run<S: LookupScheme<Fr>>(
+        &mut self,
+        scheme: &str,
+        workloads: &[Workload],
+        mut rng: impl RngCore,
+    ) -> Result<(), Error> {
+        for workload in workloads {
+            let record = run_workload::<S>(scheme, workload, &mut rng)?;
+            self.records.push(record);
+        }
+        Ok(())
+    }
+
+    /// A header and a row per record, with the round times separated by `;`.
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{CSV_HEADER}\n");
+        for record in &self.records {
+            let round_ms = record
+                .round_ms
+                .iter()
+                .map(|ms| format!("{ms:.3}"))
+                .collect::<Vec<_>>()
+                .join(";");
+            writeln!(
+                csv,
+                "{},{},{},{},{:.3},{:.3},{:.3},{round_ms},{:.3},{}",
+                record.scheme,
+                record.workload.log_table_size,
+                record.workload.log_lookup_size,
+                record.workload.iterations,
+                record.setup_ms,
+                record.preprocess_ms,
+                record.prove_ms,
+                record.verify_ms,
+                record.proof_size,
+            )
+            .unwrap();
+        }
+        csv
+    }
+
+    /// An array of an object per record, with the fields of [`Record`] and
+    /// [`Workload`] flattened.
+    pub fn to_json(&self) -> String {
+        let records = self
+            .records
+            .iter()
+            .map(|record| {
+                let round_ms = record
+                    .round_ms
+                    .iter()
+                    .map(|ms| format!("{ms:.3}"))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!(
+                    "{{\"scheme\":{},\"log_table_size\":{},\"log_lookup_size\":{},\
+                    \"iterations\":{},\"setup_ms\":{:.3},\"preprocess_ms\":{:.3},\
+                    \"prove_ms\":{:.3},\"round_ms\":[{round_ms}],\"verify_ms\":{:.3},\
+                    \"proof_size\":{}}}",
+                    json_string(&record.scheme),
+                    record.workload.log_table_size,
+                    record.workload.log_lookup_size,
+                    record.workload.iterations,
+                    record.setup_ms,
+                    record.preprocess_ms,
+                    record.prove_ms,
+                    record.verify_ms,
+                    record.proof_size,
+                )
+            })
+            .collect::<Vec<_>>();
+        format!("[{}]", records.join(","))
+    }
+}
+
+fn run_workload<S: LookupScheme<Fr>>(
+    scheme: &str,
+    workload: &Workload,
+    mut rng: impl RngCore,
+) -> Result<Record, Error> {
+    if workload.iterations == 0 {
+        return Err(Error::SizeOutOfRange {
+            name: "iterations",
+            size: 0,
+            max: usize::MAX,
+        });
+    }
+    let (table, lookup) =
+        generate_table_and_lookup(1 << workload.log_table_size, 1 << workload.log_lookup_size);
+    let (t, m) = (table.len(), lookup.len());
+    let iterations = workload.iterations as u32;
+
+    let [mut setup, mut preprocess, mut prove, mut verify] = [Duration::ZERO; 4];
+    let mut round_ms = Vec::new();
+    let mut proof_size = 0;
+    for _ in 0..iterations {
+        let (param, elapsed) = timed(|| S::setup(t, m, &mut rng))?;
+        setup += elapsed;
+        let ((pp, vp), elapsed) = timed(|| S::preprocess(&param, &table, m))?;
+        preprocess += elapsed;
+
+        metrics::reset();
+        let (proof, elapsed) = timed(|| S::prove(&pp, &lookup))?;
+        prove += elapsed;
+        let rounds = metrics::snapshot().round_ms;
+        round_ms.resize(round_ms.len().max(rounds.len()), 0);
+        for (total, ms) in round_ms.iter_mut().zip(rounds) {
+            *total += ms;
+        }
+
+        let lookup_comm = S::commit_lookup(&pp, &lookup)?;
+        let ((), elapsed) = timed(|| S::verify(&vp, &lookup_comm, &proof))?;
+        verify += elapsed;
+        proof_size = S::proof_to_bytes(&proof).len();
+    }
+
+    let mean_ms = |total: Duration| total.as_secs_f64() * 1000.0 / iterations as f64;
+    Ok(Record {
+        scheme: scheme.to_string(),
+        workload: workload.clone(),
+        setup_ms: mean_ms(setup),
+        preprocess_ms: mean_ms(preprocess),
+        prove_ms: mean_ms(prove),
+        round_ms: round_ms
+            .into_iter()
+            .map(|total| total as f64 / iterations as f64)
+            .collect(),
+        verify_ms: mean_ms(verify),
+        proof_size,
+    })
+}
+
+fn timed<T>(f: impl FnOnce() -> Result<T, Error>) -> Result<(T, Duration), Error> {
+    let start = Instant::now();
+    let output = f()?;
+    Ok((output, start.elapsed()))
+}
+
+fn json_string(value: &str) -> String {
+    let mut string = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => string.push_str("\\\""),
+            '\\' => string.push_str("\\\\"),
+            c if c.is_control() => write!(string, "\\u{:04x}", c as u32).unwrap(),
+            c => string.push(c),
+        }
+    }
+    string.push('"');
+    string
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::baloo::Baloo;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_report() {
+        let mut report = Report::default();
+        let workloads = [Workload::new(4, 2), Workload::new(5, 3).with_iterations(2)];
+        report.run::<Baloo>("baloo", &workloads, OsRng).unwrap();
+        assert_eq!(report.records.len(), 2);
+        for (record, workload) in report.records.iter().zip(&workloads) {
+            assert_eq!(&record.workload, workload);
+            assert!(record.proof_size > 0);
+            // table interpolation and the 3 rounds
+            assert_eq!(record.round_ms.len(), 4);
+        }
+
+        let csv = report.to_csv();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[2].starts_with("baloo,5,3,2,"));
+        assert!(lines
+            .iter()
+            .all(|line| line.split(',').count() == CSV_HEADER.split(',').count()));
+
+        let json = report.to_json();
+        assert!(json.starts_with("[{\"scheme\":\"baloo\",\"log_table_size\":4,"));
+        assert_eq!(json.matches("\"round_ms\":[").count(), 2);
+        assert_eq!(json_string("a\"b\\\n"), "\"a\\\"b\\\\\\u000a\"");
+
+        assert!(Report::default()
+            .run::<Baloo>("baloo", &[Workload::new(4, 2).with_iterations(0)], OsRng)
+            .is_err());
+    }
+}
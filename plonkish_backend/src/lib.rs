@@ -2,20 +2,61 @@
 
 pub mod accumulation;
 pub mod backend;
+#[cfg(feature = "bench")]
+pub mod bench;
 pub mod frontend;
 pub mod pcs;
 pub mod piop;
 pub mod poly;
+pub mod setup;
 pub mod util;
 
 pub use halo2_curves;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
 pub enum Error {
+    #[error("Invalid sum-check: {0}")]
     InvalidSumcheck(String),
+    #[error("Invalid PCS param: {0}")]
     InvalidPcsParam(String),
+    #[error("Invalid PCS opening: {0}")]
     InvalidPcsOpen(String),
+    #[error("Invalid SNARK: {0}")]
     InvalidSnark(String),
+    #[error("Serialization failed: {0}")]
     Serialization(String),
+    #[error("Transcript failed ({0:?}): {1}")]
     Transcript(std::io::ErrorKind, String),
+    /// Size of an input interpolated over a multiplicative subgroup, e.g. a
+    /// table or a lookup, isn't a power of two.
+    #[error("Size of {name} should be a power of two but got {size}")]
+    NotPowerOfTwo { name: &'static str, size: usize },
+    /// Size of an input interpolated over a multiplicative subgroup isn't
+    /// the order of any subgroup the FFTs support, e.g. not `2^a·3^b` or
+    /// with more factors of 3 than the field has.
+    #[error("Size of {name} should be the order of a 2^a·3^b subgroup but got {size}")]
+    NoSubgroup { name: &'static str, size: usize },
+    /// Size of an input is zero or exceeds what the param supports.
+    #[error("Size of {name} should be in [1, {max}] but got {size}")]
+    SizeOutOfRange {
+        name: &'static str,
+        size: usize,
+        max: usize,
+    },
+    /// Inputs which should be of the same size aren't, e.g. the columns of a
+    /// tuple lookup.
+    #[error("Size of {name} should be {expected} but got {got}")]
+    SizeMismatch {
+        name: &'static str,
+        expected: usize,
+        got: usize,
+    },
+    /// Positions in the lookup of the values missing in the table.
+    #[error("Lookup values at positions {positions:?} aren't in the table")]
+    NotInTable { positions: Vec<usize> },
+    /// A proof was cancelled by its [`ProvingJob`] before it was done.
+    ///
+    /// [`ProvingJob`]: crate::backend::baloo::job::ProvingJob
+    #[error("Proof was cancelled")]
+    Cancelled,
 }
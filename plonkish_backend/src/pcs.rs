@@ -2,8 +2,9 @@ use crate::{
     poly::Polynomial,
     util::{
         arithmetic::Field,
-        transcript::{TranscriptRead, TranscriptWrite},
-        DeserializeOwned, Serialize,
+        parallel::par_map_collect,
+        transcript::{G2TranscriptRead, G2TranscriptWrite, TranscriptRead, TranscriptWrite},
+        DeserializeOwned, Itertools, Serialize,
     },
     Error,
 };
@@ -59,6 +60,8 @@ pub trait PolynomialCommitmentScheme<F: Field>: Clone + Debug {
     where
         Self::Polynomial: 'a;
 
+    /// Writes the commitments of [`PolynomialCommitmentScheme::batch_commit`]
+    /// in the order of `polys`, however they're computed.
     fn batch_commit_and_write<'a>(
         pp: &Self::ProverParam,
         polys: impl IntoIterator<Item = &'a Self::Polynomial>,
@@ -154,13 +157,120 @@ impl<F> Evaluation<F> {
     }
 }
 
+/// Extension of a pairing-based [`PolynomialCommitmentScheme`] with
+/// commitments in the second source group, for checks with a committed
+/// polynomial on the G2 side of a pairing, e.g. `[z_I(x)]2` of Baloo or
+/// `[z_V(x)]2` of cq.
+pub trait PairingCommitmentScheme<F: Field>: PolynomialCommitmentScheme<F> {
+    type G2ProverParam: Clone + Debug + Serialize + DeserializeOwned;
+    type G2Commitment: Clone
+        + Debug
+        + Default
+        + PartialEq
+        + Eq
+        + AsRef<[Self::G2CommitmentChunk]>
+        + Serialize
+        + DeserializeOwned;
+    type G2CommitmentChunk: Clone + Debug + Default;
+
+    /// Prover param of G2 commitments to polynomials of size up to
+    /// `poly_size`, trimmed as [`PolynomialCommitmentScheme::trim`].
+    fn trim_g2(param: &Self::Param, poly_size: usize) -> Result<Self::G2ProverParam, Error>;
+
+    fn commit_g2(
+        pp: &Self::G2ProverParam,
+        poly: &Self::Polynomial,
+    ) -> Result<Self::G2Commitment, Error>;
+
+    fn commit_g2_and_write(
+        pp: &Self::G2ProverParam,
+        poly: &Self::Polynomial,
+        transcript: &mut impl G2TranscriptWrite<Self::G2CommitmentChunk, F>,
+    ) -> Result<Self::G2Commitment, Error> {
+        let comm = Self::commit_g2(pp, poly)?;
+        transcript.write_commitments_g2(comm.as_ref())?;
+        Ok(comm)
+    }
+
+    fn read_commitment_g2(
+        transcript: &mut impl G2TranscriptRead<Self::G2CommitmentChunk, F>,
+    ) -> Result<Self::G2Commitment, Error>;
+}
+
+/// Extension of a [`PolynomialCommitmentScheme`] with openings that also
+/// prove the degree of the committed polynomial to be at most a bound, for
+/// low-degree checks without the caller committing to shifts of the SRS.
+pub trait DegreeBoundCommitmentScheme<F: Field>: PolynomialCommitmentScheme<F> {
+    /// Verifier param of the degree checks of a single bound.
+    type DegreeBoundParam: Clone + Debug + Serialize + DeserializeOwned;
+
+    /// Param of [`DegreeBoundCommitmentScheme::verify_degree_bound`] with
+    /// `degree_bound`, for polynomials of less than `poly_size` coefficients.
+    /// The bound is checked against the largest power of the whole SRS, so the
+    /// openings need the prover param trimmed to all of it.
+    fn trim_degree_bound(
+        param: &Self::Param,
+        poly_size: usize,
+        degree_bound: usize,
+    ) -> Result<Self::DegreeBoundParam, Error>;
+
+    /// [`PolynomialCommitmentScheme::open`] that also proves `poly` to be of
+    /// degree at most `degree_bound`.
+    fn open_with_degree_bound(
+        pp: &Self::ProverParam,
+        poly: &Self::Polynomial,
+        comm: &Self::Commitment,
+        degree_bound: usize,
+        point: &Point<F, Self::Polynomial>,
+        eval: &F,
+        transcript: &mut impl TranscriptWrite<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error>;
+
+    fn verify_degree_bound(
+        vp: &Self::VerifierParam,
+        degree_bound_param: &Self::DegreeBoundParam,
+        comm: &Self::Commitment,
+        point: &Point<F, Self::Polynomial>,
+        eval: &F,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error>;
+}
+
+/// Commitments of `polys` in their order, each computed by `commit` in
+/// parallel, for [`PolynomialCommitmentScheme::batch_commit`] of schemes
+/// committing to every polynomial on its own.
+pub(crate) fn par_batch_commit<'a, P, C>(
+    polys: impl IntoIterator<Item = &'a P>,
+    commit: impl Fn(&P) -> Result<C, Error> + Send + Sync,
+) -> Result<Vec<C>, Error>
+where
+    P: Sync + 'a,
+    C: Send,
+{
+    par_map_collect(polys.into_iter().collect_vec(), commit)
+}
+
+/// Commitments, or polynomials, closed under linear combinations, for
+/// verifiers and aggregators to combine them without the curve types, e.g.
+/// `[p(x)]1 + γ * [q(x)]1`, or the same of G2 commitments.
 pub trait Additive<F: Field>: Clone + Debug + Default + PartialEq + Eq {
+    /// `Σ_i scalar_i * base_i`.
     fn msm<'a, 'b>(
         scalars: impl IntoIterator<Item = &'a F>,
         bases: impl IntoIterator<Item = &'b Self>,
     ) -> Self
     where
         Self: 'b;
+
+    /// [`Additive::msm`] of the pairs `(scalar_i, base_i)` of `terms`.
+    fn lin_comb<'a, 'b>(terms: impl IntoIterator<Item = (&'a F, &'b Self)>) -> Self
+    where
+        F: 'a,
+        Self: 'b,
+    {
+        let (scalars, bases): (Vec<_>, Vec<_>) = terms.into_iter().unzip();
+        Self::msm(scalars, bases)
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,7 @@
 use crate::{
     pcs::{
         multilinear::{additive, err_too_many_variates, validate_input},
+        par_batch_commit,
         univariate::ipa::{prove_bulletproof_reduction, verify_bulletproof_reduction},
         Additive, Evaluation, Point, PolynomialCommitmentScheme,
     },
@@ -149,11 +150,11 @@ where
         }
         validate_input("batch commit", pp.num_vars(), polys.iter().copied(), None)?;
 
-        Ok(polys
-            .iter()
-            .map(|poly| variable_base_msm(poly.evals(), pp.g()).into())
-            .map(MultilinearIpaCommitment)
-            .collect())
+        par_batch_commit(polys, |poly| {
+            Ok(MultilinearIpaCommitment(
+                variable_base_msm(poly.evals(), pp.g()).into(),
+            ))
+        })
     }
 
     fn open(
@@ -1,7 +1,7 @@
 use crate::{
     pcs::{
         multilinear::{additive, err_too_many_variates, quotients, validate_input},
-        Additive, Evaluation, Point, PolynomialCommitmentScheme,
+        par_batch_commit, Additive, Evaluation, Point, PolynomialCommitmentScheme,
     },
     poly::multilinear::MultilinearPolynomial,
     util::{
@@ -254,11 +254,10 @@ where
         }
         validate_input("batch commit", pp.num_vars(), polys.iter().copied(), None)?;
 
-        Ok(polys
-            .iter()
-            .map(|poly| variable_base_msm(poly.evals(), pp.eq(poly.num_vars())).into())
-            .map(MultilinearKzgCommitment)
-            .collect())
+        par_batch_commit(polys, |poly| {
+            let comm = variable_base_msm(poly.evals(), pp.eq(poly.num_vars())).into();
+            Ok(MultilinearKzgCommitment(comm))
+        })
     }
 
     fn open(
@@ -1,6 +1,7 @@
 use crate::{
     pcs::{
         multilinear::{additive, quotients},
+        par_batch_commit,
         univariate::{
             err_too_large_deree, UnivariateKzg, UnivariateKzgProverParam,
             UnivariateKzgVerifierParam,
@@ -120,10 +121,7 @@ where
         pp: &Self::ProverParam,
         polys: impl IntoIterator<Item = &'a Self::Polynomial>,
     ) -> Result<Vec<Self::Commitment>, Error> {
-        polys
-            .into_iter()
-            .map(|poly| Self::commit(pp, poly))
-            .collect()
+        par_batch_commit(polys, |poly| Self::commit(pp, poly))
     }
 
     fn open(
@@ -11,10 +11,12 @@ use crate::{
     Error,
 };
 
+mod fri;
 mod hyrax;
 pub(super) mod ipa;
 mod kzg;
 
+pub use fri::{UnivariateFri, UnivariateFriCommitment, UnivariateFriParam};
 pub use hyrax::{
     UnivariateHyrax, UnivariateHyraxCommitment, UnivariateHyraxParam, UnivariateHyraxVerifierParam,
 };
@@ -22,10 +24,13 @@ pub use ipa::{
     UnivariateIpa, UnivariateIpaCommitment, UnivariateIpaParam, UnivariateIpaVerifierParam,
 };
 pub use kzg::{
-    UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgParam, UnivariateKzgProverParam,
+    UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgDegreeBoundParam,
+    UnivariateKzgG2ProverParam, UnivariateKzgParam, UnivariateKzgProverParam,
     UnivariateKzgVerifierParam,
 };
 
+pub(crate) use kzg::powers_are_consistent;
+
 fn monomial_g_to_lagrange_g<C: CurveAffine>(monomial_g: &[C]) -> Vec<C> {
     assert!(monomial_g.len().is_power_of_two());
 
@@ -87,8 +92,7 @@ mod additive {
         poly::univariate::UnivariatePolynomial,
         util::{
             arithmetic::{
-                barycentric_interpolate, barycentric_weights, fe_to_bytes, inner_product, powers,
-                Field, PrimeField,
+                fe_to_bytes, inner_product, powers, BarycentricEvaluator, Field, PrimeField,
             },
             chain, izip, izip_eq,
             transcript::{TranscriptRead, TranscriptWrite},
@@ -230,11 +234,11 @@ mod additive {
 
         fn r_eval(&self, points: &[F], z: &F, powers_of_beta: &[F]) -> F {
             let points = self.points.iter().map(|idx| points[*idx]).collect_vec();
-            let weights = barycentric_weights(&points);
+            let evaluator = BarycentricEvaluator::new(points);
             let r_evals = self
                 .evals
                 .iter()
-                .map(|evals| barycentric_interpolate(&weights, &points, evals, z))
+                .map(|evals| evaluator.evaluate(evals, z))
                 .collect_vec();
             inner_product(&powers_of_beta[..r_evals.len()], &r_evals)
         }
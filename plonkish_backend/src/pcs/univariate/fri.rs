@@ -0,0 +1,524 @@
+//! Implementation of univariate polynomial commitment scheme from the FRI
+//! low-degree test [BBHR18]. A polynomial is committed by the Merkle root of
+//! its Reed-Solomon codeword on a coset, and opened at `z` by proving with FRI
+//! that `(f(X) - f(z)) / (X - z)` is of low degree, whose values the verifier
+//! derives from the queried values of `f`.
+//!
+//! [BBHR18]: https://eccc.weizmann.ac.il/report/2017/134/
+
+use crate::{
+    pcs::{
+        univariate::{err_too_large_deree, validate_input},
+        Evaluation, Point, PolynomialCommitmentScheme,
+    },
+    poly::univariate::{UnivariateBasis::*, UnivariatePolynomial},
+    util::{
+        arithmetic::{horner, powers, radix2_fft, root_of_unity, root_of_unity_inv, PrimeField},
+        hash::{Hash, Output},
+        parallel::parallelize,
+        transcript::{FieldTranscript, TranscriptRead, TranscriptWrite},
+        Deserialize, DeserializeOwned, Itertools, Serialize,
+    },
+    Error,
+};
+use halo2_curves::group::ff::BatchInvert;
+use rand::RngCore;
+use std::{borrow::Cow, marker::PhantomData, mem::size_of, slice};
+
+/// Log2 of the inverse rate of the Reed-Solomon code.
+const LOG_BLOWUP: usize = 2;
+
+/// Number of queries, for about 100 bits of conjectured security at rate 1/4.
+const NUM_QUERIES: usize = 50;
+
+#[derive(Debug)]
+pub struct UnivariateFri<F: PrimeField, H: Hash>(PhantomData<(F, H)>);
+
+impl<F: PrimeField, H: Hash> Clone for UnivariateFri<F, H> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UnivariateFriParam {
+    k: usize,
+    log_blowup: usize,
+    num_queries: usize,
+}
+
+impl UnivariateFriParam {
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn degree(&self) -> usize {
+        (1 << self.k) - 1
+    }
+
+    pub fn log_blowup(&self) -> usize {
+        self.log_blowup
+    }
+
+    pub fn num_queries(&self) -> usize {
+        self.num_queries
+    }
+
+    fn log_codeword_len(&self) -> usize {
+        self.k + self.log_blowup
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(bound(serialize = "F: Serialize", deserialize = "F: DeserializeOwned"))]
+pub struct UnivariateFriCommitment<F, H: Hash> {
+    codeword: Vec<F>,
+    intermediate_hashes: Vec<Output<H>>,
+    root: Output<H>,
+}
+
+impl<F: PrimeField, H: Hash> UnivariateFriCommitment<F, H> {
+    fn from_root(root: Output<H>) -> Self {
+        Self {
+            root,
+            ..Default::default()
+        }
+    }
+
+    pub fn codeword(&self) -> &[F] {
+        &self.codeword
+    }
+
+    pub fn intermediate_hashes(&self) -> &[Output<H>] {
+        &self.intermediate_hashes
+    }
+
+    pub fn root(&self) -> &Output<H> {
+        &self.root
+    }
+}
+
+impl<F: PrimeField, H: Hash> AsRef<[Output<H>]> for UnivariateFriCommitment<F, H> {
+    fn as_ref(&self) -> &[Output<H>] {
+        slice::from_ref(&self.root)
+    }
+}
+
+impl<F, H> PolynomialCommitmentScheme<F> for UnivariateFri<F, H>
+where
+    F: PrimeField + Serialize + DeserializeOwned,
+    H: Hash,
+{
+    type Param = UnivariateFriParam;
+    type ProverParam = UnivariateFriParam;
+    type VerifierParam = UnivariateFriParam;
+    type Polynomial = UnivariatePolynomial<F>;
+    type Commitment = UnivariateFriCommitment<F, H>;
+    type CommitmentChunk = Output<H>;
+
+    fn setup(poly_size: usize, _: usize, _: impl RngCore) -> Result<Self::Param, Error> {
+        assert!(poly_size.is_power_of_two());
+        let k = poly_size.ilog2() as usize;
+        assert!(k + LOG_BLOWUP <= F::S as usize);
+
+        Ok(UnivariateFriParam {
+            k,
+            log_blowup: LOG_BLOWUP,
+            num_queries: NUM_QUERIES,
+        })
+    }
+
+    fn trim(
+        param: &Self::Param,
+        poly_size: usize,
+        _: usize,
+    ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
+        assert!(poly_size.is_power_of_two());
+        let k = poly_size.ilog2() as usize;
+        if k > param.k {
+            return Err(err_too_large_deree("trim", param.degree(), poly_size - 1));
+        }
+
+        let param = UnivariateFriParam { k, ..param.clone() };
+        Ok((param.clone(), param))
+    }
+
+    fn commit(pp: &Self::ProverParam, poly: &Self::Polynomial) -> Result<Self::Commitment, Error> {
+        validate_input("commit", pp.degree(), [poly])?;
+
+        let coeffs = match poly.basis() {
+            Monomial => Cow::Borrowed(poly.coeffs()),
+            Lagrange => Cow::Owned(lagrange_to_monomial(poly.coeffs())),
+        };
+        let codeword = encode(&coeffs, pp.log_codeword_len());
+        let (intermediate_hashes, root) = merklize::<F, H>(&codeword);
+
+        Ok(UnivariateFriCommitment {
+            codeword,
+            intermediate_hashes,
+            root,
+        })
+    }
+
+    fn batch_commit<'a>(
+        pp: &Self::ProverParam,
+        polys: impl IntoIterator<Item = &'a Self::Polynomial>,
+    ) -> Result<Vec<Self::Commitment>, Error>
+    where
+        Self::Polynomial: 'a,
+    {
+        polys
+            .into_iter()
+            .map(|poly| Self::commit(pp, poly))
+            .collect()
+    }
+
+    fn open(
+        pp: &Self::ProverParam,
+        poly: &Self::Polynomial,
+        comm: &Self::Commitment,
+        point: &Point<F, Self::Polynomial>,
+        eval: &F,
+        transcript: &mut impl TranscriptWrite<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        validate_input("open", pp.degree(), [poly])?;
+        if cfg!(feature = "sanity-check") && poly.basis() == Monomial {
+            assert_eq!(horner(poly.coeffs(), point), *eval);
+        }
+
+        let log_n = pp.log_codeword_len();
+
+        // codeword of the quotient (f(X) - f(z)) / (X - z), which isn't committed
+        let mut denoms = powers(root_of_unity::<F>(log_n))
+            .take(1 << log_n)
+            .map(|omega| F::MULTIPLICATIVE_GENERATOR * omega - point)
+            .collect_vec();
+        if denoms.iter().any(|denom| bool::from(denom.is_zero())) {
+            return Err(err_point_in_domain());
+        }
+        denoms.iter_mut().batch_invert();
+        let mut codeword = comm
+            .codeword
+            .iter()
+            .zip(denoms)
+            .map(|(value, denom)| (*value - eval) * denom)
+            .collect_vec();
+
+        // commit phase, folding down to a constant as the quotient is of degree < 2^k
+        let mut shift = F::MULTIPLICATIVE_GENERATOR;
+        let mut layers = Vec::with_capacity(pp.k.saturating_sub(1));
+        for round in 0..pp.k {
+            let beta = transcript.squeeze_challenge();
+            let folded = fold(&codeword, shift, beta);
+            if round + 1 < pp.k {
+                let (intermediate_hashes, root) = merklize::<F, H>(&folded);
+                transcript.write_commitment(&root)?;
+                layers.push((folded.clone(), intermediate_hashes));
+            }
+            codeword = folded;
+            shift = shift.square();
+        }
+        if cfg!(feature = "sanity-check") {
+            assert!(codeword.iter().all_equal());
+        }
+        transcript.write_field_element(&codeword[0])?;
+
+        // query phase
+        let half = 1 << (log_n - 1);
+        for _ in 0..pp.num_queries {
+            let mut idx = squeeze_query_idx(transcript, half);
+            open_pair(&comm.codeword, &comm.intermediate_hashes, idx, transcript)?;
+            for (codeword, intermediate_hashes) in layers.iter() {
+                idx %= codeword.len() >> 1;
+                open_pair(codeword, intermediate_hashes, idx, transcript)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn batch_open<'a>(
+        pp: &Self::ProverParam,
+        polys: impl IntoIterator<Item = &'a Self::Polynomial>,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<F, Self::Polynomial>],
+        evals: &[Evaluation<F>],
+        transcript: &mut impl TranscriptWrite<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        let polys = polys.into_iter().collect_vec();
+        let comms = comms.into_iter().collect_vec();
+        for eval in evals {
+            Self::open(
+                pp,
+                polys[eval.poly()],
+                comms[eval.poly()],
+                &points[eval.point()],
+                eval.value(),
+                transcript,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn read_commitments(
+        _: &Self::VerifierParam,
+        num_polys: usize,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<Vec<Self::Commitment>, Error> {
+        transcript.read_commitments(num_polys).map(|roots| {
+            roots
+                .into_iter()
+                .map(UnivariateFriCommitment::from_root)
+                .collect_vec()
+        })
+    }
+
+    fn verify(
+        vp: &Self::VerifierParam,
+        comm: &Self::Commitment,
+        point: &Point<F, Self::Polynomial>,
+        eval: &F,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        let log_n = vp.log_codeword_len();
+
+        let mut betas = Vec::with_capacity(vp.k);
+        let mut roots = Vec::with_capacity(vp.k.saturating_sub(1));
+        for round in 0..vp.k {
+            betas.push(transcript.squeeze_challenge());
+            if round + 1 < vp.k {
+                roots.push(transcript.read_commitment()?);
+            }
+        }
+        let last = transcript.read_field_element()?;
+
+        let half = 1 << (log_n - 1);
+        for _ in 0..vp.num_queries {
+            let mut idx = squeeze_query_idx(transcript, half);
+
+            // the pair of f, and so of the quotient, at ±x
+            let (lo, hi) = read_pair::<F, H>(comm.root(), half, idx, transcript)?;
+            let x =
+                F::MULTIPLICATIVE_GENERATOR * root_of_unity::<F>(log_n).pow_vartime([idx as u64]);
+            let (mut lo, mut hi) = (
+                quotient_eval(lo, x, point, eval)?,
+                quotient_eval(hi, -x, point, eval)?,
+            );
+            if vp.k == 0 && (lo != last || hi != last) {
+                return Err(Error::InvalidPcsOpen("Consistency failure".to_string()));
+            }
+
+            let mut shift = F::MULTIPLICATIVE_GENERATOR;
+            for (round, beta) in betas.iter().enumerate() {
+                let log_n = log_n - round;
+                let x = shift * root_of_unity::<F>(log_n).pow_vartime([idx as u64]);
+                let folded = fold_pair(lo, hi, x, beta);
+                shift = shift.square();
+
+                if round + 1 == vp.k {
+                    if folded != last {
+                        return Err(Error::InvalidPcsOpen("Consistency failure".to_string()));
+                    }
+                    break;
+                }
+
+                // the folded value at x^2 is in the pair at x^2 or -x^2 of the next layer
+                let half = 1 << (log_n - 2);
+                let (next_lo, next_hi) =
+                    read_pair::<F, H>(&roots[round], half, idx % half, transcript)?;
+                if folded != if idx < half { next_lo } else { next_hi } {
+                    return Err(Error::InvalidPcsOpen("Folding failure".to_string()));
+                }
+                (idx, lo, hi) = (idx % half, next_lo, next_hi);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn batch_verify<'a>(
+        vp: &Self::VerifierParam,
+        comms: impl IntoIterator<Item = &'a Self::Commitment>,
+        points: &[Point<F, Self::Polynomial>],
+        evals: &[Evaluation<F>],
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, F>,
+    ) -> Result<(), Error> {
+        let comms = comms.into_iter().collect_vec();
+        for eval in evals {
+            Self::verify(
+                vp,
+                comms[eval.poly()],
+                &points[eval.point()],
+                eval.value(),
+                transcript,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn lagrange_to_monomial<F: PrimeField>(evals: &[F]) -> Vec<F> {
+    let k = evals.len().ilog2() as usize;
+    let n_inv = F::from(evals.len() as u64).invert().unwrap();
+    let mut coeffs = evals.to_vec();
+    radix2_fft(&mut coeffs, root_of_unity_inv(k), k);
+    coeffs.iter_mut().for_each(|coeff| *coeff *= n_inv);
+    coeffs
+}
+
+// evaluations on the coset `g·<ω>` of size `2^log_n`, with `g` the
+// multiplicative generator, so that `-x` is half of the codeword after `x`
+fn encode<F: PrimeField>(coeffs: &[F], log_n: usize) -> Vec<F> {
+    let mut codeword = vec![F::ZERO; 1 << log_n];
+    codeword
+        .iter_mut()
+        .zip(coeffs)
+        .zip(powers(F::MULTIPLICATIVE_GENERATOR))
+        .for_each(|((value, coeff), scalar)| *value = scalar * coeff);
+    radix2_fft(&mut codeword, root_of_unity(log_n), log_n);
+    codeword
+}
+
+// f'(x^2) = (f(x) + f(-x)) / 2 + β (f(x) - f(-x)) / 2x
+fn fold_pair<F: PrimeField>(lo: F, hi: F, x: F, beta: &F) -> F {
+    (lo + hi + *beta * (lo - hi) * x.invert().unwrap()) * F::TWO_INV
+}
+
+// codeword on the coset `shift·<ω>` folded into the one on `shift^2·<ω^2>`
+fn fold<F: PrimeField>(codeword: &[F], shift: F, beta: F) -> Vec<F> {
+    let half = codeword.len() >> 1;
+    let omega = root_of_unity::<F>(codeword.len().ilog2() as usize);
+    let mut folded = vec![F::ZERO; half];
+    parallelize(&mut folded, |(folded, start)| {
+        let mut x = shift * omega.pow_vartime([start as u64]);
+        for (value, idx) in folded.iter_mut().zip(start..) {
+            *value = fold_pair(codeword[idx], codeword[idx + half], x, &beta);
+            x *= omega;
+        }
+    });
+    folded
+}
+
+fn quotient_eval<F: PrimeField>(value: F, x: F, point: &F, eval: &F) -> Result<F, Error> {
+    Option::from((x - point).invert())
+        .map(|denom_inv| (value - eval) * denom_inv)
+        .ok_or_else(err_point_in_domain)
+}
+
+// Merkle tree over the pairs at `x` and `-x`, so a query opens both by a path
+fn merklize<F: PrimeField, H: Hash>(codeword: &[F]) -> (Vec<Output<H>>, Output<H>) {
+    let half = codeword.len() >> 1;
+    let depth = half.ilog2() as usize;
+    let mut hashes = vec![Output::<H>::default(); (2 * half) - 1];
+    parallelize(&mut hashes[..half], |(hashes, start)| {
+        let mut hasher = H::new();
+        for (hash, idx) in hashes.iter_mut().zip(start..) {
+            hasher.update_field_element(&codeword[idx]);
+            hasher.update_field_element(&codeword[idx + half]);
+            hasher.finalize_into_reset(hash);
+        }
+    });
+
+    let mut offset = 0;
+    for width in (1..=depth).rev().map(|depth| 1 << depth) {
+        let (input, output) = hashes[offset..].split_at_mut(width);
+        let mut hasher = H::new();
+        for (input, output) in input.chunks_exact(2).zip(output.iter_mut()) {
+            hasher.update(&input[0]);
+            hasher.update(&input[1]);
+            hasher.finalize_into_reset(output);
+        }
+        offset += width;
+    }
+
+    let root = hashes.pop().unwrap();
+    (hashes, root)
+}
+
+fn open_pair<F: PrimeField, H: Hash>(
+    codeword: &[F],
+    intermediate_hashes: &[Output<H>],
+    idx: usize,
+    transcript: &mut impl TranscriptWrite<Output<H>, F>,
+) -> Result<(), Error> {
+    let half = codeword.len() >> 1;
+    transcript.write_field_elements([&codeword[idx], &codeword[idx + half]])?;
+
+    let depth = half.ilog2() as usize;
+    let mut offset = 0;
+    for (level, width) in (1..=depth).rev().map(|depth| 1 << depth).enumerate() {
+        transcript.write_commitment(&intermediate_hashes[offset + ((idx >> level) ^ 1)])?;
+        offset += width;
+    }
+    Ok(())
+}
+
+fn read_pair<F: PrimeField, H: Hash>(
+    root: &Output<H>,
+    half: usize,
+    idx: usize,
+    transcript: &mut impl TranscriptRead<Output<H>, F>,
+) -> Result<(F, F), Error> {
+    let pair = transcript.read_field_elements(2)?;
+    let path = transcript.read_commitments(half.ilog2() as usize)?;
+
+    let mut hasher = H::new();
+    let mut output = {
+        hasher.update_field_element(&pair[0]);
+        hasher.update_field_element(&pair[1]);
+        hasher.finalize_fixed_reset()
+    };
+    for (level, neighbor) in path.iter().enumerate() {
+        if (idx >> level) & 1 == 0 {
+            hasher.update(&output);
+            hasher.update(neighbor);
+        } else {
+            hasher.update(neighbor);
+            hasher.update(&output);
+        }
+        output = hasher.finalize_fixed_reset();
+    }
+    if &output != root {
+        return Err(Error::InvalidPcsOpen(
+            "Invalid merkle tree opening".to_string(),
+        ));
+    }
+
+    Ok((pair[0], pair[1]))
+}
+
+// index in `0..cap` for a power of two `cap`
+fn squeeze_query_idx<F: PrimeField>(transcript: &mut impl FieldTranscript<F>, cap: usize) -> usize {
+    let challenge = transcript.squeeze_challenge();
+    let mut bytes = [0; size_of::<u32>()];
+    bytes.copy_from_slice(&challenge.to_repr().as_ref()[..size_of::<u32>()]);
+    u32::from_le_bytes(bytes) as usize & (cap - 1)
+}
+
+fn err_point_in_domain() -> Error {
+    Error::InvalidPcsOpen("Opening point is in the evaluation domain".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        pcs::{
+            test::{run_batch_commit_open_verify, run_commit_open_verify},
+            univariate::fri::UnivariateFri,
+        },
+        util::{hash::Keccak256, transcript::Keccak256Transcript},
+    };
+    use halo2_curves::bn256::Fr;
+
+    type Pcs = UnivariateFri<Fr, Keccak256>;
+
+    #[test]
+    fn commit_open_verify() {
+        run_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
+    }
+
+    #[test]
+    fn batch_commit_open_verify() {
+        run_batch_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     pcs::{
+        par_batch_commit,
         univariate::{additive, err_too_large_deree, monomial_g_to_lagrange_g, validate_input},
         Additive, Evaluation, Point, PolynomialCommitmentScheme,
     },
@@ -209,10 +210,7 @@ where
         pp: &Self::ProverParam,
         polys: impl IntoIterator<Item = &'a Self::Polynomial>,
     ) -> Result<Vec<Self::Commitment>, Error> {
-        polys
-            .into_iter()
-            .map(|poly| Self::commit(pp, poly))
-            .collect()
+        par_batch_commit(polys, |poly| Self::commit(pp, poly))
     }
 
     fn open(
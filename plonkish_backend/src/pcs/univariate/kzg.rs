@@ -1,22 +1,30 @@
 use crate::{
     pcs::{
+        par_batch_commit,
         univariate::{additive, err_too_large_deree, monomial_g_to_lagrange_g, validate_input},
-        Additive, Evaluation, Point, PolynomialCommitmentScheme,
+        Additive, DegreeBoundCommitmentScheme, Evaluation, PairingCommitmentScheme, Point,
+        PolynomialCommitmentScheme,
     },
-    poly::univariate::{UnivariateBasis::*, UnivariatePolynomial},
+    poly::univariate::{SparseUnivariatePolynomial, UnivariateBasis::*, UnivariatePolynomial},
     util::{
         arithmetic::{
-            batch_projective_to_affine, fixed_base_msm, powers, radix2_fft, root_of_unity_inv,
-            variable_base_msm, window_size, window_table, Curve, CurveAffine, Field,
-            MultiMillerLoop, PrimeCurveAffine, PrimeField,
+            batch_projective_to_affine, fixed_base_msm, inner_product, is_in_prime_order_subgroup,
+            powers, radix2_fft, root_of_unity, root_of_unity_inv, variable_base_msm, window_size,
+            window_table, Curve, CurveAffine, Field, MsmTable, MultiMillerLoop, PrimeCurveAffine,
+            PrimeField,
         },
-        transcript::{TranscriptRead, TranscriptWrite},
+        chain, izip,
+        transcript::{G2TranscriptRead, TranscriptRead, TranscriptWrite},
         Deserialize, DeserializeOwned, Itertools, Serialize,
     },
     Error,
 };
 use rand::RngCore;
-use std::{marker::PhantomData, ops::Neg, slice};
+use std::{
+    borrow::Cow, collections::BTreeMap, iter, marker::PhantomData, ops::Neg, slice, sync::Arc,
+};
+
+mod ptau;
 
 #[derive(Clone, Debug)]
 pub struct UnivariateKzg<M: MultiMillerLoop>(PhantomData<M>);
@@ -26,16 +34,75 @@ impl<M: MultiMillerLoop> UnivariateKzg<M> {
         pp: &UnivariateKzgProverParam<M>,
         coeffs: &[M::Scalar],
     ) -> UnivariateKzgCommitment<M::G1Affine> {
-        let comm = variable_base_msm(coeffs, &pp.monomial_g1[..coeffs.len()]).into();
-        UnivariateKzgCommitment(comm)
+        let comm = match &pp.monomial_g1_table {
+            Some(table) => table.msm(coeffs),
+            None => variable_base_msm(coeffs, &pp.monomial_g1()[..coeffs.len()]),
+        };
+        UnivariateKzgCommitment(comm.into())
     }
 
-    pub(crate) fn commit_lagrange(
+    /// Commit to the polynomial of `coeffs` in MSMs of `chunk_size` of them
+    /// at a time, so they don't have to be held in memory at once, e.g. when
+    /// read from a file.
+    pub fn commit_streaming(
+        pp: &UnivariateKzgProverParam<M>,
+        coeffs: impl IntoIterator<Item = M::Scalar>,
+        chunk_size: usize,
+    ) -> Result<UnivariateKzgCommitment<M::G1Affine>, Error> {
+        assert!(chunk_size > 0);
+
+        let mut comm = M::G1Affine::identity().to_curve();
+        let mut offset = 0;
+        for chunk in &coeffs.into_iter().chunks(chunk_size) {
+            let chunk = chunk.collect_vec();
+            if offset + chunk.len() > pp.monomial_g1().len() {
+                return Err(err_too_large_deree(
+                    "commit",
+                    pp.degree(),
+                    offset + chunk.len() - 1,
+                ));
+            }
+            comm += variable_base_msm(&chunk, &pp.monomial_g1()[offset..offset + chunk.len()]);
+            offset += chunk.len();
+        }
+        Ok(UnivariateKzgCommitment(comm.into()))
+    }
+
+    /// Commit to a sparse polynomial in an MSM of its nonzero coefficients
+    /// only.
+    pub fn commit_sparse(
+        pp: &UnivariateKzgProverParam<M>,
+        poly: &SparseUnivariatePolynomial<M::Scalar>,
+    ) -> Result<UnivariateKzgCommitment<M::G1Affine>, Error> {
+        if poly.degree() > pp.degree() {
+            return Err(err_too_large_deree("commit", pp.degree(), poly.degree()));
+        }
+        let (scalars, bases): (Vec<_>, Vec<_>) = poly
+            .terms()
+            .map(|(exponent, coeff)| (*coeff, pp.monomial_g1()[exponent]))
+            .unzip();
+        Ok(UnivariateKzgCommitment(
+            variable_base_msm(&scalars, &bases).into(),
+        ))
+    }
+
+    /// Commit to the polynomial of `evals` on the domain of their size in a
+    /// single MSM if its Lagrange basis is the one of `pp` or is precomputed
+    /// by [`UnivariateKzgProverParam::with_lagrange_g1`], otherwise after an
+    /// iFFT.
+    pub fn commit_lagrange(
         pp: &UnivariateKzgProverParam<M>,
         evals: &[M::Scalar],
     ) -> UnivariateKzgCommitment<M::G1Affine> {
-        let comm = variable_base_msm(evals, &pp.lagrange_g1[..evals.len()]).into();
-        UnivariateKzgCommitment(comm)
+        match pp.lagrange_g1_of_size(evals.len()) {
+            Some(lagrange_g1) => {
+                UnivariateKzgCommitment(variable_base_msm(evals, lagrange_g1).into())
+            }
+            None => {
+                let poly = UnivariatePolynomial::lagrange(evals.to_vec()).ifft();
+                Self::commit_monomial(pp, poly.coeffs())
+            }
+        }
     }
     pub(crate) fn commit_monomial_g2(
         param: &UnivariateKzgParam<M>,
@@ -64,9 +131,29 @@ pub struct UnivariateKzgParam<M: MultiMillerLoop> {
     monomial_g1: Vec<M::G1Affine>,
     lagrange_g1: Vec<M::G1Affine>,
     powers_of_s_g2: Vec<M::G2Affine>,
+    // degree of the largest power in G1 of the SRS the powers were read from
+    srs_degree: usize,
 }
 
 impl<M: MultiMillerLoop> UnivariateKzgParam<M> {
+    /// Param of the powers `[s^i]1` and `[s^i]2` of some `s`, e.g. of a
+    /// ceremony, with the Lagrange bases computed from the ones in G1.
+    pub(crate) fn from_powers(
+        monomial_g1: Vec<M::G1Affine>,
+        powers_of_s_g2: Vec<M::G2Affine>,
+    ) -> Self {
+        assert!(monomial_g1.len().is_power_of_two());
+
+        let lagrange_g1 = monomial_g_to_lagrange_g(&monomial_g1);
+        Self {
+            k: monomial_g1.len().ilog2() as usize,
+            srs_degree: monomial_g1.len() - 1,
+            monomial_g1,
+            lagrange_g1,
+            powers_of_s_g2,
+        }
+    }
+
     pub fn k(&self) -> usize {
         self.k
     }
@@ -75,6 +162,14 @@ impl<M: MultiMillerLoop> UnivariateKzgParam<M> {
         self.monomial_g1.len() - 1
     }
 
+    /// Degree of the largest power `[s^i]1` of the SRS the param was read
+    /// from, above [`UnivariateKzgParam::degree`] if only its first powers
+    /// were read, e.g. of a larger ceremony. Degree checks are sound only
+    /// against it, so they refuse such a param.
+    pub fn srs_degree(&self) -> usize {
+        self.srs_degree
+    }
+
     pub fn g1(&self) -> M::G1Affine {
         self.monomial_g1[0]
     }
@@ -90,6 +185,93 @@ impl<M: MultiMillerLoop> UnivariateKzgParam<M> {
     pub fn powers_of_s_g2(&self) -> &[M::G2Affine] {
         &self.powers_of_s_g2
     }
+
+    /// Check the structure of the SRS, e.g. of a third-party one, that all
+    /// points are in the prime order subgroups, both groups hold the powers of
+    /// the same `s`, and the Lagrange bases match the monomial ones. The checks
+    /// are batched with randomness from `rng`.
+    pub fn validate(&self, mut rng: impl RngCore) -> Result<(), Error> {
+        let err = |reason: &str| {
+            Err(Error::InvalidPcsParam(format!(
+                "Invalid univariate KZG param: {reason}"
+            )))
+        };
+
+        let n = self.monomial_g1.len();
+        if n != 1 << self.k
+            || self.lagrange_g1.len() != n
+            || self.powers_of_s_g2.len() < 2
+            || self.srs_degree < n - 1
+        {
+            return err("Unexpected number of points");
+        }
+        if bool::from(self.g1().is_identity() | self.g2().is_identity())
+            || self.powers_of_s_g2[1] == self.g2()
+        {
+            return err("Degenerate generators or s");
+        }
+        if !chain![&self.monomial_g1, &self.lagrange_g1].all(is_in_prime_order_subgroup)
+            || !self.powers_of_s_g2.iter().all(is_in_prime_order_subgroup)
+        {
+            return err("Point not in the prime order subgroup");
+        }
+
+        if !powers_are_consistent::<M>(&self.monomial_g1, &self.powers_of_s_g2, &mut rng) {
+            return err("Inconsistent powers of s");
+        }
+
+        // commitments to a random polynomial in both bases
+        let coeffs = iter::repeat_with(|| M::Scalar::random(&mut rng))
+            .take(n)
+            .collect_vec();
+        let mut evals = coeffs.clone();
+        radix2_fft(&mut evals, root_of_unity(self.k), self.k);
+        if variable_base_msm(&coeffs, &self.monomial_g1)
+            != variable_base_msm(&evals, &self.lagrange_g1)
+        {
+            return err("Inconsistent Lagrange bases");
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether `monomial_g1` and `powers_of_s_g2`, of at least 2 points each,
+/// are the powers of the same `s` of their first points, checked in a single
+/// multi-pairing of random combinations of them with randomness from `rng`.
+pub(crate) fn powers_are_consistent<M: MultiMillerLoop>(
+    monomial_g1: &[M::G1Affine],
+    powers_of_s_g2: &[M::G2Affine],
+    mut rng: impl RngCore,
+) -> bool {
+    assert!(monomial_g1.len() >= 2 && powers_of_s_g2.len() >= 2);
+
+    // e([s^i]1, [1]2) = e([s^(i-1)]1, [s]2) and
+    // e([1]1, [s^i]2) = e([s]1, [s^(i-1)]2), each for a random combination of i
+    let (gamma, delta) = (M::Scalar::random(&mut rng), M::Scalar::random(&mut rng));
+    let (shifted_g1, unshifted_g1) = {
+        let n = monomial_g1.len() - 1;
+        let scalars = powers(gamma).take(n).collect_vec();
+        let shifted = variable_base_msm(&scalars, &monomial_g1[1..]);
+        let unshifted = variable_base_msm(&scalars, &monomial_g1[..n]);
+        (shifted, unshifted)
+    };
+    let (shifted_g2, unshifted_g2) = {
+        let m = powers_of_s_g2.len() - 1;
+        let scalars = powers(gamma).take(m).collect_vec();
+        let shifted = variable_base_msm(&scalars, &powers_of_s_g2[1..]);
+        let unshifted = variable_base_msm(&scalars, &powers_of_s_g2[..m]);
+        (shifted, unshifted)
+    };
+    let terms = [
+        (shifted_g1.into(), powers_of_s_g2[0]),
+        ((-unshifted_g1).into(), powers_of_s_g2[1]),
+        ((monomial_g1[0] * delta).into(), shifted_g2.into()),
+        ((-monomial_g1[1] * delta).into(), unshifted_g2.into()),
+    ]
+    .map(|(g1, g2): (M::G1Affine, M::G2Affine)| (g1, M::G2Prepared::from(g2)));
+    let terms = terms.iter().map(|(g1, g2)| (g1, g2)).collect_vec();
+    M::pairings_product_is_identity(&terms)
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -99,8 +281,18 @@ impl<M: MultiMillerLoop> UnivariateKzgParam<M> {
 ))]
 pub struct UnivariateKzgProverParam<M: MultiMillerLoop> {
     k: usize,
-    monomial_g1: Vec<M::G1Affine>,
-    lagrange_g1: Vec<M::G1Affine>,
+    // shared with the params trimmed from this one by `trim_to`, of which
+    // only the first `2^k` are of this one
+    monomial_g1: Arc<[M::G1Affine]>,
+    lagrange_g1: Arc<[M::G1Affine]>,
+    // Lagrange bases of smaller domains by their size
+    sub_lagrange_g1: BTreeMap<usize, Arc<[M::G1Affine]>>,
+    // recomputed after deserialization if needed, as it's a multiple of the
+    // size of `monomial_g1`
+    #[serde(skip)]
+    monomial_g1_table: Option<MsmTable<M::G1Affine>>,
+    // degree of the largest power of the SRS this one is trimmed from
+    srs_degree: usize,
 }
 
 impl<M: MultiMillerLoop> UnivariateKzgProverParam<M> {
@@ -111,9 +303,79 @@ impl<M: MultiMillerLoop> UnivariateKzgProverParam<M> {
     ) -> Self {
         Self {
             k,
-            monomial_g1,
-            lagrange_g1,
+            srs_degree: monomial_g1.len() - 1,
+            monomial_g1: monomial_g1.into(),
+            lagrange_g1: lagrange_g1.into(),
+            sub_lagrange_g1: BTreeMap::new(),
+            monomial_g1_table: None,
+        }
+    }
+
+    /// Precompute the window multiples of `monomial_g1`, for
+    /// [`UnivariateKzg::commit_monomial`] to skip the doublings of its MSMs,
+    /// which pays off when committing many times with the same `pp`.
+    pub fn with_msm_table(mut self) -> Self {
+        self.monomial_g1_table = Some(MsmTable::new(self.monomial_g1()));
+        self
+    }
+
+    /// Precompute the Lagrange bases of the domains of `sizes`, which are
+    /// powers of two up to `2^k`, for [`UnivariateKzg::commit_lagrange`] to
+    /// commit evaluations on them without an iFFT.
+    pub fn with_lagrange_g1(mut self, sizes: impl IntoIterator<Item = usize>) -> Self {
+        for size in sizes {
+            assert!(size.is_power_of_two() && size <= self.monomial_g1().len());
+            if size != self.lagrange_g1.len() && !self.sub_lagrange_g1.contains_key(&size) {
+                let lagrange_g1 = monomial_g_to_lagrange_g(&self.monomial_g1()[..size]);
+                self.sub_lagrange_g1.insert(size, lagrange_g1.into());
+            }
         }
+        self
+    }
+
+    /// Prover param of degree `degree`, i.e. of the first `degree + 1`
+    /// powers, which must be a power of two, sharing the powers and the
+    /// precomputed Lagrange bases with this one instead of copying them, so
+    /// that one of a master SRS serves every smaller table. Its Lagrange basis
+    /// is computed only if it's not precomputed by
+    /// [`UnivariateKzgProverParam::with_lagrange_g1`], and the table of
+    /// [`UnivariateKzgProverParam::with_msm_table`] isn't kept.
+    ///
+    /// The trimmed param still has the [`UnivariateKzgProverParam::srs_degree`]
+    /// of the master SRS, whose powers above `degree` the prover holds, so it
+    /// must not be used for degree checks like of Baloo or cq, which would be
+    /// unsound against it.
+    pub fn trim_to(&self, degree: usize) -> Result<Self, Error> {
+        let size = degree + 1;
+        if !size.is_power_of_two() {
+            return Err(Error::InvalidPcsParam(format!(
+                "Size {size} to trim to should be a power of two"
+            )));
+        }
+        if size > self.monomial_g1().len() {
+            return Err(err_too_large_deree("trim_to", self.degree(), degree));
+        }
+
+        let lagrange_g1 = if size == self.lagrange_g1.len() {
+            self.lagrange_g1.clone()
+        } else if let Some(lagrange_g1) = self.sub_lagrange_g1.get(&size) {
+            lagrange_g1.clone()
+        } else {
+            monomial_g_to_lagrange_g(&self.monomial_g1()[..size]).into()
+        };
+        let sub_lagrange_g1 = self
+            .sub_lagrange_g1
+            .range(..size)
+            .map(|(size, lagrange_g1)| (*size, lagrange_g1.clone()))
+            .collect();
+        Ok(Self {
+            k: size.ilog2() as usize,
+            monomial_g1: self.monomial_g1.clone(),
+            lagrange_g1,
+            sub_lagrange_g1,
+            monomial_g1_table: None,
+            srs_degree: self.srs_degree,
+        })
     }
 
     pub fn k(&self) -> usize {
@@ -121,7 +383,13 @@ impl<M: MultiMillerLoop> UnivariateKzgProverParam<M> {
     }
 
     pub fn degree(&self) -> usize {
-        self.monomial_g1.len() - 1
+        (1 << self.k) - 1
+    }
+
+    /// [`UnivariateKzgParam::srs_degree`] of the param this one is trimmed
+    /// from, which degree bounds are checked against.
+    pub fn srs_degree(&self) -> usize {
+        self.srs_degree
     }
 
     pub fn g1(&self) -> M::G1Affine {
@@ -129,12 +397,64 @@ impl<M: MultiMillerLoop> UnivariateKzgProverParam<M> {
     }
 
     pub fn monomial_g1(&self) -> &[M::G1Affine] {
-        &self.monomial_g1
+        &self.monomial_g1[..1 << self.k]
     }
 
     pub fn lagrange_g1(&self) -> &[M::G1Affine] {
         &self.lagrange_g1
     }
+
+    /// Lagrange basis of the domain of `size`, if it's the one of `2^k` or
+    /// precomputed.
+    pub fn lagrange_g1_of_size(&self, size: usize) -> Option<&[M::G1Affine]> {
+        if size == self.lagrange_g1.len() {
+            Some(&self.lagrange_g1)
+        } else {
+            self.sub_lagrange_g1.get(&size).map(AsRef::as_ref)
+        }
+    }
+}
+
+/// `[s^i]2` of [`PairingCommitmentScheme::trim_g2`] for G2 commitments.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "M::G2Affine: Serialize",
+    deserialize = "M::G2Affine: DeserializeOwned",
+))]
+pub struct UnivariateKzgG2ProverParam<M: MultiMillerLoop> {
+    powers_of_s_g2: Vec<M::G2Affine>,
+}
+
+impl<M: MultiMillerLoop> UnivariateKzgG2ProverParam<M> {
+    pub fn degree(&self) -> usize {
+        self.powers_of_s_g2.len() - 1
+    }
+
+    pub fn powers_of_s_g2(&self) -> &[M::G2Affine] {
+        &self.powers_of_s_g2
+    }
+}
+
+/// `[s^(D-d)]2` of degree bound `d` and the degree `D` of the largest power
+/// of the SRS, for checking `[X^(D-d) * p(X)]1` against `[p(X)]1`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "M::G2Affine: Serialize",
+    deserialize = "M::G2Affine: DeserializeOwned",
+))]
+pub struct UnivariateKzgDegreeBoundParam<M: MultiMillerLoop> {
+    degree_bound: usize,
+    shift_g2: M::G2Affine,
+}
+
+impl<M: MultiMillerLoop> UnivariateKzgDegreeBoundParam<M> {
+    pub fn degree_bound(&self) -> usize {
+        self.degree_bound
+    }
+
+    pub fn shift_g2(&self) -> M::G2Affine {
+        self.shift_g2
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -262,6 +582,7 @@ where
             monomial_g1,
             lagrange_g1,
             powers_of_s_g2,
+            srs_degree: poly_size - 1,
         })
     }
 
@@ -283,7 +604,10 @@ where
             monomial_g_to_lagrange_g(&monomial_g1)
         };
 
-        let pp = Self::ProverParam::new(poly_size.ilog2() as usize, monomial_g1, lagrange_g1);
+        let pp = Self::ProverParam {
+            srs_degree: param.srs_degree(),
+            ..Self::ProverParam::new(poly_size.ilog2() as usize, monomial_g1, lagrange_g1)
+        };
         let vp = Self::VerifierParam {
             g1: param.g1(),
             g2: param.g2(),
@@ -305,10 +629,7 @@ where
         pp: &Self::ProverParam,
         polys: impl IntoIterator<Item = &'a Self::Polynomial>,
     ) -> Result<Vec<Self::Commitment>, Error> {
-        polys
-            .into_iter()
-            .map(|poly| Self::commit(pp, poly))
-            .collect()
+        par_batch_commit(polys, |poly| Self::commit(pp, poly))
     }
 
     fn open(
@@ -355,6 +676,22 @@ where
         let polys = polys.into_iter().collect_vec();
         let comms = comms.into_iter().collect_vec();
         validate_input("batch open", pp.degree(), polys.clone())?;
+        // all polynomials at the same point are combined into a single opening
+        if let Some(point) = common_point(points, evals) {
+            let beta = transcript.squeeze_challenge();
+            let powers_of_beta = powers(beta).take(evals.len()).collect_vec();
+            let poly = izip!(&powers_of_beta, evals.iter().map(|eval| polys[eval.poly()]))
+                .sum::<UnivariatePolynomial<_>>();
+            let (comm, eval) = if cfg!(feature = "sanity-check") {
+                let comms = evals.iter().map(|eval| comms[eval.poly()]);
+                let comm = UnivariateKzgCommitment::msm(&powers_of_beta, comms);
+                let eval = inner_product(&powers_of_beta, evals.iter().map(Evaluation::value));
+                (comm, eval)
+            } else {
+                (UnivariateKzgCommitment::default(), M::Scalar::ZERO)
+            };
+            return Self::open(pp, &poly, &comm, point, &eval, transcript);
+        }
         additive::batch_open::<_, Self>(pp, polys, comms, points, evals, transcript)
     }
 
@@ -389,20 +726,202 @@ where
         transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
     ) -> Result<(), Error> {
         let comms = comms.into_iter().collect_vec();
+        if let Some(point) = common_point(points, evals) {
+            let beta = transcript.squeeze_challenge();
+            let powers_of_beta = powers(beta).take(evals.len()).collect_vec();
+            let comms = evals.iter().map(|eval| comms[eval.poly()]);
+            let comm = UnivariateKzgCommitment::msm(&powers_of_beta, comms);
+            let eval = inner_product(&powers_of_beta, evals.iter().map(Evaluation::value));
+            return Self::verify(vp, &comm, point, &eval, transcript);
+        }
         additive::batch_verify::<_, Self>(vp, comms, points, evals, transcript)
     }
 }
 
+impl<M> PairingCommitmentScheme<M::Scalar> for UnivariateKzg<M>
+where
+    M: MultiMillerLoop,
+    M::Scalar: Serialize + DeserializeOwned,
+    M::G1Affine: Serialize + DeserializeOwned,
+    M::G2Affine: Serialize + DeserializeOwned,
+{
+    type G2ProverParam = UnivariateKzgG2ProverParam<M>;
+    type G2Commitment = UnivariateKzgCommitment<M::G2Affine>;
+    type G2CommitmentChunk = M::G2Affine;
+
+    fn trim_g2(param: &Self::Param, poly_size: usize) -> Result<Self::G2ProverParam, Error> {
+        if param.powers_of_s_g2.len() < poly_size {
+            let degree = param.powers_of_s_g2.len() - 1;
+            return Err(err_too_large_deree("trim", degree, poly_size - 1));
+        }
+        Ok(UnivariateKzgG2ProverParam {
+            powers_of_s_g2: param.powers_of_s_g2[..poly_size].to_vec(),
+        })
+    }
+
+    /// Commit in G2 to `poly` in either basis, the Lagrange one of any
+    /// domain up to the size of `pp` after an iFFT.
+    fn commit_g2(
+        pp: &Self::G2ProverParam,
+        poly: &Self::Polynomial,
+    ) -> Result<Self::G2Commitment, Error> {
+        let poly = match poly.basis() {
+            Monomial => Cow::Borrowed(poly),
+            Lagrange => Cow::Owned(poly.clone().into_ifft()),
+        };
+        if pp.degree() < poly.degree() {
+            return Err(err_too_large_deree("commit_g2", pp.degree(), poly.degree()));
+        }
+        let coeffs = poly.coeffs();
+        let comm = variable_base_msm(coeffs, &pp.powers_of_s_g2[..coeffs.len()]).into();
+        Ok(UnivariateKzgCommitment(comm))
+    }
+
+    fn read_commitment_g2(
+        transcript: &mut impl G2TranscriptRead<M::G2Affine, M::Scalar>,
+    ) -> Result<Self::G2Commitment, Error> {
+        transcript.read_commitment_g2().map(UnivariateKzgCommitment)
+    }
+}
+
+impl<M> DegreeBoundCommitmentScheme<M::Scalar> for UnivariateKzg<M>
+where
+    M: MultiMillerLoop,
+    M::Scalar: Serialize + DeserializeOwned,
+    M::G1Affine: Serialize + DeserializeOwned,
+    M::G2Affine: Serialize + DeserializeOwned,
+{
+    type DegreeBoundParam = UnivariateKzgDegreeBoundParam<M>;
+
+    fn trim_degree_bound(
+        param: &Self::Param,
+        poly_size: usize,
+        degree_bound: usize,
+    ) -> Result<Self::DegreeBoundParam, Error> {
+        if poly_size <= degree_bound {
+            let degree = poly_size.saturating_sub(1);
+            return Err(err_too_large_deree("trim", degree, degree_bound));
+        }
+        if poly_size > param.degree() + 1 {
+            return Err(err_too_large_deree("trim", param.degree(), poly_size - 1));
+        }
+        // shifted up to the largest power of the SRS, as the prover could
+        // commit to a shift of a larger polynomial with any power above it
+        if param.srs_degree() > param.degree() {
+            return Err(err_partial_srs(param.srs_degree(), param.degree()));
+        }
+        let shift = param.srs_degree() - degree_bound;
+        let shift_g2 = *param
+            .powers_of_s_g2
+            .get(shift)
+            .ok_or_else(|| err_too_large_deree("trim", param.degree(), shift))?;
+        Ok(UnivariateKzgDegreeBoundParam {
+            degree_bound,
+            shift_g2,
+        })
+    }
+
+    /// Write `[X^(D-d) * p(X)]1`, which the SRS only has the powers of `s`
+    /// to commit to if `deg(p) ≤ d`, followed by the opening of `p`.
+    fn open_with_degree_bound(
+        pp: &Self::ProverParam,
+        poly: &Self::Polynomial,
+        comm: &Self::Commitment,
+        degree_bound: usize,
+        point: &Point<M::Scalar, Self::Polynomial>,
+        eval: &M::Scalar,
+        transcript: &mut impl TranscriptWrite<M::G1Affine, M::Scalar>,
+    ) -> Result<(), Error> {
+        assert_eq!(poly.basis(), Monomial);
+
+        if pp.degree() < pp.srs_degree() {
+            return Err(err_partial_srs(pp.srs_degree(), pp.degree()));
+        }
+        if pp.degree() < degree_bound {
+            return Err(err_too_large_deree("open", pp.degree(), degree_bound));
+        }
+        if degree_bound < poly.degree() {
+            return Err(err_too_large_deree("open", degree_bound, poly.degree()));
+        }
+
+        let shift = pp.degree() - degree_bound;
+        let shifted = iter::repeat(M::Scalar::ZERO)
+            .take(shift)
+            .chain(poly.coeffs().iter().copied())
+            .collect_vec();
+        transcript.write_commitment(&Self::commit_monomial(pp, &shifted).0)?;
+        Self::open(pp, poly, comm, point, eval, transcript)?;
+        // folds the degree check into the pairings of the opening
+        transcript.squeeze_challenge();
+
+        Ok(())
+    }
+
+    /// Check `e([X^(D-d) * p(X)]1, [1]2) = e([p(X)]1, [s^(D-d)]2)` together
+    /// with the opening, in a single product of 3 pairings.
+    fn verify_degree_bound(
+        vp: &Self::VerifierParam,
+        degree_bound_param: &Self::DegreeBoundParam,
+        comm: &Self::Commitment,
+        point: &Point<M::Scalar, Self::Polynomial>,
+        eval: &M::Scalar,
+        transcript: &mut impl TranscriptRead<Self::CommitmentChunk, M::Scalar>,
+    ) -> Result<(), Error> {
+        let shifted = transcript.read_commitment()?;
+        let pi = transcript.read_commitment()?;
+        let gamma = transcript.squeeze_challenge();
+        let c = (pi * point + comm.0 - vp.g1 * eval + shifted * gamma).into();
+        let comm = (comm.0 * gamma).into();
+        M::pairings_product_is_identity(&[
+            (&c, &(-vp.g2).into()),
+            (&pi, &vp.s_g2.into()),
+            (&comm, &degree_bound_param.shift_g2.into()),
+        ])
+        .then_some(())
+        .ok_or_else(|| {
+            Error::InvalidPcsOpen("Invalid univariate KZG open with degree bound".to_string())
+        })
+    }
+}
+
+fn err_partial_srs(srs_degree: usize, degree: usize) -> Error {
+    Error::InvalidPcsParam(format!(
+        "Degree checks need all powers of the SRS of degree {srs_degree} but got {degree}"
+    ))
+}
+
+// the point all evaluations are at, if they share one
+fn common_point<'a, F>(points: &'a [F], evals: &[Evaluation<F>]) -> Option<&'a F> {
+    let point = evals.first()?.point();
+    evals
+        .iter()
+        .all(|eval| eval.point() == point)
+        .then(|| &points[point])
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
         pcs::{
             test::{run_batch_commit_open_verify, run_commit_open_verify},
-            univariate::kzg::UnivariateKzg,
+            univariate::kzg::{UnivariateKzg, UnivariateKzgCommitment, UnivariateKzgProverParam},
+            Additive, DegreeBoundCommitmentScheme, Evaluation, PairingCommitmentScheme,
+            PolynomialCommitmentScheme,
         },
-        util::transcript::Keccak256Transcript,
+        poly::{univariate::UnivariatePolynomial, Polynomial},
+        util::{
+            arithmetic::Field,
+            transcript::{
+                FieldTranscript, FieldTranscriptRead, FieldTranscriptWrite, InMemoryTranscript,
+                Keccak256Transcript, TranscriptWrite,
+            },
+            Itertools,
+        },
+        Error,
     };
-    use halo2_curves::bn256::Bn256;
+    use halo2_curves::bn256::{Bn256, Fr};
+    use rand::rngs::OsRng;
+    use std::iter;
 
     type Pcs = UnivariateKzg<Bn256>;
 
@@ -415,4 +934,302 @@ mod test {
     fn batch_commit_open_verify() {
         run_batch_commit_open_verify::<_, Pcs, Keccak256Transcript<_>>();
     }
+
+    #[test]
+    fn commit_g2() {
+        let param = Pcs::setup(1 << 4, 1, OsRng).unwrap();
+        let pp = Pcs::trim_g2(&param, 1 << 3).unwrap();
+        let coeffs = iter::repeat_with(|| Fr::random(OsRng))
+            .take(1 << 3)
+            .collect_vec();
+        let comm = Pcs::commit_monomial_g2(&param, &coeffs);
+
+        let poly = UnivariatePolynomial::monomial(coeffs);
+        assert_eq!(Pcs::commit_g2(&pp, &poly).unwrap(), comm);
+        let evals = poly.fft();
+        assert_eq!(Pcs::commit_g2(&pp, &evals).unwrap(), comm);
+
+        let proof = {
+            let mut transcript = Keccak256Transcript::new(());
+            Pcs::commit_g2_and_write(&pp, &poly, &mut transcript).unwrap();
+            transcript.into_proof()
+        };
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        assert_eq!(Pcs::read_commitment_g2(&mut transcript).unwrap(), comm);
+
+        let too_large = UnivariatePolynomial::rand(1 << 4, OsRng);
+        assert!(Pcs::commit_g2(&pp, &too_large).is_err());
+        assert!(Pcs::trim_g2(&param, 1 << 5).is_err());
+    }
+
+    #[test]
+    fn batch_commit_in_order() {
+        let param = Pcs::setup(1 << 4, 1, OsRng).unwrap();
+        let (pp, _) = Pcs::trim(&param, 1 << 4, 1).unwrap();
+        let polys = (1..=16)
+            .map(|n| UnivariatePolynomial::rand(n, OsRng))
+            .collect_vec();
+        assert_eq!(
+            Pcs::batch_commit(&pp, &polys).unwrap(),
+            polys
+                .iter()
+                .map(|poly| Pcs::commit(&pp, poly).unwrap())
+                .collect_vec()
+        );
+    }
+
+    #[test]
+    fn lin_comb() {
+        let param = Pcs::setup(1 << 4, 1, OsRng).unwrap();
+        let (pp, _) = Pcs::trim(&param, 1 << 4, 1).unwrap();
+        let g2_pp = Pcs::trim_g2(&param, 1 << 4).unwrap();
+        let polys = iter::repeat_with(|| UnivariatePolynomial::rand(1 << 4, OsRng))
+            .take(3)
+            .collect_vec();
+        let scalars = iter::repeat_with(|| Fr::random(OsRng))
+            .take(3)
+            .collect_vec();
+        let poly = UnivariatePolynomial::lin_comb(scalars.iter().zip(&polys));
+
+        let comms = Pcs::batch_commit(&pp, &polys).unwrap();
+        let comm = UnivariateKzgCommitment::lin_comb(scalars.iter().zip(&comms));
+        assert_eq!(comm, Pcs::commit(&pp, &poly).unwrap());
+        assert_eq!(comm, UnivariateKzgCommitment::msm(&scalars, &comms));
+
+        let comms = polys
+            .iter()
+            .map(|poly| Pcs::commit_g2(&g2_pp, poly).unwrap())
+            .collect_vec();
+        let comm = UnivariateKzgCommitment::lin_comb(scalars.iter().zip(&comms));
+        assert_eq!(comm, Pcs::commit_g2(&g2_pp, &poly).unwrap());
+    }
+
+    #[test]
+    fn open_with_degree_bound() {
+        let param = Pcs::setup(1 << 4, 1, OsRng).unwrap();
+        let (pp, vp) = Pcs::trim(&param, 1 << 4, 1).unwrap();
+        let run = |poly: &UnivariatePolynomial<Fr>, bound: usize, verifier_bound: usize| {
+            let dp = Pcs::trim_degree_bound(&param, 1 << 4, verifier_bound).unwrap();
+            let comm = Pcs::commit(&pp, poly).unwrap();
+            let point = Fr::random(OsRng);
+            let eval = poly.evaluate(&point);
+            let proof = {
+                let mut transcript = Keccak256Transcript::new(());
+                Pcs::open_with_degree_bound(
+                    &pp,
+                    poly,
+                    &comm,
+                    bound,
+                    &point,
+                    &eval,
+                    &mut transcript,
+                )?;
+                transcript.into_proof()
+            };
+            let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+            Pcs::verify_degree_bound(&vp, &dp, &comm, &point, &eval, &mut transcript)
+        };
+
+        let poly = UnivariatePolynomial::rand(6, OsRng);
+        assert_eq!(run(&poly, 5, 5), Ok(()));
+        assert_eq!(run(&poly, 15, 15), Ok(()));
+        // the prover can't shift a polynomial of a larger degree by enough
+        let poly = UnivariatePolynomial::rand(8, OsRng);
+        assert!(matches!(run(&poly, 5, 5), Err(Error::InvalidPcsParam(_))));
+        assert!(matches!(run(&poly, 7, 5), Err(Error::InvalidPcsOpen(_))));
+
+        assert!(Pcs::trim_degree_bound(&param, 1 << 4, 1 << 4).is_err());
+        assert!(Pcs::trim_degree_bound(&param, 1 << 5, 0).is_err());
+    }
+
+    #[test]
+    fn open_with_degree_bound_of_larger_param() {
+        let param = Pcs::setup(1 << 6, 1, OsRng).unwrap();
+        let dp = Pcs::trim_degree_bound(&param, 1 << 4, 5).unwrap();
+        let point = Fr::random(OsRng);
+        let open = |pp: &UnivariateKzgProverParam<Bn256>, poly: &UnivariatePolynomial<Fr>| {
+            let comm = Pcs::commit(pp, poly).unwrap();
+            let eval = poly.evaluate(&point);
+            let mut transcript = Keccak256Transcript::new(());
+            Pcs::open_with_degree_bound(pp, poly, &comm, 5, &point, &eval, &mut transcript)
+                .map(|_| (comm, eval, transcript.into_proof()))
+        };
+
+        // trimmed below the SRS, the prover can't shift up to its largest power
+        let (pp, _) = Pcs::trim(&param, 1 << 4, 1).unwrap();
+        let poly = UnivariatePolynomial::rand(6, OsRng);
+        assert!(matches!(open(&pp, &poly), Err(Error::InvalidPcsParam(_))));
+
+        let (pp, vp) = Pcs::trim(&param, 1 << 6, 1).unwrap();
+        let (comm, eval, proof) = open(&pp, &poly).unwrap();
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        assert_eq!(
+            Pcs::verify_degree_bound(&vp, &dp, &comm, &point, &eval, &mut transcript),
+            Ok(())
+        );
+
+        // of degree 7, shifted only up to the size it's trimmed to, which the
+        // rest of the SRS has the powers to commit to
+        let poly = UnivariatePolynomial::rand(8, OsRng);
+        let comm = Pcs::commit(&pp, &poly).unwrap();
+        let eval = poly.evaluate(&point);
+        let proof = {
+            let mut transcript = Keccak256Transcript::new(());
+            let shifted = iter::repeat(Fr::ZERO)
+                .take((1 << 4) - 1 - 5)
+                .chain(poly.coeffs().iter().copied())
+                .collect_vec();
+            let shifted = Pcs::commit(&pp, &UnivariatePolynomial::monomial(shifted)).unwrap();
+            transcript.write_commitment(&shifted.0).unwrap();
+            Pcs::open(&pp, &poly, &comm, &point, &eval, &mut transcript).unwrap();
+            transcript.squeeze_challenge();
+            transcript.into_proof()
+        };
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        assert_eq!(
+            Pcs::verify_degree_bound(&vp, &dp, &comm, &point, &eval, &mut transcript),
+            Err(Error::InvalidPcsOpen(
+                "Invalid univariate KZG open with degree bound".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn commit_lagrange() {
+        let param = Pcs::setup(1 << 4, 1, OsRng).unwrap();
+        let (pp, _) = Pcs::trim(&param, 1 << 4, 1).unwrap();
+        let evals = iter::repeat_with(|| Fr::random(OsRng))
+            .take(1 << 2)
+            .collect_vec();
+        let expected = {
+            let poly = UnivariatePolynomial::lagrange(evals.clone()).ifft();
+            Pcs::commit_monomial(&pp, poly.coeffs())
+        };
+
+        assert_eq!(Pcs::commit_lagrange(&pp, &evals), expected);
+        let pp = pp.with_lagrange_g1([1 << 2]);
+        assert!(pp.lagrange_g1_of_size(1 << 2).is_some());
+        assert_eq!(Pcs::commit_lagrange(&pp, &evals), expected);
+    }
+
+    #[test]
+    fn commit_with_msm_table() {
+        let param = Pcs::setup(1 << 6, 1, OsRng).unwrap();
+        let (pp, _) = Pcs::trim(&param, 1 << 6, 1).unwrap();
+        let pp_with_table = pp.clone().with_msm_table();
+        for n in [1, 5, 1 << 6] {
+            let coeffs = iter::repeat_with(|| Fr::random(OsRng))
+                .take(n)
+                .collect_vec();
+            assert_eq!(
+                Pcs::commit_monomial(&pp_with_table, &coeffs),
+                Pcs::commit_monomial(&pp, &coeffs)
+            );
+        }
+    }
+
+    #[test]
+    fn trim_to() {
+        let param = Pcs::setup(1 << 6, 1, OsRng).unwrap();
+        let (pp, _) = Pcs::trim(&param, 1 << 6, 1).unwrap();
+        let pp = pp.with_lagrange_g1([1 << 4, 1 << 2]);
+        for k in [6, 4, 3] {
+            let trimmed = pp.trim_to((1 << k) - 1).unwrap();
+            let (expected, _) = Pcs::trim(&param, 1 << k, 1).unwrap();
+            assert_eq!(trimmed.monomial_g1(), expected.monomial_g1());
+            assert_eq!(trimmed.lagrange_g1(), expected.lagrange_g1());
+            assert_eq!(trimmed.lagrange_g1_of_size(1 << 6).is_some(), k == 6);
+            assert_eq!(trimmed.srs_degree(), (1 << 6) - 1);
+            assert!(trimmed.lagrange_g1_of_size(1 << 2).is_some());
+
+            let poly = UnivariatePolynomial::rand(1 << k, OsRng);
+            assert_eq!(Pcs::commit(&trimmed, &poly), Pcs::commit(&expected, &poly));
+            assert!(Pcs::commit(&trimmed, &UnivariatePolynomial::rand(2 << k, OsRng)).is_err());
+        }
+        assert!(pp.trim_to((1 << 7) - 1).is_err());
+        assert_eq!(
+            pp.trim_to(5).unwrap_err(),
+            Error::InvalidPcsParam("Size 6 to trim to should be a power of two".to_string())
+        );
+    }
+
+    #[test]
+    fn commit_streaming() {
+        let param = Pcs::setup(1 << 6, 1, OsRng).unwrap();
+        let (pp, _) = Pcs::trim(&param, 1 << 6, 1).unwrap();
+        let coeffs = iter::repeat_with(|| Fr::random(OsRng))
+            .take(50)
+            .collect_vec();
+
+        let expected = Pcs::commit_monomial(&pp, &coeffs);
+        for chunk_size in [1, 7, 50, 64] {
+            assert_eq!(
+                Pcs::commit_streaming(&pp, coeffs.iter().copied(), chunk_size),
+                Ok(expected.clone())
+            );
+        }
+        assert!(Pcs::commit_streaming(&pp, iter::repeat(Fr::ONE).take(65), 16).is_err());
+    }
+
+    #[test]
+    fn validate() {
+        let mut param = Pcs::setup(1 << 4, 1, OsRng).unwrap();
+        assert_eq!(param.validate(OsRng), Ok(()));
+
+        param.lagrange_g1.swap(1, 2);
+        assert_eq!(
+            param.validate(OsRng),
+            Err(Error::InvalidPcsParam(
+                "Invalid univariate KZG param: Inconsistent Lagrange bases".to_string()
+            ))
+        );
+        param.lagrange_g1.swap(1, 2);
+        param.monomial_g1.swap(1, 2);
+        assert_eq!(
+            param.validate(OsRng),
+            Err(Error::InvalidPcsParam(
+                "Invalid univariate KZG param: Inconsistent powers of s".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn batch_open_at_same_point() {
+        let (k, batch_size) = (5, 4);
+        let param = Pcs::setup(1 << k, batch_size, OsRng).unwrap();
+        let (pp, vp) = Pcs::trim(&param, 1 << k, batch_size).unwrap();
+        let (proof, evals) = {
+            let mut transcript = Keccak256Transcript::new(());
+            let polys = iter::repeat_with(|| UnivariatePolynomial::<Fr>::rand(1 << k, OsRng))
+                .take(batch_size)
+                .collect_vec();
+            let comms = Pcs::batch_commit_and_write(&pp, &polys, &mut transcript).unwrap();
+            let point = UnivariatePolynomial::<Fr>::squeeze_point(k, &mut transcript);
+            let evals = (0..batch_size)
+                .map(|poly| Evaluation::new(poly, 0, polys[poly].evaluate(&point)))
+                .collect_vec();
+            transcript
+                .write_field_elements(evals.iter().map(Evaluation::value))
+                .unwrap();
+            Pcs::batch_open(&pp, &polys, &comms, &[point], &evals, &mut transcript).unwrap();
+            (transcript.into_proof(), evals)
+        };
+
+        // a single quotient on top of the commitments and evaluations, with
+        // points uncompressed
+        assert_eq!(proof.len(), (batch_size + 1) * 64 + batch_size * 32);
+
+        let mut transcript = Keccak256Transcript::from_proof((), proof.as_slice());
+        let comms = Pcs::read_commitments(&vp, batch_size, &mut transcript).unwrap();
+        let point = UnivariatePolynomial::<Fr>::squeeze_point(k, &mut transcript);
+        let evals = evals
+            .iter()
+            .zip(transcript.read_field_elements(batch_size).unwrap())
+            .map(|(eval, value)| Evaluation::new(eval.poly(), eval.point(), value))
+            .collect_vec();
+        assert_eq!(
+            Pcs::batch_verify(&vp, &comms, &[point], &evals, &mut transcript),
+            Ok(())
+        );
+    }
 }
@@ -0,0 +1,327 @@
+use crate::{
+    pcs::univariate::kzg::{powers_are_consistent, UnivariateKzgParam},
+    util::{
+        arithmetic::{
+            is_in_prime_order_subgroup, is_prime_order_curve, modulus, CurveAffine, Field,
+            MultiMillerLoop, PrimeCurveAffine, PrimeField,
+        },
+        BigUint,
+    },
+    Error,
+};
+use rand::rngs::OsRng;
+use std::{
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+};
+
+const MAGIC: &[u8; 4] = b"ptau";
+const SECTION_HEADER: u32 = 1;
+const SECTION_TAU_G1: u32 = 2;
+const SECTION_TAU_G2: u32 = 3;
+
+impl<M: MultiMillerLoop> UnivariateKzgParam<M> {
+    /// Read the first `2^k` powers of tau in both groups from a `.ptau` file,
+    /// e.g. one of the Perpetual Powers of Tau ceremony, to be used in place of
+    /// [`PolynomialCommitmentScheme::setup`].
+    ///
+    /// The points are checked to be in the prime order subgroups, and both
+    /// groups to hold the powers of the same tau with a pairing check of
+    /// random combinations of them. Only the format of snarkjs is supported,
+    /// not the transcripts of Aztec's Ignition ceremony.
+    ///
+    /// The degree of the ceremony's largest power in `G1` is recorded as
+    /// [`UnivariateKzgParam::srs_degree`], so degree checks, which anyone
+    /// holding the rest of the ceremony could pass, refuse the param unless
+    /// all of the powers are read.
+    ///
+    /// [`PolynomialCommitmentScheme::setup`]: crate::pcs::PolynomialCommitmentScheme::setup
+    pub fn read_ptau(path: impl AsRef<Path>, k: usize) -> Result<Self, Error> {
+        let file = File::open(path).map_err(err_io)?;
+        Self::from_ptau_reader(BufReader::new(file), k)
+    }
+
+    /// Same as [`UnivariateKzgParam::read_ptau`] but reading from `reader`,
+    /// which is consumed up to the end of the powers of tau in G2.
+    pub fn from_ptau_reader(mut reader: impl Read, k: usize) -> Result<Self, Error> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic).map_err(err_io)?;
+        if &magic != MAGIC {
+            return Err(err_ptau("Invalid magic".to_string()));
+        }
+        let _version = read_u32(&mut reader)?;
+        let num_sections = read_u32(&mut reader)?;
+
+        let n = 1 << k;
+        let (mut header, mut srs_degree, mut monomial_g1, mut powers_of_s_g2) =
+            (None, None, None, None);
+        for _ in 0..num_sections {
+            let section = read_u32(&mut reader)?;
+            let size = read_u64(&mut reader)?;
+            let mut reader = (&mut reader).take(size);
+            match section {
+                SECTION_HEADER => {
+                    let n8 = read_u32(&mut reader)? as usize;
+                    if n8 != repr_size::<<M::G1Affine as CurveAffine>::Base>() {
+                        return Err(err_ptau(format!("Unexpected field size {n8}")));
+                    }
+                    let mut q = vec![0; n8];
+                    reader.read_exact(&mut q).map_err(err_io)?;
+                    if BigUint::from_bytes_le(&q) != modulus::<<M::G1Affine as CurveAffine>::Base>()
+                    {
+                        return Err(err_ptau("Unexpected field modulus".to_string()));
+                    }
+                    let power = read_u32(&mut reader)? as usize;
+                    if power < k {
+                        return Err(err_ptau(format!(
+                            "Too small power {power} to have 2^{k} powers of tau"
+                        )));
+                    }
+                    header = Some(n8);
+                }
+                SECTION_TAU_G1 | SECTION_TAU_G2 => {
+                    let n8 = header
+                        .ok_or_else(|| err_ptau("Powers of tau before header".to_string()))?;
+                    if section == SECTION_TAU_G1 {
+                        // of uncompressed points of 2 coordinates of `n8` bytes
+                        let num_points = size as usize / (2 * n8);
+                        if num_points < n {
+                            return Err(err_ptau(format!(
+                                "Only {num_points} powers of tau in G1 for 2^{k}"
+                            )));
+                        }
+                        srs_degree = Some(num_points - 1);
+                        monomial_g1 = Some(read_points(&mut reader, n8, n)?);
+                    } else {
+                        powers_of_s_g2 = Some(read_points(&mut reader, n8, n)?);
+                    }
+                }
+                _ => {}
+            }
+            io::copy(&mut reader, &mut io::sink()).map_err(err_io)?;
+            if monomial_g1.is_some() && powers_of_s_g2.is_some() {
+                break;
+            }
+        }
+
+        let (Some(srs_degree), Some(monomial_g1), Some(powers_of_s_g2)) =
+            (srs_degree, monomial_g1, powers_of_s_g2)
+        else {
+            return Err(err_ptau("Missing powers of tau".to_string()));
+        };
+        if monomial_g1[0] != M::G1Affine::generator()
+            || powers_of_s_g2[0] != M::G2Affine::generator()
+        {
+            return Err(err_ptau("Unexpected generators".to_string()));
+        }
+        // `from_xy` only checks points are on the curve, which is enough only
+        // for curves of cofactor 1 like `G1` of BN254, not for its twist
+        if !(is_prime_order_curve::<M::G1Affine>()
+            || monomial_g1.iter().all(is_in_prime_order_subgroup))
+            || !powers_of_s_g2.iter().all(is_in_prime_order_subgroup)
+        {
+            return Err(err_ptau(
+                "Point not in the prime order subgroup".to_string(),
+            ));
+        }
+        if n > 1 && !powers_are_consistent::<M>(&monomial_g1, &powers_of_s_g2, OsRng) {
+            return Err(err_ptau("Inconsistent powers of tau".to_string()));
+        }
+
+        Ok(Self {
+            srs_degree,
+            ..Self::from_powers(monomial_g1, powers_of_s_g2)
+        })
+    }
+}
+
+fn err_io(err: io::Error) -> Error {
+    Error::Serialization(err.to_string())
+}
+
+fn err_ptau(reason: String) -> Error {
+    Error::Serialization(format!("Invalid ptau file: {reason}"))
+}
+
+fn read_u32(reader: &mut impl Read) -> Result<u32, Error> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes).map_err(err_io)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> Result<u64, Error> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes).map_err(err_io)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn repr_size<F: PrimeField>() -> usize {
+    F::Repr::default().as_ref().len()
+}
+
+// points are written uncompressed as little-endian coordinates in Montgomery
+// form, with `R = 2^(8 * n8)` also scaling each component of an extension
+// field coordinate
+fn read_points<C: CurveAffine>(
+    reader: &mut impl Read,
+    n8: usize,
+    n: usize,
+) -> Result<Vec<C>, Error> {
+    let r_inv = C::Base::from(2)
+        .pow_vartime([8 * n8 as u64])
+        .invert()
+        .unwrap();
+    (0..n)
+        .map(|_| {
+            let [x, y] = [(); 2].map(|_| {
+                let mut repr = <C::Base as PrimeField>::Repr::default();
+                reader.read_exact(repr.as_mut()).map_err(err_io)?;
+                Option::<C::Base>::from(C::Base::from_repr(repr))
+                    .map(|coordinate| coordinate * r_inv)
+                    .ok_or_else(|| err_ptau("Invalid coordinate".to_string()))
+            });
+            Option::from(C::from_xy(x?, y?))
+                .ok_or_else(|| err_ptau("Point not on the curve".to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        backend::baloo::Baloo,
+        pcs::univariate::UnivariateKzgParam,
+        util::{
+            arithmetic::{modulus, powers, CurveAffine, Field, PrimeCurveAffine, PrimeField},
+            test::off_subgroup_g2,
+        },
+        Error,
+    };
+    use halo2_curves::bn256::{Bn256, Fq, Fr, G1Affine, G2Affine};
+    use rand::rngs::OsRng;
+
+    fn section(section: u32, data: Vec<u8>) -> Vec<u8> {
+        [
+            section.to_le_bytes().to_vec(),
+            (data.len() as u64).to_le_bytes().to_vec(),
+            data,
+        ]
+        .concat()
+    }
+
+    fn montgomery_points<C: CurveAffine>(points: &[C]) -> Vec<u8> {
+        let r = C::Base::from(2).pow_vartime([256]);
+        points
+            .iter()
+            .flat_map(|point| {
+                let coordinates = point.coordinates().unwrap();
+                [*coordinates.x(), *coordinates.y()]
+            })
+            .flat_map(|coordinate| (coordinate * r).to_repr().as_ref().to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn from_ptau_reader() {
+        let k = 3;
+        let tau = Fr::random(OsRng);
+        let powers_of_tau = || powers(tau).take(2 << k);
+        let tau_g1 = powers_of_tau()
+            .map(|power| (G1Affine::generator() * power).into())
+            .collect::<Vec<G1Affine>>();
+        let tau_g2 = powers_of_tau()
+            .map(|power| (G2Affine::generator() * power).into())
+            .collect::<Vec<G2Affine>>();
+        let ptau_of = |q: Vec<u8>, tau_g1: &[G1Affine], tau_g2: &[G2Affine]| {
+            let header = [
+                32u32.to_le_bytes().to_vec(),
+                q,
+                (k as u32 + 1).to_le_bytes().to_vec(),
+                0u32.to_le_bytes().to_vec(),
+            ]
+            .concat();
+            [
+                b"ptau".to_vec(),
+                1u32.to_le_bytes().to_vec(),
+                4u32.to_le_bytes().to_vec(),
+                section(1, header),
+                section(2, montgomery_points(tau_g1)),
+                section(3, montgomery_points(tau_g2)),
+                section(4, montgomery_points(tau_g1)),
+            ]
+            .concat()
+        };
+        let mut q = modulus::<Fq>().to_bytes_le();
+        q.resize(32, 0);
+        let ptau = |tau_g1: &[G1Affine]| ptau_of(q.clone(), tau_g1, &tau_g2);
+
+        let param =
+            UnivariateKzgParam::<Bn256>::from_ptau_reader(ptau(&tau_g1).as_slice(), k).unwrap();
+        assert_eq!(param.k(), k);
+        assert_eq!(param.monomial_g1(), &tau_g1[..1 << k]);
+        assert_eq!(param.powers_of_s_g2(), &tau_g2[..1 << k]);
+        // of the whole ceremony, so refused by degree checks
+        assert_eq!(param.srs_degree(), (2 << k) - 1);
+        let table = (1..=4).map(Fr::from).collect::<Vec<_>>();
+        assert!(matches!(
+            Baloo::preprocess_with_srs(param, &table, 2),
+            Err(Error::InvalidPcsParam(_))
+        ));
+        let param =
+            UnivariateKzgParam::<Bn256>::from_ptau_reader(ptau(&tau_g1).as_slice(), k + 1).unwrap();
+        assert_eq!(param.srs_degree(), param.degree());
+        assert!(Baloo::preprocess_with_srs(param, &table, 2).is_ok());
+
+        assert_eq!(
+            UnivariateKzgParam::<Bn256>::from_ptau_reader(ptau(&tau_g1).as_slice(), k + 2)
+                .unwrap_err(),
+            Error::Serialization(
+                "Invalid ptau file: Too small power 4 to have 2^5 powers of tau".to_string()
+            )
+        );
+        assert_eq!(
+            UnivariateKzgParam::<Bn256>::from_ptau_reader(ptau(&tau_g1[1..]).as_slice(), k)
+                .unwrap_err(),
+            Error::Serialization("Invalid ptau file: Unexpected generators".to_string())
+        );
+
+        // of another field
+        let mut other_q = modulus::<Fr>().to_bytes_le();
+        other_q.resize(32, 0);
+        assert_eq!(
+            UnivariateKzgParam::<Bn256>::from_ptau_reader(
+                ptau_of(other_q, &tau_g1, &tau_g2).as_slice(),
+                k
+            )
+            .unwrap_err(),
+            Error::Serialization("Invalid ptau file: Unexpected field modulus".to_string())
+        );
+
+        // of another tau in G2
+        let mut other_tau_g2 = tau_g2.clone();
+        other_tau_g2.swap(1, 2);
+        assert_eq!(
+            UnivariateKzgParam::<Bn256>::from_ptau_reader(
+                ptau_of(q.clone(), &tau_g1, &other_tau_g2).as_slice(),
+                k
+            )
+            .unwrap_err(),
+            Error::Serialization("Invalid ptau file: Inconsistent powers of tau".to_string())
+        );
+
+        // on the twist but out of the prime order subgroup
+        let mut off_subgroup_tau_g2 = tau_g2.clone();
+        off_subgroup_tau_g2[1] = off_subgroup_g2();
+        assert_eq!(
+            UnivariateKzgParam::<Bn256>::from_ptau_reader(
+                ptau_of(q, &tau_g1, &off_subgroup_tau_g2).as_slice(),
+                k
+            )
+            .unwrap_err(),
+            Error::Serialization(
+                "Invalid ptau file: Point not in the prime order subgroup".to_string()
+            )
+        );
+    }
+}
@@ -1,3 +1,5 @@
 mod fractional_sum_check;
 
-pub use fractional_sum_check::{prove_fractional_sum_check, verify_fractional_sum_check};
+pub use fractional_sum_check::{
+    fraction_sum, prove_fractional_sum_check, verify_fractional_sum_check,
+};
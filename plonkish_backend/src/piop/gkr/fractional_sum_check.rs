@@ -273,6 +273,20 @@ pub fn verify_fractional_sum_check<F: PrimeField>(
     Ok((p_xs, q_xs, x))
 }
 
+/// `(p, q)` with `p / q = Σ_x p(x) / q(x)`, in the same unreduced form the
+/// fractional sumcheck outputs at its root, i.e. `q = Π_x q(x)`.
+pub fn fraction_sum<F: PrimeField>(
+    p: &MultilinearPolynomial<F>,
+    q: &MultilinearPolynomial<F>,
+) -> (F, F) {
+    p.evals()
+        .iter()
+        .zip(q.evals())
+        .fold((F::ZERO, F::ONE), |(acc_p, acc_q), (p, q)| {
+            (acc_p * q + acc_q * p, acc_q * q)
+        })
+}
+
 fn sum_check_expression<F: PrimeField>(num_batching: usize) -> Expression<F> {
     let exprs = &(0..4 * num_batching)
         .map(|idx| Expression::<F>::Polynomial(Query::new(idx, Rotation::cur())))
@@ -307,7 +321,7 @@ fn err_unmatched_sum_check_output() -> Error {
 mod test {
     use crate::{
         piop::gkr::fractional_sum_check::{
-            prove_fractional_sum_check, verify_fractional_sum_check,
+            fraction_sum, prove_fractional_sum_check, verify_fractional_sum_check,
         },
         poly::multilinear::MultilinearPolynomial,
         util::{
@@ -364,4 +378,12 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_fraction_sum() {
+        let p = MultilinearPolynomial::new([1, 2].map(Fr::from).to_vec());
+        let q = MultilinearPolynomial::new([3, 5].map(Fr::from).to_vec());
+        // 1/3 + 2/5 = 11/15
+        assert_eq!(fraction_sum(&p, &q), (Fr::from(11), Fr::from(15)));
+    }
 }
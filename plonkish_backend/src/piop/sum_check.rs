@@ -118,7 +118,7 @@ pub fn eq_xy_eval<F: PrimeField>(x: &[F], y: &[F]) -> F {
     )
 }
 
-fn identity_eval<F: PrimeField>(x: &[F]) -> F {
+pub fn identity_eval<F: PrimeField>(x: &[F]) -> F {
     inner_product(x, &powers(F::from(2)).take(x.len()).collect_vec())
 }
 
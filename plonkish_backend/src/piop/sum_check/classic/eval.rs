@@ -1,7 +1,7 @@
 use crate::{
     piop::sum_check::classic::{ClassicSumCheckProver, ClassicSumCheckRoundMessage, ProverState},
     util::{
-        arithmetic::{barycentric_interpolate, barycentric_weights, div_ceil, steps, PrimeField},
+        arithmetic::{div_ceil, steps, BarycentricEvaluator, PrimeField},
         chain,
         expression::{
             evaluator::{ExpressionRegistry, Offsets},
@@ -30,7 +30,7 @@ impl<F: PrimeField> Evaluations<F> {
 }
 
 impl<F: PrimeField> ClassicSumCheckRoundMessage<F> for Evaluations<F> {
-    type Auxiliary = (Vec<F>, Vec<F>);
+    type Auxiliary = BarycentricEvaluator<F>;
 
     fn write(&self, transcript: &mut impl FieldTranscriptWrite<F>) -> Result<(), Error> {
         transcript.write_field_elements(&self.0)
@@ -45,12 +45,11 @@ impl<F: PrimeField> ClassicSumCheckRoundMessage<F> for Evaluations<F> {
     }
 
     fn auxiliary(degree: usize) -> Self::Auxiliary {
-        let points = Self::points(degree);
-        (barycentric_weights(&points), points)
+        BarycentricEvaluator::new(Self::points(degree))
     }
 
-    fn evaluate(&self, (weights, points): &Self::Auxiliary, challenge: &F) -> F {
-        barycentric_interpolate(weights, points, &self.0, challenge)
+    fn evaluate(&self, evaluator: &Self::Auxiliary, challenge: &F) -> F {
+        evaluator.evaluate(&self.0, challenge)
     }
 }
 
@@ -2,14 +2,12 @@ use crate::{
     pcs::Additive,
     poly::{univariate::UnivariateBasis::*, Polynomial},
     util::{
-        arithmetic::{
-            div_ceil, horner, powers, radix2_fft, root_of_unity, root_of_unity_inv, Field,
-            WithSmallOrderMulGroup,
-        },
+        arithmetic::{batch_invert, div_ceil, horner, powers, Field, WithSmallOrderMulGroup},
         impl_index, izip_eq,
         parallel::{num_threads, parallelize, parallelize_iter},
         Deserialize, Itertools, Serialize,
     },
+    Error,
 };
 use halo2_curves::ff::PrimeField;
 use std::ops::{Div, DivAssign};
@@ -22,6 +20,14 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
+mod domain;
+mod sparse;
+mod subproduct;
+
+pub use domain::{MixedRadixDomain, Radix2Domain};
+pub use sparse::SparseUnivariatePolynomial;
+pub use subproduct::SubproductTree;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum UnivariateBasis {
     Monomial,
@@ -177,6 +183,8 @@ impl<F: Field> UnivariatePolynomial<F> {
         results.iter().fold(F::ZERO, |acc, result| acc + result)
     }
 
+    /// Quotient and remainder of the long division of `self` by `divisor`,
+    /// which panics if `divisor` is zero.
     pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
         assert_eq!(self.basis, Monomial);
 
@@ -205,6 +213,35 @@ impl<F: Field> UnivariatePolynomial<F> {
         }
     }
 
+    /// Quotient of `self` by `X^n - 1` for `n = domain_size`, in a single
+    /// pass over the coefficients, failing if the remainder isn't zero, i.e.
+    /// if `self` doesn't vanish on the domain of size `n`.
+    pub fn divide_by_vanishing_poly(&self, domain_size: usize) -> Result<Self, Error> {
+        assert_eq!(self.basis, Monomial);
+        assert!(domain_size > 0);
+
+        let n = domain_size;
+        if self.is_empty() {
+            return Ok(Self::zero());
+        }
+        if self.coeffs.len() <= n {
+            return Err(err_nonzero_remainder(n, self.degree()));
+        }
+        // X^i = X^(i-n) (X^n - 1) + X^(i-n), from the leading coefficient
+        let mut remainder = self.coeffs.clone();
+        let mut quotient = vec![F::ZERO; self.coeffs.len() - n];
+        for i in (n..self.coeffs.len()).rev() {
+            let coeff = remainder[i];
+            quotient[i - n] = coeff;
+            remainder[i - n] += coeff;
+        }
+        remainder.truncate(n);
+        match remainder.iter().rposition(|coeff| !coeff.is_zero_vartime()) {
+            Some(degree) => Err(err_nonzero_remainder(n, degree)),
+            None => Ok(Self::monomial(quotient)),
+        }
+    }
+
     fn truncate_leading_zeros(&mut self) {
         assert_eq!(self.basis, Monomial);
 
@@ -218,27 +255,25 @@ impl<F: Field> UnivariatePolynomial<F> {
         );
     }
 
-    pub fn poly_mul(&self, rhs: UnivariatePolynomial<F>) -> UnivariatePolynomial<F>
+    pub fn poly_mul(&self, rhs: impl Borrow<UnivariatePolynomial<F>>) -> UnivariatePolynomial<F>
     where
         F: Field + WithSmallOrderMulGroup<3>,
     {
+        let rhs = rhs.borrow();
         assert_eq!(self.basis, Monomial);
         assert_eq!(rhs.basis, Monomial);
 
         let n = self.coeffs.len() + rhs.coeffs.len() - 1;
         let size = n.next_power_of_two();
-        let log_size = size.trailing_zeros() as usize;
+        let domain = Radix2Domain::new(size.trailing_zeros() as usize);
 
         let mut self_padded = self.coeffs.to_vec();
         let mut rhs_padded = rhs.coeffs.to_vec();
         self_padded.resize(size, F::ZERO);
         rhs_padded.resize(size, F::ZERO);
 
-        let omega = root_of_unity(log_size);
-        let omega_inv = root_of_unity_inv(log_size);
-
-        radix2_fft(&mut self_padded, omega, log_size);
-        radix2_fft(&mut rhs_padded, omega, log_size);
+        domain.fft(&mut self_padded);
+        domain.fft(&mut rhs_padded);
 
         let mut result = self_padded
             .iter()
@@ -246,52 +281,126 @@ impl<F: Field> UnivariatePolynomial<F> {
             .map(|(&x, &y)| x * y)
             .collect::<Vec<_>>();
 
-        radix2_fft(&mut result, omega_inv, log_size);
-
-        let size_inv = F::from(size as u64).invert().unwrap();
-        result.iter_mut().for_each(|x| *x *= size_inv);
+        domain.ifft(&mut result);
         result.truncate(n);
         UnivariatePolynomial::monomial(result)
     }
 
     pub fn fft(&self) -> UnivariatePolynomial<F>
+    where
+        F: Field + WithSmallOrderMulGroup<3>,
+    {
+        self.clone().into_fft()
+    }
+
+    /// [`UnivariatePolynomial::fft`] in place of the coefficients, which
+    /// aren't copied unless they have to grow to a power of two.
+    pub fn into_fft(self) -> UnivariatePolynomial<F>
     where
         F: Field + WithSmallOrderMulGroup<3>,
     {
         assert_eq!(self.basis, Monomial);
 
-        let mut result = self.coeffs.clone();
+        let mut result = self.coeffs;
         let n = result.len();
         let size = n.next_power_of_two();
         result.resize(size, F::ZERO);
 
-        let log_size = size.trailing_zeros() as usize;
-        let omega = root_of_unity(log_size);
-
-        radix2_fft(&mut result, omega, log_size);
+        Radix2Domain::new(size.trailing_zeros() as usize).fft(&mut result);
 
         UnivariatePolynomial::lagrange(result)
     }
 
     pub fn ifft(&self) -> UnivariatePolynomial<F>
+    where
+        F: Field + WithSmallOrderMulGroup<3>,
+    {
+        self.clone().into_ifft()
+    }
+
+    /// [`UnivariatePolynomial::ifft`] in place of the evaluations.
+    pub fn into_ifft(self) -> UnivariatePolynomial<F>
     where
         F: Field + WithSmallOrderMulGroup<3>,
     {
         assert_eq!(self.basis, Lagrange);
         assert!(self.coeffs.len().is_power_of_two());
 
+        let mut result = self.coeffs;
+        Radix2Domain::new(result.len().trailing_zeros() as usize).ifft(&mut result);
+
+        UnivariatePolynomial::monomial(result)
+    }
+
+    /// Evaluations on the coset `g·H` of the domain `H` of `size`, with `g`
+    /// the multiplicative generator, which is in no domain of a power of two
+    /// size, so vanishing polynomials of these have no roots on it.
+    pub fn coset_fft(&self, size: usize) -> UnivariatePolynomial<F>
+    where
+        F: Field + WithSmallOrderMulGroup<3>,
+    {
+        assert_eq!(self.basis, Monomial);
+        assert!(size.is_power_of_two() && size >= self.coeffs.len());
+
         let mut result = self.coeffs.clone();
-        let size = result.len();
-        let log_size = size.trailing_zeros() as usize;
-        let size_inv = F::from(size as u64).invert().unwrap();
-        let omega_inv = root_of_unity_inv(log_size);
+        result.resize(size, F::ZERO);
+        Radix2Domain::new(size.trailing_zeros() as usize)
+            .coset_fft(&mut result, F::MULTIPLICATIVE_GENERATOR);
 
-        radix2_fft(&mut result, omega_inv, log_size);
-        result.iter_mut().for_each(|x| *x *= size_inv);
-        result.truncate(size);
+        UnivariatePolynomial::lagrange(result)
+    }
+
+    /// Inverse of [`UnivariatePolynomial::coset_fft`].
+    pub fn coset_ifft(&self) -> UnivariatePolynomial<F>
+    where
+        F: Field + WithSmallOrderMulGroup<3>,
+    {
+        assert_eq!(self.basis, Lagrange);
+        assert!(self.coeffs.len().is_power_of_two());
+
+        let mut result = self.coeffs.clone();
+        Radix2Domain::new(result.len().trailing_zeros() as usize)
+            .coset_ifft(&mut result, F::MULTIPLICATIVE_GENERATOR);
 
         UnivariatePolynomial::monomial(result)
     }
+
+    /// Quotient of `self` by `divisor` by pointwise division of their
+    /// evaluations on a coset, see [`UnivariatePolynomial::coset_fft`],
+    /// instead of long division, or `None` if `divisor` doesn't divide
+    /// `self`.
+    pub fn coset_div(&self, divisor: &Self) -> Option<UnivariatePolynomial<F>>
+    where
+        F: Field + WithSmallOrderMulGroup<3>,
+    {
+        assert_eq!(self.basis, Monomial);
+        assert_eq!(divisor.basis, Monomial);
+        assert!(!divisor.is_empty());
+
+        if self.is_empty() {
+            return Some(Self::zero());
+        }
+        if self.degree() < divisor.degree() {
+            return None;
+        }
+        let size = self.coeffs.len().next_power_of_two();
+        let mut evals = self.coset_fft(size);
+        let mut divisor_evals = divisor.coset_fft(size).into_coeffs();
+        // a root on the coset, e.g. of a divisor not vanishing on a domain
+        if divisor_evals.iter().any(|eval| eval.is_zero_vartime()) {
+            return None;
+        }
+        batch_invert(&mut divisor_evals);
+        for (eval, divisor_eval) in evals.coeffs.iter_mut().zip(divisor_evals) {
+            *eval *= divisor_eval;
+        }
+
+        // the interpolated quotient times `divisor` agrees with `self` on
+        // more points than their degree, so it's exact iff of the degree
+        let quotient = evals.coset_ifft();
+        (quotient.is_empty() || quotient.degree() <= self.degree() - divisor.degree())
+            .then_some(quotient)
+    }
 }
 
 impl<F: Field> Neg for UnivariatePolynomial<F> {
@@ -567,17 +676,16 @@ impl<F: Field> Div for &UnivariatePolynomial<F> {
     type Output = UnivariatePolynomial<F>;
 
     fn div(self, rhs: &UnivariatePolynomial<F>) -> Self::Output {
-        let mut output = self.clone();
-        output /= rhs;
-        output
+        // `div_rem` copies the dividend into the remainder already
+        let (quotient, remainder) = self.div_rem(rhs);
+        assert_eq!(remainder, UnivariatePolynomial::zero());
+        quotient
     }
 }
 
 impl<F: Field> DivAssign<&UnivariatePolynomial<F>> for UnivariatePolynomial<F> {
     fn div_assign(&mut self, rhs: &UnivariatePolynomial<F>) {
-        let (quotient, remainder) = self.clone().div_rem(rhs);
-        assert_eq!(remainder, UnivariatePolynomial::zero());
-        *self = quotient;
+        *self = &*self / rhs;
     }
 }
 
@@ -623,6 +731,12 @@ impl<F: PrimeField> From<Vec<u64>> for UnivariatePolynomial<F> {
     }
 }
 
+fn err_nonzero_remainder(domain_size: usize, degree: usize) -> Error {
+    Error::InvalidSnark(format!(
+        "Polynomial should vanish on the domain of size {domain_size} but has remainder of degree {degree}"
+    ))
+}
+
 fn div<F: Field>(a: F, b: F) -> F {
     a * b.invert().expect("Division by zero")
 }
@@ -698,10 +812,74 @@ mod tests {
         assert_eq!(p2.evaluate(&Fr::from(2)), Fr::from(68));
 
         let p3: UnivariatePolynomial<Fr> = &p1 * &p2;
-        let p4_fft: UnivariatePolynomial<Fr> = p1.poly_mul(p2);
+        let p4_fft: UnivariatePolynomial<Fr> = p1.poly_mul(&p2);
         assert_eq!(p3, p4_fft);
     }
 
+    #[test]
+    fn test_into_fft() {
+        let poly = UnivariatePolynomial::<Fr>::from(vec![1, 2, 3]);
+        let evals = poly.fft();
+        assert_eq!(poly.clone().into_fft(), evals);
+        assert_eq!(evals.clone().into_ifft(), poly);
+        assert_eq!(evals.ifft(), poly);
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let p1 = UnivariatePolynomial::<Fr>::from(vec![1, 2, 3]);
+        let p2 = UnivariatePolynomial::from(vec![5, 0, 1, 2, 3]);
+        // X^2 * p1 + 5
+        let (quotient, remainder) = p2.div_rem(&p1);
+        assert_eq!(quotient, UnivariatePolynomial::from(vec![0, 0, 1]));
+        assert_eq!(remainder, UnivariatePolynomial::from(vec![5]));
+        assert_eq!(&(&quotient * &p1) + &remainder, p2);
+
+        let (quotient, remainder) = p1.div_rem(&p2);
+        assert_eq!(quotient, UnivariatePolynomial::zero());
+        assert_eq!(remainder, p1);
+        let (quotient, remainder) = UnivariatePolynomial::zero().div_rem(&p1);
+        assert!(quotient.is_empty() && remainder.is_empty());
+    }
+
+    #[test]
+    fn test_divide_by_vanishing_poly() {
+        let p1 = UnivariatePolynomial::<Fr>::from(vec![1, 2, 3]);
+        let z_v = UnivariatePolynomial::<Fr>::from(vec![0, 0, 0, 0, 1]) + -Fr::from(1);
+        let product = &z_v * &p1;
+        assert_eq!(product.divide_by_vanishing_poly(4), Ok(p1.clone()));
+        assert_eq!(
+            UnivariatePolynomial::<Fr>::zero().divide_by_vanishing_poly(4),
+            Ok(UnivariatePolynomial::zero())
+        );
+
+        // remainder p1 of degree 2, also when of lower degree than X^4 - 1
+        let shifted = UnivariatePolynomial::from(vec![0, 0, 0, 0, 1, 2, 3]);
+        let err = Err(Error::InvalidSnark(
+            "Polynomial should vanish on the domain of size 4 but has remainder of degree 2"
+                .to_string(),
+        ));
+        assert_eq!(shifted.divide_by_vanishing_poly(4), err);
+        assert_eq!(p1.divide_by_vanishing_poly(4), err);
+    }
+
+    #[test]
+    fn test_coset_div() {
+        let p1: UnivariatePolynomial<Fr> = UnivariatePolynomial::from(vec![1, 2, 3]);
+        let p2 = p1.coset_fft(8);
+        assert_eq!(p2.coeffs().len(), 8);
+        assert_eq!(p2.coset_ifft(), p1);
+
+        // X^4 - 1 divides (X^4 - 1) * p1 but not p1 * X^4
+        let z_v = UnivariatePolynomial::<Fr>::from(vec![0, 0, 0, 0, 1]) + -Fr::from(1);
+        let product = &z_v * &p1;
+        assert_eq!(product.coset_div(&z_v), Some(p1.clone()));
+        assert_eq!(product.coset_div(&z_v), Some(&product / &z_v));
+        let shifted = UnivariatePolynomial::from(vec![0, 0, 0, 0, 1, 2, 3]);
+        assert_eq!(shifted.coset_div(&z_v), None);
+        assert_eq!(p1.coset_div(&z_v), None);
+    }
+
     #[test]
     fn test_fft_ifft() {
         let p1: UnivariatePolynomial<Fr> = UnivariatePolynomial::from(vec![1, 2, 3]);
@@ -0,0 +1,350 @@
+use crate::{
+    poly::univariate::UnivariatePolynomial,
+    util::{
+        arithmetic::{
+            div_ceil, mixed_radix_fft, modulus, powers, radix2_fft, root_of_unity, Field,
+            PrimeField,
+        },
+        BigUint, Deserialize, Serialize,
+    },
+    Error,
+};
+use std::iter;
+
+/// Multiplicative subgroup `H = <ω>` of order `n = 2^k`, with its generator,
+/// the inverses of the generator and of `n`, and the FFTs over it and its
+/// cosets, so that values of domains of different sizes don't get mixed up
+/// by deriving each from its own `root_of_unity(k)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Radix2Domain<F> {
+    log_size: usize,
+    generator: F,
+    generator_inv: F,
+    size_inv: F,
+}
+
+impl<F: PrimeField> Radix2Domain<F> {
+    /// Domain of order `2^log_size`, which panics if the field has no such
+    /// subgroup.
+    pub fn new(log_size: usize) -> Self {
+        assert!(log_size <= F::S as usize);
+
+        let generator = root_of_unity::<F>(log_size);
+        Self {
+            log_size,
+            generator,
+            generator_inv: generator.invert().unwrap(),
+            size_inv: F::from(1u64 << log_size).invert().unwrap(),
+        }
+    }
+
+    /// Domain of order `size`, which must be a power of two.
+    pub fn with_size(size: usize) -> Result<Self, Error> {
+        if !size.is_power_of_two() || size.ilog2() > F::S {
+            return Err(Error::NotPowerOfTwo {
+                name: "domain",
+                size,
+            });
+        }
+        Ok(Self::new(size.ilog2() as usize))
+    }
+
+    pub fn log_size(&self) -> usize {
+        self.log_size
+    }
+
+    pub fn size(&self) -> usize {
+        1 << self.log_size
+    }
+
+    /// `ω`.
+    pub fn generator(&self) -> F {
+        self.generator
+    }
+
+    pub fn generator_inv(&self) -> F {
+        self.generator_inv
+    }
+
+    /// `1 / n`.
+    pub fn size_inv(&self) -> F {
+        self.size_inv
+    }
+
+    /// `ω^i`.
+    pub fn element(&self, i: usize) -> F {
+        self.generator.pow([(i % self.size()) as u64])
+    }
+
+    /// `[1, ω, ..., ω^(n-1)]`.
+    pub fn elements(&self) -> Vec<F> {
+        powers(self.generator).take(self.size()).collect()
+    }
+
+    pub fn contains(&self, x: &F) -> bool {
+        self.evaluate_vanishing(x) == F::ZERO
+    }
+
+    /// `Z_H(x) = x^n - 1`.
+    pub fn evaluate_vanishing(&self, x: &F) -> F {
+        x.pow([self.size() as u64]) - F::ONE
+    }
+
+    /// `Z_H(X) = X^n - 1`.
+    pub fn vanishing_poly(&self) -> UnivariatePolynomial<F> {
+        UnivariatePolynomial::monomial(
+            iter::once(-F::ONE)
+                .chain(iter::repeat(F::ZERO).take(self.size() - 1))
+                .chain(iter::once(F::ONE))
+                .collect(),
+        )
+    }
+
+    /// Evaluations on `H` of the polynomial of the coefficients `values`, in
+    /// place.
+    pub fn fft(&self, values: &mut [F]) {
+        assert_eq!(values.len(), self.size());
+        radix2_fft(values, self.generator, self.log_size);
+    }
+
+    /// Inverse of [`Radix2Domain::fft`].
+    pub fn ifft(&self, values: &mut [F]) {
+        assert_eq!(values.len(), self.size());
+        radix2_fft(values, self.generator_inv, self.log_size);
+        values.iter_mut().for_each(|value| *value *= self.size_inv);
+    }
+
+    /// Evaluations on the coset `shift·H`, in place.
+    pub fn coset_fft(&self, values: &mut [F], shift: F) {
+        values
+            .iter_mut()
+            .zip(powers(shift))
+            .for_each(|(value, scalar)| *value *= scalar);
+        self.fft(values);
+    }
+
+    /// Inverse of [`Radix2Domain::coset_fft`].
+    pub fn coset_ifft(&self, values: &mut [F], shift: F) {
+        self.ifft(values);
+        values
+            .iter_mut()
+            .zip(powers(shift.invert().unwrap()))
+            .for_each(|(value, scalar)| *value *= scalar);
+    }
+}
+
+/// Multiplicative subgroup `H = <ω>` of order `n = 2^a·3^b`, for sizes which
+/// would otherwise be padded up to the next power of two, e.g. `n = 3·2^k`
+/// instead of `4·2^k`. The field has to have a subgroup of this order, i.e.
+/// `n | p - 1`, which for BN254 allows `b ≤ 2`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MixedRadixDomain<F> {
+    log2_size: usize,
+    log3_size: usize,
+    generator: F,
+    generator_inv: F,
+    size_inv: F,
+}
+
+impl<F: PrimeField> MixedRadixDomain<F> {
+    /// Domain of order `2^log2_size · 3^log3_size`, which panics if the field
+    /// has no such subgroup.
+    pub fn new(log2_size: usize, log3_size: usize) -> Self {
+        assert!(log2_size <= F::S as usize && log3_size <= three_adicity::<F>());
+
+        // g^((p - 1) / n) of order n for the generator g of order p - 1
+        let size = 3usize.pow(log3_size as u32) << log2_size;
+        let exp = (modulus::<F>() - 1u64) / BigUint::from(size);
+        let generator = F::MULTIPLICATIVE_GENERATOR.pow_vartime(exp.to_u64_digits());
+        Self {
+            log2_size,
+            log3_size,
+            generator,
+            generator_inv: generator.invert().unwrap(),
+            size_inv: F::from(size as u64).invert().unwrap(),
+        }
+    }
+
+    /// Domain of order `size`, which must be `2^a·3^b`.
+    pub fn with_size(size: usize) -> Result<Self, Error> {
+        let err = || Error::NoSubgroup {
+            name: "domain",
+            size,
+        };
+        if size == 0 {
+            return Err(err());
+        }
+        let log2_size = size.trailing_zeros() as usize;
+        let (mut rest, mut log3_size) = (size >> log2_size, 0);
+        while rest % 3 == 0 {
+            rest /= 3;
+            log3_size += 1;
+        }
+        if rest != 1 || log2_size > F::S as usize || log3_size > three_adicity::<F>() {
+            return Err(err());
+        }
+        Ok(Self::new(log2_size, log3_size))
+    }
+
+    /// Smallest domain of order at least `size`.
+    pub fn covering(size: usize) -> Result<Self, Error> {
+        let max_log3_size = three_adicity::<F>();
+        (0..=max_log3_size)
+            .filter_map(|log3_size| {
+                let odd = 3usize.pow(log3_size as u32);
+                let log2_size = div_ceil(size, odd).next_power_of_two().trailing_zeros();
+                (log2_size <= F::S).then_some((log2_size as usize, log3_size))
+            })
+            .min_by_key(|&(log2_size, log3_size)| 3usize.pow(log3_size as u32) << log2_size)
+            .map(|(log2_size, log3_size)| Self::new(log2_size, log3_size))
+            .ok_or(Error::NoSubgroup {
+                name: "domain",
+                size,
+            })
+    }
+
+    pub fn log2_size(&self) -> usize {
+        self.log2_size
+    }
+
+    pub fn log3_size(&self) -> usize {
+        self.log3_size
+    }
+
+    pub fn size(&self) -> usize {
+        3usize.pow(self.log3_size as u32) << self.log2_size
+    }
+
+    /// `ω`.
+    pub fn generator(&self) -> F {
+        self.generator
+    }
+
+    pub fn generator_inv(&self) -> F {
+        self.generator_inv
+    }
+
+    /// `1 / n`.
+    pub fn size_inv(&self) -> F {
+        self.size_inv
+    }
+
+    /// `[1, ω, ..., ω^(n-1)]`.
+    pub fn elements(&self) -> Vec<F> {
+        powers(self.generator).take(self.size()).collect()
+    }
+
+    /// `Z_H(x) = x^n - 1`.
+    pub fn evaluate_vanishing(&self, x: &F) -> F {
+        x.pow([self.size() as u64]) - F::ONE
+    }
+
+    /// `Z_H(X) = X^n - 1`.
+    pub fn vanishing_poly(&self) -> UnivariatePolynomial<F> {
+        UnivariatePolynomial::monomial(
+            iter::once(-F::ONE)
+                .chain(iter::repeat(F::ZERO).take(self.size() - 1))
+                .chain(iter::once(F::ONE))
+                .collect(),
+        )
+    }
+
+    /// Evaluations on `H` of the polynomial of the coefficients `values`, in
+    /// place.
+    pub fn fft(&self, values: &mut [F]) {
+        assert_eq!(values.len(), self.size());
+        mixed_radix_fft(values, self.generator, self.log2_size, self.log3_size);
+    }
+
+    /// Inverse of [`MixedRadixDomain::fft`].
+    pub fn ifft(&self, values: &mut [F]) {
+        assert_eq!(values.len(), self.size());
+        mixed_radix_fft(values, self.generator_inv, self.log2_size, self.log3_size);
+        values.iter_mut().for_each(|value| *value *= self.size_inv);
+    }
+}
+
+// largest `b` with `3^b | p - 1`
+fn three_adicity<F: PrimeField>() -> usize {
+    let mut order = modulus::<F>() - 1u64;
+    let mut adicity = 0;
+    while (&order % 3u64) == BigUint::from(0u64) {
+        order /= 3u64;
+        adicity += 1;
+    }
+    adicity
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        poly::{
+            univariate::{MixedRadixDomain, Radix2Domain, UnivariatePolynomial},
+            Polynomial,
+        },
+        util::arithmetic::{root_of_unity, Field, PrimeField},
+    };
+    use halo2_curves::bn256::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_radix2_domain() {
+        let domain = Radix2Domain::<Fr>::new(3);
+        assert_eq!(Radix2Domain::with_size(8), Ok(domain));
+        assert!(Radix2Domain::<Fr>::with_size(6).is_err());
+        assert_eq!(domain.generator(), root_of_unity::<Fr>(3));
+        assert_eq!(domain.generator() * domain.generator_inv(), Fr::ONE);
+        assert_eq!(domain.size_inv() * Fr::from(8), Fr::ONE);
+        assert_eq!(domain.element(9), domain.generator());
+        assert!(domain.elements().iter().all(|x| domain.contains(x)));
+        assert!(!domain.contains(&Fr::MULTIPLICATIVE_GENERATOR));
+
+        let poly = UnivariatePolynomial::rand(8, OsRng);
+        let mut values = poly.coeffs().to_vec();
+        domain.fft(&mut values);
+        let evals = domain.elements().iter().map(|x| poly.evaluate(x));
+        assert!(values.iter().copied().eq(evals));
+        domain.ifft(&mut values);
+        assert_eq!(values, poly.coeffs());
+
+        let shift = Fr::MULTIPLICATIVE_GENERATOR;
+        domain.coset_fft(&mut values, shift);
+        assert_eq!(values[1], poly.evaluate(&(shift * domain.generator())));
+        domain.coset_ifft(&mut values, shift);
+        assert_eq!(values, poly.coeffs());
+        assert_eq!(
+            domain.vanishing_poly().evaluate(&shift),
+            domain.evaluate_vanishing(&shift)
+        );
+    }
+    #[test]
+    fn test_mixed_radix_domain() {
+        assert_eq!(
+            MixedRadixDomain::<Fr>::new(3, 0).generator(),
+            root_of_unity::<Fr>(3)
+        );
+        assert!(MixedRadixDomain::<Fr>::with_size(10).is_err());
+        assert!(MixedRadixDomain::<Fr>::with_size(27).is_err());
+        for (size, covering) in [(5, 6), (10, 12), (17, 18), (64, 64), (65, 72)] {
+            let domain = MixedRadixDomain::<Fr>::covering(size).unwrap();
+            assert_eq!(domain.size(), covering);
+            assert_eq!(MixedRadixDomain::with_size(covering), Ok(domain));
+        }
+
+        for size in [3, 12, 18, 36] {
+            let domain = MixedRadixDomain::<Fr>::with_size(size).unwrap();
+            let elements = domain.elements();
+            assert!(elements.iter().skip(1).all(|x| *x != Fr::ONE));
+            assert_eq!(domain.evaluate_vanishing(&elements[1]), Fr::ZERO);
+
+            let poly = UnivariatePolynomial::rand(size, OsRng);
+            let mut values = poly.coeffs().to_vec();
+            domain.fft(&mut values);
+            let evals = elements.iter().map(|x| poly.evaluate(x));
+            assert!(values.iter().copied().eq(evals));
+            domain.ifft(&mut values);
+            assert_eq!(values, poly.coeffs());
+            assert_eq!(domain.vanishing_poly().evaluate(&elements[1]), Fr::ZERO);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{
+    poly::univariate::{UnivariateBasis::Monomial, UnivariatePolynomial},
+    util::{arithmetic::Field, Deserialize, Serialize},
+};
+use std::{
+    collections::BTreeMap,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+/// Univariate polynomial in monomial basis of its nonzero coefficients only,
+/// e.g. of the shifts `X^m` and `X^(d-m+1)`, which a dense one would hold
+/// `d` zeros for.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SparseUnivariatePolynomial<F> {
+    // nonzero coefficients by exponent
+    terms: BTreeMap<usize, F>,
+}
+
+impl<F> SparseUnivariatePolynomial<F> {
+    pub const fn zero() -> Self {
+        Self {
+            terms: BTreeMap::new(),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Nonzero `(exponent, coefficient)` in increasing exponent order.
+    pub fn terms(&self) -> impl Iterator<Item = (usize, &F)> {
+        self.terms
+            .iter()
+            .map(|(exponent, coeff)| (*exponent, coeff))
+    }
+
+    pub fn num_terms(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub fn degree(&self) -> usize {
+        self.terms.keys().last().copied().unwrap_or_default()
+    }
+}
+
+impl<F: Field> SparseUnivariatePolynomial<F> {
+    /// `coeff * X^exponent`.
+    pub fn term(exponent: usize, coeff: F) -> Self {
+        Self::from_terms([(exponent, coeff)])
+    }
+
+    /// Sum of `coeff * X^exponent`, with repeated exponents summed up.
+    pub fn from_terms(terms: impl IntoIterator<Item = (usize, F)>) -> Self {
+        let mut poly = Self::zero();
+        for (exponent, coeff) in terms {
+            poly.add_term(exponent, coeff);
+        }
+        poly
+    }
+
+    fn add_term(&mut self, exponent: usize, coeff: F) {
+        let sum = *self.terms.get(&exponent).unwrap_or(&F::ZERO) + coeff;
+        if sum.is_zero_vartime() {
+            self.terms.remove(&exponent);
+        } else {
+            self.terms.insert(exponent, sum);
+        }
+    }
+
+    pub fn evaluate(&self, x: &F) -> F {
+        self.terms()
+            .map(|(exponent, coeff)| x.pow_vartime([exponent as u64]) * coeff)
+            .sum()
+    }
+
+    pub fn to_dense(&self) -> UnivariatePolynomial<F> {
+        let mut coeffs = vec![F::ZERO; self.degree() + 1];
+        for (exponent, coeff) in self.terms() {
+            coeffs[exponent] = *coeff;
+        }
+        UnivariatePolynomial::monomial(coeffs)
+    }
+}
+
+impl<F: Field> Neg for &SparseUnivariatePolynomial<F> {
+    type Output = SparseUnivariatePolynomial<F>;
+
+    fn neg(self) -> SparseUnivariatePolynomial<F> {
+        SparseUnivariatePolynomial {
+            terms: self
+                .terms
+                .iter()
+                .map(|(exponent, coeff)| (*exponent, -*coeff))
+                .collect(),
+        }
+    }
+}
+
+impl<F: Field> Add for &SparseUnivariatePolynomial<F> {
+    type Output = SparseUnivariatePolynomial<F>;
+
+    fn add(self, rhs: Self) -> SparseUnivariatePolynomial<F> {
+        let mut output = self.clone();
+        for (exponent, coeff) in rhs.terms() {
+            output.add_term(exponent, *coeff);
+        }
+        output
+    }
+}
+
+impl<F: Field> Mul<&F> for &SparseUnivariatePolynomial<F> {
+    type Output = SparseUnivariatePolynomial<F>;
+
+    fn mul(self, rhs: &F) -> SparseUnivariatePolynomial<F> {
+        SparseUnivariatePolynomial::from_terms(
+            self.terms()
+                .map(|(exponent, coeff)| (exponent, *coeff * rhs)),
+        )
+    }
+}
+
+impl<F: Field> Add<&UnivariatePolynomial<F>> for &SparseUnivariatePolynomial<F> {
+    type Output = UnivariatePolynomial<F>;
+
+    fn add(self, rhs: &UnivariatePolynomial<F>) -> UnivariatePolynomial<F> {
+        assert_eq!(rhs.basis, Monomial);
+
+        let mut coeffs = rhs.coeffs.clone();
+        coeffs.resize(coeffs.len().max(self.degree() + 1), F::ZERO);
+        for (exponent, coeff) in self.terms() {
+            coeffs[exponent] += coeff;
+        }
+        UnivariatePolynomial::monomial(coeffs)
+    }
+}
+
+impl<F: Field> Sub<&UnivariatePolynomial<F>> for &SparseUnivariatePolynomial<F> {
+    type Output = UnivariatePolynomial<F>;
+
+    fn sub(self, rhs: &UnivariatePolynomial<F>) -> UnivariatePolynomial<F> {
+        self + &-rhs.clone()
+    }
+}
+
+// one shifted copy of `rhs` per term, which for a few terms is cheaper than
+// `UnivariatePolynomial::poly_mul` by FFTs of the size of the product
+impl<F: Field> Mul<&UnivariatePolynomial<F>> for &SparseUnivariatePolynomial<F> {
+    type Output = UnivariatePolynomial<F>;
+
+    fn mul(self, rhs: &UnivariatePolynomial<F>) -> UnivariatePolynomial<F> {
+        assert_eq!(rhs.basis, Monomial);
+
+        if self.is_zero() || rhs.is_empty() {
+            return UnivariatePolynomial::zero();
+        }
+        let mut coeffs = vec![F::ZERO; self.degree() + rhs.coeffs.len()];
+        for (exponent, scalar) in self.terms() {
+            for (coeff, rhs) in coeffs[exponent..].iter_mut().zip(rhs.coeffs.iter()) {
+                *coeff += *rhs * scalar;
+            }
+        }
+        UnivariatePolynomial::monomial(coeffs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        poly::univariate::{SparseUnivariatePolynomial, UnivariatePolynomial},
+        util::arithmetic::Field,
+    };
+    use halo2_curves::bn256::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_sparse_arithmetic() {
+        // X^5 - 2
+        let sparse = SparseUnivariatePolynomial::from_terms([
+            (5, Fr::ONE),
+            (0, -Fr::from(2)),
+            (3, Fr::ONE),
+            (3, -Fr::ONE),
+        ]);
+        assert_eq!(sparse.num_terms(), 2);
+        assert_eq!(sparse.degree(), 5);
+        let dense = UnivariatePolynomial::from(vec![1, 2, 3]);
+        assert_eq!(
+            sparse.to_dense(),
+            UnivariatePolynomial::monomial(vec![
+                -Fr::from(2),
+                Fr::ZERO,
+                Fr::ZERO,
+                Fr::ZERO,
+                Fr::ZERO,
+                Fr::ONE
+            ])
+        );
+
+        let x = Fr::random(OsRng);
+        assert_eq!(sparse.evaluate(&x), sparse.to_dense().evaluate(&x));
+        assert_eq!(&sparse * &dense, sparse.to_dense().poly_mul(dense.clone()));
+        assert_eq!(&sparse + &dense, &sparse.to_dense() + &dense);
+        assert_eq!(&sparse - &dense, &sparse.to_dense() - &dense);
+        assert_eq!(
+            (&(&sparse * &Fr::from(3)) + &-&sparse).to_dense(),
+            &sparse.to_dense() * Fr::from(2)
+        );
+        assert!((&sparse + &-&sparse).is_zero());
+        assert_eq!(
+            &SparseUnivariatePolynomial::zero() * &dense,
+            UnivariatePolynomial::zero()
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{
+    poly::univariate::UnivariatePolynomial,
+    util::{
+        arithmetic::{batch_invert, Field, WithSmallOrderMulGroup},
+        izip_eq,
+        parallel::par_map_collect,
+        Itertools,
+    },
+};
+
+// degree below which schoolbook multiplication and long division are faster
+// than the FFT based ones
+const FFT_THRESHOLD: usize = 64;
+
+/// Subproduct tree of some points `x_i`, with the leaves `X - x_i` and every
+/// node the product of its children, so that the root is the vanishing
+/// polynomial `∏ (X - x_i)` of the points, e.g. `Z_{H_I}` of the roots of a
+/// subtable. Building it, evaluating a polynomial at every point and
+/// interpolating evaluations at the points by it all take `O(n log² n)`
+/// instead of the `O(n²)` of the naive algorithms.
+#[derive(Clone, Debug)]
+pub struct SubproductTree<F> {
+    // nodes by level, from the leaves up to the root
+    levels: Vec<Vec<UnivariatePolynomial<F>>>,
+}
+
+impl<F: WithSmallOrderMulGroup<3>> SubproductTree<F> {
+    pub fn new(points: &[F]) -> Self {
+        assert!(!points.is_empty());
+
+        let leaves = points
+            .iter()
+            .map(|point| UnivariatePolynomial::monomial(vec![-*point, F::ONE]))
+            .collect_vec();
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let pairs = levels.last().unwrap().chunks(2).collect_vec();
+            let level = par_map_collect(pairs, |pair| match pair {
+                [lhs, rhs] => mul(lhs, rhs),
+                // an odd node is carried up as it is
+                _ => pair[0].clone(),
+            });
+            levels.push(level);
+        }
+        Self { levels }
+    }
+
+    pub fn num_points(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// `∏ (X - x_i)` of every point.
+    pub fn vanishing_poly(&self) -> &UnivariatePolynomial<F> {
+        &self.levels.last().unwrap()[0]
+    }
+
+    pub fn into_vanishing_poly(mut self) -> UnivariatePolynomial<F> {
+        self.levels.pop().unwrap().pop().unwrap()
+    }
+
+    /// Evaluations of `poly` at every point, in their order, as the
+    /// remainders of `poly` by every leaf, each computed from the remainder
+    /// by its parent.
+    pub fn evaluate(&self, poly: &UnivariatePolynomial<F>) -> Vec<F> {
+        let mut remainders = vec![rem(poly, self.vanishing_poly())];
+        for level in self.levels.iter().rev().skip(1) {
+            remainders = par_map_collect(level.iter().enumerate().collect_vec(), |(i, node)| {
+                rem(&remainders[i / 2], node)
+            });
+        }
+        remainders
+            .iter()
+            .map(|remainder| remainder.coeffs().first().copied().unwrap_or(F::ZERO))
+            .collect()
+    }
+
+    /// Barycentric weights `w_i = 1 / ∏_{j ≠ i} (x_i - x_j)` of the points,
+    /// as the inverses of `Z'(x_i)`.
+    pub fn barycentric_weights(&self) -> Vec<F> {
+        let mut weights = self.evaluate(&derivative(self.vanishing_poly()));
+        batch_invert(&mut weights);
+        weights
+    }
+
+    /// Polynomial of degree less than the number of points with `evals` at
+    /// them, as `Σ_i evals[i] w_i Z(X) / (X - x_i)` of the barycentric
+    /// weights `w_i = 1 / Z'(x_i)`, summed up from the leaves by Algorithm
+    /// 10.9 of Modern Computer Algebra.
+    pub fn interpolate(&self, evals: &[F]) -> UnivariatePolynomial<F> {
+        assert_eq!(evals.len(), self.num_points());
+
+        let mut sums = izip_eq!(self.barycentric_weights(), evals)
+            .map(|(weight, eval)| UnivariatePolynomial::monomial(vec![weight * eval]))
+            .collect_vec();
+        for level in self.levels.iter().take(self.levels.len() - 1) {
+            let pairs = sums.chunks(2).zip(level.chunks(2)).collect_vec();
+            sums = par_map_collect(pairs, |(sums, nodes)| match (sums, nodes) {
+                ([lhs, rhs], [lhs_node, rhs_node]) => &mul(lhs, rhs_node) + mul(rhs, lhs_node),
+                _ => sums[0].clone(),
+            });
+        }
+        sums.pop().unwrap()
+    }
+}
+
+fn derivative<F: WithSmallOrderMulGroup<3>>(
+    poly: &UnivariatePolynomial<F>,
+) -> UnivariatePolynomial<F> {
+    let coeffs = poly.coeffs().iter().enumerate().skip(1);
+    UnivariatePolynomial::monomial(
+        coeffs
+            .map(|(i, coeff)| *coeff * F::from(i as u64))
+            .collect(),
+    )
+}
+
+fn mul<F: WithSmallOrderMulGroup<3>>(
+    lhs: &UnivariatePolynomial<F>,
+    rhs: &UnivariatePolynomial<F>,
+) -> UnivariatePolynomial<F> {
+    if lhs.degree().min(rhs.degree()) < FFT_THRESHOLD {
+        lhs * rhs
+    } else {
+        lhs.poly_mul(rhs)
+    }
+}
+
+// remainder of `poly` by the monic `divisor`, by the Newton iteration of the
+// inverse of the reversed divisor for large quotients
+fn rem<F: WithSmallOrderMulGroup<3>>(
+    poly: &UnivariatePolynomial<F>,
+    divisor: &UnivariatePolynomial<F>,
+) -> UnivariatePolynomial<F> {
+    if poly.is_empty() || poly.degree() < divisor.degree() {
+        return poly.clone();
+    }
+    let quotient_len = poly.degree() - divisor.degree() + 1;
+    if quotient_len.min(divisor.degree()) < FFT_THRESHOLD {
+        return poly.div_rem(divisor).1;
+    }
+
+    // rev(q) = rev(poly) / rev(divisor) mod X^quotient_len
+    let reversed = |coeffs: &[F]| {
+        UnivariatePolynomial::monomial(coeffs.iter().rev().take(quotient_len).copied().collect())
+    };
+    let divisor_inv = inv_mod(&reversed(divisor.coeffs()), quotient_len);
+    let mut quotient = truncated(mul(&reversed(poly.coeffs()), &divisor_inv), quotient_len);
+    quotient.resize(quotient_len, F::ZERO);
+    quotient.reverse();
+    let quotient = UnivariatePolynomial::monomial(quotient);
+    let mut remainder = poly - mul(&quotient, divisor);
+    remainder.truncate_leading_zeros();
+    remainder
+}
+
+// inverse of `poly` with a nonzero constant term modulo `X^n`, doubling the
+// precision by `g ← g (2 - poly g)` from the inverse of the constant term
+fn inv_mod<F: WithSmallOrderMulGroup<3>>(
+    poly: &UnivariatePolynomial<F>,
+    n: usize,
+) -> UnivariatePolynomial<F> {
+    let mut inv = UnivariatePolynomial::monomial(vec![poly[0].invert().unwrap()]);
+    let mut precision = 1;
+    while precision < n {
+        precision = (2 * precision).min(n);
+        let poly = UnivariatePolynomial::monomial(truncated(poly.clone(), precision));
+        let mut error = -UnivariatePolynomial::monomial(truncated(mul(&poly, &inv), precision));
+        error += UnivariatePolynomial::monomial(vec![F::from(2)]);
+        inv = UnivariatePolynomial::monomial(truncated(mul(&inv, &error), precision));
+    }
+    inv
+}
+
+fn truncated<F>(poly: UnivariatePolynomial<F>, len: usize) -> Vec<F> {
+    let mut coeffs = poly.into_coeffs();
+    coeffs.truncate(len);
+    coeffs
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        poly::{
+            univariate::{SubproductTree, UnivariatePolynomial},
+            Polynomial,
+        },
+        util::arithmetic::{barycentric_weights, Field},
+    };
+    use halo2_curves::bn256::Fr;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_subproduct_tree() {
+        for num_points in [1, 5, 200] {
+            let points = (0..num_points)
+                .map(|_| Fr::random(OsRng))
+                .collect::<Vec<_>>();
+            let tree = SubproductTree::new(&points);
+            assert_eq!(tree.num_points(), num_points);
+            assert_eq!(
+                tree.vanishing_poly(),
+                &UnivariatePolynomial::vanishing(&points, Fr::ONE)
+            );
+
+            for degree in [0, num_points / 2, 3 * num_points] {
+                let poly = UnivariatePolynomial::rand(degree + 1, OsRng);
+                let evals = points.iter().map(|point| poly.evaluate(point));
+                assert!(tree.evaluate(&poly).into_iter().eq(evals));
+            }
+            assert_eq!(tree.barycentric_weights(), barycentric_weights(&points));
+            let evals = (0..num_points)
+                .map(|_| Fr::random(OsRng))
+                .collect::<Vec<_>>();
+            let poly = tree.interpolate(&evals);
+            assert!(poly.degree() < num_points);
+            assert_eq!(tree.evaluate(&poly), evals);
+            // the constant term of the zero polynomial is zero
+            assert_eq!(
+                tree.evaluate(&UnivariatePolynomial::zero()),
+                vec![Fr::ZERO; num_points]
+            );
+        }
+    }
+}
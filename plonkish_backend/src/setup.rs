@@ -0,0 +1,6 @@
+//! Generation of the structured reference strings, as an alternative to
+//! [`PolynomialCommitmentScheme::setup`] of a single trusted party.
+//!
+//! [`PolynomialCommitmentScheme::setup`]: crate::pcs::PolynomialCommitmentScheme::setup
+
+pub mod ceremony;
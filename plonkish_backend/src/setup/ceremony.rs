@@ -0,0 +1,354 @@
+//! Sequential powers-of-tau ceremony for the SRS of [`UnivariateKzg`], e.g.
+//! of Baloo, which is sound as long as one of its contributors forgets their
+//! secret.
+//!
+//! The [`Accumulator`] holds `[τ^i]1` and `[τ^i]2`, starting from `τ = 1`.
+//! Every contributor multiplies `τ` by a secret `x` by [`contribute`], and
+//! sends the new accumulator with a [`ContributionProof`] of `[x]1`, `[x]2`
+//! and a Schnorr proof of knowledge of `x` bound to the transcript hash so
+//! far, so it can't be replayed. The coordinator checks both by
+//! [`Ceremony::apply`] before accepting them, and chains the hash of each
+//! contribution into the transcript hash, which anyone can recompute from
+//! the published contributions to audit the ceremony.
+//!
+//! [`UnivariateKzg`]: crate::pcs::univariate::UnivariateKzg
+
+use crate::{
+    pcs::univariate::{powers_are_consistent, UnivariateKzgParam},
+    util::{
+        arithmetic::{
+            batch_projective_to_affine, fe_mod_from_le_bytes, is_in_prime_order_subgroup, powers,
+            Field, MultiMillerLoop, PrimeCurveAffine, PrimeField,
+        },
+        chain,
+        hash::{FixedOutputReset, Hash, Keccak256, Update},
+        parallel::par_map_collect,
+        Deserialize, DeserializeOwned, Itertools, Serialize,
+    },
+    Error,
+};
+use halo2_curves::group::GroupEncoding;
+use rand::RngCore;
+
+/// `[τ^i]1` and `[τ^i]2` for `i < 2^k` of the product `τ` of the secrets
+/// contributed so far.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "M::G1Affine: Serialize, M::G2Affine: Serialize",
+    deserialize = "M::G1Affine: DeserializeOwned, M::G2Affine: DeserializeOwned",
+))]
+pub struct Accumulator<M: MultiMillerLoop> {
+    powers_g1: Vec<M::G1Affine>,
+    powers_g2: Vec<M::G2Affine>,
+}
+
+impl<M: MultiMillerLoop> Accumulator<M> {
+    /// Accumulator of `τ = 1` of `2^k` powers, for `k ≥ 1`.
+    pub fn new(k: usize) -> Self {
+        assert!((1..=M::Scalar::S as usize).contains(&k));
+
+        Self {
+            powers_g1: vec![M::G1Affine::generator(); 1 << k],
+            powers_g2: vec![M::G2Affine::generator(); 1 << k],
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.powers_g1.len()
+    }
+
+    pub fn powers_g1(&self) -> &[M::G1Affine] {
+        &self.powers_g1
+    }
+
+    pub fn powers_g2(&self) -> &[M::G2Affine] {
+        &self.powers_g2
+    }
+
+    /// Keccak256 of every point.
+    pub fn hash(&self) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        for point in &self.powers_g1 {
+            Update::update(&mut hasher, point.to_bytes().as_ref());
+        }
+        for point in &self.powers_g2 {
+            Update::update(&mut hasher, point.to_bytes().as_ref());
+        }
+        let mut hash = [0; 32];
+        hash.copy_from_slice(&hasher.finalize_fixed_reset());
+        hash
+    }
+
+    // `τ` multiplied by `x`, i.e. `[τ^i]` by `x^i`
+    fn multiply(&self, x: M::Scalar) -> Self {
+        let powers_of_x = powers(x).take(self.size()).collect_vec();
+        let powers_g1: Vec<_> = par_map_collect(
+            self.powers_g1.iter().zip(&powers_of_x).collect_vec(),
+            |(point, scalar)| *point * scalar,
+        );
+        let powers_g2: Vec<_> = par_map_collect(
+            self.powers_g2.iter().zip(&powers_of_x).collect_vec(),
+            |(point, scalar)| *point * scalar,
+        );
+        Self {
+            powers_g1: batch_projective_to_affine(&powers_g1),
+            powers_g2: batch_projective_to_affine(&powers_g2),
+        }
+    }
+}
+
+/// `[x]1` and `[x]2` of the secret `x` of a contribution, and a Schnorr proof
+/// `([r]1, r + c x)` of knowledge of `x`, of the challenge `c` of the
+/// transcript hash before the contribution.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "M::G1Affine: Serialize, M::G2Affine: Serialize",
+    deserialize = "M::G1Affine: DeserializeOwned, M::G2Affine: DeserializeOwned",
+))]
+pub struct ContributionProof<M: MultiMillerLoop> {
+    x_g1: M::G1Affine,
+    x_g2: M::G2Affine,
+    r_g1: M::G1Affine,
+    z: M::Scalar,
+}
+
+impl<M: MultiMillerLoop> ContributionProof<M> {
+    pub fn x_g1(&self) -> M::G1Affine {
+        self.x_g1
+    }
+
+    pub fn x_g2(&self) -> M::G2Affine {
+        self.x_g2
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        chain![
+            self.x_g1.to_bytes().as_ref(),
+            self.x_g2.to_bytes().as_ref(),
+            self.r_g1.to_bytes().as_ref(),
+            self.z.to_repr().as_ref(),
+        ]
+        .copied()
+        .collect()
+    }
+}
+
+/// Contribute a secret sampled from `rng` to `accumulator` of the ceremony
+/// of `transcript_hash`, returning the new accumulator and the proof of the
+/// contribution. The secret is dropped once they're computed.
+pub fn contribute<M: MultiMillerLoop>(
+    accumulator: &Accumulator<M>,
+    transcript_hash: &[u8; 32],
+    mut rng: impl RngCore,
+) -> (Accumulator<M>, ContributionProof<M>) {
+    let x = loop {
+        let x = M::Scalar::random(&mut rng);
+        if !bool::from(x.is_zero()) {
+            break x;
+        }
+    };
+    let r = M::Scalar::random(&mut rng);
+    let x_g1: M::G1Affine = (M::G1Affine::generator() * x).into();
+    let r_g1: M::G1Affine = (M::G1Affine::generator() * r).into();
+    let c = challenge::<M>(transcript_hash, &x_g1, &r_g1);
+    let proof = ContributionProof {
+        x_g1,
+        x_g2: (M::G2Affine::generator() * x).into(),
+        r_g1,
+        z: r + c * x,
+    };
+    (accumulator.multiply(x), proof)
+}
+
+/// Verify that `next` is `prev` with `τ` multiplied by the secret of `proof`,
+/// which is known to the contributor, of the ceremony of `transcript_hash`
+/// before the contribution. The powers of `next` are checked in random
+/// combinations with randomness from `rng`.
+pub fn verify_contribution<M: MultiMillerLoop>(
+    prev: &Accumulator<M>,
+    next: &Accumulator<M>,
+    transcript_hash: &[u8; 32],
+    proof: &ContributionProof<M>,
+    rng: impl RngCore,
+) -> Result<(), Error> {
+    let err = |reason: &str| {
+        Err(Error::InvalidPcsParam(format!(
+            "Invalid ceremony contribution: {reason}"
+        )))
+    };
+
+    let (g1, g2) = (M::G1Affine::generator(), M::G2Affine::generator());
+    if next.powers_g1.len() != prev.size() || next.powers_g2.len() != prev.size() {
+        return err("Unexpected number of points");
+    }
+    if next.powers_g1[0] != g1 || next.powers_g2[0] != g2 {
+        return err("Unexpected generators");
+    }
+    if bool::from(proof.x_g1.is_identity()) {
+        return err("Degenerate secret");
+    }
+    if !chain![&next.powers_g1, [&proof.x_g1, &proof.r_g1]].all(is_in_prime_order_subgroup)
+        || !chain![&next.powers_g2, [&proof.x_g2]].all(is_in_prime_order_subgroup)
+    {
+        return err("Point not in the prime order subgroup");
+    }
+
+    // [z]1 = [r]1 + c [x]1
+    let c = challenge::<M>(transcript_hash, &proof.x_g1, &proof.r_g1);
+    if g1 * proof.z != proof.x_g1 * c + proof.r_g1 {
+        return err("Invalid proof of knowledge of the secret");
+    }
+
+    // e([x]1, [1]2) = e([1]1, [x]2) and e([τ']1, [1]2) = e([τ]1, [x]2)
+    let terms = [
+        (proof.x_g1, g2),
+        (-g1, proof.x_g2),
+        (next.powers_g1[1], g2),
+        (-prev.powers_g1[1], proof.x_g2),
+    ]
+    .map(|(g1, g2): (M::G1Affine, M::G2Affine)| (g1, M::G2Prepared::from(g2)));
+    let terms = terms.iter().map(|(g1, g2)| (g1, g2)).collect_vec();
+    if !M::pairings_product_is_identity(&terms[..2])
+        || !M::pairings_product_is_identity(&terms[2..])
+    {
+        return err("Secret not applied");
+    }
+
+    if !powers_are_consistent::<M>(&next.powers_g1, &next.powers_g2, rng) {
+        return err("Inconsistent powers of τ");
+    }
+    Ok(())
+}
+
+/// Coordinator of a ceremony, holding the latest accepted accumulator and
+/// the transcript hash of the contributions to it.
+#[derive(Clone, Debug)]
+pub struct Ceremony<M: MultiMillerLoop> {
+    accumulator: Accumulator<M>,
+    transcript_hash: [u8; 32],
+    num_contributions: usize,
+}
+
+impl<M: MultiMillerLoop> Ceremony<M> {
+    /// Ceremony of `2^k` powers, whose transcript hash starts as the hash of
+    /// the initial accumulator.
+    pub fn new(k: usize) -> Self {
+        let accumulator = Accumulator::new(k);
+        Self {
+            transcript_hash: accumulator.hash(),
+            accumulator,
+            num_contributions: 0,
+        }
+    }
+
+    pub fn accumulator(&self) -> &Accumulator<M> {
+        &self.accumulator
+    }
+
+    /// Hash to contribute against, i.e. to pass to [`contribute`].
+    pub fn transcript_hash(&self) -> &[u8; 32] {
+        &self.transcript_hash
+    }
+
+    pub fn num_contributions(&self) -> usize {
+        self.num_contributions
+    }
+
+    /// Accept `next` if [`verify_contribution`] of it passes, with the
+    /// transcript hash updated to `Keccak256(hash || next hash || proof)`.
+    pub fn apply(
+        &mut self,
+        next: Accumulator<M>,
+        proof: &ContributionProof<M>,
+        rng: impl RngCore,
+    ) -> Result<(), Error> {
+        verify_contribution(&self.accumulator, &next, &self.transcript_hash, proof, rng)?;
+
+        let mut hasher = Keccak256::new();
+        Update::update(&mut hasher, &self.transcript_hash);
+        Update::update(&mut hasher, &next.hash());
+        Update::update(&mut hasher, &proof.to_bytes());
+        self.transcript_hash
+            .copy_from_slice(&hasher.finalize_fixed_reset());
+        self.accumulator = next;
+        self.num_contributions += 1;
+        Ok(())
+    }
+
+    /// SRS of the accumulator, which fails if nothing was contributed, as
+    /// `τ = 1` is known to everyone.
+    pub fn into_param(self) -> Result<UnivariateKzgParam<M>, Error> {
+        if self.num_contributions == 0 {
+            return Err(Error::InvalidPcsParam(
+                "Ceremony without contributions".to_string(),
+            ));
+        }
+        let Accumulator {
+            powers_g1,
+            powers_g2,
+        } = self.accumulator;
+        Ok(UnivariateKzgParam::from_powers(powers_g1, powers_g2))
+    }
+}
+
+// challenge of the Schnorr proof, of the transcript hash so that a proof
+// can't be replayed in another ceremony or at another position
+fn challenge<M: MultiMillerLoop>(
+    transcript_hash: &[u8; 32],
+    x_g1: &M::G1Affine,
+    r_g1: &M::G1Affine,
+) -> M::Scalar {
+    let mut hasher = Keccak256::new();
+    Update::update(&mut hasher, transcript_hash);
+    Update::update(&mut hasher, x_g1.to_bytes().as_ref());
+    Update::update(&mut hasher, r_g1.to_bytes().as_ref());
+    fe_mod_from_le_bytes(hasher.finalize_fixed_reset())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcs::{univariate::UnivariateKzg, PolynomialCommitmentScheme};
+    use halo2_curves::bn256::Bn256;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_ceremony() {
+        let mut ceremony = Ceremony::<Bn256>::new(3);
+        let initial_hash = *ceremony.transcript_hash();
+        for _ in 0..2 {
+            let (next, proof) =
+                contribute(ceremony.accumulator(), ceremony.transcript_hash(), OsRng);
+            let prev_hash = *ceremony.transcript_hash();
+            ceremony.apply(next.clone(), &proof, OsRng).unwrap();
+            assert_ne!(*ceremony.transcript_hash(), prev_hash);
+
+            // replayed against the new transcript hash
+            let mut replayed = ceremony.clone();
+            assert!(replayed.apply(next, &proof, OsRng).is_err());
+        }
+        assert_eq!(ceremony.num_contributions(), 2);
+        assert_ne!(*ceremony.transcript_hash(), initial_hash);
+
+        // a contribution with a point out of the powers of τ
+        let (mut next, proof) =
+            contribute(ceremony.accumulator(), ceremony.transcript_hash(), OsRng);
+        next.powers_g1.swap(2, 3);
+        assert_eq!(
+            ceremony.clone().apply(next, &proof, OsRng),
+            Err(Error::InvalidPcsParam(
+                "Invalid ceremony contribution: Inconsistent powers of τ".to_string()
+            ))
+        );
+        // another secret than the proven one
+        let (next, _) = contribute(ceremony.accumulator(), ceremony.transcript_hash(), OsRng);
+        assert!(ceremony.clone().apply(next, &proof, OsRng).is_err());
+
+        let param = ceremony.into_param().unwrap();
+        assert_eq!(param.validate(OsRng), Ok(()));
+        let (pp, _) = UnivariateKzg::<Bn256>::trim(&param, 1 << 3, 1).unwrap();
+        assert_eq!(pp.degree(), 7);
+        assert_ne!(param.monomial_g1()[1], param.g1());
+        assert!(Ceremony::<Bn256>::new(3).into_param().is_err());
+    }
+}
@@ -2,8 +2,10 @@ pub mod arithmetic;
 pub mod code;
 pub mod expression;
 pub mod hash;
+pub mod metrics;
 pub mod parallel;
 mod timer;
+pub mod trace;
 pub mod transcript;
 
 pub use itertools::{chain, izip, Either, Itertools};
@@ -82,7 +84,8 @@ pub(crate) use {impl_index, izip_eq};
 
 #[cfg(any(test, feature = "benchmark"))]
 pub mod test {
-    use crate::util::arithmetic::Field;
+    use crate::util::arithmetic::{CurveAffine, Field};
+    use halo2_curves::bn256;
     use rand::{
         rngs::{OsRng, StdRng},
         CryptoRng, RngCore, SeedableRng,
@@ -108,4 +111,17 @@ pub mod test {
     pub fn rand_vec<F: Field>(n: usize, mut rng: impl RngCore) -> Vec<F> {
         iter::repeat_with(|| F::random(&mut rng)).take(n).collect()
     }
+
+    /// A point on the twist of `G2` of small `x`, which isn't in the subgroup
+    /// of prime order but one of a huge cofactor.
+    pub fn off_subgroup_g2() -> bn256::G2Affine {
+        let mut x = bn256::Fq2::ONE;
+        loop {
+            let y = (x.square() * x + bn256::G2Affine::b()).sqrt();
+            if let Some(y) = Option::from(y) {
+                return bn256::G2Affine::from_xy(x, y).unwrap();
+            }
+            x += bn256::Fq2::ONE;
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use crate::util::{izip_eq, parallel::parallelize, BigUint, Itertools};
+use crate::util::{izip_eq, metrics, parallel::parallelize, BigUint, Itertools};
 use halo2_curves::{
     bn256, grumpkin,
     pairing::{self, MillerLoopResult},
@@ -11,7 +11,7 @@ mod fft;
 mod msm;
 
 pub use bitvec::field::BitField;
-pub use fft::radix2_fft;
+pub use fft::{mixed_radix_fft, radix2_fft};
 pub use halo2_curves::{
     group::{
         ff::{
@@ -23,10 +23,11 @@ pub use halo2_curves::{
     },
     Coordinates, CurveAffine, CurveExt,
 };
-pub use msm::{fixed_base_msm, variable_base_msm, window_size, window_table, Msm};
+pub use msm::{fixed_base_msm, variable_base_msm, window_size, window_table, Msm, MsmTable};
 
 pub trait MultiMillerLoop: pairing::MultiMillerLoop + Debug + Sync {
     fn pairings_product_is_identity(terms: &[(&Self::G1Affine, &Self::G2Prepared)]) -> bool {
+        metrics::count_pairings(terms.len());
         Self::multi_miller_loop(terms)
             .final_exponentiation()
             .is_identity()
@@ -107,6 +108,15 @@ pub fn inner_product<'a, 'b, F: Field>(
         .unwrap_or_default()
 }
 
+/// Invert every value in place by Montgomery's trick, i.e. with a single
+/// inversion and `3(n - 1)` multiplications per thread. Zeros are left as
+/// they are, so callers rejecting them should check before.
+pub fn batch_invert<F: Field>(values: &mut [F]) {
+    parallelize(values, |(values, _)| {
+        values.iter_mut().batch_invert();
+    });
+}
+
 pub fn barycentric_weights<F: Field>(points: &[F]) -> Vec<F> {
     let mut weights = points
         .iter()
@@ -121,14 +131,14 @@ pub fn barycentric_weights<F: Field>(points: &[F]) -> Vec<F> {
                 .unwrap_or(F::ONE)
         })
         .collect_vec();
-    weights.batch_invert();
+    batch_invert(&mut weights);
     weights
 }
 
 pub fn barycentric_interpolate<F: Field>(weights: &[F], points: &[F], evals: &[F], x: &F) -> F {
     let (coeffs, sum_inv) = {
         let mut coeffs = points.iter().map(|point| *x - point).collect_vec();
-        coeffs.batch_invert();
+        batch_invert(&mut coeffs);
         coeffs.iter_mut().zip(weights).for_each(|(coeff, weight)| {
             *coeff *= weight;
         });
@@ -138,6 +148,42 @@ pub fn barycentric_interpolate<F: Field>(weights: &[F], points: &[F], evals: &[F
     inner_product(&coeffs, evals) * &sum_inv
 }
 
+/// Barycentric weights of a point set computed once, e.g. of the subdomain
+/// `H_I` of a Baloo lookup, to evaluate at any point the polynomial of degree
+/// less than the number of points interpolating any evaluations on them.
+#[derive(Clone, Debug, Default)]
+pub struct BarycentricEvaluator<F> {
+    points: Vec<F>,
+    weights: Vec<F>,
+}
+
+impl<F: Field> BarycentricEvaluator<F> {
+    /// Points should be distinct.
+    pub fn new(points: Vec<F>) -> Self {
+        let weights = barycentric_weights(&points);
+        Self { points, weights }
+    }
+
+    pub fn points(&self) -> &[F] {
+        &self.points
+    }
+
+    /// `1 / Π_{j != i}(x_i - x_j)` of each point `x_i`.
+    pub fn weights(&self) -> &[F] {
+        &self.weights
+    }
+
+    /// Evaluation at `x` of the polynomial which is `evals[i]` at `points[i]`.
+    pub fn evaluate(&self, evals: &[F], x: &F) -> F {
+        assert_eq!(evals.len(), self.points.len());
+        // the barycentric formula divides by `x - x_i`
+        match self.points.iter().position(|point| point == x) {
+            Some(i) => evals[i],
+            None => barycentric_interpolate(&self.weights, &self.points, evals, x),
+        }
+    }
+}
+
 pub fn modulus<F: PrimeField>() -> BigUint {
     BigUint::from_bytes_le((-F::ONE).to_repr().as_ref()) + 1u64
 }
@@ -219,6 +265,39 @@ pub fn div_ceil(dividend: usize, divisor: usize) -> usize {
     Integer::div_ceil(&dividend, &divisor)
 }
 
+/// Whether `point` is on the curve and in the subgroup of prime order `r` of
+/// the scalar field, i.e. `[r - 1]P = -P` as scalars are multiplied by their
+/// integer less than `r`. Points read from proofs or SRSs should be, as
+/// `from_xy` only checks they are on the curve, which isn't enough for curves
+/// of cofactor not `1` like the twist of BN254 of `G2`.
+pub fn is_in_prime_order_subgroup<C: CurveAffine>(point: &C) -> bool {
+    bool::from(point.is_on_curve())
+        && bool::from((point.to_curve() * -C::Scalar::ONE + *point).is_identity())
+}
+
+/// Whether all points of `C` are of the prime order of its scalar field, i.e.
+/// its cofactor is `1` like of `G1` of BN254 and of Pasta, for which
+/// [`is_in_prime_order_subgroup`] always holds. By Hasse's bound the curve
+/// over `F_q` has at most `q + 1 + 2√q` points, which leaves no room for a
+/// cofactor of `2` or more for scalars of more than half of it, i.e. if
+/// `2r - q - 1 > 2√q`, compared squared to stay exact in integers. Base
+/// fields of a repr other than of the scalars, like the extension of `G2`,
+/// are taken as of cofactor not `1`.
+pub fn is_prime_order_curve<C: CurveAffine>() -> bool {
+    if <C::Base as PrimeField>::Repr::default().as_ref().len()
+        != <C::Scalar as PrimeField>::Repr::default().as_ref().len()
+    {
+        return false;
+    }
+    let (q, r) = (modulus::<C::Base>(), modulus::<C::Scalar>());
+    let r_2 = r * 2u64;
+    if r_2 <= &q + 1u64 {
+        return false;
+    }
+    let diff = r_2 - &q - 1u64;
+    &diff * &diff > q * 4u64
+}
+
 pub fn batch_projective_to_affine<C: CurveAffine>(projectives: &[C::Curve]) -> Vec<C> {
     let mut affines = vec![C::identity(); projectives.len()];
     parallelize(&mut affines, |(affines, starts)| {
@@ -229,11 +308,64 @@ pub fn batch_projective_to_affine<C: CurveAffine>(projectives: &[C::Curve]) -> V
 
 #[cfg(test)]
 mod test {
-    use crate::util::arithmetic;
-    use halo2_curves::bn256;
+    use crate::util::{
+        arithmetic::{self, CurveAffine, Field, PrimeCurveAffine},
+        test::off_subgroup_g2,
+    };
+    use halo2_curves::{bn256, grumpkin, pasta};
 
     #[test]
     fn field_size() {
         assert_eq!(arithmetic::field_size::<bn256::Fr>(), 254);
     }
+
+    #[test]
+    fn batch_invert() {
+        let mut values = (0..100).map(bn256::Fr::from).collect::<Vec<_>>();
+        arithmetic::batch_invert(&mut values);
+        assert_eq!(values[0], bn256::Fr::ZERO);
+        for (i, value) in values.iter().enumerate().skip(1) {
+            assert_eq!(*value * bn256::Fr::from(i as u64), bn256::Fr::ONE);
+        }
+    }
+
+    #[test]
+    fn barycentric_evaluator() {
+        let points = (1..5).map(bn256::Fr::from).collect::<Vec<_>>();
+        let evaluator = arithmetic::BarycentricEvaluator::new(points.clone());
+        assert_eq!(evaluator.points(), points.as_slice());
+        // 1 + 2X + 3X^2
+        let poly = |x: bn256::Fr| {
+            bn256::Fr::ONE + x * bn256::Fr::from(2) + x.square() * bn256::Fr::from(3)
+        };
+        let evals = points.iter().map(|point| poly(*point)).collect::<Vec<_>>();
+        for x in [0, 2, 7].map(bn256::Fr::from) {
+            assert_eq!(evaluator.evaluate(&evals, &x), poly(x));
+        }
+    }
+
+    #[test]
+    fn is_in_prime_order_subgroup() {
+        assert!(arithmetic::is_in_prime_order_subgroup(
+            &bn256::G1Affine::generator()
+        ));
+        assert!(arithmetic::is_in_prime_order_subgroup(
+            &bn256::G2Affine::generator()
+        ));
+        assert!(arithmetic::is_in_prime_order_subgroup(
+            &bn256::G2Affine::identity()
+        ));
+        let off_subgroup = off_subgroup_g2();
+        assert!(bool::from(off_subgroup.is_on_curve()));
+        assert!(!arithmetic::is_in_prime_order_subgroup(&off_subgroup));
+    }
+
+    #[test]
+    fn is_prime_order_curve() {
+        assert!(arithmetic::is_prime_order_curve::<bn256::G1Affine>());
+        assert!(arithmetic::is_prime_order_curve::<grumpkin::G1Affine>());
+        assert!(arithmetic::is_prime_order_curve::<pasta::EpAffine>());
+        assert!(arithmetic::is_prime_order_curve::<pasta::EqAffine>());
+        assert!(!arithmetic::is_prime_order_curve::<bn256::G2Affine>());
+    }
 }
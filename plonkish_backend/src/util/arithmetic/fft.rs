@@ -2,8 +2,10 @@
 
 use crate::util::{
     arithmetic::{Field, GroupOpsOwned, ScalarMulOwned},
+    metrics,
     parallel::{join, num_threads},
     start_timer,
+    trace::trace_span,
 };
 
 pub trait FftGroup<Scalar: Field>:
@@ -20,6 +22,8 @@ where
 
 pub fn radix2_fft<Scalar: Field, G: FftGroup<Scalar>>(a: &mut [G], omega: Scalar, log2_n: usize) {
     let _timer = start_timer(|| "fft");
+    let _span = trace_span!(DEBUG, "fft", log2_n);
+    metrics::count_fft();
 
     fn bitreverse(mut n: usize, l: usize) -> usize {
         let mut r = 0;
@@ -82,6 +86,41 @@ pub fn radix2_fft<Scalar: Field, G: FftGroup<Scalar>>(a: &mut [G], omega: Scalar
     }
 }
 
+/// FFT of `a` of size `n = 2^log2_n · 3^log3_n` with `omega` of order `n`,
+/// by radix-3 steps down to [`radix2_fft`] of the power of two.
+pub fn mixed_radix_fft<F: Field>(a: &mut [F], omega: F, log2_n: usize, log3_n: usize) {
+    let n = a.len();
+    assert_eq!(n, 3usize.pow(log3_n as u32) << log2_n);
+    if log3_n == 0 {
+        return radix2_fft(a, omega, log2_n);
+    }
+
+    // a(X) = a_0(X^3) + X a_1(X^3) + X^2 a_2(X^3), of the FFTs over the
+    // subgroup of order n / 3
+    let m = n / 3;
+    let omega_cubed = omega.square() * omega;
+    let mut parts: Vec<Vec<F>> = (0..3)
+        .map(|i| a.iter().skip(i).step_by(3).copied().collect())
+        .collect();
+    parts
+        .iter_mut()
+        .for_each(|part| mixed_radix_fft(part, omega_cubed, log2_n, log3_n - 1));
+
+    // ζ = ω^m of order 3
+    let zeta = omega.pow_vartime([m as u64]);
+    let zeta_square = zeta.square();
+    let mut twiddle = F::ONE;
+    for k in 0..m {
+        let x_0 = parts[0][k];
+        let x_1 = parts[1][k] * twiddle;
+        let x_2 = parts[2][k] * twiddle.square();
+        a[k] = x_0 + x_1 + x_2;
+        a[k + m] = x_0 + x_1 * zeta + x_2 * zeta_square;
+        a[k + 2 * m] = x_0 + x_1 * zeta_square + x_2 * zeta;
+        twiddle *= omega;
+    }
+}
+
 fn recursive_butterfly_arithmetic<Scalar: Field, G: FftGroup<Scalar>>(
     a: &mut [G],
     n: usize,
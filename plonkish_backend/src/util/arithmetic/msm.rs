@@ -1,10 +1,15 @@
 use crate::{
     pcs::Additive,
     util::{
-        arithmetic::{div_ceil, field_size, CurveAffine, Field, Group, PrimeField},
-        chain, izip_eq,
+        arithmetic::{
+            batch_projective_to_affine, div_ceil, field_size, CurveAffine, Field, Group,
+            PrimeCurveAffine, PrimeField,
+        },
+        chain, izip_eq, metrics,
         parallel::{num_threads, parallelize, parallelize_iter},
-        start_timer, Itertools,
+        start_timer,
+        trace::trace_span,
+        Itertools,
     },
 };
 use std::{
@@ -98,6 +103,8 @@ pub fn variable_base_msm<'a, 'b, C: CurveAffine>(
     assert_eq!(scalars.len(), bases.len());
 
     let _timer = start_timer(|| format!("variable_base_msm-{}", scalars.len()));
+    let _span = trace_span!(DEBUG, "msm", size = scalars.len());
+    metrics::count_msm(scalars.len());
 
     let num_threads = num_threads();
     if scalars.len() <= num_threads {
@@ -162,7 +169,7 @@ fn variable_base_msm_serial<C: CurveAffine>(
     let num_bytes = scalars[0].as_ref().len();
     let num_bits = 8 * num_bytes;
 
-    let window_size = window_size(scalars.len());
+    let window_size = pippenger_window_size(scalars.len(), num_bits);
     let num_buckets = (1 << window_size) - 1;
 
     let num_windows = div_ceil(num_bits, window_size);
@@ -188,6 +195,115 @@ fn variable_base_msm_serial<C: CurveAffine>(
     }
 }
 
+const MAX_WINDOW_SIZE: usize = 24;
+
+// window size minimizing the additions of filling and summing up the buckets
+// of every window
+fn pippenger_window_size(num_scalars: usize, num_bits: usize) -> usize {
+    (1..=MAX_WINDOW_SIZE)
+        .min_by_key(|window_size| {
+            div_ceil(num_bits, *window_size) * (num_scalars + (1 << (window_size + 1)))
+        })
+        .unwrap()
+}
+
+/// Fixed bases of MSMs, each with its multiples by `2^(c * j)` for every
+/// window `j` of `c` bits, so an MSM over them fills a single set of buckets
+/// with every window and skips the doublings, at the cost of storing the
+/// multiples.
+#[derive(Clone, Debug)]
+pub struct MsmTable<C: CurveAffine> {
+    window_size: usize,
+    num_windows: usize,
+    // window multiples of each base in turn
+    multiples: Vec<C>,
+}
+
+impl<C: CurveAffine> MsmTable<C> {
+    pub fn new(bases: &[C]) -> Self {
+        let num_bits = field_size::<C::Scalar>();
+        // buckets are summed up once instead of for every window
+        let window_size = (1..=MAX_WINDOW_SIZE)
+            .min_by_key(|window_size| {
+                div_ceil(num_bits, *window_size) * bases.len() + (1 << (window_size + 1))
+            })
+            .unwrap();
+        let num_windows = div_ceil(num_bits, window_size);
+
+        let mut multiples = vec![C::Curve::identity(); bases.len() * num_windows];
+        parallelize(&mut multiples, |(multiples, start)| {
+            for (multiple, idx) in multiples.iter_mut().zip(start..) {
+                let (base, window) = (idx / num_windows, idx % num_windows);
+                *multiple = bases[base].to_curve();
+                for _ in 0..window * window_size {
+                    *multiple = multiple.double();
+                }
+            }
+        });
+
+        Self {
+            window_size,
+            num_windows,
+            multiples: batch_projective_to_affine(&multiples),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.multiples.len() / self.num_windows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.multiples.is_empty()
+    }
+
+    /// MSM of `scalars` and the first `scalars.len()` bases.
+    pub fn msm(&self, scalars: &[C::Scalar]) -> C::Curve {
+        assert!(scalars.len() <= self.len());
+
+        let _timer = start_timer(|| format!("msm_table-{}", scalars.len()));
+        let _span = trace_span!(DEBUG, "msm_table", size = scalars.len());
+        metrics::count_msm(scalars.len());
+
+        let num_threads = num_threads();
+        let chunk_size = div_ceil(scalars.len().max(1), num_threads);
+        let mut results = vec![C::Curve::identity(); div_ceil(scalars.len(), chunk_size)];
+        parallelize_iter(
+            scalars
+                .chunks(chunk_size)
+                .zip(self.multiples.chunks(chunk_size * self.num_windows))
+                .zip(results.iter_mut()),
+            |((scalars, multiples), result)| {
+                *result = self.msm_serial(scalars, multiples);
+            },
+        );
+        results
+            .iter()
+            .fold(C::Curve::identity(), |acc, result| acc + result)
+    }
+
+    fn msm_serial(&self, scalars: &[C::Scalar], multiples: &[C]) -> C::Curve {
+        let num_buckets = (1 << self.window_size) - 1;
+        let mut buckets = vec![C::Curve::identity(); num_buckets];
+        for (scalar, multiples) in scalars.iter().zip(multiples.chunks(self.num_windows)) {
+            let repr = scalar.to_repr();
+            for (idx, multiple) in multiples.iter().enumerate() {
+                let scalar = windowed_scalar(self.window_size, num_buckets, idx, repr);
+                if scalar != 0 {
+                    buckets[scalar - 1] += *multiple;
+                }
+            }
+        }
+
+        let mut running_sum = C::Curve::identity();
+        let mut result = C::Curve::identity();
+        for bucket in buckets.into_iter().rev() {
+            running_sum += bucket;
+            result += &running_sum;
+        }
+        result
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Msm<'a, F: Field, T: Additive<F>> {
     Scalar(F),
@@ -0,0 +1,162 @@
+//! Operation counters for benchmarking, compiled to no-ops without the
+//! `metrics` feature.
+//!
+//! Counters are process-wide, so the operations (`variable_base_msm`,
+//! `radix2_fft` and pairings) are counted on whichever thread of the rayon
+//! pool they run, and those of concurrent provers are counted together. For
+//! the same reason tests of the `metrics` feature are to be run with
+//! `--test-threads=1`.
+
+#[cfg(feature = "metrics")]
+pub use enabled::{
+    count_fft, count_msm, count_pairings, mark_round, peak_alloc_bytes, reset, snapshot,
+    CountingAllocator,
+};
+
+#[cfg(not(feature = "metrics"))]
+pub use disabled::{
+    count_fft, count_msm, count_pairings, mark_round, peak_alloc_bytes, reset, snapshot,
+};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Counters {
+    pub msm_count: u64,
+    /// Total number of scalar-point pairs over all MSMs.
+    pub msm_scalars: u64,
+    pub fft_count: u64,
+    pub pairing_count: u64,
+    /// Milliseconds between consecutive calls to [`mark_round`], starting
+    /// from the last [`reset`].
+    pub round_ms: Vec<u64>,
+}
+
+#[cfg(feature = "metrics")]
+mod enabled {
+    use crate::util::metrics::Counters;
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::{
+            atomic::{AtomicU64, AtomicUsize, Ordering},
+            LazyLock, Mutex,
+        },
+        time::Instant,
+    };
+
+    static MSM_COUNT: AtomicU64 = AtomicU64::new(0);
+    static MSM_SCALARS: AtomicU64 = AtomicU64::new(0);
+    static FFT_COUNT: AtomicU64 = AtomicU64::new(0);
+    static PAIRING_COUNT: AtomicU64 = AtomicU64::new(0);
+    // milliseconds of the rounds so far, and when the last one ended
+    static ROUNDS: LazyLock<Mutex<(Vec<u64>, Instant)>> =
+        LazyLock::new(|| Mutex::new((Vec::new(), Instant::now())));
+
+    static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+    static PEAK_ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+    /// Allocator which tracks the peak of allocated bytes, to be installed by
+    /// the binary with `#[global_allocator]`. Without it the peak reads 0.
+    pub struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+                PEAK_ALLOCATED.fetch_max(allocated + layout.size(), Ordering::Relaxed);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+    }
+
+    pub fn count_msm(num_scalars: usize) {
+        MSM_COUNT.fetch_add(1, Ordering::Relaxed);
+        MSM_SCALARS.fetch_add(num_scalars as u64, Ordering::Relaxed);
+    }
+
+    pub fn count_fft() {
+        FFT_COUNT.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn count_pairings(num_pairings: usize) {
+        PAIRING_COUNT.fetch_add(num_pairings as u64, Ordering::Relaxed);
+    }
+
+    pub fn mark_round() {
+        let (round_ms, last) = &mut *ROUNDS.lock().unwrap();
+        round_ms.push(last.elapsed().as_millis() as u64);
+        *last = Instant::now();
+    }
+
+    pub fn reset() {
+        for counter in [&MSM_COUNT, &MSM_SCALARS, &FFT_COUNT, &PAIRING_COUNT] {
+            counter.store(0, Ordering::Relaxed);
+        }
+        *ROUNDS.lock().unwrap() = (Vec::new(), Instant::now());
+        PEAK_ALLOCATED.store(ALLOCATED.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+
+    pub fn snapshot() -> Counters {
+        Counters {
+            msm_count: MSM_COUNT.load(Ordering::Relaxed),
+            msm_scalars: MSM_SCALARS.load(Ordering::Relaxed),
+            fft_count: FFT_COUNT.load(Ordering::Relaxed),
+            pairing_count: PAIRING_COUNT.load(Ordering::Relaxed),
+            round_ms: ROUNDS.lock().unwrap().0.clone(),
+        }
+    }
+
+    pub fn peak_alloc_bytes() -> u64 {
+        PEAK_ALLOCATED.load(Ordering::Relaxed) as u64
+    }
+}
+
+#[cfg(not(feature = "metrics"))]
+mod disabled {
+    use crate::util::metrics::Counters;
+
+    pub fn count_msm(_: usize) {}
+
+    pub fn count_fft() {}
+
+    pub fn count_pairings(_: usize) {}
+
+    pub fn mark_round() {}
+
+    pub fn reset() {}
+
+    pub fn snapshot() -> Counters {
+        Counters::default()
+    }
+
+    pub fn peak_alloc_bytes() -> u64 {
+        0
+    }
+}
+
+#[cfg(all(test, feature = "metrics", feature = "parallel"))]
+mod test {
+    use crate::util::{metrics, parallel::parallelize_iter};
+
+    #[test]
+    fn counted_on_rayon_threads() {
+        metrics::reset();
+        parallelize_iter(0..64, |num_scalars| {
+            metrics::count_msm(num_scalars);
+            metrics::count_fft();
+            metrics::count_pairings(2);
+        });
+        let counters = metrics::snapshot();
+        assert_eq!(counters.msm_count, 64);
+        assert_eq!(counters.msm_scalars, (0..64).sum::<usize>() as u64);
+        assert_eq!(counters.fft_count, 64);
+        assert_eq!(counters.pairing_count, 128);
+
+        metrics::reset();
+        assert_eq!(metrics::snapshot(), Default::default());
+    }
+}
@@ -6,6 +6,15 @@ pub fn num_threads() -> usize {
     return 1;
 }
 
+/// Run `f` in the background, on the rayon pool if `parallel`.
+pub fn spawn<F: FnOnce() + Send + 'static>(f: F) {
+    #[cfg(feature = "parallel")]
+    rayon::spawn(f);
+
+    #[cfg(not(feature = "parallel"))]
+    std::thread::spawn(f);
+}
+
 pub fn join<A, B, RA, RB>(oper_a: A, oper_b: B) -> (RA, RB)
 where
     A: FnOnce() -> RA + Send,
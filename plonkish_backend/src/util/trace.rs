@@ -0,0 +1,62 @@
+//! Spans of the `tracing` crate around setup, preprocessing, the prover
+//! rounds, FFTs and MSMs, compiled to no-ops without the `tracing` feature.
+//!
+//! Spans are at the `INFO` level except FFTs and MSMs, which are at `DEBUG`
+//! as there are many of them per proof. Any subscriber, e.g. a flamegraph
+//! layer of `tracing-subscriber`, shows then where the time of a proof goes.
+
+#[cfg(feature = "tracing")]
+pub use enabled::Span;
+
+#[cfg(not(feature = "tracing"))]
+pub use disabled::Span;
+
+/// Enter a [`Span`] of `name` at the `INFO` or `DEBUG` level with optional
+/// fields, e.g. `trace_span!(DEBUG, "fft", size = a.len())`, until it's
+/// dropped or exited.
+#[cfg(feature = "tracing")]
+macro_rules! trace_span {
+    ($level:ident, $name:literal $(, $($fields:tt)+)?) => {
+        $crate::util::trace::Span::enter(tracing::span!(
+            tracing::Level::$level,
+            $name
+            $(, $($fields)+)?
+        ))
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_span {
+    ($level:ident, $name:literal $(, $($fields:tt)+)?) => {
+        $crate::util::trace::Span
+    };
+}
+
+pub(crate) use trace_span;
+
+#[cfg(feature = "tracing")]
+mod enabled {
+    use tracing::span::EnteredSpan;
+
+    #[must_use]
+    pub struct Span(EnteredSpan);
+
+    impl Span {
+        pub fn enter(span: tracing::Span) -> Self {
+            Self(span.entered())
+        }
+
+        /// Exit the span before the end of the scope, e.g. of a prover round.
+        pub fn exit(self) {}
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+mod disabled {
+    #[must_use]
+    pub struct Span;
+
+    impl Span {
+        pub fn exit(self) {}
+    }
+}
@@ -1,7 +1,10 @@
 use crate::{
     util::{
-        arithmetic::{fe_mod_from_le_bytes, Coordinates, CurveAffine, PrimeField},
-        hash::{Hash, Keccak256, Output, Update},
+        arithmetic::{
+            fe_from_le_bytes, fe_mod_from_le_bytes, is_in_prime_order_subgroup,
+            is_prime_order_curve, Coordinates, CurveAffine, FromUniformBytes, PrimeField,
+        },
+        hash::{Hash, Keccak256, Output, Poseidon, Update},
         Itertools,
     },
     Error,
@@ -11,8 +14,15 @@ use halo2_curves::{bn256, grumpkin, pasta};
 use std::{
     fmt::Debug,
     io::{self, Cursor},
+    iter,
 };
 
+#[cfg(feature = "merlin")]
+mod merlin;
+
+#[cfg(feature = "merlin")]
+pub use self::merlin::MerlinTranscript;
+
 pub trait FieldTranscript<F> {
     fn squeeze_challenge(&mut self) -> F;
 
@@ -27,6 +37,15 @@ pub trait FieldTranscript<F> {
             .map(|fe| self.common_field_element(fe))
             .try_collect()
     }
+
+    /// Absorb a domain separation label, so that messages of different
+    /// meanings or protocols are never absorbed the same way.
+    fn common_label(&mut self, label: &'static [u8]) -> Result<(), Error>;
+
+    fn common_field_element_labeled(&mut self, label: &'static [u8], fe: &F) -> Result<(), Error> {
+        self.common_label(label)?;
+        self.common_field_element(fe)
+    }
 }
 
 pub trait FieldTranscriptRead<F>: FieldTranscript<F> {
@@ -63,6 +82,11 @@ pub trait Transcript<C, F>: FieldTranscript<F> {
             .map(|comm| self.common_commitment(comm))
             .try_collect()
     }
+
+    fn common_commitment_labeled(&mut self, label: &'static [u8], comm: &C) -> Result<(), Error> {
+        self.common_label(label)?;
+        self.common_commitment(comm)
+    }
 }
 
 pub trait TranscriptRead<C, F>: Transcript<C, F> + FieldTranscriptRead<F> {
@@ -96,6 +120,15 @@ pub trait G2Transcript<C, F>: FieldTranscript<F> {
             .map(|comm| self.common_commitment_g2(comm))
             .try_collect()
     }
+
+    fn common_commitment_g2_labeled(
+        &mut self,
+        label: &'static [u8],
+        comm: &C,
+    ) -> Result<(), Error> {
+        self.common_label(label)?;
+        self.common_commitment_g2(comm)
+    }
 }
 
 pub trait G2TranscriptRead<C, F>: FieldTranscriptRead<F> {
@@ -160,6 +193,21 @@ impl<H: Hash> InMemoryTranscript for FiatShamirTranscript<H, Cursor<Vec<u8>>> {
     }
 }
 
+impl<H: Hash, S> FiatShamirTranscript<H, S> {
+    /// Transcript reading the proof from `stream` as it's needed, e.g. from a
+    /// file or a socket, instead of from a buffer of the whole proof.
+    pub fn from_stream(stream: S) -> Self {
+        Self {
+            state: H::default(),
+            stream,
+        }
+    }
+
+    pub fn into_stream(self) -> S {
+        self.stream
+    }
+}
+
 impl<H: Hash, F: PrimeField, S> FieldTranscript<F> for FiatShamirTranscript<H, S> {
     fn squeeze_challenge(&mut self) -> F {
         let hash = self.state.finalize_fixed_reset();
@@ -171,6 +219,12 @@ impl<H: Hash, F: PrimeField, S> FieldTranscript<F> for FiatShamirTranscript<H, S
         self.state.update_field_element(fe);
         Ok(())
     }
+
+    fn common_label(&mut self, label: &'static [u8]) -> Result<(), Error> {
+        self.state.update(&(label.len() as u64).to_le_bytes());
+        self.state.update(label);
+        Ok(())
+    }
 }
 
 impl<H: Hash, F: PrimeField, R: io::Read> FieldTranscriptRead<F> for FiatShamirTranscript<H, R> {
@@ -238,7 +292,8 @@ impl<H: Hash, R: io::Read> G2TranscriptRead<G2Affine, <G2Affine as CurveAffine>:
                     io::ErrorKind::Other,
                     "Invalid elliptic curve point encoding in proof".to_string(),
                 )
-            })?;
+            })
+            .and_then(check_subgroup)?;
         self.common_commitment_g2(&ec_point)?;
         Ok(ec_point)
     }
@@ -300,7 +355,8 @@ macro_rules! impl_fs_transcript_curve_commitment {
                                 io::ErrorKind::Other,
                                 "Invalid elliptic curve point encoding in proof".to_string(),
                             )
-                        })?;
+                        })
+                        .and_then(check_subgroup)?;
                     self.common_commitment(&ec_point)?;
                     Ok(ec_point)
                 }
@@ -358,3 +414,245 @@ impl<F: PrimeField, W: io::Write> TranscriptWrite<Output<Keccak256>, F> for Kecc
         Ok(())
     }
 }
+
+const POSEIDON_T: usize = 5;
+const POSEIDON_RATE: usize = 4;
+const POSEIDON_R_F: usize = 8;
+const POSEIDON_R_P: usize = 60;
+// coordinates are absorbed as limbs of 88 bits, which fit in the native field
+const NUM_LIMB_BYTES: usize = 11;
+
+/// Transcript of Poseidon over the scalar field `F` of the commitments, so
+/// that its challenges are cheap to recompute in a circuit over `F`. Proofs
+/// are encoded as by [`FiatShamirTranscript`].
+#[derive(Debug)]
+pub struct PoseidonTranscript<F: FromUniformBytes<64>, S> {
+    state: Poseidon<F, POSEIDON_T, POSEIDON_RATE>,
+    stream: S,
+}
+
+impl<F: FromUniformBytes<64>, S> PoseidonTranscript<F, S> {
+    fn with_stream(stream: S) -> Self {
+        Self {
+            state: Poseidon::new(POSEIDON_R_F, POSEIDON_R_P),
+            stream,
+        }
+    }
+
+    fn absorb_coordinates<B: PrimeField>(&mut self, coordinates: [&B; 2]) {
+        for coordinate in coordinates {
+            let limbs = coordinate
+                .to_repr()
+                .as_ref()
+                .chunks(NUM_LIMB_BYTES)
+                .map(fe_from_le_bytes)
+                .collect_vec();
+            self.state.update(&limbs);
+        }
+    }
+}
+
+impl<F: FromUniformBytes<64>> InMemoryTranscript for PoseidonTranscript<F, Cursor<Vec<u8>>> {
+    type Param = ();
+
+    fn new(_: Self::Param) -> Self {
+        Self::with_stream(Cursor::default())
+    }
+
+    fn into_proof(self) -> Vec<u8> {
+        self.stream.into_inner()
+    }
+
+    fn from_proof(_: Self::Param, proof: &[u8]) -> Self {
+        Self::with_stream(Cursor::new(proof.to_vec()))
+    }
+}
+
+impl<F: FromUniformBytes<64>, S> FieldTranscript<F> for PoseidonTranscript<F, S> {
+    fn squeeze_challenge(&mut self) -> F {
+        let challenge = self.state.squeeze();
+        self.state.update(&[challenge]);
+        challenge
+    }
+
+    fn common_field_element(&mut self, fe: &F) -> Result<(), Error> {
+        self.state.update(&[*fe]);
+        Ok(())
+    }
+
+    fn common_label(&mut self, label: &'static [u8]) -> Result<(), Error> {
+        let limbs = iter::once(F::from(label.len() as u64))
+            .chain(label.chunks(NUM_LIMB_BYTES).map(fe_from_le_bytes))
+            .collect_vec();
+        self.state.update(&limbs);
+        Ok(())
+    }
+}
+
+impl<F: FromUniformBytes<64>, R: io::Read> FieldTranscriptRead<F> for PoseidonTranscript<F, R> {
+    fn read_field_element(&mut self) -> Result<F, Error> {
+        let fe = read_repr(&mut self.stream, "Invalid field element encoding in proof")?;
+        self.common_field_element(&fe)?;
+        Ok(fe)
+    }
+}
+
+impl<F: FromUniformBytes<64>, W: io::Write> FieldTranscriptWrite<F> for PoseidonTranscript<F, W> {
+    fn write_field_element(&mut self, fe: &F) -> Result<(), Error> {
+        self.common_field_element(fe)?;
+        write_repr(&mut self.stream, fe)
+    }
+}
+
+impl<C: CurveAffine, S> Transcript<C, C::Scalar> for PoseidonTranscript<C::Scalar, S>
+where
+    C::Scalar: FromUniformBytes<64>,
+{
+    fn common_commitment(&mut self, comm: &C) -> Result<(), Error> {
+        let coordinates = Option::<Coordinates<_>>::from(comm.coordinates()).ok_or_else(|| {
+            Error::Transcript(
+                io::ErrorKind::Other,
+                "Invalid elliptic curve point encoding".to_string(),
+            )
+        })?;
+        self.absorb_coordinates([coordinates.x(), coordinates.y()]);
+        Ok(())
+    }
+}
+
+impl<C: CurveAffine, R: io::Read> TranscriptRead<C, C::Scalar> for PoseidonTranscript<C::Scalar, R>
+where
+    C::Scalar: FromUniformBytes<64>,
+{
+    fn read_commitment(&mut self) -> Result<C, Error> {
+        let ec_point = read_point(&mut self.stream)?;
+        self.common_commitment(&ec_point)?;
+        Ok(ec_point)
+    }
+}
+
+impl<C: CurveAffine, W: io::Write> TranscriptWrite<C, C::Scalar>
+    for PoseidonTranscript<C::Scalar, W>
+where
+    C::Scalar: FromUniformBytes<64>,
+{
+    fn write_commitment(&mut self, ec_point: &C) -> Result<(), Error> {
+        self.common_commitment(ec_point)?;
+        write_point(&mut self.stream, ec_point)
+    }
+}
+
+impl<S> G2Transcript<G2Affine, bn256::Fr> for PoseidonTranscript<bn256::Fr, S> {
+    fn common_commitment_g2(&mut self, comm: &G2Affine) -> Result<(), Error> {
+        Transcript::<G2Affine, bn256::Fr>::common_commitment(self, comm)
+    }
+}
+
+impl<R: io::Read> G2TranscriptRead<G2Affine, bn256::Fr> for PoseidonTranscript<bn256::Fr, R> {
+    fn read_commitment_g2(&mut self) -> Result<G2Affine, Error> {
+        TranscriptRead::<G2Affine, bn256::Fr>::read_commitment(self)
+    }
+}
+
+impl<W: io::Write> G2TranscriptWrite<G2Affine, bn256::Fr> for PoseidonTranscript<bn256::Fr, W> {
+    fn write_commitment_g2(&mut self, ec_point: &G2Affine) -> Result<(), Error> {
+        TranscriptWrite::<G2Affine, bn256::Fr>::write_commitment(self, ec_point)
+    }
+}
+
+// big-endian repr, as by `FiatShamirTranscript`
+fn read_repr<F: PrimeField>(reader: &mut impl io::Read, err: &str) -> Result<F, Error> {
+    let mut repr = F::Repr::default();
+    reader
+        .read_exact(repr.as_mut())
+        .map_err(|err| Error::Transcript(err.kind(), err.to_string()))?;
+    repr.as_mut().reverse();
+    F::from_repr_vartime(repr)
+        .ok_or_else(|| Error::Transcript(io::ErrorKind::Other, err.to_string()))
+}
+
+fn write_repr<F: PrimeField>(writer: &mut impl io::Write, fe: &F) -> Result<(), Error> {
+    let mut repr = fe.to_repr();
+    repr.as_mut().reverse();
+    writer
+        .write_all(repr.as_ref())
+        .map_err(|err| Error::Transcript(err.kind(), err.to_string()))
+}
+
+fn read_point<C: CurveAffine>(reader: &mut impl io::Read) -> Result<C, Error> {
+    const ERR: &str = "Invalid elliptic curve point encoding in proof";
+    let x = read_repr(reader, ERR)?;
+    let y = read_repr(reader, ERR)?;
+    Option::from(C::from_xy(x, y))
+        .ok_or_else(|| Error::Transcript(io::ErrorKind::Other, ERR.to_string()))
+        .and_then(check_subgroup)
+}
+
+// a point of a small subgroup in a proof would otherwise flow into pairings,
+// which can't be on curves of cofactor 1 where the scalar multiplication of
+// the check would only cost
+fn check_subgroup<C: CurveAffine>(ec_point: C) -> Result<C, Error> {
+    if is_prime_order_curve::<C>() || is_in_prime_order_subgroup(&ec_point) {
+        Ok(ec_point)
+    } else {
+        Err(Error::Transcript(
+            io::ErrorKind::Other,
+            "Elliptic curve point in proof isn't in the prime order subgroup".to_string(),
+        ))
+    }
+}
+
+fn write_point<C: CurveAffine>(writer: &mut impl io::Write, ec_point: &C) -> Result<(), Error> {
+    let coordinates = ec_point.coordinates().unwrap();
+    write_repr(writer, coordinates.x())?;
+    write_repr(writer, coordinates.y())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::util::{
+        arithmetic::PrimeCurveAffine,
+        test::off_subgroup_g2,
+        transcript::{
+            G2TranscriptRead, G2TranscriptWrite, InMemoryTranscript, Keccak256Transcript,
+            PoseidonTranscript, TranscriptRead, TranscriptWrite,
+        },
+    };
+    use halo2_curves::bn256::{Fr, G1Affine, G2Affine};
+
+    #[test]
+    fn read_commitment_g2_off_subgroup() {
+        let proof = |g2: G2Affine| {
+            let mut transcript = Keccak256Transcript::new(());
+            TranscriptWrite::<G1Affine, Fr>::write_commitment(
+                &mut transcript,
+                &G1Affine::generator(),
+            )
+            .unwrap();
+            transcript.write_commitment_g2(&g2).unwrap();
+            transcript.into_proof()
+        };
+
+        for g2 in [G2Affine::generator(), off_subgroup_g2()] {
+            let is_in_subgroup = g2 == G2Affine::generator();
+            let proof = proof(g2);
+
+            let mut transcript = Keccak256Transcript::from_proof((), &proof);
+            let g1: G1Affine = transcript.read_commitment().unwrap();
+            assert_eq!(g1, G1Affine::generator());
+            assert_eq!(
+                G2TranscriptRead::<G2Affine, Fr>::read_commitment_g2(&mut transcript).is_ok(),
+                is_in_subgroup
+            );
+
+            // as encoded the same
+            let mut transcript = PoseidonTranscript::<Fr, _>::from_proof((), &proof);
+            let g1: G1Affine = transcript.read_commitment().unwrap();
+            assert_eq!(g1, G1Affine::generator());
+            assert_eq!(
+                G2TranscriptRead::<G2Affine, Fr>::read_commitment_g2(&mut transcript).is_ok(),
+                is_in_subgroup
+            );
+        }
+    }
+}
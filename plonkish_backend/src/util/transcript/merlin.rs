@@ -0,0 +1,237 @@
+use crate::{
+    util::{
+        arithmetic::{fe_mod_from_le_bytes, Coordinates, CurveAffine, PrimeField},
+        transcript::{
+            read_point, read_repr, write_point, write_repr, FieldTranscript, FieldTranscriptRead,
+            FieldTranscriptWrite, G2Transcript, G2TranscriptRead, G2TranscriptWrite,
+            InMemoryTranscript, Transcript, TranscriptRead, TranscriptWrite,
+        },
+    },
+    Error,
+};
+use halo2_curves::bn256::{self, G2Affine};
+use std::{
+    fmt::{self, Debug},
+    io::{self, Cursor},
+};
+
+const LABEL_PROTOCOL: &[u8] = b"plonkish_backend";
+const LABEL_CHALLENGE: &[u8] = b"challenge";
+const LABEL_DOMAIN_SEPARATOR: &[u8] = b"dom-sep";
+const LABEL_FIELD_ELEMENT: &[u8] = b"field_element";
+const LABEL_COMMITMENT: &[u8] = b"commitment";
+const LABEL_COMMITMENT_G2: &[u8] = b"commitment_g2";
+
+/// Transcript of a [`merlin::Transcript`], which labels every message by its
+/// kind, or by the label given to the `_labeled` methods. Proofs are encoded as by [`FiatShamirTranscript`].
+///
+/// An application with its own transcript, e.g. with its statement already
+/// appended, passes it in by [`MerlinTranscript::from_transcript`] and takes
+/// it back with the proof appended by [`MerlinTranscript::into_transcript`].
+///
+/// [`FiatShamirTranscript`]: crate::util::transcript::FiatShamirTranscript
+pub struct MerlinTranscript<S> {
+    state: ::merlin::Transcript,
+    stream: S,
+}
+
+impl<S> MerlinTranscript<S> {
+    pub fn from_transcript(state: ::merlin::Transcript, stream: S) -> Self {
+        Self { state, stream }
+    }
+
+    pub fn into_transcript(self) -> (::merlin::Transcript, S) {
+        (self.state, self.stream)
+    }
+
+    fn append_coordinates<B: PrimeField>(&mut self, label: &'static [u8], coordinates: [&B; 2]) {
+        let message = coordinates
+            .into_iter()
+            .flat_map(|coordinate| coordinate.to_repr().as_ref().to_vec())
+            .collect::<Vec<_>>();
+        self.state.append_message(label, &message);
+    }
+}
+
+impl<S: Debug> Debug for MerlinTranscript<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MerlinTranscript")
+            .field("stream", &self.stream)
+            .finish_non_exhaustive()
+    }
+}
+
+impl InMemoryTranscript for MerlinTranscript<Cursor<Vec<u8>>> {
+    type Param = ();
+
+    fn new(_: Self::Param) -> Self {
+        Self::from_transcript(::merlin::Transcript::new(LABEL_PROTOCOL), Cursor::default())
+    }
+
+    fn into_proof(self) -> Vec<u8> {
+        self.stream.into_inner()
+    }
+
+    fn from_proof(_: Self::Param, proof: &[u8]) -> Self {
+        Self::from_transcript(
+            ::merlin::Transcript::new(LABEL_PROTOCOL),
+            Cursor::new(proof.to_vec()),
+        )
+    }
+}
+
+impl<F: PrimeField, S> FieldTranscript<F> for MerlinTranscript<S> {
+    fn squeeze_challenge(&mut self) -> F {
+        let mut bytes = [0; 64];
+        self.state.challenge_bytes(LABEL_CHALLENGE, &mut bytes);
+        fe_mod_from_le_bytes(bytes)
+    }
+
+    fn common_field_element(&mut self, fe: &F) -> Result<(), Error> {
+        self.common_field_element_labeled(LABEL_FIELD_ELEMENT, fe)
+    }
+
+    fn common_label(&mut self, label: &'static [u8]) -> Result<(), Error> {
+        self.state.append_message(LABEL_DOMAIN_SEPARATOR, label);
+        Ok(())
+    }
+
+    // labeled by `label` itself as a message of Merlin
+    fn common_field_element_labeled(&mut self, label: &'static [u8], fe: &F) -> Result<(), Error> {
+        self.state.append_message(label, fe.to_repr().as_ref());
+        Ok(())
+    }
+}
+
+impl<F: PrimeField, R: io::Read> FieldTranscriptRead<F> for MerlinTranscript<R> {
+    fn read_field_element(&mut self) -> Result<F, Error> {
+        let fe = read_repr(&mut self.stream, "Invalid field element encoding in proof")?;
+        self.common_field_element(&fe)?;
+        Ok(fe)
+    }
+}
+
+impl<F: PrimeField, W: io::Write> FieldTranscriptWrite<F> for MerlinTranscript<W> {
+    fn write_field_element(&mut self, fe: &F) -> Result<(), Error> {
+        self.common_field_element(fe)?;
+        write_repr(&mut self.stream, fe)
+    }
+}
+
+impl<C: CurveAffine, S> Transcript<C, C::Scalar> for MerlinTranscript<S> {
+    fn common_commitment(&mut self, comm: &C) -> Result<(), Error> {
+        self.common_commitment_labeled(LABEL_COMMITMENT, comm)
+    }
+
+    fn common_commitment_labeled(&mut self, label: &'static [u8], comm: &C) -> Result<(), Error> {
+        let coordinates = coordinates(comm)?;
+        self.append_coordinates(label, [coordinates.x(), coordinates.y()]);
+        Ok(())
+    }
+}
+
+impl<C: CurveAffine, R: io::Read> TranscriptRead<C, C::Scalar> for MerlinTranscript<R> {
+    fn read_commitment(&mut self) -> Result<C, Error> {
+        let ec_point = read_point(&mut self.stream)?;
+        self.common_commitment(&ec_point)?;
+        Ok(ec_point)
+    }
+}
+
+impl<C: CurveAffine, W: io::Write> TranscriptWrite<C, C::Scalar> for MerlinTranscript<W> {
+    fn write_commitment(&mut self, ec_point: &C) -> Result<(), Error> {
+        self.common_commitment(ec_point)?;
+        write_point(&mut self.stream, ec_point)
+    }
+}
+
+impl<S> G2Transcript<G2Affine, bn256::Fr> for MerlinTranscript<S> {
+    fn common_commitment_g2(&mut self, comm: &G2Affine) -> Result<(), Error> {
+        self.common_commitment_g2_labeled(LABEL_COMMITMENT_G2, comm)
+    }
+
+    fn common_commitment_g2_labeled(
+        &mut self,
+        label: &'static [u8],
+        comm: &G2Affine,
+    ) -> Result<(), Error> {
+        let coordinates = coordinates(comm)?;
+        self.append_coordinates(label, [coordinates.x(), coordinates.y()]);
+        Ok(())
+    }
+}
+
+impl<R: io::Read> G2TranscriptRead<G2Affine, bn256::Fr> for MerlinTranscript<R> {
+    fn read_commitment_g2(&mut self) -> Result<G2Affine, Error> {
+        let ec_point = read_point(&mut self.stream)?;
+        self.common_commitment_g2(&ec_point)?;
+        Ok(ec_point)
+    }
+}
+
+impl<W: io::Write> G2TranscriptWrite<G2Affine, bn256::Fr> for MerlinTranscript<W> {
+    fn write_commitment_g2(&mut self, ec_point: &G2Affine) -> Result<(), Error> {
+        self.common_commitment_g2(ec_point)?;
+        write_point(&mut self.stream, ec_point)
+    }
+}
+
+fn coordinates<C: CurveAffine>(comm: &C) -> Result<Coordinates<C>, Error> {
+    Option::from(comm.coordinates()).ok_or_else(|| {
+        Error::Transcript(
+            io::ErrorKind::Other,
+            "Invalid elliptic curve point encoding".to_string(),
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::MerlinTranscript;
+    use crate::util::{
+        arithmetic::PrimeCurveAffine,
+        transcript::{
+            FieldTranscript, FieldTranscriptRead, FieldTranscriptWrite, G2TranscriptRead,
+            G2TranscriptWrite, InMemoryTranscript, TranscriptRead, TranscriptWrite,
+        },
+    };
+    use halo2_curves::bn256::{Fr, G1Affine, G2Affine};
+    use std::io::Cursor;
+
+    #[test]
+    fn from_transcript() {
+        let fe = Fr::from(7);
+        let write = |label: &'static [u8]| {
+            let mut transcript = MerlinTranscript::from_transcript(
+                merlin::Transcript::new(label),
+                Cursor::<Vec<u8>>::default(),
+            );
+            transcript.write_field_element(&fe).unwrap();
+            transcript.write_commitment(&G1Affine::generator()).unwrap();
+            transcript
+                .write_commitment_g2(&G2Affine::generator())
+                .unwrap();
+            let challenge: Fr = transcript.squeeze_challenge();
+            (challenge, transcript.into_transcript().1.into_inner())
+        };
+
+        let (challenge, proof) = write(b"app");
+        let mut transcript =
+            MerlinTranscript::from_transcript(merlin::Transcript::new(b"app"), proof.as_slice());
+        let read_fe: Fr = transcript.read_field_element().unwrap();
+        let read_comm: G1Affine = transcript.read_commitment().unwrap();
+        let read_comm_g2: G2Affine = transcript.read_commitment_g2().unwrap();
+        let read_challenge: Fr = transcript.squeeze_challenge();
+        assert_eq!(read_fe, fe);
+        assert_eq!(read_comm, G1Affine::generator());
+        assert_eq!(read_comm_g2, G2Affine::generator());
+        assert_eq!(read_challenge, challenge);
+
+        // bound to the state of the application's transcript
+        assert_ne!(write(b"other app").0, challenge);
+        let mut transcript = MerlinTranscript::new(());
+        transcript.write_field_element(&fe).unwrap();
+        let other_challenge: Fr = transcript.squeeze_challenge();
+        assert_ne!(other_challenge, challenge);
+    }
+}